@@ -0,0 +1,180 @@
+use crate::constants::database as db_constants;
+use crate::database::models::CatalogEmote;
+use serde_json::Value;
+
+/// エモートカタログのJSONエクスポートを解析
+///
+/// 対応フォーマット:
+/// - twitch: Helix Get Global/Channel Emotes のレスポンス（`{"data": [...]}`）
+/// - 7tv: エモートセット（`{"emotes": [...]}`）またはユーザー（`{"emote_set": {"emotes": [...]}}`）
+/// - bttv: グローバル（配列）またはチャンネル（`channelEmotes` / `sharedEmotes`）
+/// - ffz: ルームまたはグローバルセット（`{"sets": {"<id>": {"emoticons": [...]}}}`）
+pub fn parse_emote_export(
+    source: &str,
+    json: &str,
+    channel_id: Option<i64>,
+) -> Result<Vec<CatalogEmote>, String> {
+    let value: Value =
+        serde_json::from_str(json).map_err(|e| format!("Invalid emote JSON: {}", e))?;
+
+    let entries: Vec<(String, String, Option<String>)> = match source {
+        db_constants::EMOTE_SOURCE_TWITCH => {
+            let list = value.get("data").unwrap_or(&value);
+            as_array(list)
+                .iter()
+                .filter_map(|emote| {
+                    let image_url = emote
+                        .pointer("/images/url_1x")
+                        .and_then(Value::as_str)
+                        .map(str::to_string);
+                    Some((
+                        id_string(emote.get("id")?)?,
+                        name_string(emote, "name")?,
+                        image_url,
+                    ))
+                })
+                .collect()
+        }
+        db_constants::EMOTE_SOURCE_7TV => {
+            let list = value
+                .pointer("/emote_set/emotes")
+                .or_else(|| value.get("emotes"))
+                .unwrap_or(&value);
+            as_array(list)
+                .iter()
+                .filter_map(|emote| {
+                    // host.url はスキーム省略形（//cdn.7tv.app/emote/<id>）
+                    let image_url = emote
+                        .pointer("/data/host/url")
+                        .and_then(Value::as_str)
+                        .map(|url| format!("https:{}/1x.webp", url));
+                    Some((
+                        id_string(emote.get("id")?)?,
+                        name_string(emote, "name")?,
+                        image_url,
+                    ))
+                })
+                .collect()
+        }
+        db_constants::EMOTE_SOURCE_BTTV => {
+            let lists: Vec<&Value> = if value.is_array() {
+                vec![&value]
+            } else {
+                ["channelEmotes", "sharedEmotes"]
+                    .iter()
+                    .filter_map(|key| value.get(*key))
+                    .collect()
+            };
+            lists
+                .into_iter()
+                .flat_map(as_array)
+                .filter_map(|emote| {
+                    let id = id_string(emote.get("id")?)?;
+                    let image_url = Some(format!("https://cdn.betterttv.net/emote/{}/1x", id));
+                    Some((id, name_string(emote, "code")?, image_url))
+                })
+                .collect()
+        }
+        db_constants::EMOTE_SOURCE_FFZ => value
+            .get("sets")
+            .and_then(Value::as_object)
+            .map(|sets| {
+                sets.values()
+                    .filter_map(|set| set.get("emoticons"))
+                    .flat_map(as_array)
+                    .filter_map(|emote| {
+                        let image_url = emote
+                            .pointer("/urls/1")
+                            .and_then(Value::as_str)
+                            .map(str::to_string);
+                        Some((
+                            id_string(emote.get("id")?)?,
+                            name_string(emote, "name")?,
+                            image_url,
+                        ))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => return Err(format!("Unsupported emote source: {}", source)),
+    };
+
+    Ok(entries
+        .into_iter()
+        .map(|(emote_id, name, image_url)| CatalogEmote {
+            source: source.to_string(),
+            emote_id,
+            name,
+            channel_id,
+            image_url,
+        })
+        .collect())
+}
+
+fn as_array(value: &Value) -> &[Value] {
+    value.as_array().map(Vec::as_slice).unwrap_or(&[])
+}
+
+/// IDは文字列（Twitch/7TV/BTTV）と数値（FFZ）の両方がある
+fn id_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn name_string(emote: &Value, key: &str) -> Option<String> {
+    emote
+        .get(key)
+        .and_then(Value::as_str)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_third_party_exports() {
+        let seventv = parse_emote_export(
+            "7tv",
+            r#"{"id": "1", "emote_set": {"emotes": [{"id": "60ae", "name": "catJAM", "data": {"host": {"url": "//cdn.7tv.app/emote/60ae"}}}]}}"#,
+            Some(3),
+        )
+        .unwrap();
+        assert_eq!(seventv.len(), 1);
+        assert_eq!(seventv[0].name, "catJAM");
+        assert_eq!(seventv[0].channel_id, Some(3));
+        assert_eq!(
+            seventv[0].image_url.as_deref(),
+            Some("https://cdn.7tv.app/emote/60ae/1x.webp")
+        );
+
+        let bttv = parse_emote_export(
+            "bttv",
+            r#"{"channelEmotes": [{"id": "a1", "code": "monkaS"}], "sharedEmotes": [{"id": "b2", "code": "PepeLaugh"}]}"#,
+            Some(3),
+        )
+        .unwrap();
+        let names: Vec<&str> = bttv.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["monkaS", "PepeLaugh"]);
+
+        let bttv_global =
+            parse_emote_export("bttv", r#"[{"id": "c3", "code": "FeelsGoodMan"}]"#, None).unwrap();
+        assert_eq!(bttv_global[0].channel_id, None);
+
+        let ffz = parse_emote_export(
+            "ffz",
+            r#"{"room": {}, "sets": {"123": {"emoticons": [{"id": 128054, "name": "OMEGALUL", "urls": {"1": "https://cdn.frankerfacez.com/emote/128054/1"}}]}}}"#,
+            Some(3),
+        )
+        .unwrap();
+        assert_eq!(ffz[0].emote_id, "128054");
+        assert_eq!(ffz[0].name, "OMEGALUL");
+
+        assert!(parse_emote_export("unknown", "[]", None).is_err());
+        assert!(parse_emote_export("7tv", "not json", None).is_err());
+    }
+}
@@ -0,0 +1,175 @@
+use crate::constants::kick;
+use serde::Deserialize;
+
+/// Kick API クライアント
+///
+/// 公開チャンネル情報エンドポイント（/channels/{slug}）から配信状態とチャットルームIDを取得します。
+/// ベースURLを差し替えることでローカルのモックサーバーに向けられます。
+pub struct KickApiClient {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+/// チャンネル情報（/channels/{slug} のレスポンス）
+#[derive(Debug, Clone, Deserialize)]
+pub struct KickChannel {
+    pub followers_count: Option<i64>,
+    pub chatroom: Option<KickChatroom>,
+    pub livestream: Option<KickLivestream>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KickChatroom {
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KickLivestream {
+    pub id: i64,
+    pub session_title: Option<String>,
+    /// 配信開始時刻（UTC, "YYYY-MM-DD HH:MM:SS"）
+    pub start_time: Option<String>,
+    pub created_at: Option<String>,
+    #[serde(default = "default_is_live")]
+    pub is_live: bool,
+    pub viewer_count: Option<i64>,
+    pub language: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub thumbnail: Option<KickThumbnail>,
+    #[serde(default)]
+    pub categories: Vec<KickCategory>,
+}
+
+fn default_is_live() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KickThumbnail {
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KickCategory {
+    pub id: i64,
+    pub name: String,
+}
+
+impl KickApiClient {
+    pub fn new() -> Self {
+        Self::with_base_url(kick::API_BASE_URL)
+    }
+
+    /// ベースURLを指定して作成（テスト用モックサーバーなど）
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(kick::REQUEST_TIMEOUT_SECS))
+            .connect_timeout(std::time::Duration::from_secs(10))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// スラッグ（URL上のチャンネル名）からチャンネル情報を取得
+    pub async fn get_channel(
+        &self,
+        slug: &str,
+    ) -> Result<KickChannel, Box<dyn std::error::Error + Send + Sync>> {
+        let url = format!("{}/channels/{}", self.base_url, slug.to_lowercase());
+        let response = self
+            .client
+            .get(&url)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .send()
+            .await?
+            // ステータスコード付きのエラーにすることで CollectorError が種別を判定できる
+            .error_for_status()?;
+
+        Ok(response.json::<KickChannel>().await?)
+    }
+}
+
+impl Default for KickApiClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// 1リクエストだけ応答するモックHTTPサーバーを起動し、ベースURLを返す
+    async fn spawn_mock_server(status_line: &'static str, body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await;
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status_line,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+        format!("http://{}/api/v2", addr)
+    }
+
+    #[tokio::test]
+    async fn test_get_channel_parses_livestream() {
+        let base_url = spawn_mock_server(
+            "200 OK",
+            r#"{
+                "id": 1,
+                "user_id": 10,
+                "slug": "streamer",
+                "followers_count": 1234,
+                "chatroom": {"id": 99},
+                "user": {"username": "Streamer", "profile_pic": null},
+                "livestream": {
+                    "id": 555,
+                    "session_title": "Hello",
+                    "start_time": "2025-01-06 12:00:00",
+                    "is_live": true,
+                    "viewer_count": 42,
+                    "thumbnail": {"url": "https://example.com/thumb.jpg"},
+                    "categories": [{"id": 7, "name": "Just Chatting"}]
+                }
+            }"#,
+        )
+        .await;
+
+        let channel = KickApiClient::with_base_url(base_url)
+            .get_channel("Streamer")
+            .await
+            .unwrap();
+        assert_eq!(channel.chatroom.unwrap().id, 99);
+        let livestream = channel.livestream.unwrap();
+        assert_eq!(livestream.id, 555);
+        assert_eq!(livestream.viewer_count, Some(42));
+        assert_eq!(livestream.categories[0].name, "Just Chatting");
+    }
+
+    #[tokio::test]
+    async fn test_get_channel_not_found() {
+        let base_url = spawn_mock_server("404 Not Found", "{}").await;
+        let error = KickApiClient::with_base_url(base_url)
+            .get_channel("missing")
+            .await
+            .unwrap_err();
+        let status = error
+            .downcast_ref::<reqwest::Error>()
+            .and_then(reqwest::Error::status);
+        assert_eq!(status, Some(reqwest::StatusCode::NOT_FOUND));
+    }
+}
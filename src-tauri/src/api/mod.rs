@@ -1,3 +1,5 @@
+pub mod emote_catalog;
+pub mod kick_api;
 pub mod twitch_api;
 pub mod youtube_api;
 pub mod youtube_live_chat;
@@ -10,10 +10,12 @@ use tauri::Emitter;
 use tokio::sync::Mutex;
 use twitch_api::{
     helix::{
+        chat::{ChannelEmote, GetChannelEmotesRequest, GetGlobalEmotesRequest, GlobalEmote},
+        schedule::{GetChannelStreamScheduleRequest, ScheduledBroadcasts},
         search::{Category, SearchCategoriesRequest},
         streams::{GetStreamsRequest, Stream},
         users::{GetUsersRequest, User},
-        HelixClient,
+        ClientRequestError, HelixClient, HelixRequestGetError,
     },
     twitch_oauth2::{AccessToken, UserToken as TwitchApiUserToken},
     types,
@@ -791,6 +793,139 @@ impl TwitchApiClient {
             }
         }
     }
+
+    /// グローバルエモート一覧を取得
+    pub async fn get_global_emotes(
+        &self,
+    ) -> Result<Vec<GlobalEmote>, Box<dyn std::error::Error + Send + Sync>> {
+        let token = self.get_user_token().await?;
+        let request = GetGlobalEmotesRequest::new();
+
+        // リクエストをトラッキング
+        {
+            let mut limiter = self.rate_limiter.lock().await;
+            limiter.track_request();
+        }
+
+        match self.client.req_get(request.clone(), &token).await {
+            Ok(response) => Ok(response.data),
+            Err(e) => {
+                // 401エラーの場合、トークンをリフレッシュして再試行
+                if e.to_string().contains(twitch::ERROR_UNAUTHORIZED)
+                    || e.to_string().contains(twitch::ERROR_UNAUTHORIZED_TEXT)
+                {
+                    eprintln!("Token expired, attempting refresh...");
+                    let _new_token = self.refresh_token().await?;
+                    let refreshed_token = self.get_user_token().await?;
+
+                    // 再試行もトラッキング
+                    {
+                        let mut limiter = self.rate_limiter.lock().await;
+                        limiter.track_request();
+                    }
+
+                    let response = self.client.req_get(request, &refreshed_token).await?;
+                    Ok(response.data)
+                } else {
+                    Err(e.into())
+                }
+            }
+        }
+    }
+
+    /// チャンネル固有エモート（サブスク・ビッツ・フォロワーエモート）を取得
+    pub async fn get_channel_emotes(
+        &self,
+        broadcaster_id: &str,
+    ) -> Result<Vec<ChannelEmote>, Box<dyn std::error::Error + Send + Sync>> {
+        let token = self.get_user_token().await?;
+        let request = GetChannelEmotesRequest::broadcaster_id(broadcaster_id);
+
+        // リクエストをトラッキング
+        {
+            let mut limiter = self.rate_limiter.lock().await;
+            limiter.track_request();
+        }
+
+        match self.client.req_get(request.clone(), &token).await {
+            Ok(response) => Ok(response.data),
+            Err(e) => {
+                // 401エラーの場合、トークンをリフレッシュして再試行
+                if e.to_string().contains(twitch::ERROR_UNAUTHORIZED)
+                    || e.to_string().contains(twitch::ERROR_UNAUTHORIZED_TEXT)
+                {
+                    eprintln!("Token expired, attempting refresh...");
+                    let _new_token = self.refresh_token().await?;
+                    let refreshed_token = self.get_user_token().await?;
+
+                    // 再試行もトラッキング
+                    {
+                        let mut limiter = self.rate_limiter.lock().await;
+                        limiter.track_request();
+                    }
+
+                    let response = self.client.req_get(request, &refreshed_token).await?;
+                    Ok(response.data)
+                } else {
+                    Err(e.into())
+                }
+            }
+        }
+    }
+
+    /// チャンネルの配信スケジュールを取得（現在時刻以降のセグメント）
+    ///
+    /// スケジュールを設定していないチャンネルは 404 が返るため None を返します。
+    pub async fn get_channel_schedule(
+        &self,
+        broadcaster_id: &str,
+    ) -> Result<Option<ScheduledBroadcasts>, Box<dyn std::error::Error + Send + Sync>> {
+        let token = self.get_user_token().await?;
+        let request = GetChannelStreamScheduleRequest::broadcaster_id(broadcaster_id);
+
+        // リクエストをトラッキング
+        {
+            let mut limiter = self.rate_limiter.lock().await;
+            limiter.track_request();
+        }
+
+        let result = match self.client.req_get(request.clone(), &token).await {
+            Ok(response) => Ok(response.data),
+            Err(e) => {
+                // 401エラーの場合、トークンをリフレッシュして再試行
+                if e.to_string().contains(twitch::ERROR_UNAUTHORIZED)
+                    || e.to_string().contains(twitch::ERROR_UNAUTHORIZED_TEXT)
+                {
+                    eprintln!("Token expired, attempting refresh...");
+                    let _new_token = self.refresh_token().await?;
+                    let refreshed_token = self.get_user_token().await?;
+
+                    // 再試行もトラッキング
+                    {
+                        let mut limiter = self.rate_limiter.lock().await;
+                        limiter.track_request();
+                    }
+
+                    self.client
+                        .req_get(request, &refreshed_token)
+                        .await
+                        .map(|response| response.data)
+                } else {
+                    Err(e)
+                }
+            }
+        };
+
+        match result {
+            Ok(schedule) => Ok(Some(schedule)),
+            // 配信スケジュールが未設定のチャンネルは 404 を返す
+            Err(ClientRequestError::HelixRequestGetError(HelixRequestGetError::Error {
+                status,
+                ..
+            })) if status.as_u16() == 404 => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
 }
 
 /// Twitch APIレート制限トラッカー
@@ -1,17 +1,39 @@
 // Keyring is not used in this file as it doesn't have AppHandle access
 use crate::constants::youtube;
+use crate::database::repositories::youtube_quota_repository::QuotaUsageRow;
+use crate::database::repositories::YouTubeQuotaRepository;
+use crate::database::DatabaseManager;
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Local, NaiveDate, Utc, Weekday};
+use google_youtube3::api::{Video, VideoCategoryListResponse};
 use google_youtube3::YouTube;
 use hyper_rustls::HttpsConnectorBuilder;
 use hyper_util::client::legacy::connect::HttpConnector;
 use hyper_util::client::legacy::Client;
 use hyper_util::rt::TokioExecutor;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 use yup_oauth2::{ApplicationSecret, InstalledFlowAuthenticator, InstalledFlowReturnMethod};
 
 #[allow(dead_code)]
 pub struct YouTubeApiClient {
     hub: Arc<YouTube<hyper_rustls::HttpsConnector<HttpConnector>>>,
     access_token: Option<String>,
+    quota_tracker: Arc<Mutex<YouTubeQuotaTracker>>,
+    /// フィード取得用のHTTPクライアント
+    http_client: reqwest::Client,
+    /// チャンネルID → アップロード再生リストID（フィードが取得できない場合に使用）
+    uploads_playlists: HashMap<String, String>,
+    /// 地域コード → 動画カテゴリ一覧（videoCategories.list の結果）
+    video_categories: HashMap<String, VideoCategoryCache>,
+}
+
+/// 地域ごとの動画カテゴリ一覧（カテゴリID → カテゴリ名）
+struct VideoCategoryCache {
+    fetched_at: Instant,
+    names: HashMap<String, String>,
 }
 
 #[allow(dead_code)]
@@ -20,6 +42,7 @@ impl YouTubeApiClient {
         client_id: String,
         client_secret: String,
         _redirect_uri: String,
+        daily_quota_budget: u32,
     ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
         let secret = ApplicationSecret {
             client_id,
@@ -50,7 +73,27 @@ impl YouTubeApiClient {
         // Note: Token retrieval requires AppHandle which this struct doesn't have
         let access_token = None;
 
-        Ok(Self { hub, access_token })
+        Ok(Self {
+            hub,
+            access_token,
+            quota_tracker: Arc::new(Mutex::new(YouTubeQuotaTracker::new(daily_quota_budget))),
+            http_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(youtube::FEED_REQUEST_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_else(|_| reqwest::Client::new()),
+            uploads_playlists: HashMap::new(),
+            video_categories: HashMap::new(),
+        })
+    }
+
+    /// クォータトラッカーを取得
+    pub fn get_quota_tracker(&self) -> Arc<Mutex<YouTubeQuotaTracker>> {
+        Arc::clone(&self.quota_tracker)
+    }
+
+    /// リクエストのクォータ消費を記録
+    async fn track(&self, endpoint: YouTubeEndpoint) {
+        self.quota_tracker.lock().await.track(endpoint);
     }
 
     // アクセストークンの取得は不要（hubに組み込まれている）
@@ -68,7 +111,9 @@ impl YouTubeApiClient {
             youtube::PART_ID.to_string(),
             youtube::PART_SNIPPET.to_string(),
             youtube::PART_CONTENT_DETAILS.to_string(),
+            youtube::PART_STATISTICS.to_string(),
         ];
+        self.track(YouTubeEndpoint::Channels).await;
         let (_, response) = self
             .hub
             .channels()
@@ -80,49 +125,136 @@ impl YouTubeApiClient {
         Ok(response.items.and_then(|items| items.into_iter().next()))
     }
 
-    pub async fn get_live_stream(
+    /// チャンネルの配信中のライブと配信予定枠を取得
+    ///
+    /// search.list（100 units）は使わず、新着動画IDをフィード（またはアップロード再生リスト）から取得し、
+    /// videos.list（50件ごとに1 unit）の liveStreamingDetails で配信中・配信予定かどうかを判定します。
+    pub async fn get_broadcasts(
         &mut self,
         channel_id: &str,
-    ) -> Result<Option<google_youtube3::api::Video>, Box<dyn std::error::Error + Send + Sync>> {
-        // チャンネルのライブストリームを検索
-        let part = vec![
-            youtube::PART_ID.to_string(),
-            youtube::PART_SNIPPET.to_string(),
-            "liveStreamingDetails".to_string(),
-            "statistics".to_string(),
-        ];
+    ) -> Result<ChannelBroadcasts, Box<dyn std::error::Error + Send + Sync>> {
+        let video_ids = self.get_recent_video_ids(channel_id).await?;
+        let videos = self.get_videos(&video_ids).await?;
+        Ok(split_broadcasts(videos))
+    }
+
+    /// チャンネルの新着動画IDを取得
+    ///
+    /// クォータを消費しないフィードを優先し、取得できない場合はアップロード再生リストを使います。
+    pub async fn get_recent_video_ids(
+        &mut self,
+        channel_id: &str,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        match self.fetch_feed_video_ids(channel_id).await {
+            Ok(video_ids) if !video_ids.is_empty() => return Ok(video_ids),
+            Ok(_) => {}
+            Err(e) => eprintln!(
+                "[YouTube] Failed to fetch feed for channel {}, falling back to uploads playlist: {}",
+                channel_id, e
+            ),
+        }
+        self.fetch_uploads_video_ids(channel_id).await
+    }
+
+    /// チャンネルのフィード（RSS/Atom）から新着動画IDを取得
+    async fn fetch_feed_video_ids(
+        &self,
+        channel_id: &str,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let response = self
+            .http_client
+            .get(youtube::CHANNEL_FEED_URL)
+            .query(&[("channel_id", channel_id)])
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!(
+                "YouTube feed error for channel '{}': {}",
+                channel_id, status
+            )
+            .into());
+        }
+
+        Ok(parse_feed_video_ids(&response.text().await?))
+    }
+
+    /// アップロード再生リストから新着動画IDを取得（再生リストIDはチャンネルごとにキャッシュ）
+    async fn fetch_uploads_video_ids(
+        &mut self,
+        channel_id: &str,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let playlist_id = match self.uploads_playlists.get(channel_id) {
+            Some(playlist_id) => playlist_id.clone(),
+            None => {
+                let part = vec![youtube::PART_CONTENT_DETAILS.to_string()];
+                self.track(YouTubeEndpoint::Channels).await;
+                let (_, response) = self
+                    .hub
+                    .channels()
+                    .list(&part)
+                    .add_id(channel_id)
+                    .doit()
+                    .await?;
+                let playlist_id = response
+                    .items
+                    .and_then(|items| items.into_iter().next())
+                    .and_then(|channel| channel.content_details)
+                    .and_then(|details| details.related_playlists)
+                    .and_then(|playlists| playlists.uploads)
+                    .ok_or_else(|| {
+                        format!("Uploads playlist not found for channel '{}'", channel_id)
+                    })?;
+                self.uploads_playlists
+                    .insert(channel_id.to_string(), playlist_id.clone());
+                playlist_id
+            }
+        };
 
+        let part = vec![youtube::PART_CONTENT_DETAILS.to_string()];
+        self.track(YouTubeEndpoint::PlaylistItems).await;
         let (_, response) = self
             .hub
-            .search()
+            .playlist_items()
             .list(&part)
-            .channel_id(channel_id)
-            .event_type(youtube::EVENT_TYPE_LIVE)
-            .add_type(youtube::TYPE_VIDEO)
-            .max_results(youtube::MAX_RESULTS_DEFAULT)
+            .playlist_id(&playlist_id)
+            .max_results(youtube::RECENT_VIDEO_LIMIT)
             .doit()
             .await?;
 
-        if let Some(items) = response.items {
-            if let Some(search_result) = items.into_iter().next() {
-                if let Some(video_id) = search_result.id.and_then(|id| id.video_id) {
-                    // 動画の詳細を取得
-                    let (_, video_response) = self
-                        .hub
-                        .videos()
-                        .list(&part)
-                        .add_id(&video_id)
-                        .doit()
-                        .await?;
-
-                    return Ok(video_response
-                        .items
-                        .and_then(|items| items.into_iter().next()));
-                }
+        Ok(response
+            .items
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|item| item.content_details.and_then(|details| details.video_id))
+            .collect())
+    }
+
+    /// 動画の詳細（snippet・liveStreamingDetails・statistics）を50件ずつまとめて取得
+    pub async fn get_videos(
+        &mut self,
+        video_ids: &[String],
+    ) -> Result<Vec<Video>, Box<dyn std::error::Error + Send + Sync>> {
+        let part = vec![
+            youtube::PART_ID.to_string(),
+            youtube::PART_SNIPPET.to_string(),
+            youtube::PART_LIVE_STREAMING_DETAILS.to_string(),
+            youtube::PART_STATISTICS.to_string(),
+        ];
+
+        let mut videos = Vec::new();
+        for batch in video_id_batches(video_ids) {
+            self.track(YouTubeEndpoint::Videos).await;
+            let mut request = self.hub.videos().list(&part);
+            for video_id in &batch {
+                request = request.add_id(video_id);
             }
+            let (_, response) = request.doit().await?;
+            videos.extend(response.items.unwrap_or_default());
         }
 
-        Ok(None)
+        Ok(videos)
     }
 
     pub async fn get_channel_by_id(
@@ -131,10 +263,11 @@ impl YouTubeApiClient {
     ) -> Result<Option<google_youtube3::api::Channel>, Box<dyn std::error::Error + Send + Sync>>
     {
         let part = vec![
-            "id".to_string(),
-            "snippet".to_string(),
-            "statistics".to_string(),
+            youtube::PART_ID.to_string(),
+            youtube::PART_SNIPPET.to_string(),
+            youtube::PART_STATISTICS.to_string(),
         ];
+        self.track(YouTubeEndpoint::Channels).await;
         let (_, response) = self
             .hub
             .channels()
@@ -146,6 +279,101 @@ impl YouTubeApiClient {
         Ok(response.items.and_then(|items| items.into_iter().next()))
     }
 
+    /// 動画カテゴリIDからカテゴリ名を取得
+    ///
+    /// カテゴリ一覧は地域ごとにキャッシュし、有効期間が過ぎた場合のみ videoCategories.list を呼び出します。
+    pub async fn get_video_category_name(
+        &mut self,
+        region_code: &str,
+        category_id: &str,
+    ) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let ttl = Duration::from_secs(youtube::VIDEO_CATEGORY_CACHE_TTL_SECS);
+        let is_fresh = self
+            .video_categories
+            .get(region_code)
+            .is_some_and(|cache| cache.fetched_at.elapsed() < ttl);
+
+        if !is_fresh {
+            let part = vec![youtube::PART_SNIPPET.to_string()];
+            self.track(YouTubeEndpoint::VideoCategories).await;
+            let (_, response) = self
+                .hub
+                .video_categories()
+                .list(&part)
+                .region_code(region_code)
+                .doit()
+                .await?;
+            self.video_categories.insert(
+                region_code.to_string(),
+                VideoCategoryCache {
+                    fetched_at: Instant::now(),
+                    names: video_category_names(response),
+                },
+            );
+        }
+
+        Ok(self
+            .video_categories
+            .get(region_code)
+            .and_then(|cache| cache.names.get(category_id).cloned()))
+    }
+
+    /// @ハンドル（@ は含まない）からチャンネルを取得
+    pub async fn get_channel_by_handle(
+        &mut self,
+        handle: &str,
+    ) -> Result<Option<google_youtube3::api::Channel>, Box<dyn std::error::Error + Send + Sync>>
+    {
+        let part = vec![
+            youtube::PART_ID.to_string(),
+            youtube::PART_SNIPPET.to_string(),
+            youtube::PART_STATISTICS.to_string(),
+        ];
+        self.track(YouTubeEndpoint::Channels).await;
+        let (_, response) = self
+            .hub
+            .channels()
+            .list(&part)
+            .for_handle(handle)
+            .doit()
+            .await?;
+
+        Ok(response.items.and_then(|items| items.into_iter().next()))
+    }
+
+    /// 解析済みのチャンネル指定からチャンネルを取得
+    ///
+    /// カスタムURL（/c/）を直接引くAPIはないため、同名のハンドル、旧ユーザー名の順に探します
+    /// （search.list は 100 units かかるため使いません）。動画の場合は投稿者のチャンネルを返します。
+    pub async fn resolve_channel(
+        &mut self,
+        reference: &YouTubeChannelRef,
+    ) -> Result<Option<google_youtube3::api::Channel>, Box<dyn std::error::Error + Send + Sync>>
+    {
+        match reference {
+            YouTubeChannelRef::ChannelId(channel_id) => self.get_channel_by_id(channel_id).await,
+            YouTubeChannelRef::Handle(handle) => self.get_channel_by_handle(handle).await,
+            YouTubeChannelRef::Username(username) => self.get_channel_by_username(username).await,
+            YouTubeChannelRef::CustomUrl(name) => match self.get_channel_by_handle(name).await? {
+                Some(channel) => Ok(Some(channel)),
+                None => self.get_channel_by_username(name).await,
+            },
+            YouTubeChannelRef::Video(video_id) => {
+                let channel_id = self
+                    .get_videos(std::slice::from_ref(video_id))
+                    .await?
+                    .into_iter()
+                    .next()
+                    .and_then(|video| video.snippet)
+                    .and_then(|snippet| snippet.channel_id);
+                match channel_id {
+                    Some(channel_id) => self.get_channel_by_id(&channel_id).await,
+                    None => Ok(None),
+                }
+            }
+        }
+    }
+
     pub fn get_hub(&self) -> Arc<YouTube<hyper_rustls::HttpsConnector<HttpConnector>>> {
         Arc::clone(&self.hub)
     }
@@ -164,3 +392,733 @@ impl YouTubeApiClient {
         Ok(())
     }
 }
+
+/// クォータを消費する YouTube Data API のエンドポイント
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YouTubeEndpoint {
+    Search,
+    Videos,
+    Channels,
+    PlaylistItems,
+    VideoCategories,
+    LiveChatMessages,
+}
+
+impl YouTubeEndpoint {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Search => "search.list",
+            Self::Videos => "videos.list",
+            Self::Channels => "channels.list",
+            Self::PlaylistItems => "playlistItems.list",
+            Self::VideoCategories => "videoCategories.list",
+            Self::LiveChatMessages => "liveChatMessages.list",
+        }
+    }
+
+    /// 1回の呼び出しで消費するクォータ
+    pub fn cost(&self) -> u32 {
+        match self {
+            Self::Search => youtube::QUOTA_COST_SEARCH_LIST,
+            Self::Videos => youtube::QUOTA_COST_VIDEOS_LIST,
+            Self::Channels => youtube::QUOTA_COST_CHANNELS_LIST,
+            Self::PlaylistItems => youtube::QUOTA_COST_PLAYLIST_ITEMS_LIST,
+            Self::VideoCategories => youtube::QUOTA_COST_VIDEO_CATEGORIES_LIST,
+            Self::LiveChatMessages => youtube::QUOTA_COST_LIVE_CHAT_MESSAGES_LIST,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [
+            Self::Search,
+            Self::Videos,
+            Self::Channels,
+            Self::PlaylistItems,
+            Self::VideoCategories,
+            Self::LiveChatMessages,
+        ]
+        .into_iter()
+        .find(|endpoint| endpoint.name() == name)
+    }
+}
+
+/// フィード（Atom）から動画IDを掲載順に取り出す
+pub fn parse_feed_video_ids(xml: &str) -> Vec<String> {
+    const OPEN: &str = "<yt:videoId>";
+    const CLOSE: &str = "</yt:videoId>";
+
+    let mut video_ids = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(OPEN) {
+        rest = &rest[start + OPEN.len()..];
+        let Some(end) = rest.find(CLOSE) else {
+            break;
+        };
+        let video_id = rest[..end].trim();
+        if !video_id.is_empty() {
+            video_ids.push(video_id.to_string());
+        }
+        rest = &rest[end + CLOSE.len()..];
+    }
+    video_ids
+}
+
+/// 動画IDを重複を除いて videos.list の1回分ずつに分割
+fn video_id_batches(video_ids: &[String]) -> Vec<Vec<String>> {
+    let mut seen = HashSet::new();
+    let unique: Vec<String> = video_ids
+        .iter()
+        .filter(|id| seen.insert(id.as_str()))
+        .cloned()
+        .collect();
+    unique
+        .chunks(youtube::VIDEOS_LIST_MAX_IDS)
+        .map(|chunk| chunk.to_vec())
+        .collect()
+}
+
+/// 配信中（開始済みで未終了）のライブを選ぶ（複数ある場合は最も新しく開始したもの）
+pub fn find_live_video(videos: Vec<Video>) -> Option<Video> {
+    videos
+        .into_iter()
+        .filter(|video| {
+            video
+                .live_streaming_details
+                .as_ref()
+                .is_some_and(|details| {
+                    details.actual_start_time.is_some() && details.actual_end_time.is_none()
+                })
+        })
+        .max_by_key(|video| {
+            video
+                .live_streaming_details
+                .as_ref()
+                .and_then(|details| details.actual_start_time)
+        })
+}
+
+/// チャンネルの配信中のライブと配信予定枠
+#[derive(Debug, Default)]
+pub struct ChannelBroadcasts {
+    pub live: Option<Video>,
+    /// liveBroadcastContent が upcoming で予定開始時刻のある動画
+    pub upcoming: Vec<Video>,
+}
+
+/// videos.list の結果を配信中のライブと配信予定枠に振り分け
+pub fn split_broadcasts(videos: Vec<Video>) -> ChannelBroadcasts {
+    let upcoming = videos
+        .iter()
+        .filter(|video| {
+            video
+                .snippet
+                .as_ref()
+                .and_then(|snippet| snippet.live_broadcast_content.as_deref())
+                == Some(youtube::LIVE_BROADCAST_CONTENT_UPCOMING)
+                && video
+                    .live_streaming_details
+                    .as_ref()
+                    .is_some_and(|details| details.scheduled_start_time.is_some())
+        })
+        .cloned()
+        .collect();
+
+    ChannelBroadcasts {
+        live: find_live_video(videos),
+        upcoming,
+    }
+}
+
+/// videoCategories.list の結果をカテゴリID → カテゴリ名に変換
+fn video_category_names(response: VideoCategoryListResponse) -> HashMap<String, String> {
+    response
+        .items
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|category| {
+            let title = category.snippet.and_then(|snippet| snippet.title)?;
+            Some((category.id?, title))
+        })
+        .collect()
+}
+
+/// ユーザーが入力したチャンネルの指定方法
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum YouTubeChannelRef {
+    /// UC で始まる正規のチャンネルID
+    ChannelId(String),
+    /// @ハンドル（@ は含まない）
+    Handle(String),
+    /// /user/ の旧ユーザー名
+    Username(String),
+    /// /c/ のカスタムURL
+    CustomUrl(String),
+    /// watch・youtu.be・/live/ などの動画ID（投稿者のチャンネルを使う）
+    Video(String),
+}
+
+/// 正規のチャンネルID（UC + 22文字）かどうか
+pub fn is_channel_id(value: &str) -> bool {
+    value.len() == 24
+        && value.starts_with("UC")
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// チャンネルの指定（@ハンドル・チャンネルID・各種URL）を解析
+///
+/// スキームや www. は省略可能です。URL以外の文字列はチャンネルIDでなければハンドルとして扱います。
+pub fn parse_channel_reference(input: &str) -> Option<YouTubeChannelRef> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+
+    let looks_like_url = input.contains("youtube.com") || input.contains("youtu.be");
+    if !looks_like_url {
+        if is_channel_id(input) {
+            return Some(YouTubeChannelRef::ChannelId(input.to_string()));
+        }
+        let handle = input.strip_prefix('@').unwrap_or(input);
+        if handle.is_empty() || handle.contains('/') {
+            return None;
+        }
+        return Some(YouTubeChannelRef::Handle(handle.to_string()));
+    }
+
+    let url = if input.contains("://") {
+        url::Url::parse(input).ok()?
+    } else {
+        url::Url::parse(&format!("https://{}", input)).ok()?
+    };
+    let host = url.host_str()?.to_ascii_lowercase();
+    let segments: Vec<String> = url
+        .path_segments()?
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            percent_encoding::percent_decode_str(segment)
+                .decode_utf8_lossy()
+                .into_owned()
+        })
+        .collect();
+    let first = segments.first().map(String::as_str);
+    let second = segments.get(1).cloned();
+
+    if host == "youtu.be" {
+        return match (first, second) {
+            (Some(video_id), None) => Some(YouTubeChannelRef::Video(video_id.to_string())),
+            _ => None,
+        };
+    }
+    if host != "youtube.com" && !host.ends_with(".youtube.com") {
+        return None;
+    }
+
+    match first? {
+        "channel" => second
+            .filter(|id| is_channel_id(id))
+            .map(YouTubeChannelRef::ChannelId),
+        "user" => second.map(YouTubeChannelRef::Username),
+        "c" => second.map(YouTubeChannelRef::CustomUrl),
+        "watch" => url
+            .query_pairs()
+            .find(|(key, _)| key == "v")
+            .map(|(_, id)| YouTubeChannelRef::Video(id.into_owned())),
+        "live" | "shorts" | "embed" => second.map(YouTubeChannelRef::Video),
+        handle if handle.starts_with('@') && handle.len() > 1 => {
+            Some(YouTubeChannelRef::Handle(handle[1..].to_string()))
+        }
+        // youtube.com/{名前} の旧形式はカスタムURLとして扱う
+        name if !name.contains('.') => Some(YouTubeChannelRef::CustomUrl(name.to_string())),
+        _ => None,
+    }
+}
+
+/// クォータの日次予算を分け合うリクエスト元の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum QuotaConsumer {
+    /// 配信検出のポーリング（チャンネルごと）
+    ChannelPoll,
+    /// ライブチャットの取得（配信ごと）
+    LiveChat,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct EndpointUsage {
+    calls: u32,
+    units: u32,
+}
+
+/// 太平洋時間（米国の夏時間を考慮）のUTCからのオフセット
+///
+/// 夏時間: 3月第2日曜 2:00 PST（10:00 UTC）から 11月第1日曜 2:00 PDT（09:00 UTC）まで
+fn pacific_offset(now: DateTime<Utc>) -> ChronoDuration {
+    let year = now.year();
+    let dst_start = NaiveDate::from_weekday_of_month_opt(year, 3, Weekday::Sun, 2)
+        .and_then(|d| d.and_hms_opt(10, 0, 0))
+        .map(|dt| dt.and_utc());
+    let dst_end = NaiveDate::from_weekday_of_month_opt(year, 11, Weekday::Sun, 1)
+        .and_then(|d| d.and_hms_opt(9, 0, 0))
+        .map(|dt| dt.and_utc());
+
+    match (dst_start, dst_end) {
+        (Some(start), Some(end)) if now >= start && now < end => ChronoDuration::hours(-7),
+        _ => ChronoDuration::hours(-8),
+    }
+}
+
+/// クォータの日付（太平洋時間）
+fn quota_date(now: DateTime<Utc>) -> NaiveDate {
+    (now + pacific_offset(now)).date_naive()
+}
+
+/// 次にクォータがリセットされる時刻（翌日の太平洋時間0時）
+fn next_quota_reset(now: DateTime<Utc>) -> DateTime<Utc> {
+    let midnight = quota_date(now)
+        .succ_opt()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|dt| dt.and_utc())
+        .unwrap_or(now);
+    // 0時時点のオフセットで換算する（夏時間の切り替えは2時のため0時をまたがない）
+    midnight - pacific_offset(midnight + ChronoDuration::hours(8))
+}
+
+/// YouTube Data API クォータトラッカー
+///
+/// エンドポイントごとの消費量を太平洋時間の日付単位で集計します。
+/// 未保存の使用量は `save_quota_usage` でDBへ書き出し、起動時に `restore` で当日分を復元します。
+pub struct YouTubeQuotaTracker {
+    quota_date: NaiveDate,
+    usage: BTreeMap<&'static str, EndpointUsage>,
+    /// DB未保存の使用量（日付をまたいだ場合に備えて日付ごとに保持）
+    pending: BTreeMap<(NaiveDate, &'static str), EndpointUsage>,
+    daily_budget: u32,
+    /// 種類 → (リクエスト元の数, 1リクエストあたりのユニット数)
+    consumers: BTreeMap<QuotaConsumer, (usize, u32)>,
+}
+
+impl YouTubeQuotaTracker {
+    pub fn new(daily_budget: u32) -> Self {
+        Self {
+            quota_date: quota_date(Utc::now()),
+            usage: BTreeMap::new(),
+            pending: BTreeMap::new(),
+            daily_budget: daily_budget.max(1),
+            consumers: BTreeMap::new(),
+        }
+    }
+
+    /// 日付が変わっていれば当日の使用量をリセット
+    fn roll_over(&mut self, now: DateTime<Utc>) {
+        let today = quota_date(now);
+        if today != self.quota_date {
+            self.quota_date = today;
+            self.usage.clear();
+        }
+    }
+
+    /// リクエストを記録
+    pub fn track(&mut self, endpoint: YouTubeEndpoint) {
+        self.track_at(endpoint, Utc::now());
+    }
+
+    fn track_at(&mut self, endpoint: YouTubeEndpoint, now: DateTime<Utc>) {
+        self.roll_over(now);
+        let usage = self.usage.entry(endpoint.name()).or_default();
+        usage.calls += 1;
+        usage.units += endpoint.cost();
+
+        let pending = self
+            .pending
+            .entry((self.quota_date, endpoint.name()))
+            .or_default();
+        pending.calls += 1;
+        pending.units += endpoint.cost();
+    }
+
+    /// DBに保存されていた当日の使用量を復元
+    pub fn restore(&mut self, rows: &[QuotaUsageRow]) {
+        for row in rows {
+            let Some(endpoint) = YouTubeEndpoint::from_name(&row.endpoint) else {
+                continue;
+            };
+            let usage = self.usage.entry(endpoint.name()).or_default();
+            usage.calls += row.calls;
+            usage.units += row.units;
+        }
+    }
+
+    /// 未保存の使用量を取り出す
+    fn take_pending(&mut self) -> Vec<(NaiveDate, QuotaUsageRow)> {
+        std::mem::take(&mut self.pending)
+            .into_iter()
+            .map(|((date, endpoint), usage)| {
+                (
+                    date,
+                    QuotaUsageRow {
+                        endpoint: endpoint.to_string(),
+                        calls: usage.calls,
+                        units: usage.units,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// 保存に失敗した使用量を未保存に戻す
+    fn return_pending(&mut self, rows: Vec<(NaiveDate, QuotaUsageRow)>) {
+        for (date, row) in rows {
+            let Some(endpoint) = YouTubeEndpoint::from_name(&row.endpoint) else {
+                continue;
+            };
+            let usage = self.pending.entry((date, endpoint.name())).or_default();
+            usage.calls += row.calls;
+            usage.units += row.units;
+        }
+    }
+
+    /// 当日のクォータ日付（YYYY-MM-DD）
+    pub fn quota_date(&self) -> String {
+        self.quota_date.to_string()
+    }
+
+    fn units_used(&self) -> u32 {
+        self.usage.values().map(|usage| usage.units).sum()
+    }
+
+    /// 日次予算を分け合うリクエスト元の数を設定
+    pub fn set_consumers(&mut self, kind: QuotaConsumer, count: usize, units_per_request: u32) {
+        self.consumers.insert(kind, (count, units_per_request));
+    }
+
+    /// 日次予算内に収まるリクエスト間隔
+    ///
+    /// 登録されたすべてのリクエスト元（ポーリング中のチャンネルと収集中のライブチャット）が
+    /// 同じ間隔でリクエストする場合に、残りの予算を次のリセットまでの時間で均等に使える最小間隔です。
+    /// 予算が足りない場合はリセットまでの時間を返します。
+    pub fn budget_interval(&self) -> Duration {
+        self.budget_interval_at(Utc::now())
+    }
+
+    fn budget_interval_at(&self, now: DateTime<Utc>) -> Duration {
+        let resets_in = (next_quota_reset(now) - now).to_std().unwrap_or_default();
+        let used = if quota_date(now) == self.quota_date {
+            self.units_used()
+        } else {
+            0
+        };
+        let remaining = self.daily_budget.saturating_sub(used) as u64;
+        let units_per_round: u64 = self
+            .consumers
+            .values()
+            .map(|(count, units)| *count as u64 * *units as u64)
+            .sum::<u64>()
+            .max(1);
+        if remaining < units_per_round {
+            return resets_in;
+        }
+
+        Duration::from_secs(resets_in.as_secs() * units_per_round / remaining)
+    }
+
+    /// 現在のステータスを取得
+    pub fn get_status(&self) -> YouTubeQuotaStatus {
+        let now = Utc::now();
+        let today = quota_date(now);
+        let units_used = if today == self.quota_date {
+            self.units_used()
+        } else {
+            0
+        };
+
+        let endpoints = if today == self.quota_date {
+            self.usage
+                .iter()
+                .filter_map(|(name, usage)| {
+                    let endpoint = YouTubeEndpoint::from_name(name)?;
+                    Some(YouTubeEndpointUsage {
+                        endpoint: endpoint.name().to_string(),
+                        unit_cost: endpoint.cost(),
+                        calls: usage.calls,
+                        units: usage.units,
+                    })
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        YouTubeQuotaStatus {
+            quota_date: today.to_string(),
+            units_used,
+            daily_budget: self.daily_budget,
+            daily_limit: youtube::QUOTA_DAILY_LIMIT,
+            units_remaining: self.daily_budget.saturating_sub(units_used),
+            usage_percent: (units_used as f32 / self.daily_budget as f32) * 100.0,
+            resets_at: next_quota_reset(now).with_timezone(&Local).to_rfc3339(),
+            resets_in_seconds: (next_quota_reset(now) - now).num_seconds().max(0) as u64,
+            endpoints,
+        }
+    }
+}
+
+/// 当日のクォータ使用量をDBから読み込んでトラッカーに復元
+pub async fn load_quota_usage(
+    tracker: &Mutex<YouTubeQuotaTracker>,
+    db_manager: &DatabaseManager,
+) -> Result<(), duckdb::Error> {
+    let quota_date = tracker.lock().await.quota_date();
+    let rows = db_manager
+        .with_connection(|conn| YouTubeQuotaRepository::get_usage(conn, &quota_date))
+        .await?;
+    tracker.lock().await.restore(&rows);
+    Ok(())
+}
+
+/// 未保存のクォータ使用量をDBへ書き出す（失敗した分は次回に再試行）
+pub async fn save_quota_usage(
+    tracker: &Mutex<YouTubeQuotaTracker>,
+    db_manager: &DatabaseManager,
+) -> Result<(), duckdb::Error> {
+    let pending = tracker.lock().await.take_pending();
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    let updated_at = Local::now().to_rfc3339();
+    let result = db_manager
+        .with_connection(|conn| {
+            for (date, row) in &pending {
+                YouTubeQuotaRepository::add_usage(conn, &date.to_string(), row, &updated_at)?;
+            }
+            Ok::<(), duckdb::Error>(())
+        })
+        .await;
+
+    if result.is_err() {
+        tracker.lock().await.return_pending(pending);
+    }
+    result
+}
+
+/// エンドポイント別のクォータ使用量
+#[derive(Debug, Clone, Serialize)]
+pub struct YouTubeEndpointUsage {
+    pub endpoint: String,
+    /// 1回あたりの消費量
+    pub unit_cost: u32,
+    pub calls: u32,
+    pub units: u32,
+}
+
+/// YouTube Data API クォータのステータス情報
+#[derive(Debug, Clone, Serialize)]
+pub struct YouTubeQuotaStatus {
+    /// クォータの日付（太平洋時間）
+    pub quota_date: String,
+    /// 当日の消費量
+    pub units_used: u32,
+    /// 設定された1日の予算
+    pub daily_budget: u32,
+    /// APIの1日の上限
+    pub daily_limit: u32,
+    /// 予算の残り
+    pub units_remaining: u32,
+    /// 予算に対する使用率（0.0 - 100.0）
+    pub usage_percent: f32,
+    /// 次のリセット時刻（ローカル時刻のRFC3339）
+    pub resets_at: String,
+    pub resets_in_seconds: u64,
+    pub endpoints: Vec<YouTubeEndpointUsage>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use google_youtube3::api::VideoListResponse;
+
+    #[test]
+    fn test_parse_feed_video_ids() {
+        let xml = include_str!("fixtures/youtube_channel_feed.xml");
+        assert_eq!(
+            parse_feed_video_ids(xml),
+            vec!["liveVid0001", "upcoming0002", "archive0003"]
+        );
+        assert!(parse_feed_video_ids("<feed></feed>").is_empty());
+    }
+
+    #[test]
+    fn test_video_id_batches_dedup_and_split() {
+        let mut video_ids: Vec<String> = (0..120).map(|i| format!("video{:03}", i)).collect();
+        video_ids.push("video000".to_string());
+
+        let batches = video_id_batches(&video_ids);
+        let sizes: Vec<usize> = batches.iter().map(|batch| batch.len()).collect();
+        assert_eq!(sizes, vec![50, 50, 20]);
+        assert!(video_id_batches(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_find_live_video_from_videos_list() {
+        let response: VideoListResponse =
+            serde_json::from_str(include_str!("fixtures/youtube_videos_list.json")).unwrap();
+        let live = find_live_video(response.items.unwrap()).unwrap();
+        assert_eq!(live.id.as_deref(), Some("liveVid0001"));
+        assert_eq!(
+            live.live_streaming_details
+                .and_then(|details| details.concurrent_viewers),
+            Some(1234)
+        );
+    }
+
+    #[test]
+    fn test_split_broadcasts_from_videos_list() {
+        let response: VideoListResponse =
+            serde_json::from_str(include_str!("fixtures/youtube_videos_list.json")).unwrap();
+        let broadcasts = split_broadcasts(response.items.unwrap());
+        assert_eq!(
+            broadcasts.live.and_then(|video| video.id).as_deref(),
+            Some("liveVid0001")
+        );
+        let upcoming: Vec<&str> = broadcasts
+            .upcoming
+            .iter()
+            .filter_map(|video| video.id.as_deref())
+            .collect();
+        assert_eq!(upcoming, vec!["upcoming0002"]);
+    }
+
+    #[test]
+    fn test_video_category_names() {
+        let response: VideoCategoryListResponse = serde_json::from_str(
+            r#"{
+                "kind": "youtube#videoCategoryListResponse",
+                "items": [
+                    {"kind": "youtube#videoCategory", "id": "20", "snippet": {"title": "Gaming", "assignable": true}},
+                    {"kind": "youtube#videoCategory", "id": "24", "snippet": {"title": "Entertainment", "assignable": true}},
+                    {"kind": "youtube#videoCategory", "id": "99"}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let names = video_category_names(response);
+        assert_eq!(names.len(), 2);
+        assert_eq!(names.get("20").map(String::as_str), Some("Gaming"));
+        assert_eq!(names.get("24").map(String::as_str), Some("Entertainment"));
+    }
+
+    #[test]
+    fn test_parse_channel_reference() {
+        use YouTubeChannelRef::*;
+        let channel_id = "UCabcdefghijklmnopqrstuv";
+        let cases = [
+            (
+                channel_id.to_string(),
+                Some(ChannelId(channel_id.to_string())),
+            ),
+            (
+                format!("https://www.youtube.com/channel/{}/videos", channel_id),
+                Some(ChannelId(channel_id.to_string())),
+            ),
+            ("@Example".to_string(), Some(Handle("Example".to_string()))),
+            (
+                "youtube.com/@Example/live".to_string(),
+                Some(Handle("Example".to_string())),
+            ),
+            (
+                "https://www.youtube.com/@%E3%83%86%E3%82%B9%E3%83%88".to_string(),
+                Some(Handle("テスト".to_string())),
+            ),
+            (
+                "https://www.youtube.com/user/OldName".to_string(),
+                Some(Username("OldName".to_string())),
+            ),
+            (
+                "https://youtube.com/c/CustomName".to_string(),
+                Some(CustomUrl("CustomName".to_string())),
+            ),
+            (
+                "https://m.youtube.com/watch?v=liveVid0001&t=10".to_string(),
+                Some(Video("liveVid0001".to_string())),
+            ),
+            (
+                "https://youtu.be/liveVid0001?si=share".to_string(),
+                Some(Video("liveVid0001".to_string())),
+            ),
+            (
+                "https://www.youtube.com/live/liveVid0001".to_string(),
+                Some(Video("liveVid0001".to_string())),
+            ),
+            ("https://www.youtube.com/channel/short".to_string(), None),
+            ("https://example.com/@Example".to_string(), None),
+            ("   ".to_string(), None),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                parse_channel_reference(&input),
+                expected,
+                "input: {}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_quota_date_follows_pacific_time() {
+        // 冬時間（PST, UTC-8）: 1/6 07:59 UTC は 1/5
+        let winter = Utc.with_ymd_and_hms(2025, 1, 6, 7, 59, 0).unwrap();
+        assert_eq!(quota_date(winter).to_string(), "2025-01-05");
+        assert_eq!(
+            next_quota_reset(winter),
+            Utc.with_ymd_and_hms(2025, 1, 6, 8, 0, 0).unwrap()
+        );
+
+        // 夏時間（PDT, UTC-7）: 7/1 07:00 UTC は 7/1 0:00
+        let summer = Utc.with_ymd_and_hms(2025, 7, 1, 7, 0, 0).unwrap();
+        assert_eq!(quota_date(summer).to_string(), "2025-07-01");
+
+        // 夏時間開始日（2025-03-09）の前夜はリセットが 07:00 UTC になる
+        let before_dst = Utc.with_ymd_and_hms(2025, 3, 9, 9, 0, 0).unwrap();
+        assert_eq!(
+            next_quota_reset(before_dst),
+            Utc.with_ymd_and_hms(2025, 3, 10, 7, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_budget_interval_stretches_with_usage() {
+        let now = Utc.with_ymd_and_hms(2025, 1, 6, 8, 0, 0).unwrap(); // 太平洋時間 0:00
+        let mut tracker = YouTubeQuotaTracker::new(10_000);
+        tracker.quota_date = quota_date(now);
+
+        // 10,000 units を24時間で、101 units のポーリングを2チャンネル分
+        tracker.set_consumers(QuotaConsumer::ChannelPoll, 2, 101);
+        assert_eq!(
+            tracker.budget_interval_at(now),
+            Duration::from_secs(86_400 * 202 / 10_000)
+        );
+
+        // ライブチャットの取得も同じ予算を分け合う
+        tracker.set_consumers(QuotaConsumer::LiveChat, 1, 5);
+        assert_eq!(
+            tracker.budget_interval_at(now),
+            Duration::from_secs(86_400 * 207 / 10_000)
+        );
+
+        for _ in 0..99 {
+            tracker.track_at(YouTubeEndpoint::Search, now);
+        }
+        assert_eq!(tracker.units_used(), 9_900);
+        assert_eq!(tracker.budget_interval_at(now), Duration::from_secs(86_400));
+
+        // 日付が変われば使用量はリセットされる
+        let next_day = now + ChronoDuration::days(1);
+        tracker.track_at(YouTubeEndpoint::Videos, next_day);
+        assert_eq!(tracker.units_used(), 1);
+        assert_eq!(tracker.take_pending().len(), 2);
+    }
+}
@@ -1,29 +1,54 @@
-use crate::constants::youtube;
-use crate::database::models::ChatMessage;
-use google_youtube3::api::LiveChatMessage;
+use crate::api::youtube_api::{save_quota_usage, YouTubeEndpoint, YouTubeQuotaTracker};
+use crate::collectors::replay;
+use crate::constants::{database as db_constants, youtube};
+use crate::database::models::{ChatMessage, MonetizationEvent};
+use crate::database::repositories::MonetizationRepository;
+use crate::database::DatabaseManager;
+use crate::logger::AppLogger;
+use crate::websocket::twitch_irc::IngestCounters;
+use chrono::Local;
+use google_youtube3::api::{LiveChatMessage, LiveChatMessageSnippet};
 use google_youtube3::{hyper_rustls, hyper_util, YouTube};
 use hyper_util::client::legacy::connect::HttpConnector;
 use std::sync::Arc;
-use tokio::sync::{mpsc, Mutex};
-use tokio::time::{interval, Duration, MissedTickBehavior};
+use std::time::Instant;
+use tokio::sync::{watch, Mutex};
+use tokio::time::{sleep, Duration};
+
+type YouTubeHub = YouTube<hyper_rustls::HttpsConnector<HttpConnector>>;
+
+/// liveChatMessages.list 1回分の取得結果
+pub struct LiveChatPage {
+    pub messages: Vec<ChatMessage>,
+    /// Super Chat・Super Sticker・メンバーシップ等の収益イベント
+    pub monetization_events: Vec<MonetizationEvent>,
+    /// 次回の取得までに待つ時間（APIが返す pollingIntervalMillis）
+    pub polling_interval: Duration,
+    /// チャットが終了している（offlineAt が設定されている）
+    pub is_offline: bool,
+}
 
 /// YouTube Live Chat APIクライアント
-#[allow(dead_code)]
 pub struct YouTubeLiveChatClient {
-    hub: Arc<YouTube<hyper_rustls::HttpsConnector<HttpConnector>>>,
+    hub: Arc<YouTubeHub>,
+    quota_tracker: Arc<Mutex<YouTubeQuotaTracker>>,
+    channel_id: i64,
     stream_id: i64,
     live_chat_id: Option<String>,
     next_page_token: Option<String>,
 }
 
-#[allow(dead_code)]
 impl YouTubeLiveChatClient {
     pub fn new(
-        hub: Arc<YouTube<hyper_rustls::HttpsConnector<HttpConnector>>>,
+        hub: Arc<YouTubeHub>,
+        quota_tracker: Arc<Mutex<YouTubeQuotaTracker>>,
+        channel_id: i64,
         stream_id: i64,
     ) -> Self {
         Self {
             hub,
+            quota_tracker,
+            channel_id,
             stream_id,
             live_chat_id: None,
             next_page_token: None,
@@ -41,8 +66,12 @@ impl YouTubeLiveChatClient {
         &mut self,
         video_id: &str,
     ) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
-        let part = vec!["liveStreamingDetails".to_string()];
+        let part = vec![youtube::PART_LIVE_STREAMING_DETAILS.to_string()];
 
+        self.quota_tracker
+            .lock()
+            .await
+            .track(YouTubeEndpoint::Videos);
         let (_, response) = self
             .hub
             .videos()
@@ -51,29 +80,32 @@ impl YouTubeLiveChatClient {
             .doit()
             .await?;
 
-        if let Some(videos) = response.items {
-            if let Some(video) = videos.into_iter().next() {
-                if let Some(details) = video.live_streaming_details {
-                    if let Some(chat_id) = details.active_live_chat_id {
-                        self.set_live_chat_id(chat_id.clone());
-                        return Ok(Some(chat_id));
-                    }
-                }
-            }
+        let live_chat_id = response
+            .items
+            .and_then(|videos| videos.into_iter().next())
+            .and_then(|video| video.live_streaming_details)
+            .and_then(|details| details.active_live_chat_id);
+
+        if let Some(chat_id) = &live_chat_id {
+            self.set_live_chat_id(chat_id.clone());
         }
 
-        Ok(None)
+        Ok(live_chat_id)
     }
 
     /// ライブチャットメッセージを取得
     pub async fn fetch_chat_messages(
         &mut self,
-    ) -> Result<Vec<ChatMessage>, Box<dyn std::error::Error + Send + Sync>> {
-        if self.live_chat_id.is_none() {
-            return Ok(vec![]);
-        }
+    ) -> Result<LiveChatPage, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(live_chat_id) = self.live_chat_id.as_ref() else {
+            return Ok(LiveChatPage {
+                messages: vec![],
+                monetization_events: vec![],
+                polling_interval: polling_interval(None),
+                is_offline: false,
+            });
+        };
 
-        let live_chat_id = self.live_chat_id.as_ref().unwrap();
         let part = vec![
             youtube::PART_SNIPPET.to_string(),
             youtube::PART_AUTHOR_DETAILS.to_string(),
@@ -85,242 +117,527 @@ impl YouTubeLiveChatClient {
             request = request.page_token(page_token);
         }
 
+        self.quota_tracker
+            .lock()
+            .await
+            .track(YouTubeEndpoint::LiveChatMessages);
         let (_, response) = request.doit().await?;
 
         // 次のページトークンを保存
         self.next_page_token = response.next_page_token;
 
-        let mut chat_messages = Vec::new();
-
-        if let Some(items) = response.items {
-            for item in items {
-                if let Some(chat_message) = self.convert_to_chat_message(item) {
-                    chat_messages.push(chat_message);
-                }
+        let mut messages = Vec::new();
+        let mut monetization_events = Vec::new();
+        for item in response.items.unwrap_or_default() {
+            if let Some(event) = to_monetization_event(&item, self.channel_id, self.stream_id) {
+                monetization_events.push(event);
+            }
+            if let Some(message) = to_chat_message(item, self.channel_id, self.stream_id) {
+                messages.push(message);
             }
         }
 
-        Ok(chat_messages)
+        Ok(LiveChatPage {
+            messages,
+            monetization_events,
+            polling_interval: polling_interval(response.polling_interval_millis),
+            is_offline: response.offline_at.is_some(),
+        })
     }
+}
 
-    /// LiveChatMessageをChatMessageに変換
-    fn convert_to_chat_message(&self, live_chat_message: LiveChatMessage) -> Option<ChatMessage> {
-        let snippet = live_chat_message.snippet?;
-        let author_details = live_chat_message.author_details?;
-        let message_text = snippet.display_message.clone()?;
-        let message_type = self.determine_message_type(&snippet);
-
-        let user_id = author_details.channel_id;
-        let user_name = author_details.display_name?;
-        let published_at = snippet.published_at?;
-
-        // YouTubeのタイムスタンプはすでにRFC3339形式
-        let timestamp = published_at;
-
-        Some(ChatMessage {
-            id: None,
-            channel_id: None, // YouTube の場合は channel_id を保存しない
-            display_name: Some(user_name.clone()),
-            stream_id: Some(self.stream_id),
-            timestamp: timestamp.to_string(),
-            platform: youtube::PLATFORM_NAME.to_string(),
-            user_id,
-            user_name: user_name.clone(),
-            message: message_text,
-            message_type,
-            badges: None,     // YouTube の場合は badges を保存しない（現状未対応）
-            badge_info: None, // YouTube の場合は badge_info も未対応
+/// 再試行しても回復しない取得エラーか（チャット終了・無効化・権限なしなど）
+fn is_terminal_error(err: &(dyn std::error::Error + 'static)) -> bool {
+    let Some(google_youtube3::Error::BadRequest(body)) =
+        err.downcast_ref::<google_youtube3::Error>()
+    else {
+        return false;
+    };
+    body.pointer("/error/errors")
+        .and_then(|errors| errors.as_array())
+        .is_some_and(|errors| {
+            errors.iter().any(|error| {
+                error
+                    .get("reason")
+                    .and_then(|reason| reason.as_str())
+                    .is_some_and(|reason| youtube::LIVE_CHAT_TERMINAL_REASONS.contains(&reason))
+            })
         })
-    }
+}
 
-    /// メッセージタイプを決定
-    fn determine_message_type(
-        &self,
-        snippet: &google_youtube3::api::LiveChatMessageSnippet,
-    ) -> String {
-        if snippet.super_chat_details.is_some() {
-            youtube::MESSAGE_TYPE_SUPERCHAT.to_string()
-        } else if snippet.fan_funding_event_details.is_some() {
-            youtube::MESSAGE_TYPE_FAN_FUNDING.to_string()
-        } else if snippet.new_sponsor_details.is_some() {
-            youtube::MESSAGE_TYPE_SPONSOR.to_string()
-        } else {
-            youtube::MESSAGE_TYPE_NORMAL.to_string()
-        }
+/// APIが指定した取得間隔を Duration に変換（未指定・過小な値は補正）
+fn polling_interval(polling_interval_millis: Option<u32>) -> Duration {
+    let millis = polling_interval_millis
+        .map(u64::from)
+        .unwrap_or(youtube::LIVE_CHAT_DEFAULT_POLL_INTERVAL_MS)
+        .max(youtube::LIVE_CHAT_MIN_POLL_INTERVAL_MS);
+    Duration::from_millis(millis)
+}
+
+/// LiveChatMessageをChatMessageに変換
+fn to_chat_message(
+    live_chat_message: LiveChatMessage,
+    channel_id: i64,
+    stream_id: i64,
+) -> Option<ChatMessage> {
+    let platform_message_id = live_chat_message.id;
+    let snippet = live_chat_message.snippet?;
+    let author_details = live_chat_message.author_details?;
+    let message_text = snippet.display_message.clone()?;
+    let message_type = determine_message_type(&snippet);
+
+    let user_id = author_details.channel_id;
+    let user_name = author_details.display_name?;
+    // 他プラットフォームと同じくローカル時刻のRFC3339で保存
+    let timestamp = snippet.published_at?.with_timezone(&Local).to_rfc3339();
+
+    Some(ChatMessage {
+        id: None,
+        channel_id: Some(channel_id),
+        display_name: Some(user_name.clone()),
+        stream_id: Some(stream_id),
+        timestamp,
+        platform: youtube::PLATFORM_NAME.to_string(),
+        user_id,
+        user_name,
+        message: message_text,
+        message_type,
+        badges: None,     // YouTube の場合は badges を保存しない（現状未対応）
+        badge_info: None, // YouTube の場合は badge_info も未対応
+        payload: None,
+        platform_message_id,
+        emotes: None, // YouTube のカスタム絵文字は未対応
+        bits: None,
+        reply_parent_id: None,
+        reply_parent_user_name: None,
+        reply_thread_id: None,
+        is_auto_discovered: false,
+    })
+}
+
+/// メッセージタイプを決定
+fn determine_message_type(snippet: &LiveChatMessageSnippet) -> String {
+    if snippet.super_chat_details.is_some() {
+        youtube::MESSAGE_TYPE_SUPERCHAT.to_string()
+    } else if snippet.super_sticker_details.is_some() {
+        youtube::MESSAGE_TYPE_SUPERSTICKER.to_string()
+    } else if snippet.fan_funding_event_details.is_some() {
+        youtube::MESSAGE_TYPE_FAN_FUNDING.to_string()
+    } else if snippet.new_sponsor_details.is_some() {
+        youtube::MESSAGE_TYPE_SPONSOR.to_string()
+    } else if snippet.member_milestone_chat_details.is_some() {
+        youtube::MESSAGE_TYPE_MEMBER_MILESTONE.to_string()
+    } else if snippet.membership_gifting_details.is_some() {
+        youtube::MESSAGE_TYPE_MEMBERSHIP_GIFTING.to_string()
+    } else if snippet.gift_membership_received_details.is_some() {
+        youtube::MESSAGE_TYPE_GIFT_MEMBERSHIP_RECEIVED.to_string()
+    } else {
+        youtube::MESSAGE_TYPE_NORMAL.to_string()
     }
+}
 
-    /// 定期的にチャットメッセージを収集してチャンネルに送信
-    pub async fn start_collection(
-        &mut self,
-        message_tx: tokio::sync::mpsc::UnboundedSender<crate::database::models::ChatMessage>,
-        poll_interval_secs: u64,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let mut interval = interval(Duration::from_secs(poll_interval_secs));
-        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
-
-        println!(
-            "Starting YouTube live chat collection for stream_id: {}",
-            self.stream_id
-        );
+/// 収益に関わるメッセージから金額・ティア・メンバーシップ情報を取り出す
+///
+/// 通常のチャットは None。chat_messages とは platform_message_id で対応します。
+fn to_monetization_event(
+    live_chat_message: &LiveChatMessage,
+    channel_id: i64,
+    stream_id: i64,
+) -> Option<MonetizationEvent> {
+    let platform_message_id = live_chat_message.id.clone()?;
+    let snippet = live_chat_message.snippet.as_ref()?;
+    let event_type = determine_message_type(snippet);
+    if event_type == youtube::MESSAGE_TYPE_NORMAL {
+        return None;
+    }
 
-        loop {
-            interval.tick().await;
-
-            match self.fetch_chat_messages().await {
-                Ok(messages) => {
-                    let message_count = messages.len();
-                    if message_count > 0 {
-                        for message in messages {
-                            if let Err(e) = message_tx.send(message) {
-                                eprintln!("Failed to send YouTube chat message: {}", e);
-                                // エラーが発生しても継続
-                            }
-                        }
-                        println!("Sent {} YouTube chat messages to channel", message_count);
-                    }
-                }
-                Err(e) => {
-                    eprintln!("Failed to fetch YouTube chat messages: {}", e);
-                    // エラーが発生しても継続
-                }
-            }
-        }
+    let mut event = MonetizationEvent {
+        id: None,
+        platform: youtube::PLATFORM_NAME.to_string(),
+        platform_message_id,
+        channel_id: Some(channel_id),
+        stream_id: Some(stream_id),
+        event_type,
+        amount_micros: None,
+        currency: None,
+        amount_display: None,
+        tier: None,
+        membership_level: None,
+        membership_months: None,
+        gift_count: None,
+        sticker_id: None,
+        timestamp: snippet.published_at?.with_timezone(&Local).to_rfc3339(),
+    };
+
+    if let Some(details) = &snippet.super_chat_details {
+        event.amount_micros = details.amount_micros.map(|m| m as i64);
+        event.currency = details.currency.clone();
+        event.amount_display = details.amount_display_string.clone();
+        event.tier = details.tier.map(|t| t as i32);
+    } else if let Some(details) = &snippet.super_sticker_details {
+        event.amount_micros = details.amount_micros.map(|m| m as i64);
+        event.currency = details.currency.clone();
+        event.amount_display = details.amount_display_string.clone();
+        event.tier = details.tier.map(|t| t as i32);
+        event.sticker_id = details
+            .super_sticker_metadata
+            .as_ref()
+            .and_then(|metadata| metadata.sticker_id.clone());
+    } else if let Some(details) = &snippet.fan_funding_event_details {
+        event.amount_micros = details.amount_micros.map(|m| m as i64);
+        event.currency = details.currency.clone();
+        event.amount_display = details.amount_display_string.clone();
+    } else if let Some(details) = &snippet.new_sponsor_details {
+        event.membership_level = details.member_level_name.clone();
+    } else if let Some(details) = &snippet.member_milestone_chat_details {
+        event.membership_level = details.member_level_name.clone();
+        event.membership_months = details.member_month.map(|m| m as i32);
+    } else if let Some(details) = &snippet.membership_gifting_details {
+        event.membership_level = details.gift_memberships_level_name.clone();
+        event.gift_count = details.gift_memberships_count;
+    } else if let Some(details) = &snippet.gift_membership_received_details {
+        event.membership_level = details.member_level_name.clone();
     }
+
+    Some(event)
 }
 
-/// YouTube Live Chatコレクター（コレクター統合用）
-#[allow(dead_code)]
+/// 1本のライブ配信のチャット収集タスク
+///
+/// ライブチャットが終了する（offlineAt が返る）まで、APIが指定する間隔で取得して保存します。
+/// 停止の指示を受けた場合やコレクターが破棄された場合は、保存待ちのメッセージを書き込んでから終了します。
 pub struct YouTubeLiveChatCollector {
-    client: Arc<Mutex<YouTubeLiveChatClient>>,
-    message_tx: mpsc::UnboundedSender<ChatMessage>,
-    db_handler_task: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    video_id: String,
+    stop_tx: watch::Sender<bool>,
 }
 
-#[allow(dead_code)]
 impl YouTubeLiveChatCollector {
-    pub fn new(
-        hub: Arc<YouTube<hyper_rustls::HttpsConnector<HttpConnector>>>,
-        db_conn: Arc<Mutex<duckdb::Connection>>,
+    /// ビデオIDからライブチャットIDを解決して収集を開始
+    pub async fn start(
+        hub: Arc<YouTubeHub>,
+        quota_tracker: Arc<Mutex<YouTubeQuotaTracker>>,
+        db_manager: Arc<DatabaseManager>,
+        counters: Arc<IngestCounters>,
+        logger: Arc<AppLogger>,
+        channel_id: i64,
         stream_id: i64,
-    ) -> Self {
-        let (message_tx, message_rx) = mpsc::unbounded_channel();
-
-        // データベース書き込みハンドラーを起動
-        let db_conn_clone = Arc::clone(&db_conn);
-        let db_handler_task = tokio::spawn(async move {
-            Self::start_db_handler(db_conn_clone, message_rx).await;
-        });
-
-        let client = YouTubeLiveChatClient::new(Arc::clone(&hub), stream_id);
-        Self {
-            client: Arc::new(Mutex::new(client)),
-            message_tx,
-            db_handler_task: Arc::new(Mutex::new(Some(db_handler_task))),
-        }
-    }
-
-    /// ライブチャットIDを設定してコレクションを開始
-    pub async fn start_with_video_id(
-        &self,
         video_id: &str,
-        poll_interval_secs: u64,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let mut client = self.client.lock().await;
-
-        // ビデオIDからライブチャットIDを取得
-        let video_id_clone = video_id.to_string();
-        if let Some(live_chat_id) = client.get_live_chat_id_from_video(&video_id_clone).await? {
-            println!(
-                "Found live chat ID: {} for video: {}",
-                live_chat_id, video_id
-            );
-
-            // コレクションを開始 - clientを独立したインスタンスとして作成
-            let message_tx_clone = self.message_tx.clone();
-            let mut client_for_task = YouTubeLiveChatClient::new(
-                Arc::clone(&self.client.lock().await.hub),
-                self.client.lock().await.stream_id,
-            );
-            let collection_task = tokio::spawn(async move {
-                if let Err(e) = client_for_task
-                    .start_collection(message_tx_clone, poll_interval_secs)
-                    .await
-                {
-                    eprintln!(
-                        "YouTube live chat collection failed for video {}: {}",
-                        video_id_clone, e
-                    );
-                }
-            });
-
-            let mut db_handler_task = self.db_handler_task.lock().await;
-            *db_handler_task = Some(collection_task);
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut client =
+            YouTubeLiveChatClient::new(hub, Arc::clone(&quota_tracker), channel_id, stream_id);
+        let live_chat_id = client
+            .get_live_chat_id_from_video(video_id)
+            .await?
+            .ok_or("Live chat not available for this video")?;
+
+        logger.info(&format!(
+            "[YouTubeChat] Found live chat ID {} for video {} (channel {})",
+            live_chat_id, video_id, channel_id
+        ));
+
+        let (stop_tx, stop_rx) = watch::channel(false);
+        tokio::spawn(Self::run_collection(
+            client,
+            quota_tracker,
+            db_manager,
+            counters,
+            logger,
+            video_id.to_string(),
+            stop_rx,
+        ));
+
+        Ok(Self {
+            video_id: video_id.to_string(),
+            stop_tx,
+        })
+    }
 
-            Ok(())
-        } else {
-            Err("Live chat not available for this video".into())
-        }
+    /// 収集対象のビデオID
+    pub fn video_id(&self) -> &str {
+        &self.video_id
     }
 
-    /// コレクションを停止
-    pub async fn stop_collection(&self) {
-        let mut task = self.db_handler_task.lock().await;
-        if let Some(task_handle) = task.take() {
-            task_handle.abort();
-        }
+    /// コレクションを停止（保存待ちのメッセージは書き込んでから終了）
+    pub fn stop(&self) {
+        let _ = self.stop_tx.send(true);
     }
 
-    /// データベース書き込みハンドラーを起動
-    async fn start_db_handler(
-        db_conn: Arc<Mutex<duckdb::Connection>>,
-        mut message_rx: mpsc::UnboundedReceiver<ChatMessage>,
+    /// 取得と保存を繰り返す
+    ///
+    /// 保存に失敗したメッセージは CHAT_PENDING_BATCH_CAPACITY 件まで保持し、次回の取得時にまとめて再試行します。
+    /// 取得間隔はAPIの指定に従いますが、クォータの日次予算を超える場合はさらに延長します。
+    /// チャットの終了・無効化など再試行しても回復しないエラーの場合は収集を終了します。
+    async fn run_collection(
+        mut client: YouTubeLiveChatClient,
+        quota_tracker: Arc<Mutex<YouTubeQuotaTracker>>,
+        db_manager: Arc<DatabaseManager>,
+        counters: Arc<IngestCounters>,
+        logger: Arc<AppLogger>,
+        video_id: String,
+        mut stop_rx: watch::Receiver<bool>,
     ) {
-        let mut batch = Vec::new();
-        let mut interval = tokio::time::interval(Duration::from_secs(5));
+        let mut pending: Vec<ChatMessage> = Vec::new();
+        let mut pending_events: Vec<MonetizationEvent> = Vec::new();
+        let quota_save_interval = Duration::from_secs(youtube::LIVE_CHAT_QUOTA_SAVE_INTERVAL_SECS);
+        let mut quota_saved_at = Instant::now();
 
         loop {
-            tokio::select! {
-                msg = message_rx.recv() => {
-                    match msg {
-                        Some(chat_message) => {
-                            batch.push(chat_message);
-                            // バッチサイズに達したら即座に書き込み
-                            if batch.len() >= 100 {
-                                Self::flush_batch(&db_conn, &mut batch).await;
-                            }
-                        }
-                        None => break, // チャンネルが閉じられた
+            let wait = match client.fetch_chat_messages().await {
+                Ok(page) => {
+                    pending.extend(page.messages);
+                    pending_events.extend(page.monetization_events);
+                    Self::flush_batch(
+                        &db_manager,
+                        &mut pending,
+                        &mut pending_events,
+                        &counters,
+                        &logger,
+                    )
+                    .await;
+                    Self::discard_overflow(&mut pending, &mut pending_events, &counters, &logger);
+
+                    if page.is_offline {
+                        logger.info(&format!(
+                            "[YouTubeChat] Live chat ended for video {}",
+                            video_id
+                        ));
+                        break;
                     }
+                    // 配信検出のポーリングや他のライブチャットと日次予算を分け合う
+                    let budget_interval = quota_tracker.lock().await.budget_interval();
+                    page.polling_interval.max(budget_interval)
                 }
-                _ = interval.tick() => {
-                    // 定期的にバッチをフラッシュ
-                    Self::flush_batch(&db_conn, &mut batch).await;
+                Err(e) => {
+                    logger.error(&format!(
+                        "[YouTubeChat] Failed to fetch live chat for video {}: {}",
+                        video_id, e
+                    ));
+                    if is_terminal_error(e.as_ref()) {
+                        logger.info(&format!(
+                            "[YouTubeChat] Stopping live chat collection for video {}",
+                            video_id
+                        ));
+                        break;
+                    }
+                    Duration::from_secs(youtube::LIVE_CHAT_RETRY_DELAY_SECS)
                 }
+            };
+
+            // クォータ使用量はページごとではなく一定間隔でまとめて保存
+            if quota_saved_at.elapsed() >= quota_save_interval {
+                Self::save_quota_usage(&quota_tracker, &db_manager, &logger).await;
+                quota_saved_at = Instant::now();
             }
+
+            // 停止の指示（またはコレクターの破棄）を待ちながら次の取得まで待機
+            tokio::select! {
+                _ = sleep(wait) => {}
+                _ = stop_rx.wait_for(|stopped| *stopped) => break,
+            }
+        }
+
+        Self::flush_batch(
+            &db_manager,
+            &mut pending,
+            &mut pending_events,
+            &counters,
+            &logger,
+        )
+        .await;
+        Self::save_quota_usage(&quota_tracker, &db_manager, &logger).await;
+        if !pending.is_empty() {
+            let dropped = counters.record_dropped(pending.len() as u64);
+            logger.error(&format!(
+                "[YouTubeChat] Discarded {} unsaved chat messages for video {} ({} dropped so far)",
+                pending.len(),
+                video_id,
+                dropped
+            ));
+        }
+    }
+
+    /// 保存待ちが上限を超えた分を破棄して件数を記録（DB障害が続いてもメモリを使い切らないため）
+    fn discard_overflow(
+        pending: &mut Vec<ChatMessage>,
+        pending_events: &mut Vec<MonetizationEvent>,
+        counters: &IngestCounters,
+        logger: &AppLogger,
+    ) {
+        let capacity = db_constants::CHAT_PENDING_BATCH_CAPACITY;
+        if pending.len() <= capacity && pending_events.len() <= capacity {
+            return;
         }
 
-        // ループ終了時に残りのメッセージを書き込み
-        if !batch.is_empty() {
-            Self::flush_batch(&db_conn, &mut batch).await;
+        let discarded = pending.len().saturating_sub(capacity);
+        pending.truncate(capacity);
+        pending_events.truncate(capacity);
+        let dropped = counters.record_dropped(discarded as u64);
+        logger.error(&format!(
+            "[YouTubeChat] Pending chat exceeded {} messages; discarded {} ({} dropped so far)",
+            capacity, discarded, dropped
+        ));
+    }
+
+    async fn save_quota_usage(
+        quota_tracker: &Mutex<YouTubeQuotaTracker>,
+        db_manager: &DatabaseManager,
+        logger: &AppLogger,
+    ) {
+        if let Err(e) = save_quota_usage(quota_tracker, db_manager).await {
+            logger.error(&format!("[YouTubeChat] Failed to save quota usage: {}", e));
         }
     }
 
-    /// バッチメッセージをデータベースに書き込み
-    async fn flush_batch(db_conn: &Arc<Mutex<duckdb::Connection>>, batch: &mut Vec<ChatMessage>) {
-        if batch.is_empty() {
+    /// バッチメッセージと収益イベントをデータベースに書き込み
+    async fn flush_batch(
+        db_manager: &Arc<DatabaseManager>,
+        batch: &mut Vec<ChatMessage>,
+        events: &mut Vec<MonetizationEvent>,
+        counters: &IngestCounters,
+        logger: &Arc<AppLogger>,
+    ) {
+        if batch.is_empty() && events.is_empty() {
             return;
         }
 
-        let conn = db_conn.lock().await;
-        match crate::database::writer::DatabaseWriter::insert_chat_messages_batch(&conn, batch) {
-            Ok(_) => {
-                println!("Saved {} YouTube chat messages to database", batch.len());
+        let started = Instant::now();
+
+        // 収益イベントは同じメッセージIDで重複排除されるため、失敗時の再試行で二重計上されない
+        let result = db_manager
+            .with_connection(|conn| {
+                let inserted = crate::database::writer::DatabaseWriter::insert_chat_messages_batch(
+                    conn, batch,
+                )?;
+                MonetizationRepository::insert_events(conn, events)?;
+                Ok::<_, duckdb::Error>(inserted)
+            })
+            .await;
+
+        match result {
+            Ok(inserted) => {
+                // 記録モードでは保存したチャットをキャプチャファイルにも書き出す
+                if let Some(recorder) = replay::active_recorder() {
+                    recorder.record_chat_batch(&inserted);
+                }
+                counters.record_insert(batch.len(), started);
                 batch.clear();
+                events.clear();
             }
             Err(e) => {
-                eprintln!("Failed to save YouTube chat messages batch: {}", e);
+                logger.error(&format!(
+                    "[YouTubeChat] Failed to save chat messages: {}",
+                    e
+                ));
                 // エラーが発生してもバッチはクリアせず、次回再試行
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use google_youtube3::api::{
+        LiveChatMembershipGiftingDetails, LiveChatMessageAuthorDetails, LiveChatSuperChatDetails,
+    };
+
+    #[test]
+    fn test_to_chat_message_sets_channel_and_stream() {
+        let item = LiveChatMessage {
+            id: Some("yt-1".to_string()),
+            snippet: Some(LiveChatMessageSnippet {
+                display_message: Some("hello".to_string()),
+                published_at: Some("2025-01-06T03:00:00Z".parse().unwrap()),
+                ..Default::default()
+            }),
+            author_details: Some(LiveChatMessageAuthorDetails {
+                channel_id: Some("UCviewer".to_string()),
+                display_name: Some("Viewer".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let message = to_chat_message(item, 3, 30).unwrap();
+        assert_eq!(message.channel_id, Some(3));
+        assert_eq!(message.stream_id, Some(30));
+        assert_eq!(message.platform_message_id.as_deref(), Some("yt-1"));
+        assert_eq!(message.message_type, "normal");
+        assert_eq!(
+            chrono::DateTime::parse_from_rfc3339(&message.timestamp)
+                .unwrap()
+                .timestamp(),
+            1736132400
+        );
+
+        assert_eq!(polling_interval(Some(2500)), Duration::from_millis(2500));
+        assert_eq!(polling_interval(Some(0)), Duration::from_millis(1000));
+        assert_eq!(polling_interval(None), Duration::from_millis(5000));
+    }
+
+    #[test]
+    fn test_to_monetization_event_extracts_amounts() {
+        let super_chat = LiveChatMessage {
+            id: Some("yt-sc".to_string()),
+            snippet: Some(LiveChatMessageSnippet {
+                published_at: Some("2025-01-06T03:00:00Z".parse().unwrap()),
+                super_chat_details: Some(LiveChatSuperChatDetails {
+                    amount_display_string: Some("¥1,000".to_string()),
+                    amount_micros: Some(1_000_000_000),
+                    currency: Some("JPY".to_string()),
+                    tier: Some(3),
+                    user_comment: Some("nice".to_string()),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let event = to_monetization_event(&super_chat, 3, 30).unwrap();
+        assert_eq!(event.event_type, "superchat");
+        assert_eq!(event.platform_message_id, "yt-sc");
+        assert_eq!(event.amount_micros, Some(1_000_000_000));
+        assert_eq!(event.currency.as_deref(), Some("JPY"));
+        assert_eq!(event.tier, Some(3));
+
+        let gifting = LiveChatMessage {
+            id: Some("yt-gift".to_string()),
+            snippet: Some(LiveChatMessageSnippet {
+                published_at: Some("2025-01-06T03:01:00Z".parse().unwrap()),
+                membership_gifting_details: Some(LiveChatMembershipGiftingDetails {
+                    gift_memberships_count: Some(5),
+                    gift_memberships_level_name: Some("Member".to_string()),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let event = to_monetization_event(&gifting, 3, 30).unwrap();
+        assert_eq!(event.event_type, "membershipgifting");
+        assert_eq!(event.gift_count, Some(5));
+        assert_eq!(event.amount_micros, None);
+
+        let normal = LiveChatMessage {
+            id: Some("yt-normal".to_string()),
+            snippet: Some(LiveChatMessageSnippet {
+                published_at: Some("2025-01-06T03:02:00Z".parse().unwrap()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(to_monetization_event(&normal, 3, 30).is_none());
+    }
+
+    #[test]
+    fn test_is_terminal_error_by_reason() {
+        let bad_request = |reason: &str| {
+            google_youtube3::Error::BadRequest(serde_json::json!({
+                "error": { "code": 403, "errors": [{ "reason": reason }] }
+            }))
+        };
+
+        assert!(is_terminal_error(&bad_request("liveChatEnded")));
+        assert!(is_terminal_error(&bad_request("forbidden")));
+        // クォータ超過は予算の回復後に再試行する
+        assert!(!is_terminal_error(&bad_request("quotaExceeded")));
+    }
+}
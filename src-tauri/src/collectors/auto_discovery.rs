@@ -1,31 +1,52 @@
 use crate::api::twitch_api::TwitchApiClient;
+use crate::collectors::poller::ChannelPoller;
+use crate::collectors::twitch::TwitchCollector;
 use crate::commands::discovery::DiscoveredStreamInfo;
 use crate::config::settings::{AutoDiscoverySettings, SettingsManager};
+use crate::constants::database as db_constants;
+use crate::database::models::Stream;
 use crate::database::repositories::base;
 use crate::database::repositories::game_category_repository::GameCategoryRepository;
 use crate::database::repositories::stream_stats_repository::StreamStatsRepository;
 use crate::database::repositories::ChannelRepository;
+use crate::database::writer::DatabaseWriter;
 use crate::database::DatabaseManager;
 use crate::error::ResultExt;
 use crate::DiscoveredStreamsCache;
 use chrono::Local;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::Ordering;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 use tokio::time::{interval, Duration};
 
+/// 自動発見ループの状態（ヘルス表示用）
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AutoDiscoveryStatus {
+    pub is_running: bool,
+    pub last_run_at: Option<String>,
+    pub last_success_at: Option<String>,
+    pub last_error: Option<String>,
+    pub last_discovered_count: usize,
+    pub run_count: u64,
+    pub error_count: u64,
+}
+
 /// 自動発見ポーラー
 ///
 /// 設定に基づいてTwitchの上位配信を定期的に取得し、
 /// 新しく発見した配信を自動的に監視対象に追加する
+///
+/// チャット収集を有効にした場合は、視聴者数上位の配信のIRCにJOIN予算の範囲で参加する
 pub struct AutoDiscoveryPoller {
     twitch_client: Option<Arc<TwitchApiClient>>,
     db_manager: Arc<DatabaseManager>,
     app_handle: AppHandle,
     task_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+    status: Arc<RwLock<AutoDiscoveryStatus>>,
 }
 
 impl AutoDiscoveryPoller {
@@ -39,9 +60,18 @@ impl AutoDiscoveryPoller {
             db_manager,
             app_handle,
             task_handle: Arc::new(Mutex::new(None)),
+            status: Arc::new(RwLock::new(AutoDiscoveryStatus::default())),
         }
     }
 
+    /// 自動発見ループの状態を取得
+    pub fn get_status(&self) -> AutoDiscoveryStatus {
+        self.status
+            .read()
+            .map(|status| status.clone())
+            .unwrap_or_default()
+    }
+
     /// 自動発見を開始
     pub async fn start(&self) -> Result<(), String> {
         // 設定をロード
@@ -92,6 +122,11 @@ impl AutoDiscoveryPoller {
         // 既存のタスクを停止
         self.stop().await;
 
+        let status = Arc::clone(&self.status);
+        if let Ok(mut status) = status.write() {
+            status.is_running = true;
+        }
+
         // 新しいタスクを開始
         let task = tokio::spawn(async move {
             let poll_interval_secs = auto_discovery_settings.poll_interval as u64;
@@ -125,6 +160,11 @@ impl AutoDiscoveryPoller {
                 }
                 is_first_run = false;
 
+                if let Ok(mut status) = status.write() {
+                    status.last_run_at = Some(Local::now().to_rfc3339());
+                    status.run_count += 1;
+                }
+
                 // 最新の設定を再読み込み
                 let current_settings = match SettingsManager::load_settings(&app_handle) {
                     Ok(s) => s,
@@ -138,6 +178,7 @@ impl AutoDiscoveryPoller {
                     Some(s) if s.enabled => s,
                     _ => {
                         eprintln!("[AutoDiscovery] Auto-discovery disabled, stopping...");
+                        Self::part_discovered_chat(&db_manager, &app_handle).await;
                         break;
                     }
                 };
@@ -151,15 +192,37 @@ impl AutoDiscoveryPoller {
                 )
                 .await
                 {
-                    Ok(count) => {
+                    Ok(streams) => {
+                        let count = streams.len();
                         eprintln!("[AutoDiscovery] Discovered {} streams", count);
+                        if let Ok(mut status) = status.write() {
+                            status.last_success_at = Some(Local::now().to_rfc3339());
+                            status.last_error = None;
+                            status.last_discovered_count = count;
+                        }
                         if count > 0 {
                             // 新しいチャンネルが追加されたことをフロントエンドに通知
                             let _ = app_handle.emit("channels-updated", ());
                         }
+
+                        // 発見結果に合わせてチャットのJOIN/PARTを行う
+                        if let Err(e) = Self::sync_discovered_chat(
+                            current_auto_discovery,
+                            &streams,
+                            &db_manager,
+                            &app_handle,
+                        )
+                        .await
+                        {
+                            eprintln!("[AutoDiscovery] Error syncing discovered chat: {}", e);
+                        }
                     }
                     Err(e) => {
                         eprintln!("[AutoDiscovery] Error discovering streams: {}", e);
+                        if let Ok(mut status) = status.write() {
+                            status.last_error = Some(e.to_string());
+                            status.error_count += 1;
+                        }
                     }
                 }
 
@@ -179,6 +242,9 @@ impl AutoDiscoveryPoller {
                 }
             }
 
+            if let Ok(mut status) = status.write() {
+                status.is_running = false;
+            }
             eprintln!("[AutoDiscovery] Polling stopped");
         });
 
@@ -194,17 +260,228 @@ impl AutoDiscoveryPoller {
         let mut handle = self.task_handle.lock().await;
         if let Some(task) = handle.take() {
             task.abort();
+            if let Ok(mut status) = self.status.write() {
+                status.is_running = false;
+            }
+            Self::part_discovered_chat(&self.db_manager, &self.app_handle).await;
             eprintln!("[AutoDiscovery] Stopped");
         }
     }
 
+    /// ChannelPoller が保持するTwitchコレクター（IRC接続）を取得
+    async fn twitch_collector(app_handle: &AppHandle) -> Option<Arc<TwitchCollector>> {
+        let poller = app_handle.try_state::<Arc<Mutex<ChannelPoller>>>()?;
+        let poller = poller.lock().await;
+        poller.get_twitch_collector().cloned()
+    }
+
+    /// 発見結果に合わせて自動発見チャンネルのチャット収集を同期
+    ///
+    /// - 視聴者数上位 `chat_join_budget` 件（手動登録済みを除く）のIRCに参加し、
+    ///   発見した配信の streams 行にチャットを紐付け
+    /// - 上位から外れたチャンネルからは退出（チャットは保持）
+    /// - 発見されなくなったチャンネルは配信を終了し、チャット収集が有効な場合のみ
+    ///   猶予期間（`DISCOVERED_CHAT_PURGE_GRACE_MINUTES`）を過ぎてからチャットごと削除
+    async fn sync_discovered_chat(
+        settings: &AutoDiscoverySettings,
+        streams: &[DiscoveredStreamInfo],
+        db_manager: &Arc<DatabaseManager>,
+        app_handle: &AppHandle,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let Some(twitch_collector) = Self::twitch_collector(app_handle).await else {
+            return Ok(());
+        };
+
+        let mut ranked: Vec<&DiscoveredStreamInfo> = streams.iter().collect();
+        ranked.sort_by(|a, b| b.viewer_count.cmp(&a.viewer_count));
+
+        // (channels.id, login, streams.id)
+        let targets: Vec<(i64, String, Option<i64>)> = if settings.chat_enabled {
+            let budget = settings.chat_join_budget as usize;
+            db_manager
+                .with_connection(|conn| {
+                    let mut targets = Vec::new();
+                    for stream in ranked {
+                        if targets.len() >= budget {
+                            break;
+                        }
+                        let channel_name = stream
+                            .display_name
+                            .as_deref()
+                            .unwrap_or(&stream.channel_name);
+                        let Some(id) = ChannelRepository::ensure_auto_discovered(
+                            conn,
+                            &stream.channel_id,
+                            channel_name,
+                            stream.twitch_user_id,
+                        )?
+                        else {
+                            continue;
+                        };
+                        let stream_db_id = Self::upsert_discovered_stream(conn, id, stream)?;
+                        targets.push((id, stream.channel_id.clone(), stream_db_id));
+                    }
+                    Ok::<_, duckdb::Error>(targets)
+                })
+                .await?
+        } else {
+            Vec::new()
+        };
+
+        let discovered_logins: Vec<String> = streams.iter().map(|s| s.channel_id.clone()).collect();
+        db_manager
+            .with_connection(|conn| {
+                ChannelRepository::touch_auto_discovered(conn, &discovered_logins)
+            })
+            .await?;
+
+        let connected: HashSet<i64> = twitch_collector
+            .get_irc_statuses()
+            .await
+            .into_iter()
+            .map(|status| status.channel_id)
+            .collect();
+
+        for (channel_id, login, stream_db_id) in &targets {
+            if !connected.contains(channel_id) {
+                match twitch_collector
+                    .start_chat_collection(*channel_id, login, true)
+                    .await
+                {
+                    Ok(()) => eprintln!("[AutoDiscovery] Joined chat: {}", login),
+                    Err(e) => {
+                        eprintln!("[AutoDiscovery] Failed to join chat {}: {}", login, e);
+                        continue;
+                    }
+                }
+            }
+            twitch_collector
+                .update_stream_id(*channel_id, *stream_db_id)
+                .await;
+        }
+
+        let target_ids: HashSet<i64> = targets.iter().map(|(id, _, _)| *id).collect();
+        let discovered_logins: HashSet<&str> =
+            discovered_logins.iter().map(String::as_str).collect();
+        let auto_channels = db_manager
+            .with_connection(ChannelRepository::get_auto_discovered_channels)
+            .await?;
+
+        let mut removed = Vec::new();
+        for (channel_id, login) in auto_channels {
+            if target_ids.contains(&channel_id) {
+                continue;
+            }
+            if connected.contains(&channel_id) {
+                let _ = twitch_collector.stop_chat_collection(channel_id).await;
+                eprintln!("[AutoDiscovery] Parted chat: {}", login);
+            }
+            if discovered_logins.contains(login.as_str()) {
+                continue;
+            }
+
+            let purge_enabled = settings.chat_enabled;
+            let purged = db_manager
+                .with_connection(|conn| {
+                    ChannelRepository::end_auto_discovered_streams(conn, channel_id, None)?;
+                    if !purge_enabled
+                        || !ChannelRepository::is_discovery_expired(
+                            conn,
+                            channel_id,
+                            db_constants::DISCOVERED_CHAT_PURGE_GRACE_MINUTES,
+                        )?
+                    {
+                        return Ok(false);
+                    }
+                    ChannelRepository::delete_channel_and_related(conn, channel_id)?;
+                    Ok::<_, duckdb::Error>(true)
+                })
+                .await?;
+            if purged {
+                eprintln!(
+                    "[AutoDiscovery] Purged undiscovered channel and its chat: {} (id: {})",
+                    login, channel_id
+                );
+                removed.push(channel_id);
+            }
+        }
+
+        for channel_id in removed {
+            let _ = app_handle.emit("channel-removed", channel_id);
+        }
+
+        Ok(())
+    }
+
+    /// 発見した配信の streams 行を作成または更新し、以前の配信を終了させる
+    ///
+    /// 配信IDが取得できなかった場合は None（チャットは配信に紐付けない）
+    fn upsert_discovered_stream(
+        conn: &duckdb::Connection,
+        channel_id: i64,
+        stream: &DiscoveredStreamInfo,
+    ) -> Result<Option<i64>, duckdb::Error> {
+        let (Some(stream_id), Some(started_at)) = (&stream.stream_id, &stream.started_at) else {
+            return Ok(None);
+        };
+        ChannelRepository::end_auto_discovered_streams(conn, channel_id, Some(stream_id.as_str()))?;
+        let id = DatabaseWriter::insert_or_update_stream(
+            conn,
+            channel_id,
+            &Stream {
+                id: None,
+                channel_id,
+                stream_id: stream_id.clone(),
+                title: stream.title.clone(),
+                category: stream.category.clone(),
+                thumbnail_url: None,
+                started_at: started_at.clone(),
+                ended_at: None,
+            },
+        )?;
+        Ok(Some(id))
+    }
+
+    /// 自動発見チャンネルのチャットからすべて退出
+    async fn part_discovered_chat(db_manager: &Arc<DatabaseManager>, app_handle: &AppHandle) {
+        let Some(twitch_collector) = Self::twitch_collector(app_handle).await else {
+            return;
+        };
+
+        let auto_channels = match db_manager
+            .with_connection(ChannelRepository::get_auto_discovered_channels)
+            .await
+        {
+            Ok(channels) => channels,
+            Err(e) => {
+                eprintln!("[AutoDiscovery] Failed to load discovered channels: {}", e);
+                return;
+            }
+        };
+
+        let connected: HashSet<i64> = twitch_collector
+            .get_irc_statuses()
+            .await
+            .into_iter()
+            .map(|status| status.channel_id)
+            .collect();
+        for (channel_id, login) in auto_channels {
+            if connected.contains(&channel_id) {
+                let _ = twitch_collector.stop_chat_collection(channel_id).await;
+                eprintln!("[AutoDiscovery] Parted chat: {}", login);
+            }
+        }
+    }
+
     /// 配信を発見してメモリキャッシュに保存し、統計データをDBに記録
+    ///
+    /// 戻り値: 発見した配信の一覧
     async fn discover_streams(
         twitch_client: &TwitchApiClient,
         settings: &AutoDiscoverySettings,
         db_manager: &Arc<DatabaseManager>,
         app_handle: &AppHandle,
-    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<Vec<DiscoveredStreamInfo>, Box<dyn std::error::Error + Send + Sync>> {
         eprintln!("[AutoDiscovery] ===== DISCOVER STREAMS CALLED =====");
 
         // フィルター条件を準備
@@ -259,7 +536,7 @@ impl AutoDiscoveryPoller {
             .collect();
 
         if filtered_streams.is_empty() {
-            return Ok(0);
+            return Ok(Vec::new());
         }
 
         // User IDを収集
@@ -323,6 +600,8 @@ impl AutoDiscoveryPoller {
                 viewer_count: Some(stream.viewer_count as i32),
                 follower_count,
                 broadcaster_type: broadcaster_type.clone(),
+                stream_id: Some(stream.id.to_string()),
+                started_at: Some(stream.started_at.as_str().to_string()),
             };
             discovered_streams_info.push(stream_info);
 
@@ -395,6 +674,7 @@ impl AutoDiscoveryPoller {
                         let box_art_url = category_box_art.get(game_id).map(String::as_str);
                         GameCategoryRepository::upsert_category(
                             conn,
+                            db_constants::PLATFORM_TWITCH,
                             game_id,
                             game_name,
                             box_art_url,
@@ -411,7 +691,7 @@ impl AutoDiscoveryPoller {
         // メモリキャッシュに保存
         let cache: tauri::State<'_, Arc<DiscoveredStreamsCache>> = app_handle.state();
         let mut streams_lock = cache.streams.lock().await;
-        *streams_lock = discovered_streams_info;
+        *streams_lock = discovered_streams_info.clone();
         drop(streams_lock);
 
         // フロントエンドにイベントを発行（キャッシュ無効化のトリガー）
@@ -429,7 +709,7 @@ impl AutoDiscoveryPoller {
             discovered_count
         );
 
-        Ok(discovered_count)
+        Ok(discovered_streams_info)
     }
 
     /// オフラインになった自動発見チャンネルをクリーンアップ
@@ -0,0 +1,170 @@
+/// チャンネル単位のバックオフとサーキットブレーカー
+///
+/// 失敗が続くチャンネルは再試行間隔を指数的に延ばし、一定回数連続で失敗した場合
+/// （またはチャンネル不在など回復しないエラーの場合）はブレーカーを開いて一定時間ポーリングを止めます。
+/// 停止時間の経過後は1回だけ試行し（半開）、成功すれば通常状態に戻ります。
+use crate::collectors::collector_trait::CollectorError;
+use crate::constants::polling;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// サーキットブレーカーの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    /// 通常状態
+    #[default]
+    Closed,
+    /// ポーリング停止中
+    Open,
+    /// 停止時間経過後の試行中
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ChannelBackoff {
+    consecutive_failures: u32,
+    breaker: BreakerState,
+    retry_at: Option<Instant>,
+}
+
+impl ChannelBackoff {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    pub fn breaker_state(&self) -> BreakerState {
+        self.breaker
+    }
+
+    /// 再試行が許可される時刻（失敗していない場合は None）
+    pub fn retry_at(&self) -> Option<Instant> {
+        self.retry_at
+    }
+
+    /// ポーリング試行を記録（停止時間を過ぎていれば半開状態にする）
+    pub fn on_attempt(&mut self, now: Instant) {
+        if self.breaker == BreakerState::Open && self.retry_at.is_none_or(|at| at <= now) {
+            self.breaker = BreakerState::HalfOpen;
+        }
+    }
+
+    /// 成功を記録（バックオフとブレーカーをリセット）
+    pub fn on_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.breaker = BreakerState::Closed;
+        self.retry_at = None;
+    }
+
+    /// 失敗を記録し、次回試行までの待ち時間を返す
+    pub fn on_failure(&mut self, error: &CollectorError, now: Instant) -> Duration {
+        let delay = match error {
+            // レート制限はチャンネル固有の問題ではないためブレーカーの判定に含めない
+            CollectorError::RateLimited { retry_after, .. } => {
+                retry_after.unwrap_or_else(|| Self::backoff_delay(self.consecutive_failures + 1))
+            }
+            _ => {
+                self.consecutive_failures += 1;
+                let trip = error.trips_breaker()
+                    || self.breaker == BreakerState::HalfOpen
+                    || self.consecutive_failures >= polling::CIRCUIT_BREAKER_FAILURE_THRESHOLD;
+                if trip {
+                    self.breaker = BreakerState::Open;
+                    Duration::from_secs(polling::CIRCUIT_BREAKER_OPEN_SECS)
+                } else {
+                    Self::backoff_delay(self.consecutive_failures)
+                }
+            }
+        };
+
+        self.retry_at = Some(now + delay);
+        delay
+    }
+
+    /// 予定していた間隔とバックオフを考慮した、次回ポーリングまでの待ち時間
+    pub fn next_wait(&self, planned: Duration, now: Instant) -> Duration {
+        match self.retry_at {
+            Some(at) => at.saturating_duration_since(now).max(planned),
+            None => planned,
+        }
+    }
+
+    /// 連続失敗回数に応じた待ち時間（指数バックオフ、上限あり）
+    fn backoff_delay(failures: u32) -> Duration {
+        let exponent = failures.saturating_sub(1).min(16);
+        let secs = polling::BACKOFF_BASE_SECS
+            .saturating_mul(1 << exponent)
+            .min(polling::BACKOFF_MAX_SECS);
+        Duration::from_secs(secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transient() -> CollectorError {
+        CollectorError::Transient("connection reset".to_string())
+    }
+
+    #[test]
+    fn test_exponential_backoff_and_breaker() {
+        let mut backoff = ChannelBackoff::new();
+        let now = Instant::now();
+
+        let first = backoff.on_failure(&transient(), now);
+        let second = backoff.on_failure(&transient(), now);
+        assert_eq!(first, Duration::from_secs(polling::BACKOFF_BASE_SECS));
+        assert_eq!(second, first * 2);
+        assert_eq!(backoff.breaker_state(), BreakerState::Closed);
+
+        for _ in 2..polling::CIRCUIT_BREAKER_FAILURE_THRESHOLD {
+            backoff.on_failure(&transient(), now);
+        }
+        assert_eq!(backoff.breaker_state(), BreakerState::Open);
+
+        // 停止時間経過後の試行で失敗すると再び停止
+        let reopen_at = now + Duration::from_secs(polling::CIRCUIT_BREAKER_OPEN_SECS);
+        backoff.on_attempt(reopen_at);
+        assert_eq!(backoff.breaker_state(), BreakerState::HalfOpen);
+        backoff.on_failure(&transient(), reopen_at);
+        assert_eq!(backoff.breaker_state(), BreakerState::Open);
+
+        backoff.on_attempt(reopen_at + Duration::from_secs(polling::CIRCUIT_BREAKER_OPEN_SECS));
+        backoff.on_success();
+        assert_eq!(backoff.breaker_state(), BreakerState::Closed);
+        assert_eq!(backoff.consecutive_failures(), 0);
+        assert!(backoff.retry_at().is_none());
+    }
+
+    #[test]
+    fn test_rate_limit_uses_retry_after() {
+        let mut backoff = ChannelBackoff::new();
+        let now = Instant::now();
+        let error = CollectorError::RateLimited {
+            retry_after: Some(Duration::from_secs(42)),
+            message: "429 Too Many Requests".to_string(),
+        };
+
+        assert_eq!(backoff.on_failure(&error, now), Duration::from_secs(42));
+        assert_eq!(backoff.consecutive_failures(), 0);
+        assert_eq!(
+            backoff.next_wait(Duration::from_secs(10), now),
+            Duration::from_secs(42)
+        );
+    }
+
+    #[test]
+    fn test_channel_not_found_trips_immediately() {
+        let mut backoff = ChannelBackoff::new();
+        let error = CollectorError::classify("User not found");
+        assert!(matches!(error, CollectorError::ChannelNotFound(_)));
+
+        backoff.on_failure(&error, Instant::now());
+        assert_eq!(backoff.breaker_state(), BreakerState::Open);
+    }
+}
@@ -1,14 +1,206 @@
+use crate::constants::twitch;
 use crate::database::models::{Channel, StreamData};
 use async_trait::async_trait;
+use std::time::Duration;
+use thiserror::Error;
+use twitch_api::helix::{ClientRequestError, HelixRequestGetError};
+
+/// ポーリング時のエラー種別
+///
+/// ポーラーはこの種別をもとにバックオフ・サーキットブレーカー・認証エラー通知を判断します。
+#[derive(Debug, Clone, Error)]
+pub enum CollectorError {
+    /// 認証エラー（トークン期限切れなど）
+    #[error("Authentication error: {0}")]
+    Auth(String),
+
+    /// レート制限・クォータ超過
+    #[error("Rate limited: {message}")]
+    RateLimited {
+        retry_after: Option<Duration>,
+        message: String,
+    },
+
+    /// チャンネルが見つからない
+    #[error("Channel not found: {0}")]
+    ChannelNotFound(String),
+
+    /// 一時的なエラー（ネットワーク障害など、再試行で回復する可能性がある）
+    #[error("{0}")]
+    Transient(String),
+
+    /// 再試行しても回復しないエラー
+    #[error("Fatal error: {0}")]
+    Fatal(String),
+}
+
+impl CollectorError {
+    /// HTTPステータスコードから種別を判定
+    pub fn from_status(status: u16, message: impl Into<String>) -> Self {
+        let message = message.into();
+        match status {
+            429 => CollectorError::RateLimited {
+                retry_after: None,
+                message,
+            },
+            401 => CollectorError::Auth(message),
+            404 => CollectorError::ChannelNotFound(message),
+            400..=499 => CollectorError::Fatal(message),
+            _ => CollectorError::Transient(message),
+        }
+    }
+
+    /// APIクライアントのエラーから種別を判定
+    ///
+    /// reqwest / Twitch Helix / YouTube Data API のエラーはHTTPステータスで判定し、
+    /// ステータスを持たないエラーのみメッセージで判定します。
+    pub fn from_error(err: &(dyn std::error::Error + 'static)) -> Self {
+        let message = err.to_string();
+        // YouTube はクォータ超過を 403 で返すため、ステータスより理由を優先する
+        if is_quota_exceeded(&message.to_lowercase()) {
+            return CollectorError::RateLimited {
+                retry_after: None,
+                message,
+            };
+        }
+
+        match http_status(err) {
+            Some(status) => CollectorError::from_status(status, message),
+            None => CollectorError::classify(message),
+        }
+    }
+
+    /// ステータスを持たないエラーのメッセージから種別を判定
+    pub fn classify(message: impl Into<String>) -> Self {
+        let message = message.into();
+        let lower = message.to_lowercase();
+
+        if is_quota_exceeded(&lower) || lower.contains("too many requests") {
+            CollectorError::RateLimited {
+                retry_after: None,
+                message,
+            }
+        } else if message.contains(twitch::ERROR_UNAUTHORIZED_TEXT)
+            || lower.contains("not authorized")
+            || lower.contains("authentication expired")
+            || lower.contains("invalid_grant")
+        {
+            CollectorError::Auth(message)
+        } else if lower.contains("user not found") || lower.contains("channelnotfound") {
+            CollectorError::ChannelNotFound(message)
+        } else if lower.contains("no app handle available") {
+            CollectorError::Fatal(message)
+        } else {
+            CollectorError::Transient(message)
+        }
+    }
+
+    /// 1回の失敗でサーキットブレーカーを開くべきエラーか
+    pub fn trips_breaker(&self) -> bool {
+        matches!(
+            self,
+            CollectorError::ChannelNotFound(_) | CollectorError::Fatal(_)
+        )
+    }
+}
+
+impl From<Box<dyn std::error::Error + Send + Sync>> for CollectorError {
+    fn from(err: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        CollectorError::from_error(err.as_ref())
+    }
+}
+
+/// クォータ・レート制限超過を表すエラー理由（YouTube Data API）が含まれるか
+fn is_quota_exceeded(lower_message: &str) -> bool {
+    lower_message.contains("quotaexceeded") || lower_message.contains("ratelimitexceeded")
+}
+
+/// エラーとその原因をたどり、HTTPステータスコードを取得
+fn http_status(err: &(dyn std::error::Error + 'static)) -> Option<u16> {
+    let mut current = Some(err);
+    while let Some(err) = current {
+        if let Some(status) = own_http_status(err) {
+            return Some(status);
+        }
+        current = err.source();
+    }
+    None
+}
+
+fn own_http_status(err: &(dyn std::error::Error + 'static)) -> Option<u16> {
+    if let Some(err) = err.downcast_ref::<reqwest::Error>() {
+        return err.status().map(|status| status.as_u16());
+    }
+    if let Some(err) = err.downcast_ref::<ClientRequestError<reqwest::Error>>() {
+        return match err {
+            ClientRequestError::RequestError(err) => err.status().map(|status| status.as_u16()),
+            ClientRequestError::HelixRequestGetError(err) => helix_get_status(err),
+            _ => None,
+        };
+    }
+    if let Some(err) = err.downcast_ref::<HelixRequestGetError>() {
+        return helix_get_status(err);
+    }
+    if let Some(err) = err.downcast_ref::<google_youtube3::Error>() {
+        return match err {
+            google_youtube3::Error::BadRequest(body) => body
+                .pointer("/error/code")
+                .and_then(|code| code.as_u64())
+                .and_then(|code| u16::try_from(code).ok()),
+            google_youtube3::Error::Failure(response) => Some(response.status().as_u16()),
+            _ => None,
+        };
+    }
+    None
+}
+
+fn helix_get_status(err: &HelixRequestGetError) -> Option<u16> {
+    match err {
+        HelixRequestGetError::Error { status, .. }
+        | HelixRequestGetError::InvalidResponse { status, .. }
+        | HelixRequestGetError::DeserializeError(_, _, _, status) => Some(status.as_u16()),
+        _ => None,
+    }
+}
 
 #[async_trait]
 pub trait Collector {
-    async fn poll_channel(
-        &self,
-        channel: &Channel,
-    ) -> Result<Option<StreamData>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn poll_channel(&self, channel: &Channel) -> Result<Option<StreamData>, CollectorError>;
     async fn start_collection(
         &self,
         channel: &Channel,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_by_status() {
+        assert!(matches!(
+            CollectorError::from_status(429, "too many"),
+            CollectorError::RateLimited { .. }
+        ));
+        assert!(matches!(
+            CollectorError::from_status(404, "missing"),
+            CollectorError::ChannelNotFound(_)
+        ));
+        assert!(matches!(
+            CollectorError::from_status(403, "forbidden"),
+            CollectorError::Fatal(_)
+        ));
+        assert!(matches!(
+            CollectorError::from_status(503, "unavailable"),
+            CollectorError::Transient(_)
+        ));
+
+        // ステータスを持たないエラーのメッセージ中の数字では判定しない
+        let error: Box<dyn std::error::Error + Send + Sync> =
+            "connection to 10.0.4.29:404 timed out".into();
+        assert!(matches!(
+            CollectorError::from(error),
+            CollectorError::Transient(_)
+        ));
+    }
+}
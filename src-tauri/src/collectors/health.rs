@@ -0,0 +1,106 @@
+/// コレクターのヘルス状態
+///
+/// チャンネルごとのポーリング状態・IRC接続状態・自動発見ループの状態をまとめ、
+/// コマンドと定期イベント（collector-status-updated）の両方から参照できるようにします。
+use crate::collectors::auto_discovery::{AutoDiscoveryPoller, AutoDiscoveryStatus};
+use crate::collectors::poller::{ChannelPoller, CollectorStatus};
+use crate::constants::polling;
+use crate::websocket::twitch_irc::{ChatIngestMetrics, IrcConnectionStatus};
+use chrono::Local;
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Mutex;
+use tokio::time::{interval, Duration, MissedTickBehavior};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectorHealthSnapshot {
+    pub collectors: Vec<CollectorStatus>,
+    pub irc_connections: Vec<IrcConnectionStatus>,
+    /// Kickチャット（Pusher WebSocket）の接続状態
+    pub kick_chat_connections: Vec<IrcConnectionStatus>,
+    /// 自動発見ポーラーが未初期化の場合は None
+    pub auto_discovery: Option<AutoDiscoveryStatus>,
+    /// DBへ未コミットでチャットスプールに残っているメッセージ数
+    pub chat_spool_depth: u64,
+    /// Twitchチャット取り込みキューの深さ・破棄数・保存レイテンシ
+    pub chat_ingest: ChatIngestMetrics,
+    /// Kickチャット取り込みキューの深さ・破棄数・保存レイテンシ
+    pub kick_chat_ingest: ChatIngestMetrics,
+    /// YouTubeライブチャットの破棄数・保存レイテンシ（キューは持たない）
+    pub youtube_chat_ingest: ChatIngestMetrics,
+    pub generated_at: String,
+}
+
+/// 現在のヘルス状態を収集
+pub async fn collect_health_snapshot(app_handle: &AppHandle) -> CollectorHealthSnapshot {
+    let (collectors, twitch_collector, kick_collector, youtube_collector) =
+        match app_handle.try_state::<Arc<Mutex<ChannelPoller>>>() {
+            Some(poller) => {
+                // pollerのロックは状態のコピーにのみ使用
+                let poller = poller.lock().await;
+                (
+                    poller.get_statuses(),
+                    poller.get_twitch_collector().cloned(),
+                    poller.get_kick_collector().cloned(),
+                    poller.get_youtube_collector().cloned(),
+                )
+            }
+            None => (Vec::new(), None, None, None),
+        };
+
+    let (irc_connections, chat_spool_depth, chat_ingest) = match twitch_collector {
+        Some(twitch_collector) => (
+            twitch_collector.get_irc_statuses().await,
+            twitch_collector.get_chat_spool_depth(),
+            twitch_collector.get_chat_ingest_metrics(),
+        ),
+        None => (Vec::new(), 0, ChatIngestMetrics::default()),
+    };
+
+    let (kick_chat_connections, kick_chat_ingest) = match kick_collector {
+        Some(kick_collector) => (
+            kick_collector.get_chat_statuses().await,
+            kick_collector.get_chat_ingest_metrics(),
+        ),
+        None => (Vec::new(), ChatIngestMetrics::default()),
+    };
+
+    let youtube_chat_ingest = youtube_collector
+        .map(|youtube_collector| youtube_collector.get_chat_ingest_metrics())
+        .unwrap_or_default();
+
+    let auto_discovery = match app_handle.try_state::<Arc<Mutex<Option<AutoDiscoveryPoller>>>>() {
+        Some(state) => {
+            let discovery_poller = state.lock().await;
+            discovery_poller.as_ref().map(|poller| poller.get_status())
+        }
+        None => None,
+    };
+
+    CollectorHealthSnapshot {
+        collectors,
+        irc_connections,
+        kick_chat_connections,
+        auto_discovery,
+        chat_spool_depth,
+        chat_ingest,
+        kick_chat_ingest,
+        youtube_chat_ingest,
+        generated_at: Local::now().to_rfc3339(),
+    }
+}
+
+/// ヘルス状態を collector-status-updated イベントとして定期的に発行
+pub fn start_health_reporter(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = interval(Duration::from_secs(polling::STATUS_EVENT_INTERVAL_SECS));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            ticker.tick().await;
+            let snapshot = collect_health_snapshot(&app_handle).await;
+            let _ = app_handle.emit("collector-status-updated", snapshot);
+        }
+    });
+}
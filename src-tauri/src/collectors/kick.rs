@@ -0,0 +1,157 @@
+use crate::api::kick_api::{KickApiClient, KickChannel};
+use crate::collectors::collector_trait::{Collector, CollectorError};
+use crate::database::models::{Channel, StreamData};
+use crate::database::DatabaseManager;
+use crate::logger::AppLogger;
+use crate::websocket::kick_chat::KickChatManager;
+use crate::websocket::twitch_irc::{ChatIngestMetrics, IrcConnectionStatus};
+use async_trait::async_trait;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use std::sync::Arc;
+
+/// Kickコレクター
+///
+/// channels.channel_id にはKickのスラッグ（kick.com/{slug}）を保存します。
+/// 配信状態はチャンネル情報APIから、チャットはPusher WebSocketから収集します。
+pub struct KickCollector {
+    api_client: Arc<KickApiClient>,
+    chat_manager: Arc<KickChatManager>,
+}
+
+impl KickCollector {
+    pub fn new(db_manager: Arc<DatabaseManager>, logger: Arc<AppLogger>) -> Self {
+        Self {
+            api_client: Arc::new(KickApiClient::new()),
+            chat_manager: Arc::new(KickChatManager::new(db_manager, logger)),
+        }
+    }
+
+    /// Kickの配信情報をStreamDataに変換（配信していない場合は None）
+    fn to_stream_data(channel: &KickChannel) -> Option<StreamData> {
+        let livestream = channel.livestream.as_ref().filter(|ls| ls.is_live)?;
+        let category = livestream.categories.first();
+
+        // start_time はUTCの "YYYY-MM-DD HH:MM:SS"
+        let started_at = livestream
+            .start_time
+            .as_deref()
+            .or(livestream.created_at.as_deref())
+            .and_then(|start_time| {
+                DateTime::parse_from_rfc3339(start_time)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .ok()
+                    .or_else(|| {
+                        NaiveDateTime::parse_from_str(start_time, "%Y-%m-%d %H:%M:%S")
+                            .ok()
+                            .map(|naive| Utc.from_utc_datetime(&naive))
+                    })
+            })
+            .map(|dt| dt.to_rfc3339())
+            .unwrap_or_else(|| Local::now().to_rfc3339());
+
+        Some(StreamData {
+            stream_id: livestream.id.to_string(),
+            title: livestream.session_title.clone(),
+            category: category.map(|c| c.name.clone()),
+            game_id: category.map(|c| c.id.to_string()),
+            thumbnail_url: livestream.thumbnail.as_ref().and_then(|t| t.url.clone()),
+            started_at,
+            viewer_count: livestream.viewer_count.map(|v| v as i32),
+            follower_count: channel.followers_count.map(|v| v as i32),
+            language: livestream.language.clone(),
+            tags: Some(livestream.tags.clone()),
+        })
+    }
+
+    /// チャット収集を開始（スラッグからチャットルームIDを解決）
+    pub async fn start_chat_collection(&self, channel_id: i64, slug: &str) -> Result<(), String> {
+        let channel = self
+            .api_client
+            .get_channel(slug)
+            .await
+            .map_err(|e| e.to_string())?;
+        let chatroom_id = channel
+            .chatroom
+            .map(|chatroom| chatroom.id)
+            .ok_or_else(|| format!("Kick chatroom not found for channel: {}", slug))?;
+
+        self.chat_manager
+            .start_channel_collection(channel_id, slug, chatroom_id)
+            .await
+    }
+
+    /// チャット収集を停止
+    pub async fn stop_chat_collection(&self, channel_id: i64) -> Result<(), String> {
+        self.chat_manager.stop_channel_collection(channel_id).await
+    }
+
+    /// 配信状態変更時にstream_idを更新
+    pub async fn update_stream_id(&self, channel_id: i64, stream_id: Option<i64>) {
+        self.chat_manager
+            .update_channel_stream(channel_id, stream_id)
+            .await;
+    }
+
+    /// チャット接続状態を取得
+    pub async fn get_chat_statuses(&self) -> Vec<IrcConnectionStatus> {
+        self.chat_manager.get_connection_statuses().await
+    }
+
+    /// チャット取り込みの状態を取得
+    pub fn get_chat_ingest_metrics(&self) -> ChatIngestMetrics {
+        self.chat_manager.ingest_metrics()
+    }
+}
+
+#[async_trait]
+impl Collector for KickCollector {
+    async fn poll_channel(&self, channel: &Channel) -> Result<Option<StreamData>, CollectorError> {
+        let kick_channel = self.api_client.get_channel(&channel.channel_id).await?;
+        Ok(Self::to_stream_data(&kick_channel))
+    }
+
+    async fn start_collection(
+        &self,
+        _channel: &Channel,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // Kickの公開APIは認証不要
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_stream_data() {
+        let channel: KickChannel = serde_json::from_str(
+            r#"{
+                "followers_count": 1234,
+                "chatroom": {"id": 99},
+                "livestream": {
+                    "id": 555,
+                    "session_title": "Hello",
+                    "start_time": "2025-01-06 12:00:00",
+                    "is_live": true,
+                    "viewer_count": 42,
+                    "thumbnail": {"url": "https://example.com/thumb.jpg"},
+                    "categories": [{"id": 7, "name": "Just Chatting"}]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let stream_data = KickCollector::to_stream_data(&channel).unwrap();
+        assert_eq!(stream_data.stream_id, "555");
+        assert_eq!(stream_data.category.as_deref(), Some("Just Chatting"));
+        assert_eq!(stream_data.game_id.as_deref(), Some("7"));
+        assert_eq!(stream_data.started_at, "2025-01-06T12:00:00+00:00");
+        assert_eq!(stream_data.viewer_count, Some(42));
+        assert_eq!(stream_data.follower_count, Some(1234));
+
+        let offline: KickChannel =
+            serde_json::from_str(r#"{"followers_count": 1, "livestream": null}"#).unwrap();
+        assert!(KickCollector::to_stream_data(&offline).is_none());
+    }
+}
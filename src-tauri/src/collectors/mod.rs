@@ -1,5 +1,12 @@
 pub mod auto_discovery;
+pub mod backoff;
 pub mod collector_trait;
+pub mod health;
+pub mod kick;
+pub mod poll_policy;
 pub mod poller;
+pub mod replay;
+pub mod stream_session;
 pub mod twitch;
+pub mod twitch_scheduler;
 pub mod youtube;
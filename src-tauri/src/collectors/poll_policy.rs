@@ -0,0 +1,328 @@
+/// チャンネル別ポーリング間隔の決定
+///
+/// ポリシー（ライブ中/オフライン/静穏時間帯/開始時間帯）とレート制限の使用状況から
+/// 次回ポーリングまでの間隔を算出します。ポリシーは毎回DBから読み込むため、
+/// 変更は再起動せずに次回のポーリングから反映されます。
+///
+/// YouTubeはクォータの日次予算に収まるよう間隔を延長します。
+use crate::api::youtube_api::QuotaConsumer;
+use crate::collectors::poller::PollContext;
+use crate::constants::{database as db_constants, polling, twitch};
+use crate::database::{
+    models::{Channel, PollPolicy},
+    repositories::PollPolicyRepository,
+};
+use chrono::{
+    DateTime, Datelike, Duration as ChronoDuration, Local, NaiveDateTime, TimeZone, Timelike, Utc,
+};
+use std::collections::{HashMap, HashSet};
+use tokio::time::{Duration, Instant};
+
+/// 開始時間帯（曜日: 月曜=0, 時: 0-23、ローカル時刻）
+type StartSlot = (u32, u32);
+
+/// 過去の配信開始時刻（UTC）から、よく配信を開始する曜日・時刻を学習する
+pub fn learn_start_slots(started_ats: &[String]) -> HashSet<StartSlot> {
+    let mut counts: HashMap<StartSlot, usize> = HashMap::new();
+
+    for started_at in started_ats {
+        let Ok(naive) = NaiveDateTime::parse_from_str(started_at, "%Y-%m-%d %H:%M:%S%.f")
+            .or_else(|_| NaiveDateTime::parse_from_str(started_at, "%Y-%m-%d %H:%M:%S"))
+        else {
+            continue;
+        };
+        let local = Utc.from_utc_datetime(&naive).with_timezone(&Local);
+        *counts
+            .entry((local.weekday().num_days_from_monday(), local.hour()))
+            .or_insert(0) += 1;
+    }
+
+    counts
+        .into_iter()
+        .filter(|(_, count)| *count >= polling::START_WINDOW_MIN_OCCURRENCES)
+        .map(|(slot, _)| slot)
+        .collect()
+}
+
+/// 静穏時間帯かどうか（日付をまたぐ指定にも対応）
+fn in_quiet_hours(hour: u32, start: i32, end: i32) -> bool {
+    let (hour, start, end) = (hour as i32, start.rem_euclid(24), end.rem_euclid(24));
+    if start == end {
+        false
+    } else if start < end {
+        hour >= start && hour < end
+    } else {
+        hour >= start || hour < end
+    }
+}
+
+/// 開始時間帯かどうか（学習した開始時刻の1時間前から開始時刻の時間内まで）
+fn in_start_window(slots: &HashSet<StartSlot>, now: DateTime<Local>) -> bool {
+    let current = (now.weekday().num_days_from_monday(), now.hour());
+    let next_hour = now + ChronoDuration::hours(1);
+    let upcoming = (next_hour.weekday().num_days_from_monday(), next_hour.hour());
+    slots.contains(&current) || slots.contains(&upcoming)
+}
+
+/// ポーリング間隔を算出する
+///
+/// 優先順位: ライブ中 > 開始時間帯 > 静穏時間帯 > オフライン。
+/// rate_limit_usage が閾値を超えている場合は間隔を延長します。
+pub fn compute_interval(
+    policy: Option<&PollPolicy>,
+    base_interval: i32,
+    is_live: bool,
+    now: DateTime<Local>,
+    start_slots: &HashSet<StartSlot>,
+    rate_limit_usage: Option<f32>,
+) -> Duration {
+    let seconds = match policy {
+        None => base_interval,
+        Some(policy) if is_live => policy.live_interval.unwrap_or(base_interval),
+        Some(policy) => {
+            let start_window = policy
+                .start_window_interval
+                .filter(|_| in_start_window(start_slots, now));
+            let quiet = match (
+                policy.quiet_hours_start,
+                policy.quiet_hours_end,
+                policy.quiet_interval,
+            ) {
+                (Some(start), Some(end), Some(interval))
+                    if in_quiet_hours(now.hour(), start, end) =>
+                {
+                    Some(interval)
+                }
+                _ => None,
+            };
+            start_window
+                .or(quiet)
+                .or(policy.offline_interval)
+                .unwrap_or(base_interval)
+        }
+    };
+
+    let mut seconds = (seconds.max(0) as u64).max(polling::MIN_POLL_INTERVAL_SECS);
+    if rate_limit_usage.is_some_and(|usage| usage >= twitch::RATE_LIMIT_SLOWDOWN_THRESHOLD_PERCENT)
+    {
+        seconds *= twitch::RATE_LIMIT_SLOWDOWN_FACTOR as u64;
+    }
+    Duration::from_secs(seconds)
+}
+
+/// チャンネルごとの次回ポーリング間隔を決定するプランナー
+///
+/// 学習した開始時間帯はチャンネルごとにキャッシュし、一定間隔で再計算します。
+#[derive(Default)]
+pub struct PollPlanner {
+    start_slots: HashMap<i64, (Instant, HashSet<StartSlot>)>,
+}
+
+impl PollPlanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 不要になったチャンネルのキャッシュを破棄
+    pub fn forget(&mut self, channel_id: i64) {
+        self.start_slots.remove(&channel_id);
+    }
+
+    /// 次回ポーリングまでの間隔を算出
+    pub async fn next_interval(
+        &mut self,
+        ctx: &PollContext,
+        channel: &Channel,
+        is_live: bool,
+    ) -> Duration {
+        let Some(channel_id) = channel.id else {
+            return compute_interval(
+                None,
+                channel.poll_interval,
+                is_live,
+                Local::now(),
+                &HashSet::new(),
+                None,
+            );
+        };
+
+        let refresh_slots = self
+            .start_slots
+            .get(&channel_id)
+            .is_none_or(|(loaded_at, _)| {
+                loaded_at.elapsed() >= Duration::from_secs(polling::START_WINDOW_REFRESH_SECS)
+            });
+
+        // DB read (lock held briefly)
+        let loaded = ctx
+            .db_manager
+            .with_connection(|conn| -> Result<_, duckdb::Error> {
+                let policy = PollPolicyRepository::get(conn, channel_id)?;
+                let start_times = if refresh_slots
+                    && policy
+                        .as_ref()
+                        .is_some_and(|p| p.start_window_interval.is_some())
+                {
+                    Some(PollPolicyRepository::get_recent_start_times(
+                        conn,
+                        channel_id,
+                        polling::START_WINDOW_LOOKBACK_DAYS,
+                    )?)
+                } else {
+                    None
+                };
+                Ok((policy, start_times))
+            })
+            .await;
+
+        let policy = match loaded {
+            Ok((policy, start_times)) => {
+                if let Some(start_times) = start_times {
+                    self.start_slots.insert(
+                        channel_id,
+                        (Instant::now(), learn_start_slots(&start_times)),
+                    );
+                }
+                policy
+            }
+            Err(e) => {
+                eprintln!(
+                    "[PollPlanner] Failed to load poll policy for channel {}: {}",
+                    channel_id, e
+                );
+                None
+            }
+        };
+
+        // Twitchの場合はレート制限の使用状況に応じて減速
+        let rate_limit_usage = if channel.platform == db_constants::PLATFORM_TWITCH {
+            match ctx.twitch_collector {
+                Some(ref twitch_collector) => Some(
+                    twitch_collector
+                        .get_api_client()
+                        .get_rate_limiter()
+                        .lock()
+                        .await
+                        .get_status()
+                        .usage_percent,
+                ),
+                None => None,
+            }
+        } else {
+            None
+        };
+
+        let empty = HashSet::new();
+        let start_slots = self
+            .start_slots
+            .get(&channel_id)
+            .map(|(_, slots)| slots)
+            .unwrap_or(&empty);
+
+        let interval = compute_interval(
+            policy.as_ref(),
+            channel.poll_interval,
+            is_live,
+            Local::now(),
+            start_slots,
+            rate_limit_usage,
+        );
+
+        // YouTubeの場合はポーリング中の全チャンネルと収集中のライブチャットでクォータの日次予算を分け合う
+        if channel.platform == db_constants::PLATFORM_YOUTUBE {
+            if let Some(ref youtube_collector) = ctx.youtube_collector {
+                let polling_channels = ctx
+                    .status_map
+                    .read()
+                    .map(|map| {
+                        map.values()
+                            .filter(|status| {
+                                status.is_running
+                                    && status.platform == db_constants::PLATFORM_YOUTUBE
+                            })
+                            .count()
+                    })
+                    .unwrap_or(1);
+                let quota_tracker = youtube_collector.get_quota_tracker();
+                let mut quota_tracker = quota_tracker.lock().await;
+                quota_tracker.set_consumers(
+                    QuotaConsumer::ChannelPoll,
+                    polling_channels,
+                    youtube_collector.estimated_units_per_poll(),
+                );
+                return interval.max(quota_tracker.budget_interval());
+            }
+        }
+
+        interval
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> PollPolicy {
+        PollPolicy {
+            channel_id: 1,
+            live_interval: Some(30),
+            offline_interval: Some(300),
+            quiet_hours_start: Some(2),
+            quiet_hours_end: Some(8),
+            quiet_interval: Some(1800),
+            start_window_interval: Some(60),
+            updated_at: None,
+        }
+    }
+
+    fn at_hour(hour: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2025, 1, 6, hour, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn test_quiet_hours_wrap_midnight() {
+        assert!(in_quiet_hours(23, 22, 6));
+        assert!(in_quiet_hours(3, 22, 6));
+        assert!(!in_quiet_hours(6, 22, 6));
+        assert!(!in_quiet_hours(12, 22, 6));
+        assert!(!in_quiet_hours(12, 5, 5));
+    }
+
+    #[test]
+    fn test_compute_interval_priorities() {
+        let policy = policy();
+        let no_slots = HashSet::new();
+
+        // ポリシー未設定なら channels.poll_interval
+        assert_eq!(
+            compute_interval(None, 60, false, at_hour(12), &no_slots, None),
+            Duration::from_secs(60)
+        );
+        // ライブ中は静穏時間帯でもライブ間隔
+        assert_eq!(
+            compute_interval(Some(&policy), 60, true, at_hour(3), &no_slots, None),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            compute_interval(Some(&policy), 60, false, at_hour(3), &no_slots, None),
+            Duration::from_secs(1800)
+        );
+        assert_eq!(
+            compute_interval(Some(&policy), 60, false, at_hour(12), &no_slots, None),
+            Duration::from_secs(300)
+        );
+
+        // 月曜13時に開始する傾向がある場合、12時台は開始時間帯
+        let slots: HashSet<StartSlot> = [(0, 13)].into_iter().collect();
+        assert_eq!(
+            compute_interval(Some(&policy), 60, false, at_hour(12), &slots, None),
+            Duration::from_secs(60)
+        );
+
+        // レート制限使用率が高い場合は減速
+        assert_eq!(
+            compute_interval(Some(&policy), 60, true, at_hour(12), &no_slots, Some(95.0)),
+            Duration::from_secs(30 * twitch::RATE_LIMIT_SLOWDOWN_FACTOR as u64)
+        );
+    }
+}
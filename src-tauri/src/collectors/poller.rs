@@ -1,20 +1,35 @@
-use crate::collectors::collector_trait::Collector;
+use crate::collectors::backoff::{BreakerState, ChannelBackoff};
+use crate::collectors::collector_trait::{Collector, CollectorError};
+use crate::collectors::kick::KickCollector;
+use crate::collectors::poll_policy::PollPlanner;
+use crate::collectors::replay::{self, ReplayCollector};
+use crate::collectors::stream_session::{LiveTransition, OfflineTransition, StreamSession};
 use crate::collectors::twitch::TwitchCollector;
+use crate::collectors::twitch_scheduler::TwitchPollScheduler;
+use crate::collectors::youtube::YouTubeCollector;
+use crate::config::settings::{SettingsManager, StreamSessionSettings};
 use crate::constants::database as db_constants;
 use crate::database::{
-    models::{Channel, ChannelStatsEvent, Stream, StreamData, StreamStats},
-    repositories::ChannelRepository,
+    models::{
+        Channel, ChannelStatsEvent, Stream, StreamData, StreamEndedEvent, StreamStartedEvent,
+        StreamStats,
+    },
+    repositories::{
+        ChannelRepository, ScheduledStreamRepository, StreamEventRepository, StreamMetadata,
+        StreamRepository,
+    },
     writer::DatabaseWriter,
     DatabaseManager,
 };
 use crate::logger::AppLogger;
-use chrono::Local;
+use chrono::{DateTime, Local};
 use duckdb::Connection;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
+use std::time::Instant;
 use tauri::{AppHandle, Emitter, Manager, State};
-use tokio::time::{interval, Duration, MissedTickBehavior};
+use tokio::time::{sleep, Duration};
 
 #[derive(Debug, Clone, Serialize)]
 pub struct CollectorStatus {
@@ -27,13 +42,593 @@ pub struct CollectorStatus {
     pub last_error: Option<String>,
     pub poll_count: u64,
     pub error_count: u64,
+    /// 連続失敗回数
+    pub consecutive_failures: u32,
+    /// サーキットブレーカーの状態
+    pub breaker_state: BreakerState,
+    /// 失敗後に再試行が許可される時刻
+    pub next_retry_at: Option<String>,
+}
+
+/// ポーリング結果の処理に必要な共有コンテキスト
+///
+/// チャンネル単位のポーリングタスクとTwitchバッチスケジューラーの両方から使用します。
+#[derive(Clone)]
+pub struct PollContext {
+    pub db_manager: Arc<DatabaseManager>,
+    pub app_handle: AppHandle,
+    pub status_map: Arc<RwLock<HashMap<i64, CollectorStatus>>>,
+    pub twitch_collector: Option<Arc<TwitchCollector>>,
+    pub kick_collector: Option<Arc<KickCollector>>,
+    pub youtube_collector: Option<Arc<YouTubeCollector>>,
+}
+
+impl PollContext {
+    pub fn logger(&self) -> State<'_, AppLogger> {
+        self.app_handle.state::<AppLogger>()
+    }
+
+    /// ポーリング開始を記録し、累計ポーリング回数を返す
+    pub fn record_poll_started(&self, channel_id: i64) -> u64 {
+        let now = Local::now().to_rfc3339();
+        if let Ok(mut map) = self.status_map.write() {
+            if let Some(status) = map.get_mut(&channel_id) {
+                status.last_poll_at = Some(now);
+                status.poll_count += 1;
+                return status.poll_count;
+            }
+        }
+        0
+    }
+
+    fn record_success(&self, channel_id: i64) {
+        let now = Local::now().to_rfc3339();
+        if let Ok(mut map) = self.status_map.write() {
+            if let Some(status) = map.get_mut(&channel_id) {
+                status.last_success_at = Some(now);
+                status.last_error = None;
+            }
+        }
+    }
+
+    pub fn record_error(&self, channel_id: i64, error_msg: String) {
+        if let Ok(mut map) = self.status_map.write() {
+            if let Some(status) = map.get_mut(&channel_id) {
+                status.last_error = Some(error_msg);
+                status.error_count += 1;
+            }
+        }
+    }
+
+    /// バックオフ・サーキットブレーカーの状態を記録
+    fn record_backoff(&self, channel_id: i64, backoff: &ChannelBackoff) {
+        let next_retry_at = backoff.retry_at().map(|at| {
+            let remaining = at.saturating_duration_since(Instant::now());
+            (Local::now() + chrono::Duration::from_std(remaining).unwrap_or_default()).to_rfc3339()
+        });
+        if let Ok(mut map) = self.status_map.write() {
+            if let Some(status) = map.get_mut(&channel_id) {
+                status.consecutive_failures = backoff.consecutive_failures();
+                status.breaker_state = backoff.breaker_state();
+                status.next_retry_at = next_retry_at;
+            }
+        }
+    }
+
+    /// ポーリング停止を記録
+    pub fn record_stopped(&self, channel_id: i64, error_msg: Option<String>) {
+        if let Ok(mut map) = self.status_map.write() {
+            if let Some(status) = map.get_mut(&channel_id) {
+                if error_msg.is_some() {
+                    status.last_error = error_msg;
+                }
+                status.is_running = false;
+            }
+        }
+    }
+
+    /// Twitchトークンの有効期限をチェックし、必要に応じてリフレッシュ
+    pub async fn refresh_twitch_token_if_needed(&self) {
+        if let Some(ref twitch_collector) = self.twitch_collector {
+            match twitch_collector.check_and_refresh_token_if_needed().await {
+                Ok(true) => {
+                    self.logger().info("Twitch token refreshed proactively");
+                }
+                Ok(false) => {
+                    // トークンはまだ有効
+                }
+                Err(e) => {
+                    self.logger()
+                        .error(&format!("Failed to check/refresh Twitch token: {}", e));
+                }
+            }
+        }
+    }
+
+    /// 手動登録のTwitch/Kickチャンネルであればチャット接続を開始
+    pub async fn start_chat_if_needed(&self, channel: &Channel) {
+        if channel.is_auto_discovered {
+            return;
+        }
+        if channel.platform == db_constants::PLATFORM_KICK {
+            self.start_kick_chat(channel).await;
+            return;
+        }
+        if channel.platform != db_constants::PLATFORM_TWITCH {
+            return;
+        }
+        let (Some(ref twitch_collector), Some(channel_id)) = (&self.twitch_collector, channel.id)
+        else {
+            return;
+        };
+
+        // IRC接続にはlogin name (channel_id)を使用、display name (channel_name)ではない
+        if let Err(e) = twitch_collector
+            .start_chat_collection(channel_id, &channel.channel_id, false)
+            .await
+        {
+            eprintln!(
+                "[ChannelPoller] Failed to start IRC for {} (login: {}): {}",
+                channel.channel_name, channel.channel_id, e
+            );
+        } else {
+            println!(
+                "[ChannelPoller] Started IRC for channel {} ({}, login: {})",
+                channel_id, channel.channel_name, channel.channel_id
+            );
+        }
+    }
+
+    /// Kickチャンネルのチャット（Pusher WebSocket）接続を開始
+    async fn start_kick_chat(&self, channel: &Channel) {
+        let (Some(ref kick_collector), Some(channel_id)) = (&self.kick_collector, channel.id)
+        else {
+            return;
+        };
+
+        // チャットルームIDの解決にはスラッグ (channel_id) を使用
+        if let Err(e) = kick_collector
+            .start_chat_collection(channel_id, &channel.channel_id)
+            .await
+        {
+            eprintln!(
+                "[ChannelPoller] Failed to start Kick chat for {} (slug: {}): {}",
+                channel.channel_name, channel.channel_id, e
+            );
+        } else {
+            println!(
+                "[ChannelPoller] Started Kick chat for channel {} ({}, slug: {})",
+                channel_id, channel.channel_name, channel.channel_id
+            );
+        }
+    }
+
+    /// チャンネル情報を再取得（更新されている可能性があるため）
+    ///
+    /// 戻り値: Ok(None) = 削除または無効化されたためポーリングを終了すべき
+    pub async fn reload_channel(&self, channel_id: i64) -> Result<Option<Channel>, String> {
+        // DB read (lock held briefly)
+        let channel = self
+            .db_manager
+            .with_connection(|conn| ChannelPoller::get_channel(conn, channel_id))
+            .await
+            .map_err(|e| e.to_string())?;
+
+        match channel {
+            Some(ch) if ch.enabled => Ok(Some(ch)),
+            Some(_) => {
+                // チャンネルが無効化された場合はポーリングを終了
+                self.logger().info(&format!(
+                    "Channel {} was disabled, stopping polling",
+                    channel_id
+                ));
+                Ok(None)
+            }
+            None => {
+                // チャンネルが削除された場合はポーリングを終了
+                self.logger().info(&format!(
+                    "Channel {} was deleted, stopping polling",
+                    channel_id
+                ));
+                Ok(None)
+            }
+        }
+    }
+
+    /// 配信セッションを初期化（前回起動時から終了していない配信を引き継ぐ）
+    pub async fn init_session(&self, channel_id: i64) -> StreamSession {
+        let mut session = StreamSession::new(self.load_offline_confirmation());
+        match self
+            .db_manager
+            .with_connection(|conn| ChannelPoller::restore_stream_session(conn, channel_id))
+            .await
+        {
+            Ok(Some(stream_db_id)) => session.restore(stream_db_id),
+            Ok(None) => {}
+            Err(e) => {
+                self.logger().error(&format!(
+                    "Failed to restore stream session for channel {}: {}",
+                    channel_id, e
+                ));
+            }
+        }
+        session
+    }
+
+    /// 設定からオフライン確定猶予時間を読み込む
+    fn load_offline_confirmation(&self) -> Duration {
+        let seconds = SettingsManager::load_settings(&self.app_handle)
+            .map(|settings| settings.stream_session.offline_confirmation_seconds)
+            .unwrap_or_else(|_| StreamSessionSettings::default().offline_confirmation_seconds);
+        Duration::from_secs(seconds)
+    }
+
+    /// ポーリング結果を処理する（保存・セッション遷移・イベント発行・ステータス更新）
+    pub async fn handle_poll_result(
+        &self,
+        channel: &Channel,
+        session: &mut StreamSession,
+        backoff: &mut ChannelBackoff,
+        poll_result: Result<Option<StreamData>, CollectorError>,
+    ) {
+        let Some(channel_id) = channel.id else {
+            return;
+        };
+
+        // 記録モードではポーリング結果をキャプチャファイルにも書き出す
+        if let (Some(recorder), Ok(stream_data)) = (replay::active_recorder(), &poll_result) {
+            recorder.record_stream(channel, stream_data.as_ref());
+        }
+
+        backoff.on_attempt(Instant::now());
+        match poll_result {
+            Ok(Some(stream_data)) => {
+                backoff.on_success();
+                self.handle_live(channel, channel_id, session, stream_data)
+                    .await
+            }
+            Ok(None) => {
+                backoff.on_success();
+                self.handle_offline(channel, channel_id, session).await
+            }
+            Err(e) => self.handle_error(channel, channel_id, backoff, e),
+        }
+        self.record_backoff(channel_id, backoff);
+    }
+
+    async fn handle_live(
+        &self,
+        channel: &Channel,
+        channel_id: i64,
+        session: &mut StreamSession,
+        stream_data: StreamData,
+    ) {
+        // ストリーム情報をデータベースに保存（DB write - lock held briefly）
+        let save_result = self
+            .db_manager
+            .with_connection(|conn| ChannelPoller::save_stream_data(conn, channel, &stream_data))
+            .await
+            .map_err(|e| e.to_string());
+
+        let stream_db_id = match save_result {
+            Ok(stream_db_id) => stream_db_id,
+            Err(e) => {
+                self.logger().error(&format!(
+                    "Failed to save stream data for channel {}: {}",
+                    channel_id, e
+                ));
+                self.record_error(channel_id, format!("Failed to save data: {}", e));
+                return;
+            }
+        };
+
+        self.record_success(channel_id);
+
+        // Twitch手動登録チャンネルの場合、IRC Managerにstream_idを通知
+        if channel.platform == db_constants::PLATFORM_TWITCH && !channel.is_auto_discovered {
+            if let Some(ref twitch_collector) = self.twitch_collector {
+                twitch_collector
+                    .update_stream_id(channel_id, Some(stream_db_id))
+                    .await;
+            }
+        }
+        if channel.platform == db_constants::PLATFORM_KICK && !channel.is_auto_discovered {
+            if let Some(ref kick_collector) = self.kick_collector {
+                kick_collector
+                    .update_stream_id(channel_id, Some(stream_db_id))
+                    .await;
+            }
+        }
+        // YouTubeはライブ配信（動画）ごとにチャットが分かれるため、検出した動画のチャットを収集
+        if channel.platform == db_constants::PLATFORM_YOUTUBE {
+            if let Some(ref youtube_collector) = self.youtube_collector {
+                if let Err(e) = youtube_collector
+                    .start_chat_collection(channel_id, stream_db_id, &stream_data.stream_id)
+                    .await
+                {
+                    self.logger().error(&format!(
+                        "Failed to start YouTube chat for channel {} (video {}): {}",
+                        channel_id, stream_data.stream_id, e
+                    ));
+                }
+            }
+        }
+
+        // 配信セッションの状態遷移
+        let previous_stream_db_id = match session.on_live(stream_db_id) {
+            LiveTransition::Started => Some(None),
+            LiveTransition::Replaced {
+                previous_stream_db_id,
+            } => Some(Some(previous_stream_db_id)),
+            LiveTransition::Resumed => {
+                self.logger().info(&format!(
+                    "Stream {} of channel {} resumed within offline confirmation window",
+                    stream_db_id, channel_id
+                ));
+                None
+            }
+            LiveTransition::Continued => None,
+        };
+
+        if let Some(previous_stream_db_id) = previous_stream_db_id {
+            if let Some(previous) = previous_stream_db_id {
+                self.end_stream_session(channel_id, previous).await;
+            }
+            self.match_scheduled_stream(channel_id, stream_db_id, &stream_data)
+                .await;
+
+            self.logger().info(&format!(
+                "Stream started for channel {} (stream {})",
+                channel_id, stream_db_id
+            ));
+            let _ = self.app_handle.emit(
+                "stream-started",
+                StreamStartedEvent {
+                    channel_id,
+                    stream_id: stream_db_id,
+                    platform_stream_id: stream_data.stream_id.clone(),
+                    title: stream_data.title.clone(),
+                    category: stream_data.category.clone(),
+                    started_at: stream_data.started_at.clone(),
+                    is_reconnect: previous_stream_db_id.is_some(),
+                },
+            );
+        }
+
+        // イベント発行: チャンネルがライブ中
+        let event = ChannelStatsEvent {
+            channel_id,
+            is_live: true,
+            viewer_count: stream_data.viewer_count,
+            title: stream_data.title.clone(),
+        };
+        let _ = self.app_handle.emit("channel-stats-updated", event);
+    }
+
+    async fn handle_offline(
+        &self,
+        channel: &Channel,
+        channel_id: i64,
+        session: &mut StreamSession,
+    ) {
+        // 配信していない - オフラインイベントを発行
+        // Update status with success (not live is valid state)
+        self.record_success(channel_id);
+
+        // 猶予時間内の一時的な切断では配信を終了させない
+        session.set_offline_confirmation(self.load_offline_confirmation());
+        if let OfflineTransition::Ended { stream_db_id } = session.on_offline(Instant::now()) {
+            self.end_stream_session(channel_id, stream_db_id).await;
+
+            // Twitch手動登録チャンネルの場合、IRC Managerにオフライン通知
+            if channel.platform == db_constants::PLATFORM_TWITCH && !channel.is_auto_discovered {
+                if let Some(ref twitch_collector) = self.twitch_collector {
+                    twitch_collector.update_stream_id(channel_id, None).await;
+                }
+            }
+            if channel.platform == db_constants::PLATFORM_KICK && !channel.is_auto_discovered {
+                if let Some(ref kick_collector) = self.kick_collector {
+                    kick_collector.update_stream_id(channel_id, None).await;
+                }
+            }
+            if channel.platform == db_constants::PLATFORM_YOUTUBE {
+                if let Some(ref youtube_collector) = self.youtube_collector {
+                    let _ = youtube_collector.stop_chat_collection(channel_id).await;
+                }
+            }
+        }
+
+        let event = ChannelStatsEvent {
+            channel_id,
+            is_live: false,
+            viewer_count: None,
+            title: None,
+        };
+        let _ = self.app_handle.emit("channel-stats-updated", event);
+    }
+
+    fn handle_error(
+        &self,
+        channel: &Channel,
+        channel_id: i64,
+        backoff: &mut ChannelBackoff,
+        e: CollectorError,
+    ) {
+        let error_msg = format!("Failed to poll channel {}: {}", channel_id, e);
+        self.logger().error(&error_msg);
+
+        let retry_delay = backoff.on_failure(&e, Instant::now());
+        if backoff.breaker_state() == BreakerState::Open {
+            self.logger().error(&format!(
+                "Circuit breaker opened for channel {} after {} consecutive failures, pausing for {} seconds",
+                channel_id,
+                backoff.consecutive_failures(),
+                retry_delay.as_secs()
+            ));
+        }
+
+        if let CollectorError::Auth(_) = e {
+            self.logger().error("Token authentication issue detected. Automatic refresh will be attempted on next poll or during periodic check.");
+
+            // フロントエンドに通知（オプション）
+            #[derive(Clone, serde::Serialize)]
+            struct AuthErrorEvent {
+                platform: String,
+                channel_id: i64,
+                message: String,
+            }
+
+            let _ = self.app_handle.emit(
+                "auth-error",
+                AuthErrorEvent {
+                    platform: channel.platform.clone(),
+                    channel_id,
+                    message: "Token may have expired. Automatic refresh will be attempted."
+                        .to_string(),
+                },
+            );
+        }
+
+        // Update status with error
+        self.record_error(channel_id, error_msg);
+    }
+
+    /// 配信を終了させ、サマリー付きの stream-ended イベントを発行する
+    async fn end_stream_session(&self, channel_id: i64, stream_db_id: i64) {
+        let result = self
+            .db_manager
+            .with_connection(|conn| -> Result<Option<StreamEndedEvent>, duckdb::Error> {
+                if DatabaseWriter::close_stream(conn, stream_db_id)? == 0 {
+                    // 既に終了済み
+                    return Ok(None);
+                }
+                let info = StreamRepository::get_stream_info_by_id(conn, stream_db_id)?;
+                Ok(Some(StreamEndedEvent {
+                    channel_id,
+                    stream_id: info.id,
+                    platform_stream_id: info.stream_id,
+                    title: info.title,
+                    category: info.category,
+                    started_at: info.started_at,
+                    ended_at: info.ended_at,
+                    duration_minutes: info.duration_minutes,
+                    peak_viewers: info.peak_viewers,
+                    avg_viewers: info.avg_viewers,
+                    minutes_watched: info.minutes_watched,
+                    follower_gain: info.follower_gain,
+                    total_chat_messages: info.total_chat_messages,
+                }))
+            })
+            .await;
+
+        match result {
+            Ok(Some(event)) => {
+                self.logger().info(&format!(
+                    "Stream ended for channel {} (stream {}, {} minutes, peak {} viewers)",
+                    channel_id, stream_db_id, event.duration_minutes, event.peak_viewers
+                ));
+                let _ = self.app_handle.emit("stream-ended", event);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                self.logger().error(&format!(
+                    "Failed to close stream {} for channel {}: {}",
+                    stream_db_id, channel_id, e
+                ));
+            }
+        }
+    }
+
+    /// Twitchの配信スケジュールを取得して配信予定に反映する
+    pub async fn sync_twitch_schedule(&self, collector: &TwitchCollector, channel: &Channel) {
+        let Some(channel_id) = channel.id else {
+            return;
+        };
+
+        let entries = match collector.get_scheduled_streams(channel).await {
+            Ok(entries) => entries,
+            Err(e) => {
+                self.logger().error(&format!(
+                    "Failed to get Twitch schedule for channel {}: {}",
+                    channel_id, e
+                ));
+                return;
+            }
+        };
+
+        // 取得したページより先の予定は取り消さない
+        let horizon = entries
+            .iter()
+            .filter_map(|entry| DateTime::parse_from_rfc3339(&entry.scheduled_start).ok())
+            .max()
+            .map(|dt| dt.to_rfc3339());
+        let now = Local::now().to_rfc3339();
+        if let Err(e) = self
+            .db_manager
+            .with_connection(|conn| {
+                ScheduledStreamRepository::sync_channel_schedule(
+                    conn,
+                    channel_id,
+                    db_constants::PLATFORM_TWITCH,
+                    &entries,
+                    horizon.as_deref(),
+                    &now,
+                )
+            })
+            .await
+        {
+            self.logger().error(&format!(
+                "Failed to save Twitch schedule for channel {}: {}",
+                channel_id, e
+            ));
+        }
+    }
+
+    /// 始まった配信を配信予定と対応付ける
+    async fn match_scheduled_stream(
+        &self,
+        channel_id: i64,
+        stream_db_id: i64,
+        stream_data: &StreamData,
+    ) {
+        match self
+            .db_manager
+            .with_connection(|conn| {
+                ScheduledStreamRepository::match_started_stream(
+                    conn,
+                    channel_id,
+                    stream_db_id,
+                    &stream_data.stream_id,
+                    &stream_data.started_at,
+                )
+            })
+            .await
+        {
+            Ok(Some(schedule_id)) => {
+                self.logger().info(&format!(
+                    "Stream {} of channel {} matched scheduled stream {}",
+                    stream_db_id, channel_id, schedule_id
+                ));
+            }
+            Ok(None) => {}
+            Err(e) => {
+                self.logger().error(&format!(
+                    "Failed to match scheduled stream for channel {}: {}",
+                    channel_id, e
+                ));
+            }
+        }
+    }
 }
 
 pub struct ChannelPoller {
     collectors: HashMap<String, Arc<dyn Collector + Send + Sync>>,
     twitch_collector: Option<Arc<TwitchCollector>>,
+    kick_collector: Option<Arc<KickCollector>>,
+    youtube_collector: Option<Arc<YouTubeCollector>>,
     tasks: HashMap<i64, tokio::task::JoinHandle<()>>,
     status_map: Arc<RwLock<HashMap<i64, CollectorStatus>>>,
+    twitch_scheduler: TwitchPollScheduler,
 }
 
 impl ChannelPoller {
@@ -41,31 +636,76 @@ impl ChannelPoller {
         Self {
             collectors: HashMap::new(),
             twitch_collector: None,
+            kick_collector: None,
+            youtube_collector: None,
             tasks: HashMap::new(),
             status_map: Arc::new(RwLock::new(HashMap::new())),
+            twitch_scheduler: TwitchPollScheduler::new(),
         }
     }
 
-    pub fn register_collector(
-        &mut self,
-        platform: String,
-        collector: Arc<dyn Collector + Send + Sync>,
-    ) {
-        self.collectors.insert(platform.clone(), collector);
-    }
-
     /// Register Twitch collector specifically for token management
+    ///
+    /// 再認証で差し替えた場合も、起動中のバッチスケジューラーは次のサイクルから新しいコレクターを使います。
     pub fn register_twitch_collector(&mut self, collector: Arc<TwitchCollector>) {
         self.twitch_collector = Some(collector.clone());
+        self.twitch_scheduler.set_collector(Some(collector.clone()));
         self.collectors
             .insert(db_constants::PLATFORM_TWITCH.to_string(), collector);
     }
 
+    /// Kickコレクターを登録（チャット接続の管理用に保持）
+    pub fn register_kick_collector(&mut self, collector: Arc<KickCollector>) {
+        self.kick_collector = Some(collector.clone());
+        self.collectors
+            .insert(db_constants::PLATFORM_KICK.to_string(), collector);
+    }
+
+    /// YouTubeコレクターを登録（ライブチャット収集の管理用に保持）
+    pub fn register_youtube_collector(&mut self, collector: Arc<YouTubeCollector>) {
+        self.youtube_collector = Some(collector.clone());
+        self.collectors
+            .insert(db_constants::PLATFORM_YOUTUBE.to_string(), collector);
+    }
+
+    /// 再生コレクターを全プラットフォームに登録（ライブのコレクターは使用しない）
+    pub fn register_replay_collector(&mut self, collector: Arc<ReplayCollector>) {
+        self.twitch_collector = None;
+        self.twitch_scheduler.set_collector(None);
+        self.kick_collector = None;
+        self.youtube_collector = None;
+        for platform in db_constants::SUPPORTED_PLATFORMS {
+            self.collectors
+                .insert(platform.to_string(), collector.clone());
+        }
+    }
+
+    /// 全チャンネルのコレクターステータスを取得（channel_id順）
+    pub fn get_statuses(&self) -> Vec<CollectorStatus> {
+        let mut statuses: Vec<CollectorStatus> = self
+            .status_map
+            .read()
+            .map(|map| map.values().cloned().collect())
+            .unwrap_or_default();
+        statuses.sort_by_key(|status| status.channel_id);
+        statuses
+    }
+
     /// Get Twitch collector for rate limit tracking
     pub fn get_twitch_collector(&self) -> Option<&Arc<TwitchCollector>> {
         self.twitch_collector.as_ref()
     }
 
+    /// Kickコレクターを取得（チャット接続状態の参照用）
+    pub fn get_kick_collector(&self) -> Option<&Arc<KickCollector>> {
+        self.kick_collector.as_ref()
+    }
+
+    /// YouTubeコレクターを取得
+    pub fn get_youtube_collector(&self) -> Option<&Arc<YouTubeCollector>> {
+        self.youtube_collector.as_ref()
+    }
+
     pub fn start_polling(
         &mut self,
         channel: Channel,
@@ -94,7 +734,6 @@ impl ChannelPoller {
             "[ChannelPoller] Starting polling for channel {} ({}) with interval {} seconds",
             channel_id, channel.channel_name, poll_interval_secs
         );
-        let db_manager = Arc::new(db_manager.inner().clone());
 
         // Initialize status
         if let Ok(mut status_map) = self.status_map.write() {
@@ -110,253 +749,75 @@ impl ChannelPoller {
                     last_error: None,
                     poll_count: 0,
                     error_count: 0,
+                    consecutive_failures: 0,
+                    breaker_state: BreakerState::Closed,
+                    next_retry_at: None,
                 },
             );
         }
 
-        let status_map = Arc::clone(&self.status_map);
-        let twitch_collector_for_task = self.twitch_collector.clone();
+        let ctx = PollContext {
+            db_manager: Arc::new(db_manager.inner().clone()),
+            app_handle,
+            status_map: Arc::clone(&self.status_map),
+            twitch_collector: self.twitch_collector.clone(),
+            kick_collector: self.kick_collector.clone(),
+            youtube_collector: self.youtube_collector.clone(),
+        };
 
-        let task = tokio::spawn(async move {
-            // 手動登録チャンネルかつTwitchの場合、IRC接続を開始
-            if channel.platform == db_constants::PLATFORM_TWITCH && !channel.is_auto_discovered {
-                if let Some(ref twitch_collector) = &twitch_collector_for_task {
-                    // IRC接続にはlogin name (channel_id)を使用、display name (channel_name)ではない
-                    if let Err(e) = twitch_collector
-                        .start_chat_collection(channel_id, &channel.channel_id)
-                        .await
-                    {
-                        eprintln!(
-                            "[ChannelPoller] Failed to start IRC for {} (login: {}): {}",
-                            channel.channel_name, channel.channel_id, e
-                        );
-                    } else {
-                        println!(
-                            "[ChannelPoller] Started IRC for channel {} ({}, login: {})",
-                            channel_id, channel.channel_name, channel.channel_id
-                        );
-                    }
-                }
+        // Twitchチャンネルはバッチスケジューラーでまとめてポーリング
+        if channel.platform == db_constants::PLATFORM_TWITCH {
+            if let Some(ref twitch_collector) = self.twitch_collector {
+                self.twitch_scheduler
+                    .ensure_started(ctx, Arc::clone(twitch_collector));
+                self.twitch_scheduler.add_channel(channel_id);
+                return Ok(());
             }
+        }
 
-            // Get logger from app_handle
-            let logger = app_handle.state::<AppLogger>();
-
-            let mut interval = interval(poll_interval);
-            interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        let task = tokio::spawn(async move {
+            ctx.start_chat_if_needed(&channel).await;
 
             // 初回認証
             if let Err(e) = collector.start_collection(&channel).await {
-                logger.error(&format!(
+                ctx.logger().error(&format!(
                     "Failed to start collection for channel {}: {}",
                     channel_id, e
                 ));
                 // Update status with error
-                if let Ok(mut map) = status_map.write() {
-                    if let Some(status) = map.get_mut(&channel_id) {
-                        status.last_error = Some(format!("Failed to start collection: {}", e));
-                        status.is_running = false;
-                    }
-                }
+                ctx.record_stopped(
+                    channel_id,
+                    Some(format!("Failed to start collection: {}", e)),
+                );
                 return;
             }
 
-            loop {
-                interval.tick().await;
-
-                // Update last poll time
-                let now = Local::now().to_rfc3339();
-                let poll_count = if let Ok(mut map) = status_map.write() {
-                    if let Some(status) = map.get_mut(&channel_id) {
-                        status.last_poll_at = Some(now.clone());
-                        status.poll_count += 1;
-                        status.poll_count
-                    } else {
-                        0
-                    }
-                } else {
-                    0
-                };
+            let mut session = ctx.init_session(channel_id).await;
+            let mut backoff = ChannelBackoff::new();
+            let mut planner = PollPlanner::new();
 
-                // Twitch プラットフォームの場合、10回のポーリングごとにトークン有効期限をチェック
-                if channel.platform == db_constants::PLATFORM_TWITCH && poll_count % 10 == 0 {
-                    if let Some(ref twitch_collector) = twitch_collector_for_task {
-                        match twitch_collector.check_and_refresh_token_if_needed().await {
-                            Ok(true) => {
-                                logger.info("Twitch token refreshed proactively");
-                            }
-                            Ok(false) => {
-                                // トークンはまだ有効
-                            }
-                            Err(e) => {
-                                logger
-                                    .error(&format!("Failed to check/refresh Twitch token: {}", e));
-                            }
-                        }
-                    }
-                }
+            loop {
+                ctx.record_poll_started(channel_id);
 
-                // チャンネル情報を再取得（更新されている可能性があるため）
-                // DB read (lock held briefly)
-                let updated_channel = match db_manager
-                    .with_connection(|conn| Self::get_channel(conn, channel_id))
-                    .await
-                {
+                let updated_channel = match ctx.reload_channel(channel_id).await {
                     Ok(Some(ch)) => ch,
-                    Ok(None) => {
-                        // チャンネルが削除された場合はタスクを終了
-                        logger.info(&format!(
-                            "Channel {} was deleted, stopping polling",
-                            channel_id
-                        ));
-                        break;
-                    }
+                    Ok(None) => break,
                     Err(e) => {
-                        logger.error(&format!("Failed to get channel: {}", e));
+                        ctx.logger().error(&format!("Failed to get channel: {}", e));
+                        sleep(poll_interval).await;
                         continue;
                     }
                 };
 
-                if !updated_channel.enabled {
-                    // チャンネルが無効化された場合はタスクを終了
-                    logger.info(&format!(
-                        "Channel {} was disabled, stopping polling",
-                        channel_id
-                    ));
-                    break;
-                }
-
-                // チャンネル情報を定期的に更新（Twitchの場合のみ）
-                // 注: 実際のダウンキャストは複雑なため、ここではスキップ
-                // 代わりに、start_polling時に一度だけ取得する方式を採用する必要がある
-
                 // ポーリング実行（Network I/O - no lock held）
-                let poll_result = collector
-                    .poll_channel(&updated_channel)
-                    .await
-                    .map_err(|e| e.to_string());
-                match poll_result {
-                    Ok(Some(stream_data)) => {
-                        // ストリーム情報をデータベースに保存（DB write - lock held briefly）
-                        let save_result = db_manager
-                            .with_connection(|conn| {
-                                Self::save_stream_data(conn, &updated_channel, &stream_data)
-                            })
-                            .await
-                            .map_err(|e| e.to_string());
-                        match save_result {
-                            Ok(stream_db_id) => {
-                                // Update status with success
-                                let now = Local::now().to_rfc3339();
-                                if let Ok(mut map) = status_map.write() {
-                                    if let Some(status) = map.get_mut(&channel_id) {
-                                        status.last_success_at = Some(now);
-                                        status.last_error = None;
-                                    }
-                                }
-
-                                // Twitch手動登録チャンネルの場合、IRC Managerにstream_idを通知
-                                if updated_channel.platform == db_constants::PLATFORM_TWITCH
-                                    && !updated_channel.is_auto_discovered
-                                {
-                                    if let Some(ref twitch_collector) = twitch_collector_for_task {
-                                        twitch_collector
-                                            .update_stream_id(channel_id, Some(stream_db_id))
-                                            .await;
-                                    }
-                                }
-
-                                // イベント発行: チャンネルがライブ中
-                                let event = ChannelStatsEvent {
-                                    channel_id,
-                                    is_live: true,
-                                    viewer_count: stream_data.viewer_count,
-                                    title: stream_data.title.clone(),
-                                };
-                                let _ = app_handle.emit("channel-stats-updated", event);
-                            }
-                            Err(e) => {
-                                logger.error(&format!(
-                                    "Failed to save stream data for channel {}: {}",
-                                    channel_id, e
-                                ));
-                                // Update status with error
-                                if let Ok(mut map) = status_map.write() {
-                                    if let Some(status) = map.get_mut(&channel_id) {
-                                        status.last_error =
-                                            Some(format!("Failed to save data: {}", e));
-                                        status.error_count += 1;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    Ok(None) => {
-                        // 配信していない - オフラインイベントを発行
-                        // Update status with success (not live is valid state)
-                        let now = Local::now().to_rfc3339();
-                        if let Ok(mut map) = status_map.write() {
-                            if let Some(status) = map.get_mut(&channel_id) {
-                                status.last_success_at = Some(now);
-                                status.last_error = None;
-                            }
-                        }
-
-                        // Twitch手動登録チャンネルの場合、IRC Managerにオフライン通知
-                        if updated_channel.platform == db_constants::PLATFORM_TWITCH
-                            && !updated_channel.is_auto_discovered
-                        {
-                            if let Some(ref twitch_collector) = twitch_collector_for_task {
-                                twitch_collector.update_stream_id(channel_id, None).await;
-                            }
-                        }
-
-                        let event = ChannelStatsEvent {
-                            channel_id,
-                            is_live: false,
-                            viewer_count: None,
-                            title: None,
-                        };
-                        let _ = app_handle.emit("channel-stats-updated", event);
-                    }
-                    Err(e) => {
-                        let error_msg = format!("Failed to poll channel {}: {}", channel_id, e);
-                        logger.error(&error_msg);
-
-                        // トークン関連のエラーかチェック
-                        let error_str = e.to_string();
-                        let is_token_error = error_str.contains("not authorized")
-                            || error_str.contains("Unauthorized")
-                            || error_str.contains("Token")
-                            || error_str.contains("401");
-
-                        if is_token_error {
-                            logger.error("Token authentication issue detected. Automatic refresh will be attempted on next poll or during periodic check.");
-
-                            // フロントエンドに通知（オプション）
-                            #[derive(Clone, serde::Serialize)]
-                            struct AuthErrorEvent {
-                                platform: String,
-                                channel_id: i64,
-                                message: String,
-                            }
-
-                            let _ = app_handle.emit("auth-error", AuthErrorEvent {
-                                platform: channel.platform.clone(),
-                                channel_id,
-                                message: "Token may have expired. Automatic refresh will be attempted.".to_string(),
-                            });
-                        }
-
-                        // Update status with error
-                        if let Ok(mut map) = status_map.write() {
-                            if let Some(status) = map.get_mut(&channel_id) {
-                                status.last_error = Some(error_msg);
-                                status.error_count += 1;
-                            }
-                        }
-                    }
-                }
+                let poll_result = collector.poll_channel(&updated_channel).await;
+                ctx.handle_poll_result(&updated_channel, &mut session, &mut backoff, poll_result)
+                    .await;
+
+                // ポリシーとバックオフに応じて次回ポーリングまで待機
+                let is_live = session.current_stream_db_id().is_some();
+                let planned = planner.next_interval(&ctx, &updated_channel, is_live).await;
+                sleep(backoff.next_wait(planned, Instant::now())).await;
             }
         });
 
@@ -382,9 +843,42 @@ impl ChannelPoller {
             }
         }
 
+        // チャット接続を停止（Kickチャンネルの場合、接続がなければ何もしない）
+        if let Some(ref kick_collector) = self.kick_collector {
+            if kick_collector
+                .stop_chat_collection(channel_id)
+                .await
+                .is_ok()
+            {
+                println!(
+                    "[ChannelPoller] Stopped Kick chat for channel {}",
+                    channel_id
+                );
+            }
+        }
+
+        // ライブチャット収集を停止（YouTubeチャンネルの場合、収集中でなければ何もしない）
+        if let Some(ref youtube_collector) = self.youtube_collector {
+            if youtube_collector
+                .stop_chat_collection(channel_id)
+                .await
+                .is_ok()
+            {
+                println!(
+                    "[ChannelPoller] Stopped YouTube chat for channel {}",
+                    channel_id
+                );
+            }
+        }
+
         if let Some(task) = self.tasks.remove(&channel_id) {
             task.abort();
             println!("[ChannelPoller] Task aborted for channel {}", channel_id);
+        } else if self.twitch_scheduler.remove_channel(channel_id) {
+            println!(
+                "[ChannelPoller] Removed channel {} from Twitch scheduler",
+                channel_id
+            );
         } else {
             println!(
                 "[ChannelPoller] No running task found for channel {}",
@@ -404,6 +898,19 @@ impl ChannelPoller {
         ChannelRepository::get_by_id(conn, channel_id)
     }
 
+    /// 終了していない配信から引き継ぐ配信IDを決定する
+    /// 最新の1件以外は前回の異常終了などで取り残されたものとして終了させる
+    fn restore_stream_session(
+        conn: &Connection,
+        channel_id: i64,
+    ) -> Result<Option<i64>, duckdb::Error> {
+        let open_stream_ids = StreamRepository::get_open_stream_ids(conn, channel_id)?;
+        for stale_id in open_stream_ids.iter().skip(1) {
+            DatabaseWriter::close_stream(conn, *stale_id)?;
+        }
+        Ok(open_stream_ids.first().copied())
+    }
+
     /// ストリーム統計情報をデータベースに保存する
     /// 戻り値: データベース上のstream_id
     fn save_stream_data(
@@ -436,10 +943,11 @@ impl ChannelPoller {
         };
 
         // StreamStatsを作成して保存
+        let collected_at = Local::now().to_rfc3339();
         let stats = StreamStats {
             id: None,
             stream_id: stream_db_id,
-            collected_at: Local::now().to_rfc3339(),
+            collected_at: collected_at.clone(),
             viewer_count: stream_data.viewer_count,
             chat_rate_1min: None, // Calculated dynamically when needed
             category: stream_data.category.clone(),
@@ -453,79 +961,38 @@ impl ChannelPoller {
         // ストリーム統計を保存
         DatabaseWriter::insert_stream_stats(conn, &stats)?;
 
+        // タイトル・カテゴリ・タグ・言語の変更をstream_eventsに記録
+        StreamEventRepository::record_changes(
+            conn,
+            channel_id,
+            stream_db_id,
+            &StreamMetadata::from_stream_data(stream_data),
+            &collected_at,
+        )?;
+
         // ゲームカテゴリをgame_categoriesテーブルに自動保存（ID->名前解決用）
+        // カテゴリ名が取得できずIDのままの場合（YouTubeの videoCategories.list 失敗時など）は保存しない
         if let (Some(game_id), Some(game_name)) = (&stream_data.game_id, &stream_data.category) {
             use crate::database::repositories::GameCategoryRepository;
             // 現時点では配信データからゲームの box_art_url を取得できないため、NULL のまま保存
             // （AutoDiscovery/Twitch Games API 経由で後から上書きされる）
-            if let Err(e) = GameCategoryRepository::upsert_category(conn, game_id, game_name, None)
-            {
-                eprintln!(
-                    "[Poller] Warning: Failed to upsert game_category {}: {}",
-                    game_id, e
-                );
-                // エラーでもストリームデータ保存は成功させる（非致命的）
+            if game_name != game_id {
+                if let Err(e) = GameCategoryRepository::upsert_category(
+                    conn,
+                    &channel.platform,
+                    game_id,
+                    game_name,
+                    None,
+                ) {
+                    eprintln!(
+                        "[Poller] Warning: Failed to upsert game_category {}: {}",
+                        game_id, e
+                    );
+                    // エラーでもストリームデータ保存は成功させる（非致命的）
+                }
             }
         }
 
         Ok(stream_db_id)
     }
-
-    /// チャット収集を開始する（ストリーム開始時に呼び出し）
-    /// TODO: チャット機能実装中
-    #[allow(dead_code)]
-    pub async fn start_chat_collection(
-        &self,
-        _channel: &Channel,
-        _stream_id: i64,
-        _video_id: Option<&str>,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        // チャット機能は現在無効化中
-        // match channel.platform.as_str() {
-        //     "twitch" => {
-        //         if let Some(twitch_collector) = self.collectors.get("twitch") {
-        //             if let Ok(twitch_collector) = twitch_collector.as_ref().downcast_ref::<crate::collectors::twitch::TwitchCollector>() {
-        //                 twitch_collector.start_chat_collection(stream_id, &channel.channel_name, "oauth_token_here").await?;
-        //             }
-        //         }
-        //     }
-        //     "youtube" => {
-        //         if let Some(youtube_collector) = self.collectors.get("youtube") {
-        //             if let Ok(youtube_collector) = youtube_collector.as_ref().downcast_ref::<crate::collectors::youtube::YouTubeCollector>() {
-        //                 if let Some(video_id) = video_id {
-        //                     youtube_collector.start_chat_collection(stream_id, video_id, 30).await?;
-        //                 }
-        //             }
-        //         }
-        //     }
-        //     _ => {}
-        // }
-        Ok(())
-    }
-
-    /// チャット収集を停止する（ストリーム終了時に呼び出し）
-    /// TODO: チャット機能実装中
-    #[allow(dead_code)]
-    pub async fn stop_chat_collection(&self, _channel: &Channel, _video_id: Option<&str>) {
-        // チャット機能は現在無効化中
-        // match channel.platform.as_str() {
-        //     "twitch" => {
-        //         if let Some(twitch_collector) = self.collectors.get("twitch") {
-        //             if let Ok(twitch_collector) = twitch_collector.as_ref().downcast_ref::<crate::collectors::twitch::TwitchCollector>() {
-        //                 twitch_collector.stop_chat_collection(&channel.channel_name).await;
-        //             }
-        //         }
-        //     }
-        //     "youtube" => {
-        //         if let Some(youtube_collector) = self.collectors.get("youtube") {
-        //             if let Ok(youtube_collector) = youtube_collector.as_ref().downcast_ref::<crate::collectors::youtube::YouTubeCollector>() {
-        //                 if let Some(video_id) = video_id {
-        //                     youtube_collector.stop_chat_collection(video_id).await;
-        //                 }
-        //             }
-        //         }
-        //     }
-        //     _ => {}
-        // }
-    }
 }
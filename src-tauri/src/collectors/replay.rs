@@ -0,0 +1,532 @@
+/// 記録・再生コレクター
+///
+/// ライブのStreamDataとチャットをJSONL形式のキャプチャファイルに記録（tee）し、
+/// 後から同じ内容を実時間または加速した速度で再生します。
+/// 認証情報なしでポーラー・チャット取り込み・分析処理を動かせるため、
+/// 分析不具合の再現やダッシュボードのデモに使用します。
+///
+/// キャプチャファイルは1行1レコードで、例えば次の形式です。
+/// `{"offset_ms":0,"recorded_at":"...","platform":"twitch","channel":"shroud","type":"stream","stream":{...}}`
+/// `{"offset_ms":1200,"recorded_at":"...","platform":"twitch","channel":"shroud","type":"chat","message":{...}}`
+use crate::collectors::collector_trait::{Collector, CollectorError};
+use crate::database::models::{Channel, ChatMessage, StreamData};
+use crate::database::repositories::StreamRepository;
+use crate::database::writer::DatabaseWriter;
+use crate::database::DatabaseManager;
+use crate::logger::AppLogger;
+use async_trait::async_trait;
+use chrono::{DateTime, Local};
+use duckdb::OptionalExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Instant;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Duration};
+
+/// キャプチャファイルの1レコード
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayRecord {
+    /// 記録開始からの経過時間（ミリ秒）
+    pub offset_ms: u64,
+    /// 記録時刻（RFC3339）
+    pub recorded_at: String,
+    pub platform: String,
+    /// プラットフォーム上のチャンネルID（channels.channel_id）
+    pub channel: String,
+    #[serde(flatten)]
+    pub event: ReplayEvent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReplayEvent {
+    /// ポーリング結果（None = オフライン）
+    Stream { stream: Option<StreamData> },
+    /// チャットメッセージ
+    Chat { message: Box<ChatMessage> },
+}
+
+/// 読み込んだキャプチャ（offset_ms順）
+#[derive(Debug, Clone, Default)]
+pub struct ReplayCapture {
+    records: Vec<ReplayRecord>,
+}
+
+impl ReplayCapture {
+    /// JSONLファイルを読み込み（空行は無視）
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut records = Vec::new();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: ReplayRecord = serde_json::from_str(&line)
+                .map_err(|e| format!("Invalid replay record at line {}: {}", index + 1, e))?;
+            records.push(record);
+        }
+
+        Ok(Self::from_records(records))
+    }
+
+    pub fn from_records(mut records: Vec<ReplayRecord>) -> Self {
+        records.sort_by_key(|record| record.offset_ms);
+        Self { records }
+    }
+
+    /// 記録開始時刻（最初のレコードの記録時刻）
+    fn started_at(&self) -> Option<DateTime<Local>> {
+        let first = self.records.first()?;
+        DateTime::parse_from_rfc3339(&first.recorded_at)
+            .ok()
+            .map(|dt| {
+                dt.with_timezone(&Local) - chrono::Duration::milliseconds(first.offset_ms as i64)
+            })
+    }
+
+    /// 指定位置の時点で最後に記録されたポーリング結果
+    ///
+    /// 戻り値: None = まだ記録がない、Some(None) = オフライン
+    pub fn stream_at(
+        &self,
+        platform: &str,
+        channel: &str,
+        position_ms: u64,
+    ) -> Option<Option<&StreamData>> {
+        self.records
+            .iter()
+            .take_while(|record| record.offset_ms <= position_ms)
+            .filter(|record| record.platform == platform && record.channel == channel)
+            .filter_map(|record| match &record.event {
+                ReplayEvent::Stream { stream } => Some(stream.as_ref()),
+                ReplayEvent::Chat { .. } => None,
+            })
+            .last()
+    }
+
+    fn chat_records(&self) -> impl Iterator<Item = (&ReplayRecord, &ChatMessage)> {
+        self.records
+            .iter()
+            .filter_map(|record| match &record.event {
+                ReplayEvent::Chat { message } => Some((record, message.as_ref())),
+                ReplayEvent::Stream { .. } => None,
+            })
+    }
+}
+
+/// 再生位置の管理
+///
+/// 記録時のタイムスタンプは再生開始時刻を起点に付け替え、再生速度に合わせて圧縮します。
+#[derive(Debug, Clone)]
+pub struct ReplayClock {
+    started: Instant,
+    started_wall: DateTime<Local>,
+    capture_started: Option<DateTime<Local>>,
+    speed: f64,
+}
+
+impl ReplayClock {
+    pub fn start(capture: &ReplayCapture, speed: f64) -> Self {
+        Self {
+            started: Instant::now(),
+            started_wall: Local::now(),
+            capture_started: capture.started_at(),
+            speed: if speed > 0.0 { speed } else { 1.0 },
+        }
+    }
+
+    /// 現在の再生位置（キャプチャ上の経過ミリ秒）
+    pub fn position_ms(&self) -> u64 {
+        (self.started.elapsed().as_millis() as f64 * self.speed) as u64
+    }
+
+    /// 指定位置に達するまでの待ち時間
+    fn wait_for(&self, offset_ms: u64) -> Duration {
+        let target = Duration::from_millis((offset_ms as f64 / self.speed) as u64);
+        target.saturating_sub(self.started.elapsed())
+    }
+
+    /// 記録時のタイムスタンプを再生時刻に変換（解釈できない場合はそのまま）
+    pub fn map_timestamp(&self, timestamp: &str) -> String {
+        let (Some(capture_started), Ok(original)) = (
+            self.capture_started,
+            DateTime::parse_from_rfc3339(timestamp),
+        ) else {
+            return timestamp.to_string();
+        };
+
+        let elapsed_ms = (original.with_timezone(&Local) - capture_started).num_milliseconds();
+        let scaled_ms = (elapsed_ms as f64 / self.speed) as i64;
+        (self.started_wall + chrono::Duration::milliseconds(scaled_ms)).to_rfc3339()
+    }
+}
+
+/// キャプチャファイルからポーリング結果を返すコレクター
+pub struct ReplayCollector {
+    capture: Arc<ReplayCapture>,
+    clock: ReplayClock,
+}
+
+impl ReplayCollector {
+    pub fn new(capture: Arc<ReplayCapture>, clock: ReplayClock) -> Self {
+        Self { capture, clock }
+    }
+}
+
+#[async_trait]
+impl Collector for ReplayCollector {
+    async fn poll_channel(&self, channel: &Channel) -> Result<Option<StreamData>, CollectorError> {
+        let position_ms = self.clock.position_ms();
+        let stream = self
+            .capture
+            .stream_at(&channel.platform, &channel.channel_id, position_ms)
+            .flatten()
+            .cloned()
+            .map(|mut stream| {
+                stream.started_at = self.clock.map_timestamp(&stream.started_at);
+                stream
+            });
+        Ok(stream)
+    }
+
+    async fn start_collection(
+        &self,
+        _channel: &Channel,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(())
+    }
+}
+
+/// キャプチャファイルのチャットを記録時の間隔で chat_messages に書き込む
+pub struct ReplayChatSource;
+
+impl ReplayChatSource {
+    pub fn spawn(
+        capture: Arc<ReplayCapture>,
+        clock: ReplayClock,
+        db_manager: Arc<DatabaseManager>,
+        logger: Arc<AppLogger>,
+    ) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            // (platform, channel) -> channels.id
+            let mut channel_ids: HashMap<(String, String), i64> = HashMap::new();
+            // channels.id -> 配信中の streams.id（待機のたびに取り直す）
+            let mut open_stream_ids: HashMap<i64, Option<i64>> = HashMap::new();
+            let mut batch: Vec<ChatMessage> = Vec::new();
+            let mut replayed = 0usize;
+
+            for (record, message) in capture.chat_records() {
+                let wait = clock.wait_for(record.offset_ms);
+                if !wait.is_zero() {
+                    replayed += batch.len();
+                    Self::flush(&db_manager, &mut batch, &logger).await;
+                    sleep(wait).await;
+                    // 待機中にポーラーが配信を開始・終了している可能性がある
+                    open_stream_ids.clear();
+                }
+
+                let key = (record.platform.clone(), record.channel.clone());
+                let channel_id = match channel_ids.get(&key) {
+                    Some(id) => Some(*id),
+                    None => {
+                        let id = Self::resolve_channel_id(&db_manager, &key.0, &key.1).await;
+                        if let Some(id) = id {
+                            channel_ids.insert(key, id);
+                        }
+                        id
+                    }
+                };
+                // 監視対象に登録されていないチャンネルのチャットは再生しない
+                let Some(channel_id) = channel_id else {
+                    continue;
+                };
+
+                let stream_id = match open_stream_ids.get(&channel_id) {
+                    Some(id) => *id,
+                    None => {
+                        let id = db_manager
+                            .with_connection(|conn| {
+                                StreamRepository::get_open_stream_ids(conn, channel_id)
+                            })
+                            .await
+                            .ok()
+                            .and_then(|ids| ids.first().copied());
+                        open_stream_ids.insert(channel_id, id);
+                        id
+                    }
+                };
+
+                batch.push(ChatMessage {
+                    id: None,
+                    channel_id: Some(channel_id),
+                    stream_id,
+                    timestamp: clock.map_timestamp(&message.timestamp),
+                    // 同じDBへの再生が重複として除外されないよう元のIDは引き継がない
+                    platform_message_id: None,
+                    ..message.clone()
+                });
+            }
+
+            replayed += batch.len();
+            Self::flush(&db_manager, &mut batch, &logger).await;
+            logger.info(&format!(
+                "[Replay] Chat replay finished ({} messages)",
+                replayed
+            ));
+        })
+    }
+
+    async fn resolve_channel_id(
+        db_manager: &DatabaseManager,
+        platform: &str,
+        channel: &str,
+    ) -> Option<i64> {
+        db_manager
+            .with_connection(|conn| {
+                conn.query_row(
+                    "SELECT id FROM channels WHERE platform = ? AND channel_id = ?",
+                    [platform, channel],
+                    |row| row.get::<_, i64>(0),
+                )
+                .optional()
+            })
+            .await
+            .ok()
+            .flatten()
+    }
+
+    async fn flush(db_manager: &DatabaseManager, batch: &mut Vec<ChatMessage>, logger: &AppLogger) {
+        if batch.is_empty() {
+            return;
+        }
+
+        let result = db_manager
+            .with_connection(|conn| DatabaseWriter::insert_chat_messages_batch(conn, batch))
+            .await;
+        if let Err(e) = result {
+            logger.error(&format!("[Replay] Failed to save chat messages: {}", e));
+        }
+        batch.clear();
+    }
+}
+
+/// ライブのStreamDataとチャットをキャプチャファイルに記録するレコーダー
+pub struct ReplayRecorder {
+    writer: Mutex<BufWriter<File>>,
+    started: Instant,
+    /// channels.id -> (platform, channel_id)（チャットのチャンネル特定用）
+    channel_keys: RwLock<HashMap<i64, (String, String)>>,
+}
+
+static ACTIVE_RECORDER: RwLock<Option<Arc<ReplayRecorder>>> = RwLock::new(None);
+
+/// 記録中のレコーダーを設定（None で記録を停止）
+pub fn set_active_recorder(recorder: Option<Arc<ReplayRecorder>>) {
+    if let Ok(mut active) = ACTIVE_RECORDER.write() {
+        *active = recorder;
+    }
+}
+
+/// 記録中のレコーダーを取得
+pub fn active_recorder() -> Option<Arc<ReplayRecorder>> {
+    ACTIVE_RECORDER
+        .read()
+        .ok()
+        .and_then(|active| active.clone())
+}
+
+impl ReplayRecorder {
+    /// キャプチャファイルを作成（既存ファイルには追記しない）
+    pub fn create(path: &Path) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(Self {
+            writer: Mutex::new(BufWriter::new(File::create(path)?)),
+            started: Instant::now(),
+            channel_keys: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// ポーリング結果を記録
+    pub fn record_stream(&self, channel: &Channel, stream: Option<&StreamData>) {
+        if let (Some(id), Ok(mut keys)) = (channel.id, self.channel_keys.write()) {
+            keys.insert(id, (channel.platform.clone(), channel.channel_id.clone()));
+        }
+
+        self.write_records(std::iter::once(self.record(
+            &channel.platform,
+            &channel.channel_id,
+            ReplayEvent::Stream {
+                stream: stream.cloned(),
+            },
+        )));
+    }
+
+    /// 保存したチャットメッセージを記録
+    pub fn record_chat_batch(&self, messages: &[&ChatMessage]) {
+        if messages.is_empty() {
+            return;
+        }
+
+        let keys = match self.channel_keys.read() {
+            Ok(keys) => keys.clone(),
+            Err(_) => return,
+        };
+
+        self.write_records(messages.iter().map(|&message| {
+            // ポーリング未記録のチャンネルは channels.id をそのまま使用
+            let (platform, channel) = message
+                .channel_id
+                .and_then(|id| keys.get(&id).cloned())
+                .unwrap_or_else(|| {
+                    (
+                        message.platform.clone(),
+                        message.channel_id.unwrap_or_default().to_string(),
+                    )
+                });
+            self.record(
+                &platform,
+                &channel,
+                ReplayEvent::Chat {
+                    message: Box::new(message.clone()),
+                },
+            )
+        }));
+    }
+
+    fn record(&self, platform: &str, channel: &str, event: ReplayEvent) -> ReplayRecord {
+        ReplayRecord {
+            offset_ms: self.started.elapsed().as_millis() as u64,
+            recorded_at: Local::now().to_rfc3339(),
+            platform: platform.to_string(),
+            channel: channel.to_string(),
+            event,
+        }
+    }
+
+    fn write_records(&self, records: impl Iterator<Item = ReplayRecord>) {
+        let Ok(mut writer) = self.writer.lock() else {
+            return;
+        };
+
+        for record in records {
+            match serde_json::to_string(&record) {
+                Ok(line) => {
+                    if let Err(e) = writeln!(writer, "{}", line) {
+                        eprintln!("[Replay] Failed to write capture record: {}", e);
+                        return;
+                    }
+                }
+                Err(e) => eprintln!("[Replay] Failed to serialize capture record: {}", e),
+            }
+        }
+
+        // 異常終了時に記録が失われないよう都度フラッシュ
+        if let Err(e) = writer.flush() {
+            eprintln!("[Replay] Failed to flush capture file: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel() -> Channel {
+        Channel {
+            id: Some(1),
+            platform: "twitch".to_string(),
+            channel_id: "streamer".to_string(),
+            channel_name: "Streamer".to_string(),
+            display_name: "Streamer".to_string(),
+            profile_image_url: String::new(),
+            enabled: true,
+            poll_interval: 60,
+            follower_count: 0,
+            broadcaster_type: String::new(),
+            view_count: 0,
+            is_auto_discovered: false,
+            discovered_at: String::new(),
+            twitch_user_id: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn stream_data(viewers: i32) -> StreamData {
+        StreamData {
+            stream_id: "s1".to_string(),
+            title: Some("title".to_string()),
+            category: None,
+            game_id: None,
+            thumbnail_url: None,
+            started_at: "2025-01-06T12:00:00+00:00".to_string(),
+            viewer_count: Some(viewers),
+            follower_count: None,
+            language: None,
+            tags: None,
+        }
+    }
+
+    #[test]
+    fn test_record_and_load_capture() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.jsonl");
+
+        let recorder = ReplayRecorder::create(&path).unwrap();
+        recorder.record_stream(&channel(), Some(&stream_data(10)));
+        recorder.record_chat_batch(&[&ChatMessage {
+            channel_id: Some(1),
+            stream_id: Some(1),
+            timestamp: "2025-01-06T12:00:01+00:00".to_string(),
+            platform: "twitch".to_string(),
+            user_id: Some("u1".to_string()),
+            user_name: "viewer".to_string(),
+            message: "hello".to_string(),
+            message_type: "normal".to_string(),
+            ..Default::default()
+        }]);
+        recorder.record_stream(&channel(), None);
+
+        let capture = ReplayCapture::load(&path).unwrap();
+        assert_eq!(capture.records.len(), 3);
+
+        let (record, message) = capture.chat_records().next().unwrap();
+        // channels.id からプラットフォーム上のチャンネルIDに変換されている
+        assert_eq!(record.channel, "streamer");
+        assert_eq!(message.message, "hello");
+    }
+
+    #[test]
+    fn test_stream_at_follows_timeline() {
+        let record = |offset_ms, stream| ReplayRecord {
+            offset_ms,
+            recorded_at: "2025-01-06T12:00:00+00:00".to_string(),
+            platform: "twitch".to_string(),
+            channel: "streamer".to_string(),
+            event: ReplayEvent::Stream { stream },
+        };
+        let capture = ReplayCapture::from_records(vec![
+            record(5_000, None),
+            record(0, Some(stream_data(10))),
+            record(1_000, Some(stream_data(20))),
+        ]);
+
+        assert!(capture.stream_at("twitch", "other", 10_000).is_none());
+        let at_500 = capture.stream_at("twitch", "streamer", 500).unwrap();
+        assert_eq!(at_500.unwrap().viewer_count, Some(10));
+        let at_2000 = capture.stream_at("twitch", "streamer", 2_000).unwrap();
+        assert_eq!(at_2000.unwrap().viewer_count, Some(20));
+        assert!(matches!(
+            capture.stream_at("twitch", "streamer", 5_000),
+            Some(None)
+        ));
+    }
+}
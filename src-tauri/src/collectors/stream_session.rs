@@ -0,0 +1,185 @@
+/// 配信セッションの状態管理
+///
+/// ポーリング結果（ライブ / オフライン）からセッションの開始・継続・終了を判定します。
+/// 一時的な配信切断に備え、オフラインはオフライン確定猶予時間を経過してから確定させます。
+use std::time::{Duration, Instant};
+
+/// セッション状態
+#[derive(Debug, Clone, PartialEq)]
+enum SessionState {
+    Offline,
+    Live {
+        stream_db_id: i64,
+        /// 最初にオフラインを観測した時刻（None = ライブ継続中）
+        offline_since: Option<Instant>,
+    },
+}
+
+/// ライブ観測時の遷移
+#[derive(Debug, Clone, PartialEq)]
+pub enum LiveTransition {
+    /// オフラインから配信開始
+    Started,
+    /// 同一配信の継続
+    Continued,
+    /// 猶予時間内に同一配信が再開された
+    Resumed,
+    /// 別の配信IDに切り替わった（再接続で新しいstream_idが発行された場合など）
+    Replaced { previous_stream_db_id: i64 },
+}
+
+/// オフライン観測時の遷移
+#[derive(Debug, Clone, PartialEq)]
+pub enum OfflineTransition {
+    /// 既にオフライン
+    None,
+    /// オフライン確定待ち
+    Pending,
+    /// 猶予時間を経過し配信終了が確定した
+    Ended { stream_db_id: i64 },
+}
+
+/// 1チャンネル分の配信セッション状態機械
+#[derive(Debug, Clone)]
+pub struct StreamSession {
+    state: SessionState,
+    offline_confirmation: Duration,
+}
+
+impl StreamSession {
+    pub fn new(offline_confirmation: Duration) -> Self {
+        Self {
+            state: SessionState::Offline,
+            offline_confirmation,
+        }
+    }
+
+    /// 前回起動時から継続中の配信（ended_at IS NULL）を引き継ぐ
+    pub fn restore(&mut self, stream_db_id: i64) {
+        self.state = SessionState::Live {
+            stream_db_id,
+            offline_since: None,
+        };
+    }
+
+    /// オフライン確定猶予時間を更新（設定変更時）
+    pub fn set_offline_confirmation(&mut self, offline_confirmation: Duration) {
+        self.offline_confirmation = offline_confirmation;
+    }
+
+    /// 現在のセッションの配信ID（DB）
+    pub fn current_stream_db_id(&self) -> Option<i64> {
+        match self.state {
+            SessionState::Live { stream_db_id, .. } => Some(stream_db_id),
+            SessionState::Offline => None,
+        }
+    }
+
+    /// ライブを観測した
+    pub fn on_live(&mut self, stream_db_id: i64) -> LiveTransition {
+        let transition = match self.state {
+            SessionState::Offline => LiveTransition::Started,
+            SessionState::Live {
+                stream_db_id: current,
+                offline_since,
+            } => {
+                if current != stream_db_id {
+                    LiveTransition::Replaced {
+                        previous_stream_db_id: current,
+                    }
+                } else if offline_since.is_some() {
+                    LiveTransition::Resumed
+                } else {
+                    LiveTransition::Continued
+                }
+            }
+        };
+
+        self.state = SessionState::Live {
+            stream_db_id,
+            offline_since: None,
+        };
+        transition
+    }
+
+    /// オフラインを観測した
+    pub fn on_offline(&mut self, now: Instant) -> OfflineTransition {
+        match self.state {
+            SessionState::Offline => OfflineTransition::None,
+            SessionState::Live {
+                stream_db_id,
+                offline_since: None,
+            } => {
+                if self.offline_confirmation.is_zero() {
+                    self.state = SessionState::Offline;
+                    return OfflineTransition::Ended { stream_db_id };
+                }
+                self.state = SessionState::Live {
+                    stream_db_id,
+                    offline_since: Some(now),
+                };
+                OfflineTransition::Pending
+            }
+            SessionState::Live {
+                stream_db_id,
+                offline_since: Some(since),
+            } => {
+                if now.saturating_duration_since(since) >= self.offline_confirmation {
+                    self.state = SessionState::Offline;
+                    OfflineTransition::Ended { stream_db_id }
+                } else {
+                    OfflineTransition::Pending
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline_is_confirmed_after_window() {
+        let mut session = StreamSession::new(Duration::from_secs(180));
+        let t0 = Instant::now();
+
+        assert_eq!(session.on_live(1), LiveTransition::Started);
+        assert_eq!(session.on_live(1), LiveTransition::Continued);
+        assert_eq!(session.on_offline(t0), OfflineTransition::Pending);
+        assert_eq!(
+            session.on_offline(t0 + Duration::from_secs(60)),
+            OfflineTransition::Pending
+        );
+        assert_eq!(
+            session.on_offline(t0 + Duration::from_secs(180)),
+            OfflineTransition::Ended { stream_db_id: 1 }
+        );
+        assert_eq!(
+            session.on_offline(t0 + Duration::from_secs(240)),
+            OfflineTransition::None
+        );
+        assert_eq!(session.current_stream_db_id(), None);
+    }
+
+    #[test]
+    fn test_reconnect_within_window() {
+        let mut session = StreamSession::new(Duration::from_secs(180));
+        let t0 = Instant::now();
+
+        session.on_live(1);
+        assert_eq!(session.on_offline(t0), OfflineTransition::Pending);
+        // 同一stream_idで再開
+        assert_eq!(session.on_live(1), LiveTransition::Resumed);
+
+        assert_eq!(session.on_offline(t0), OfflineTransition::Pending);
+        // 新しいstream_idで再開
+        assert_eq!(
+            session.on_live(2),
+            LiveTransition::Replaced {
+                previous_stream_db_id: 1
+            }
+        );
+        assert_eq!(session.current_stream_db_id(), Some(2));
+    }
+}
@@ -1,11 +1,17 @@
 use crate::api::twitch_api::TwitchApiClient;
-use crate::collectors::collector_trait::Collector;
-use crate::database::models::{Channel, StreamData};
+use crate::collectors::collector_trait::{Collector, CollectorError};
+use crate::constants::{database as db_constants, twitch};
+use crate::database::chat_spool::ChatSpool;
+use crate::database::models::{Channel, ScheduledStream, StreamData};
 use crate::database::DatabaseManager;
 use crate::logger::AppLogger;
-use crate::websocket::twitch_irc::TwitchIrcManager;
+use crate::websocket::twitch_irc::{ChatIngestMetrics, IrcConnectionStatus, TwitchIrcManager};
 use async_trait::async_trait;
+use chrono::DateTime;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+use twitch_api::helix::{schedule::ScheduledBroadcasts, streams::Stream};
 
 pub struct TwitchCollector {
     api_client: Arc<TwitchApiClient>,
@@ -19,9 +25,14 @@ impl TwitchCollector {
         client_secret: Option<String>,
         app_handle: tauri::AppHandle,
         db_manager: Arc<DatabaseManager>,
+        chat_spool: Option<Arc<ChatSpool>>,
         logger: Arc<AppLogger>,
     ) -> Self {
-        let irc_manager = Arc::new(TwitchIrcManager::new(db_manager, Arc::clone(&logger)));
+        let irc_manager = Arc::new(TwitchIrcManager::new(
+            db_manager,
+            chat_spool,
+            Arc::clone(&logger),
+        ));
 
         Self {
             api_client: Arc::new(
@@ -40,31 +51,265 @@ impl TwitchCollector {
     pub async fn initialize_irc(&self) {
         self.irc_manager.start_db_handler().await;
     }
+
+    /// APIエラーを種別付きのエラーに変換
+    ///
+    /// レート制限の場合は、トラッカーの最古エントリが期限切れになるまでを再試行待ち時間とします。
+    async fn to_collector_error(
+        &self,
+        error: Box<dyn std::error::Error + Send + Sync>,
+    ) -> CollectorError {
+        match CollectorError::from_error(error.as_ref()) {
+            CollectorError::RateLimited {
+                retry_after: None,
+                message,
+            } => {
+                let retry_after = self
+                    .api_client
+                    .get_rate_limiter()
+                    .lock()
+                    .await
+                    .get_status()
+                    .oldest_entry_expires_in_seconds
+                    .map(|secs| Duration::from_secs(secs.max(1) as u64));
+                CollectorError::RateLimited {
+                    retry_after,
+                    message,
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// Twitch APIのストリーム情報をStreamDataに変換
+    fn to_stream_data(stream: &Stream, follower_count: Option<i32>) -> StreamData {
+        StreamData {
+            stream_id: stream.id.to_string(),
+            title: Some(stream.title.to_string()),
+            category: Some(stream.game_name.to_string()),
+            game_id: Some(stream.game_id.to_string()),
+            thumbnail_url: Some(stream.thumbnail_url.to_string()),
+            started_at: stream.started_at.as_str().to_string(),
+            viewer_count: Some(stream.viewer_count as i32),
+            follower_count,
+            language: Some(stream.language.clone()),
+            tags: Some(stream.tags.clone()),
+        }
+    }
+
+    /// Twitch APIの配信スケジュールを配信予定に変換
+    ///
+    /// canceled_until が設定されたセグメントと休暇期間中のセグメントは取り消し扱いにします。
+    fn to_scheduled_streams(
+        channel_id: i64,
+        schedule: &ScheduledBroadcasts,
+    ) -> Vec<ScheduledStream> {
+        let parse = |timestamp: &str| DateTime::parse_from_rfc3339(timestamp).ok();
+        let vacation = schedule.vacation.as_ref().and_then(|vacation| {
+            Some((
+                parse(vacation.start_time.as_str())?,
+                parse(vacation.end_time.as_str())?,
+            ))
+        });
+
+        schedule
+            .segments
+            .iter()
+            .map(|segment| {
+                let on_vacation = vacation.is_some_and(|(start, end)| {
+                    parse(segment.start_time.as_str())
+                        .is_some_and(|segment_start| start <= segment_start && segment_start <= end)
+                });
+                let status = if segment.canceled_until.is_some() || on_vacation {
+                    db_constants::SCHEDULE_STATUS_CANCELED
+                } else {
+                    db_constants::SCHEDULE_STATUS_SCHEDULED
+                };
+                ScheduledStream {
+                    id: None,
+                    channel_id,
+                    platform: db_constants::PLATFORM_TWITCH.to_string(),
+                    schedule_id: segment.id.to_string(),
+                    title: Some(segment.title.clone()).filter(|title| !title.is_empty()),
+                    category: segment
+                        .category
+                        .as_ref()
+                        .map(|category| category.name.clone()),
+                    scheduled_start: segment.start_time.as_str().to_string(),
+                    scheduled_end: Some(segment.end_time.as_str().to_string()),
+                    status: status.to_string(),
+                    stream_id: None,
+                    actual_start: None,
+                }
+            })
+            .collect()
+    }
+
+    /// チャンネルの配信スケジュールを取得（スケジュール未設定の場合は空）
+    pub async fn get_scheduled_streams(
+        &self,
+        channel: &Channel,
+    ) -> Result<Vec<ScheduledStream>, Box<dyn std::error::Error + Send + Sync>> {
+        let (Some(channel_id), Some(twitch_user_id)) = (channel.id, channel.twitch_user_id) else {
+            return Err(format!(
+                "Twitch user ID is not resolved for channel {}",
+                channel.channel_id
+            )
+            .into());
+        };
+
+        let schedule = self
+            .api_client
+            .get_channel_schedule(&twitch_user_id.to_string())
+            .await?;
+        Ok(schedule
+            .map(|schedule| Self::to_scheduled_streams(channel_id, &schedule))
+            .unwrap_or_default())
+    }
+
+    /// 複数チャンネルの配信状態をまとめて取得
+    ///
+    /// Get Streams を最大100件ずつのチャンクで呼び出し、結果をチャンネルごとに振り分けます。
+    /// フォロワー数はライブ中のチャンネルのみ取得します。
+    /// 戻り値: (channels.id, ポーリング結果) のベクター（エラーはチャンネル単位）
+    pub async fn poll_channels_batch(
+        &self,
+        channels: &[Channel],
+    ) -> Vec<(i64, Result<Option<StreamData>, CollectorError>)> {
+        let mut results = Vec::with_capacity(channels.len());
+
+        // twitch_user_idが未設定のチャンネルはloginからまとめて解決（後方互換性）
+        let missing_logins: Vec<&str> = channels
+            .iter()
+            .filter(|c| c.twitch_user_id.is_none())
+            .map(|c| c.channel_id.as_str())
+            .collect();
+        let mut resolved_ids: HashMap<String, String> = HashMap::new();
+        // 解決に失敗したログイン → エラー（一時的な失敗でチャンネルが見つからない扱いにしない）
+        let mut unresolved: HashMap<String, String> = HashMap::new();
+        for chunk in missing_logins.chunks(twitch::MAX_STREAMS_PER_REQUEST) {
+            match self.api_client.get_users_by_logins(chunk).await {
+                Ok(users) => {
+                    for user in users {
+                        resolved_ids
+                            .insert(user.login.to_string().to_lowercase(), user.id.to_string());
+                    }
+                }
+                Err(e) => {
+                    eprintln!(
+                        "[TwitchCollector] Failed to resolve user ids by login: {}",
+                        e
+                    );
+                    for login in chunk {
+                        unresolved.insert(login.to_lowercase(), e.to_string());
+                    }
+                }
+            }
+        }
+
+        let mut targets: Vec<(i64, String)> = Vec::with_capacity(channels.len());
+        for channel in channels {
+            let Some(channel_id) = channel.id else {
+                continue;
+            };
+            let user_id = match channel.twitch_user_id {
+                Some(id) => Some(id.to_string()),
+                None => resolved_ids
+                    .get(&channel.channel_id.to_lowercase())
+                    .cloned(),
+            };
+            match user_id {
+                Some(user_id) => targets.push((channel_id, user_id)),
+                None => {
+                    let error = match unresolved.get(&channel.channel_id.to_lowercase()) {
+                        Some(e) => CollectorError::Transient(format!(
+                            "Failed to resolve Twitch user id for {}: {}",
+                            channel.channel_id, e
+                        )),
+                        None => CollectorError::ChannelNotFound(format!(
+                            "Twitch user not found: {}",
+                            channel.channel_id
+                        )),
+                    };
+                    results.push((channel_id, Err(error)));
+                }
+            }
+        }
+
+        for chunk in targets.chunks(twitch::MAX_STREAMS_PER_REQUEST) {
+            let user_ids: Vec<&str> = chunk.iter().map(|(_, id)| id.as_str()).collect();
+
+            let streams = match self.api_client.get_streams_by_user_ids(&user_ids).await {
+                Ok(streams) => streams,
+                Err(e) => {
+                    // チャンク全体のエラーはどのチャンネルが原因か分からないため、
+                    // 1回でサーキットブレーカーを開く種別は一時的なエラーとして各チャンネルに返す
+                    let error = match self.to_collector_error(e).await {
+                        error if error.trips_breaker() && chunk.len() > 1 => {
+                            CollectorError::Transient(error.to_string())
+                        }
+                        error => error,
+                    };
+                    results.extend(
+                        chunk
+                            .iter()
+                            .map(|(channel_id, _)| (*channel_id, Err(error.clone()))),
+                    );
+                    continue;
+                }
+            };
+
+            let streams_by_user: HashMap<String, &Stream> = streams
+                .iter()
+                .map(|stream| (stream.user_id.to_string(), stream))
+                .collect();
+
+            // フォロワー数を取得（ライブ中のチャンネルのみ、エラー時は None）
+            let live_user_ids: Vec<&str> = user_ids
+                .iter()
+                .copied()
+                .filter(|id| streams_by_user.contains_key(*id))
+                .collect();
+            let follower_counts: HashMap<String, i32> =
+                match self.api_client.get_followers_batch(&live_user_ids).await {
+                    Ok(counts) => counts.into_iter().collect(),
+                    Err(e) => {
+                        eprintln!("[TwitchCollector] Failed to get follower counts: {}", e);
+                        HashMap::new()
+                    }
+                };
+
+            for (channel_id, user_id) in chunk {
+                let stream_data = streams_by_user.get(user_id).map(|stream| {
+                    Self::to_stream_data(stream, follower_counts.get(user_id).copied())
+                });
+                results.push((*channel_id, Ok(stream_data)));
+            }
+        }
+
+        results
+    }
 }
 
 #[async_trait]
 impl Collector for TwitchCollector {
-    async fn poll_channel(
-        &self,
-        channel: &Channel,
-    ) -> Result<Option<StreamData>, Box<dyn std::error::Error + Send + Sync>> {
+    async fn poll_channel(&self, channel: &Channel) -> Result<Option<StreamData>, CollectorError> {
         // twitch_user_idがあればそれを優先使用、なければloginで取得（後方互換性）
         let user_id_string = if let Some(twitch_user_id) = channel.twitch_user_id {
             twitch_user_id.to_string()
         } else {
             // loginからuser_idを取得
-            let user = self
-                .api_client
-                .get_user_by_login(&channel.channel_id)
-                .await?;
-            user.id.to_string()
+            match self.api_client.get_user_by_login(&channel.channel_id).await {
+                Ok(user) => user.id.to_string(),
+                Err(e) => return Err(self.to_collector_error(e).await),
+            }
         };
 
         // 配信情報を取得
-        let stream_opt = self
-            .api_client
-            .get_stream_by_user_id(&user_id_string)
-            .await?;
+        let stream_opt = match self.api_client.get_stream_by_user_id(&user_id_string).await {
+            Ok(stream_opt) => stream_opt,
+            Err(e) => return Err(self.to_collector_error(e).await),
+        };
 
         if let Some(stream) = stream_opt {
             // フォロワー数を取得（エラー時は None）
@@ -84,16 +329,7 @@ impl Collector for TwitchCollector {
             };
 
             // Twitch APIから取得したストリーム情報を構造化して返す
-            Ok(Some(StreamData {
-                stream_id: stream.id.to_string(),
-                title: Some(stream.title.to_string()),
-                category: Some(stream.game_name.to_string()),
-                game_id: Some(stream.game_id.to_string()),
-                thumbnail_url: Some(stream.thumbnail_url.to_string()),
-                started_at: stream.started_at.as_str().to_string(),
-                viewer_count: Some(stream.viewer_count as i32),
-                follower_count,
-            }))
+            Ok(Some(Self::to_stream_data(&stream, follower_count)))
         } else {
             // 配信していない場合はNone
             Ok(None)
@@ -132,11 +368,14 @@ impl TwitchCollector {
         Ok(refreshed)
     }
 
-    /// チャット収集を開始（手動登録チャンネルに対して）
+    /// チャット収集を開始
+    ///
+    /// `auto_discovered` が true の場合、収集したチャットを自動発見由来として記録します。
     pub async fn start_chat_collection(
         &self,
         channel_id: i64,
         channel_name: &str,
+        auto_discovered: bool,
     ) -> Result<(), String> {
         let access_token = self
             .api_client
@@ -144,7 +383,7 @@ impl TwitchCollector {
             .await
             .map_err(|e| e.to_string())?;
         self.irc_manager
-            .start_channel_collection(channel_id, channel_name, &access_token)
+            .start_channel_collection(channel_id, channel_name, &access_token, auto_discovered)
             .await
             .map_err(|e| e.to_string())
     }
@@ -160,4 +399,19 @@ impl TwitchCollector {
             .update_channel_stream(channel_id, stream_id)
             .await;
     }
+
+    /// IRC接続状態を取得
+    pub async fn get_irc_statuses(&self) -> Vec<IrcConnectionStatus> {
+        self.irc_manager.get_connection_statuses().await
+    }
+
+    /// チャットスプールに残っている未コミットのメッセージ数を取得
+    pub fn get_chat_spool_depth(&self) -> u64 {
+        self.irc_manager.spool_depth()
+    }
+
+    /// チャット取り込みキューの状態を取得
+    pub fn get_chat_ingest_metrics(&self) -> ChatIngestMetrics {
+        self.irc_manager.ingest_metrics()
+    }
 }
@@ -0,0 +1,274 @@
+/// Twitchチャンネルのバッチポーリングスケジューラー
+///
+/// チャンネルごとにタスクを起動する代わりに、ポーリング時刻に達したTwitchチャンネルを
+/// まとめて Get Streams（最大100件/リクエスト）で問い合わせ、結果をチャンネルごとに振り分けます。
+use crate::collectors::backoff::ChannelBackoff;
+use crate::collectors::collector_trait::{Collector, CollectorError};
+use crate::collectors::poll_policy::PollPlanner;
+use crate::collectors::poller::PollContext;
+use crate::collectors::stream_session::StreamSession;
+use crate::collectors::twitch::TwitchCollector;
+use crate::constants::twitch;
+use crate::database::models::Channel;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::task::JoinHandle;
+use tokio::time::{interval, Duration, Instant, MissedTickBehavior};
+
+pub struct TwitchPollScheduler {
+    /// channels.id -> 次回ポーリング時刻
+    schedule: Arc<Mutex<HashMap<i64, Instant>>>,
+    /// 現在のコレクター（再認証で差し替えられるため、スケジューラーは毎サイクル参照する）
+    collector: Arc<Mutex<Option<Arc<TwitchCollector>>>>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl TwitchPollScheduler {
+    pub fn new() -> Self {
+        Self {
+            schedule: Arc::new(Mutex::new(HashMap::new())),
+            collector: Arc::new(Mutex::new(None)),
+            task: None,
+        }
+    }
+
+    /// 使用するコレクターを差し替える（次のサイクルから新しいコレクターでポーリングする）
+    pub fn set_collector(&self, collector: Option<Arc<TwitchCollector>>) {
+        if let Ok(mut current) = self.collector.lock() {
+            *current = collector;
+        }
+    }
+
+    /// スケジューラータスクが起動していなければ起動する
+    pub fn ensure_started(&mut self, ctx: PollContext, collector: Arc<TwitchCollector>) {
+        self.set_collector(Some(collector));
+        if self.task.as_ref().is_some_and(|task| !task.is_finished()) {
+            return;
+        }
+
+        println!("[TwitchScheduler] Starting batch polling scheduler");
+        let schedule = Arc::clone(&self.schedule);
+        let collector = Arc::clone(&self.collector);
+        self.task = Some(tokio::spawn(Self::run(ctx, collector, schedule)));
+    }
+
+    /// チャンネルをスケジュールに追加（次回ティックで即座にポーリング）
+    pub fn add_channel(&self, channel_id: i64) {
+        if let Ok(mut schedule) = self.schedule.lock() {
+            schedule.insert(channel_id, Instant::now());
+        }
+    }
+
+    /// チャンネルをスケジュールから削除
+    /// 戻り値: スケジュールに登録されていた場合 true
+    pub fn remove_channel(&self, channel_id: i64) -> bool {
+        self.schedule
+            .lock()
+            .map(|mut schedule| schedule.remove(&channel_id).is_some())
+            .unwrap_or(false)
+    }
+
+    /// 次回ポーリング時刻を更新（スケジュールから削除済みの場合は何もしない）
+    fn reschedule(schedule: &Mutex<HashMap<i64, Instant>>, channel_id: i64, next_poll_at: Instant) {
+        if let Ok(mut schedule) = schedule.lock() {
+            if let Some(at) = schedule.get_mut(&channel_id) {
+                *at = next_poll_at;
+            }
+        }
+    }
+
+    fn poll_interval(channel: &Channel) -> Duration {
+        Duration::from_secs(channel.poll_interval.max(1) as u64)
+    }
+
+    async fn run(
+        mut ctx: PollContext,
+        current_collector: Arc<Mutex<Option<Arc<TwitchCollector>>>>,
+        schedule: Arc<Mutex<HashMap<i64, Instant>>>,
+    ) {
+        let mut ticker = interval(Duration::from_secs(twitch::SCHEDULER_TICK_SECS));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        // channels.id -> 配信セッション（スケジューラータスク内でのみ保持）
+        let mut sessions: HashMap<i64, StreamSession> = HashMap::new();
+        // channels.id -> バックオフ状態
+        let mut backoffs: HashMap<i64, ChannelBackoff> = HashMap::new();
+        let mut planner = PollPlanner::new();
+        // channels.id -> 配信スケジュールの最終取得時刻
+        let mut schedule_synced_at: HashMap<i64, Instant> = HashMap::new();
+        let mut cycle_count: u64 = 0;
+        let mut active_collector: Option<Arc<TwitchCollector>> = None;
+
+        loop {
+            ticker.tick().await;
+
+            // コレクターが差し替えられていれば、新しいIRC接続でチャットを開始し直すためセッションを作り直す
+            let Some(latest) = current_collector.lock().ok().and_then(|c| c.clone()) else {
+                continue;
+            };
+            if !active_collector
+                .as_ref()
+                .is_some_and(|active| Arc::ptr_eq(active, &latest))
+            {
+                if active_collector.is_some() {
+                    println!(
+                        "[TwitchScheduler] Twitch collector was replaced, restarting sessions"
+                    );
+                    sessions.clear();
+                }
+                ctx.twitch_collector = Some(Arc::clone(&latest));
+                active_collector = Some(Arc::clone(&latest));
+            }
+            let collector = latest;
+
+            let now = Instant::now();
+            let due_ids: Vec<i64> = match schedule.lock() {
+                Ok(schedule) => {
+                    // スケジュールから外れたチャンネルのセッションを破棄
+                    sessions.retain(|channel_id, _| {
+                        let keep = schedule.contains_key(channel_id);
+                        if !keep {
+                            backoffs.remove(channel_id);
+                            schedule_synced_at.remove(channel_id);
+                            planner.forget(*channel_id);
+                        }
+                        keep
+                    });
+                    schedule
+                        .iter()
+                        .filter(|(_, next_poll_at)| **next_poll_at <= now)
+                        .map(|(channel_id, _)| *channel_id)
+                        .collect()
+                }
+                Err(_) => continue,
+            };
+
+            if due_ids.is_empty() {
+                continue;
+            }
+
+            // 10サイクルごとにトークン有効期限をチェック
+            cycle_count += 1;
+            if cycle_count % 10 == 0 {
+                ctx.refresh_twitch_token_if_needed().await;
+            }
+
+            // チャンネル情報を再取得（削除・無効化されたチャンネルはスケジュールから外す）
+            let mut due_channels: Vec<Channel> = Vec::with_capacity(due_ids.len());
+            for channel_id in due_ids {
+                ctx.record_poll_started(channel_id);
+                match ctx.reload_channel(channel_id).await {
+                    Ok(Some(channel)) => due_channels.push(channel),
+                    Ok(None) => {
+                        if let Ok(mut schedule) = schedule.lock() {
+                            schedule.remove(&channel_id);
+                        }
+                        sessions.remove(&channel_id);
+                        backoffs.remove(&channel_id);
+                        planner.forget(channel_id);
+                        schedule_synced_at.remove(&channel_id);
+                        ctx.record_stopped(channel_id, None);
+                    }
+                    Err(e) => {
+                        ctx.logger().error(&format!("Failed to get channel: {}", e));
+                    }
+                }
+            }
+
+            // 新規チャンネルの初期化（認証・IRC接続・セッション復元）
+            let has_new_channels = due_channels
+                .iter()
+                .any(|channel| !channel.id.is_some_and(|id| sessions.contains_key(&id)));
+            if has_new_channels {
+                if let Err(e) = collector.start_collection(&due_channels[0]).await {
+                    let error_msg = format!("Failed to start collection: {}", e);
+                    ctx.logger().error(&error_msg);
+                    let error = CollectorError::from_error(e.as_ref());
+                    // 認証できるまで新規チャンネルはポーリングしない（バックオフしながら再試行）
+                    due_channels.retain(|channel| {
+                        let Some(channel_id) = channel.id else {
+                            return false;
+                        };
+                        if sessions.contains_key(&channel_id) {
+                            return true;
+                        }
+                        ctx.record_error(channel_id, error_msg.clone());
+                        let backoff = backoffs.entry(channel_id).or_default();
+                        backoff.on_failure(&error, std::time::Instant::now());
+                        let wait = backoff
+                            .next_wait(Self::poll_interval(channel), std::time::Instant::now());
+                        Self::reschedule(&schedule, channel_id, Instant::now() + wait);
+                        false
+                    });
+                } else {
+                    for channel in &due_channels {
+                        let Some(channel_id) = channel.id else {
+                            continue;
+                        };
+                        if let Entry::Vacant(entry) = sessions.entry(channel_id) {
+                            ctx.start_chat_if_needed(channel).await;
+                            entry.insert(ctx.init_session(channel_id).await);
+                        }
+                    }
+                }
+            }
+
+            if due_channels.is_empty() {
+                continue;
+            }
+
+            // ポーリング実行（Network I/O - 最大100チャンネル/リクエスト）
+            let results = collector.poll_channels_batch(&due_channels).await;
+            let channels_by_id: HashMap<i64, &Channel> = due_channels
+                .iter()
+                .filter_map(|channel| channel.id.map(|id| (id, channel)))
+                .collect();
+
+            for (channel_id, poll_result) in results {
+                let (Some(channel), Some(session)) = (
+                    channels_by_id.get(&channel_id),
+                    sessions.get_mut(&channel_id),
+                ) else {
+                    continue;
+                };
+
+                let backoff = backoffs.entry(channel_id).or_default();
+                ctx.handle_poll_result(channel, session, backoff, poll_result)
+                    .await;
+
+                // ポリシーとバックオフに応じて次回ポーリング時刻を決定
+                let is_live = session.current_stream_db_id().is_some();
+                let planned = planner.next_interval(&ctx, channel, is_live).await;
+                let wait = backoff.next_wait(planned, std::time::Instant::now());
+                Self::reschedule(&schedule, channel_id, Instant::now() + wait);
+            }
+
+            // 手動登録チャンネルの配信スケジュールを定期的に取得（配信予定との照合・遵守率の集計用）
+            // ポーリングループを止めないよう1サイクルあたりの件数を制限し、残りは次回以降に回す
+            let schedule_sync_interval = Duration::from_secs(twitch::SCHEDULE_SYNC_INTERVAL_SECS);
+            let mut schedule_synced = 0;
+            for channel in &due_channels {
+                if schedule_synced >= twitch::SCHEDULE_SYNC_MAX_PER_CYCLE {
+                    break;
+                }
+                let Some(channel_id) = channel.id else {
+                    continue;
+                };
+                if channel.is_auto_discovered || channel.twitch_user_id.is_none() {
+                    continue;
+                }
+                if schedule_synced_at
+                    .get(&channel_id)
+                    .is_some_and(|synced_at| synced_at.elapsed() < schedule_sync_interval)
+                {
+                    continue;
+                }
+                // 失敗しても次の間隔まで再試行しない
+                schedule_synced_at.insert(channel_id, Instant::now());
+                ctx.sync_twitch_schedule(&collector, channel).await;
+                schedule_synced += 1;
+            }
+        }
+    }
+}
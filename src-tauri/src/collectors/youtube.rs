@@ -1,50 +1,258 @@
-use crate::api::youtube_api::YouTubeApiClient;
+use crate::api::youtube_api::{
+    load_quota_usage, save_quota_usage, QuotaConsumer, YouTubeApiClient, YouTubeChannelRef,
+    YouTubeQuotaTracker,
+};
 use crate::api::youtube_live_chat::YouTubeLiveChatCollector;
-use crate::collectors::collector_trait::Collector;
-use crate::database::models::{Channel, StreamData};
+use crate::collectors::collector_trait::{Collector, CollectorError};
+use crate::constants::{database as db_constants, youtube};
+use crate::database::models::{Channel, ScheduledStream, StreamData};
+use crate::database::repositories::ScheduledStreamRepository;
 use crate::database::DatabaseManager;
+use crate::logger::AppLogger;
+use crate::websocket::twitch_irc::{ChatIngestMetrics, IngestCounters};
 use async_trait::async_trait;
 use chrono::Local;
+use google_youtube3::api::Video;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
-#[allow(dead_code)]
 pub struct YouTubeCollector {
     api_client: Arc<Mutex<YouTubeApiClient>>,
-    chat_collectors: Arc<Mutex<HashMap<String, YouTubeLiveChatCollector>>>,
+    /// APIクライアントと共有するクォータトラッカー（ポーリング中もロックせずに参照できるよう保持）
+    quota_tracker: Arc<Mutex<YouTubeQuotaTracker>>,
+    /// チャンネル（channels.id）ごとのライブチャット収集
+    chat_collectors: Arc<Mutex<HashMap<i64, YouTubeLiveChatCollector>>>,
+    /// ライブチャット取り込みの累計値（すべての収集で共有）
+    chat_ingest_counters: Arc<IngestCounters>,
+    /// 動画カテゴリ名を取得する地域コード
+    region_code: String,
     db_manager: Arc<DatabaseManager>,
+    logger: Arc<AppLogger>,
 }
 
-#[allow(dead_code)]
 impl YouTubeCollector {
     pub async fn new(
         client_id: String,
         client_secret: String,
         redirect_uri: String,
+        daily_quota_budget: u32,
+        region_code: String,
         db_manager: Arc<DatabaseManager>,
+        logger: Arc<AppLogger>,
     ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
-        let api_client = YouTubeApiClient::new(client_id, client_secret, redirect_uri).await?;
+        let api_client =
+            YouTubeApiClient::new(client_id, client_secret, redirect_uri, daily_quota_budget)
+                .await?;
+        let quota_tracker = api_client.get_quota_tracker();
+
+        // 再起動しても当日の使用量を引き継ぐ
+        if let Err(e) = load_quota_usage(&quota_tracker, &db_manager).await {
+            logger.error(&format!("[YouTube] Failed to load quota usage: {}", e));
+        }
+
         Ok(Self {
             api_client: Arc::new(Mutex::new(api_client)),
+            quota_tracker,
             chat_collectors: Arc::new(Mutex::new(HashMap::new())),
+            chat_ingest_counters: Arc::new(IngestCounters::default()),
+            region_code,
             db_manager,
+            logger,
         })
     }
+
+    /// クォータトラッカーを取得
+    pub fn get_quota_tracker(&self) -> Arc<Mutex<YouTubeQuotaTracker>> {
+        Arc::clone(&self.quota_tracker)
+    }
+
+    /// ライブチャット取り込みの状態を取得
+    pub fn get_chat_ingest_metrics(&self) -> ChatIngestMetrics {
+        self.chat_ingest_counters.metrics(0, 0)
+    }
+
+    /// 1回のポーリングで消費するクォータの見積もり
+    ///
+    /// 通常はフィード（0 unit）+ videos.list（1 unit）。フィードが取得できない場合の
+    /// playlistItems.list も含めて見積もります（videoCategories.list は1日1回程度のため含めない）。
+    pub fn estimated_units_per_poll(&self) -> u32 {
+        youtube::QUOTA_COST_PLAYLIST_ITEMS_LIST + youtube::QUOTA_COST_VIDEOS_LIST
+    }
+
+    /// チャンネルの指定（ハンドル・URLなど）から正規のチャンネルを取得
+    pub async fn resolve_channel(
+        &self,
+        reference: &YouTubeChannelRef,
+    ) -> Result<Option<google_youtube3::api::Channel>, Box<dyn std::error::Error + Send + Sync>>
+    {
+        let result = {
+            let mut client = self.api_client.lock().await;
+            client.resolve_channel(reference).await
+        };
+        self.save_quota_usage().await;
+        result
+    }
+
+    /// 配信予定枠を配信予定として保存
+    ///
+    /// フィードは直近の動画しか含まないため、取得結果から消えただけでは取り消しとせず、
+    /// videos.list で削除済み（取得できない）または予定枠でなくなったと確認できた予定のみ取り消し扱いにします。
+    async fn save_scheduled_streams(&self, channel: &Channel, upcoming: &[Video]) {
+        let Some(channel_id) = channel.id else {
+            return;
+        };
+
+        let entries: Vec<ScheduledStream> = upcoming
+            .iter()
+            .filter_map(|video| {
+                let details = video.live_streaming_details.as_ref()?;
+                Some(ScheduledStream {
+                    id: None,
+                    channel_id,
+                    platform: db_constants::PLATFORM_YOUTUBE.to_string(),
+                    schedule_id: video.id.clone()?,
+                    title: video.snippet.as_ref().and_then(|s| s.title.clone()),
+                    category: None,
+                    scheduled_start: details.scheduled_start_time?.to_rfc3339(),
+                    scheduled_end: details.scheduled_end_time.map(|dt| dt.to_rfc3339()),
+                    status: db_constants::SCHEDULE_STATUS_SCHEDULED.to_string(),
+                    stream_id: None,
+                    actual_start: None,
+                })
+            })
+            .collect();
+
+        let now = Local::now().to_rfc3339();
+        let missing = self
+            .db_manager
+            .with_connection(|conn| {
+                ScheduledStreamRepository::upsert_schedules(conn, &entries, &now)?;
+                let pending = ScheduledStreamRepository::get_pending_schedule_ids(
+                    conn,
+                    channel_id,
+                    db_constants::PLATFORM_YOUTUBE,
+                    &now,
+                )?;
+                Ok::<_, duckdb::Error>(
+                    pending
+                        .into_iter()
+                        .filter(|id| !entries.iter().any(|entry| &entry.schedule_id == id))
+                        .collect::<Vec<String>>(),
+                )
+            })
+            .await;
+        let missing = match missing {
+            Ok(missing) if !missing.is_empty() => missing,
+            Ok(_) => return,
+            Err(e) => {
+                self.logger.error(&format!(
+                    "[YouTube] Failed to save scheduled streams for channel {}: {}",
+                    channel_id, e
+                ));
+                return;
+            }
+        };
+
+        let videos = {
+            let mut client = self.api_client.lock().await;
+            client.get_videos(&missing).await
+        };
+        self.save_quota_usage().await;
+        let videos = match videos {
+            Ok(videos) => videos,
+            Err(e) => {
+                self.logger.error(&format!(
+                    "[YouTube] Failed to confirm scheduled streams for channel {}: {}",
+                    channel_id, e
+                ));
+                return;
+            }
+        };
+
+        // 予定枠のまま、または開始済み（配信との対応付けを待つ）の動画は取り消さない
+        let still_scheduled: Vec<&str> = videos
+            .iter()
+            .filter(|video| {
+                let is_upcoming = video
+                    .snippet
+                    .as_ref()
+                    .and_then(|snippet| snippet.live_broadcast_content.as_deref())
+                    == Some(youtube::LIVE_BROADCAST_CONTENT_UPCOMING);
+                let has_started = video
+                    .live_streaming_details
+                    .as_ref()
+                    .is_some_and(|details| details.actual_start_time.is_some());
+                is_upcoming || has_started
+            })
+            .filter_map(|video| video.id.as_deref())
+            .collect();
+        let canceled: Vec<String> = missing
+            .into_iter()
+            .filter(|id| !still_scheduled.contains(&id.as_str()))
+            .collect();
+
+        if let Err(e) = self
+            .db_manager
+            .with_connection(|conn| {
+                ScheduledStreamRepository::cancel_schedules(
+                    conn,
+                    db_constants::PLATFORM_YOUTUBE,
+                    &canceled,
+                    &now,
+                )
+            })
+            .await
+        {
+            self.logger.error(&format!(
+                "[YouTube] Failed to cancel scheduled streams for channel {}: {}",
+                channel_id, e
+            ));
+        }
+    }
+
+    async fn save_quota_usage(&self) {
+        if let Err(e) = save_quota_usage(&self.quota_tracker, &self.db_manager).await {
+            self.logger
+                .error(&format!("[YouTube] Failed to save quota usage: {}", e));
+        }
+    }
 }
 
 #[async_trait]
 impl Collector for YouTubeCollector {
-    async fn poll_channel(
-        &self,
-        channel: &Channel,
-    ) -> Result<Option<StreamData>, Box<dyn std::error::Error + Send + Sync>> {
-        let mut client = self.api_client.lock().await;
-
-        // チャンネルIDからライブストリームを取得
-        let stream_opt = client.get_live_stream(&channel.channel_id).await?;
+    async fn poll_channel(&self, channel: &Channel) -> Result<Option<StreamData>, CollectorError> {
+        // チャンネルIDからライブストリームと配信予定枠を取得し、カテゴリIDをカテゴリ名に変換
+        let (broadcasts, category_name) = {
+            let mut client = self.api_client.lock().await;
+            let broadcasts = client.get_broadcasts(&channel.channel_id).await;
+            let category_id = broadcasts
+                .as_ref()
+                .ok()
+                .and_then(|broadcasts| broadcasts.live.as_ref())
+                .and_then(|video| video.snippet.as_ref())
+                .and_then(|snippet| snippet.category_id.clone());
+            let category_name = match category_id {
+                Some(category_id) => client
+                    .get_video_category_name(&self.region_code, &category_id)
+                    .await
+                    .unwrap_or_else(|e| {
+                        self.logger.error(&format!(
+                            "[YouTube] Failed to get video category {}: {}",
+                            category_id, e
+                        ));
+                        None
+                    }),
+                None => None,
+            };
+            (broadcasts, category_name)
+        };
+        self.save_quota_usage().await;
+        let broadcasts = broadcasts?;
+        self.save_scheduled_streams(channel, &broadcasts.upcoming)
+            .await;
 
-        if let Some(video) = stream_opt {
+        if let Some(video) = broadcasts.live {
             // 視聴者数を取得（statisticsから）
             let viewer_count = video
                 .live_streaming_details
@@ -78,12 +286,19 @@ impl Collector for YouTubeCollector {
             Ok(Some(StreamData {
                 stream_id,
                 title: video.snippet.as_ref().and_then(|s| s.title.clone()),
-                category: video.snippet.as_ref().and_then(|s| s.category_id.clone()),
+                // カテゴリ名が取得できない場合はIDをそのまま使う（game_categories で後から名前に解決される）
+                category: category_name
+                    .or_else(|| video.snippet.as_ref().and_then(|s| s.category_id.clone())),
                 game_id: video.snippet.as_ref().and_then(|s| s.category_id.clone()),
                 thumbnail_url,
                 started_at,
                 viewer_count,
                 follower_count: None, // YouTube APIではフォロワー数は取得していない
+                language: video
+                    .snippet
+                    .as_ref()
+                    .and_then(|s| s.default_audio_language.clone()),
+                tags: video.snippet.as_ref().and_then(|s| s.tags.clone()),
             }))
         } else {
             Ok(None)
@@ -100,34 +315,77 @@ impl Collector for YouTubeCollector {
 }
 
 impl YouTubeCollector {
-    /// チャット収集を開始（ストリーム開始時に呼び出し）
-    /// TODO: チャット機能実装中
-    #[allow(dead_code)]
+    /// チャット収集を開始（ライブ配信を検出したときに呼び出し）
+    ///
+    /// 同じ動画の収集中であれば何もしません。別の動画に切り替わった場合は前の収集を停止します。
     pub async fn start_chat_collection(
         &self,
-        _stream_id: i64,
-        _video_id: &str,
-        _poll_interval_secs: u64,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        // チャット機能は現在無効化中
-        // let mut client = self.api_client.lock().await;
-        // let hub = client.get_hub().clone();
-        // let db_conn = Arc::clone(&self.db_conn);
-        // let chat_collector = YouTubeLiveChatCollector::new(hub, db_conn, stream_id);
-        // chat_collector.start_with_video_id(video_id, poll_interval_secs).await?;
-        // let mut chat_collectors = self.chat_collectors.lock().await;
-        // chat_collectors.insert(video_id.to_string(), chat_collector);
+        channel_id: i64,
+        stream_id: i64,
+        video_id: &str,
+    ) -> Result<(), String> {
+        let mut chat_collectors = self.chat_collectors.lock().await;
+
+        if let Some(existing) = chat_collectors.get(&channel_id) {
+            if existing.video_id() == video_id {
+                return Ok(());
+            }
+            existing.stop();
+        }
+        if chat_collectors.remove(&channel_id).is_some() {
+            self.update_live_chat_consumers(chat_collectors.len()).await;
+        }
+
+        let hub = self.api_client.lock().await.get_hub();
+        let collector = YouTubeLiveChatCollector::start(
+            hub,
+            Arc::clone(&self.quota_tracker),
+            Arc::clone(&self.db_manager),
+            Arc::clone(&self.chat_ingest_counters),
+            Arc::clone(&self.logger),
+            channel_id,
+            stream_id,
+            video_id,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+        chat_collectors.insert(channel_id, collector);
+        self.update_live_chat_consumers(chat_collectors.len()).await;
+
+        self.logger.info(&format!(
+            "[YouTubeChat] Started collection for channel {} (video: {})",
+            channel_id, video_id
+        ));
         Ok(())
     }
 
-    /// チャット収集を停止（ストリーム終了時に呼び出し）
-    /// TODO: チャット機能実装中
-    #[allow(dead_code)]
-    pub async fn stop_chat_collection(&self, _video_id: &str) {
-        // チャット機能は現在無効化中
-        // let mut chat_collectors = self.chat_collectors.lock().await;
-        // if let Some(collector) = chat_collectors.remove(video_id) {
-        //     collector.stop_collection().await;
-        // }
+    /// 収集中のライブチャット数をクォータの配分に反映
+    async fn update_live_chat_consumers(&self, count: usize) {
+        self.quota_tracker.lock().await.set_consumers(
+            QuotaConsumer::LiveChat,
+            count,
+            youtube::QUOTA_COST_LIVE_CHAT_MESSAGES_LIST,
+        );
+    }
+
+    /// チャット収集を停止（配信終了時に呼び出し）
+    pub async fn stop_chat_collection(&self, channel_id: i64) -> Result<(), String> {
+        let mut chat_collectors = self.chat_collectors.lock().await;
+        match chat_collectors.remove(&channel_id) {
+            Some(collector) => {
+                collector.stop();
+                self.update_live_chat_consumers(chat_collectors.len()).await;
+                self.logger.info(&format!(
+                    "[YouTubeChat] Stopped collection for channel {} (video: {})",
+                    channel_id,
+                    collector.video_id()
+                ));
+                Ok(())
+            }
+            None => Err(format!(
+                "No YouTube chat collection found for channel_id: {}",
+                channel_id
+            )),
+        }
     }
 }
@@ -1,5 +1,9 @@
+use crate::database::models::ScheduledStream;
+use crate::database::repositories::base::{ChannelFilter, TimeRangeFilter};
+use crate::database::repositories::{ScheduleAdherence, ScheduledStreamRepository};
 use crate::database::{analytics, chat_analytics, DatabaseManager};
 use crate::error::ResultExt;
+use chrono::Local;
 use tauri::State;
 
 #[tauri::command]
@@ -139,6 +143,7 @@ pub async fn detect_chat_spikes(
     start_time: Option<String>,
     end_time: Option<String>,
     min_spike_ratio: Option<f64>,
+    exclude_deleted: Option<bool>,
 ) -> Result<Vec<chat_analytics::ChatSpike>, String> {
     db_manager
         .with_connection(|conn| {
@@ -149,6 +154,7 @@ pub async fn detect_chat_spikes(
                 start_time.as_deref(),
                 end_time.as_deref(),
                 min_spike_ratio.unwrap_or(2.0),
+                exclude_deleted.unwrap_or(false),
             )
             .db_context("detect chat spikes")
             .map_err(|e| e.to_string())
@@ -187,6 +193,7 @@ pub async fn get_top_chatters(
     start_time: Option<String>,
     end_time: Option<String>,
     limit: Option<i32>,
+    exclude_deleted: Option<bool>,
 ) -> Result<Vec<chat_analytics::TopChatter>, String> {
     db_manager
         .with_connection(|conn| {
@@ -197,6 +204,7 @@ pub async fn get_top_chatters(
                 start_time.as_deref(),
                 end_time.as_deref(),
                 limit.unwrap_or(50),
+                exclude_deleted.unwrap_or(false),
             )
             .db_context("get top chatters")
             .map_err(|e| e.to_string())
@@ -204,6 +212,83 @@ pub async fn get_top_chatters(
         .await
 }
 
+#[tauri::command]
+pub async fn get_moderation_stats(
+    db_manager: State<'_, DatabaseManager>,
+    channel_id: Option<i64>,
+    stream_id: Option<i64>,
+    start_time: Option<String>,
+    end_time: Option<String>,
+) -> Result<chat_analytics::ModerationStats, String> {
+    db_manager
+        .with_connection(|conn| {
+            chat_analytics::get_moderation_stats(
+                conn,
+                channel_id,
+                stream_id,
+                start_time.as_deref(),
+                end_time.as_deref(),
+            )
+            .db_context("get moderation stats")
+            .map_err(|e| e.to_string())
+        })
+        .await
+}
+
+#[tauri::command]
+pub async fn get_monetization_stats(
+    db_manager: State<'_, DatabaseManager>,
+    channel_id: Option<i64>,
+    stream_id: Option<i64>,
+    start_time: Option<String>,
+    end_time: Option<String>,
+    limit: Option<i32>,
+) -> Result<chat_analytics::MonetizationStats, String> {
+    let channel = ChannelFilter {
+        channel_id,
+        stream_id,
+    };
+    let time_range = TimeRangeFilter {
+        start_time,
+        end_time,
+    };
+
+    db_manager
+        .with_connection(|conn| {
+            chat_analytics::get_monetization_stats(conn, &channel, &time_range, limit.unwrap_or(50))
+                .db_context("get monetization stats")
+                .map_err(|e| e.to_string())
+        })
+        .await
+}
+
+/// Super Chat 等の売上を登録済みの換算レートで基準通貨に揃えて取得
+#[tauri::command]
+pub async fn get_super_chat_revenue(
+    db_manager: State<'_, DatabaseManager>,
+    channel_id: Option<i64>,
+    stream_id: Option<i64>,
+    start_time: Option<String>,
+    end_time: Option<String>,
+) -> Result<chat_analytics::SuperChatRevenue, String> {
+    let channel = ChannelFilter {
+        channel_id,
+        stream_id,
+    };
+    let time_range = TimeRangeFilter {
+        start_time,
+        end_time,
+    };
+
+    db_manager
+        .with_connection(|conn| {
+            chat_analytics::get_super_chat_revenue(conn, &channel, &time_range)
+                .db_context("get super chat revenue")
+                .map_err(|e| e.to_string())
+        })
+        .await
+}
+
 #[tauri::command]
 pub async fn get_time_pattern_stats(
     db_manager: State<'_, DatabaseManager>,
@@ -247,3 +332,49 @@ pub async fn get_chatter_behavior_stats(
         })
         .await
 }
+
+/// 配信予定（Twitch の配信スケジュール、YouTube の配信予定枠）を予定時刻順に取得
+#[tauri::command]
+pub async fn get_scheduled_streams(
+    db_manager: State<'_, DatabaseManager>,
+    channel_id: Option<i64>,
+    start_time: Option<String>,
+    end_time: Option<String>,
+) -> Result<Vec<ScheduledStream>, String> {
+    db_manager
+        .with_connection(|conn| {
+            ScheduledStreamRepository::get_scheduled_streams(
+                conn,
+                channel_id,
+                start_time.as_deref(),
+                end_time.as_deref(),
+            )
+            .db_context("get scheduled streams")
+            .map_err(|e| e.to_string())
+        })
+        .await
+}
+
+/// チャンネル別の配信予定の遵守率・開始の遅れ・取り消し数を取得
+#[tauri::command]
+pub async fn get_schedule_adherence(
+    db_manager: State<'_, DatabaseManager>,
+    channel_id: Option<i64>,
+    start_time: Option<String>,
+    end_time: Option<String>,
+) -> Result<Vec<ScheduleAdherence>, String> {
+    let now = Local::now().to_rfc3339();
+    db_manager
+        .with_connection(|conn| {
+            ScheduledStreamRepository::adherence_by_channel(
+                conn,
+                channel_id,
+                start_time.as_deref(),
+                end_time.as_deref(),
+                &now,
+            )
+            .db_context("get schedule adherence")
+            .map_err(|e| e.to_string())
+        })
+        .await
+}
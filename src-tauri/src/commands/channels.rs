@@ -1,4 +1,6 @@
+use crate::api::youtube_api::is_channel_id;
 use crate::collectors::poller::ChannelPoller;
+use crate::constants::database as db_constants;
 use crate::database::{
     models::{Channel, ChannelWithStats},
     repositories::{channel_repository::CreateChannelParams, ChannelRepository},
@@ -25,8 +27,9 @@ pub struct AddChannelRequest {
 pub async fn add_channel(
     app_handle: AppHandle,
     db_manager: State<'_, DatabaseManager>,
-    request: AddChannelRequest,
+    mut request: AddChannelRequest,
 ) -> Result<Channel, String> {
+    request.channel_id = validate_channel_id(&request.platform, &request.channel_id)?;
     let poll_interval = request.poll_interval.unwrap_or(60);
 
     let channel = db_manager
@@ -71,6 +74,36 @@ pub async fn add_channel(
     Ok(channel)
 }
 
+/// プラットフォームとチャンネルIDを検証し、保存用に正規化したチャンネルIDを返す
+fn validate_channel_id(platform: &str, channel_id: &str) -> Result<String, String> {
+    if !db_constants::SUPPORTED_PLATFORMS.contains(&platform) {
+        return Err(format!("Unsupported platform: {}", platform));
+    }
+
+    let channel_id = channel_id.trim();
+    if channel_id.is_empty() {
+        return Err("Channel ID must not be empty".to_string());
+    }
+
+    // Kickはスラッグ（kick.com/{slug}）で識別するため、URLに使える文字のみ許可
+    if platform == db_constants::PLATFORM_KICK {
+        let is_valid_slug = channel_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !is_valid_slug {
+            return Err(format!("Invalid Kick channel slug: {}", channel_id));
+        }
+        return Ok(channel_id.to_ascii_lowercase());
+    }
+
+    // YouTubeは正規のチャンネルID（UC...）で識別する（ハンドルやURLは validate_youtube_channel で解決）
+    if platform == db_constants::PLATFORM_YOUTUBE && !is_channel_id(channel_id) {
+        return Err(format!("Invalid YouTube channel ID: {}", channel_id));
+    }
+
+    Ok(channel_id.to_string())
+}
+
 #[tauri::command]
 pub async fn remove_channel(
     app_handle: AppHandle,
@@ -13,6 +13,8 @@ pub struct ChatMessagesQuery {
     pub end_time: Option<String>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
+    /// message_type での絞り込み（例: sub, raid）。未指定時は全種別
+    pub message_types: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -38,7 +40,10 @@ pub async fn get_chat_messages(
             CAST(cm.timestamp AS VARCHAR) as timestamp,
             cm.platform,
             cm.user_id, cm.user_name, cm.display_name, cm.message, cm.message_type,
-            CAST(cm.badges AS VARCHAR) as badges, cm.badge_info
+            CAST(cm.badges AS VARCHAR) as badges, cm.badge_info, cm.payload,
+            cm.platform_message_id, cm.emotes, cm.bits,
+            cm.reply_parent_id, cm.reply_parent_user_name, cm.reply_thread_id,
+            cm.is_auto_discovered
         FROM chat_messages cm
         INNER JOIN streams s ON cm.stream_id = s.id
         WHERE 1=1
@@ -67,6 +72,12 @@ pub async fn get_chat_messages(
         params.push(end_time.clone());
     }
 
+    if let Some(message_types) = query.message_types.as_ref().filter(|t| !t.is_empty()) {
+        let placeholders = vec!["?"; message_types.len()].join(", ");
+        sql.push_str(&format!(" AND cm.message_type IN ({})", placeholders));
+        params.extend(message_types.iter().cloned());
+    }
+
     sql.push_str(" ORDER BY cm.timestamp DESC");
 
     if let Some(limit) = query.limit {
@@ -143,7 +154,10 @@ pub async fn get_chat_messages_around_timestamp(
             CAST(cm.timestamp AS VARCHAR) as timestamp,
             cm.platform,
             cm.user_id, cm.user_name, cm.display_name, cm.message, cm.message_type,
-            CAST(cm.badges AS VARCHAR) as badges, cm.badge_info
+            CAST(cm.badges AS VARCHAR) as badges, cm.badge_info, cm.payload,
+            cm.platform_message_id, cm.emotes, cm.bits,
+            cm.reply_parent_id, cm.reply_parent_user_name, cm.reply_thread_id,
+            cm.is_auto_discovered
         FROM chat_messages cm
         WHERE cm.stream_id = ?
           AND cm.timestamp >= ?
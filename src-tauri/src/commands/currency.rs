@@ -0,0 +1,66 @@
+use crate::database::{
+    models::CurrencyRate,
+    repositories::{base::with_transaction, CurrencyRateRepository},
+    DatabaseManager,
+};
+use crate::error::ResultExt;
+use chrono::Local;
+use tauri::State;
+
+/// 登録済みの通貨換算レートを取得
+#[tauri::command]
+pub async fn get_currency_rates(
+    db_manager: State<'_, DatabaseManager>,
+) -> Result<Vec<CurrencyRate>, String> {
+    db_manager
+        .with_connection(|conn| {
+            CurrencyRateRepository::get_rates(conn)
+                .db_context("get currency rates")
+                .map_err(|e| e.to_string())
+        })
+        .await
+}
+
+/// 通貨換算レートを保存（既存のレートはすべて置き換えられます）
+///
+/// rate は「1通貨単位あたりの基準通貨での金額」です。通貨コードは大文字に揃えます。
+#[tauri::command]
+pub async fn save_currency_rates(
+    db_manager: State<'_, DatabaseManager>,
+    base_currency: String,
+    rates: Vec<CurrencyRate>,
+) -> Result<usize, String> {
+    let base_currency = base_currency.trim().to_uppercase();
+    if base_currency.is_empty() {
+        return Err("Base currency is required".to_string());
+    }
+
+    let rates = rates
+        .into_iter()
+        .map(|rate| {
+            let currency = rate.currency.trim().to_uppercase();
+            if currency.is_empty() {
+                return Err("Currency code is required".to_string());
+            }
+            if !rate.rate.is_finite() || rate.rate <= 0.0 {
+                return Err(format!("Invalid rate for {}: {}", currency, rate.rate));
+            }
+            Ok(CurrencyRate {
+                currency,
+                base_currency: base_currency.clone(),
+                ..rate
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let updated_at = Local::now().to_rfc3339();
+
+    db_manager
+        .with_connection(|conn| {
+            with_transaction(conn, |conn| {
+                CurrencyRateRepository::replace_rates(conn, &base_currency, &rates, &updated_at)
+            })
+            .db_context("save currency rates")
+            .map_err(|e| e.to_string())
+        })
+        .await
+}
@@ -15,6 +15,7 @@ pub async fn get_word_frequency_analysis(
     start_time: Option<String>,
     end_time: Option<String>,
     limit: Option<i32>,
+    exclude_deleted: Option<bool>,
 ) -> Result<data_science_analytics::WordFrequencyResult, String> {
     db_manager
         .with_connection(|conn| {
@@ -25,6 +26,7 @@ pub async fn get_word_frequency_analysis(
                 start_time.as_deref(),
                 end_time.as_deref(),
                 limit.unwrap_or(100),
+                exclude_deleted.unwrap_or(false),
             )
             .db_context("get word frequency analysis")
             .map_err(|e| e.to_string())
@@ -155,6 +157,31 @@ pub async fn get_chatter_activity_scores(
         .await
 }
 
+#[tauri::command]
+pub async fn get_reply_graph(
+    db_manager: State<'_, DatabaseManager>,
+    channel_id: Option<i64>,
+    stream_id: Option<i64>,
+    start_time: Option<String>,
+    end_time: Option<String>,
+    limit: Option<i32>,
+) -> Result<data_science_analytics::ReplyGraphResult, String> {
+    db_manager
+        .with_connection(|conn| {
+            data_science_analytics::get_reply_graph(
+                conn,
+                channel_id,
+                stream_id,
+                start_time.as_deref(),
+                end_time.as_deref(),
+                limit.unwrap_or(100),
+            )
+            .db_context("get reply graph")
+            .map_err(|e| e.to_string())
+        })
+        .await
+}
+
 // ============================================================================
 // Phase 4: Anomaly Detection Commands
 // ============================================================================
@@ -1,7 +1,8 @@
 use crate::collectors::auto_discovery::AutoDiscoveryPoller;
+use crate::collectors::poller::ChannelPoller;
 use crate::config::settings::{AutoDiscoverySettings, SettingsManager};
 use crate::constants::database as db_constants;
-use crate::database::{repositories::ChannelRepository, DatabaseManager};
+use crate::database::{models::Channel, repositories::ChannelRepository, DatabaseManager};
 use crate::error::ResultExt;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -28,12 +29,15 @@ pub async fn save_auto_discovery_settings(
     app_handle: AppHandle,
     mut settings: AutoDiscoverySettings,
     auto_discovery_poller: State<'_, Arc<Mutex<Option<AutoDiscoveryPoller>>>>,
-    channel_poller: State<'_, Arc<Mutex<crate::collectors::poller::ChannelPoller>>>,
+    channel_poller: State<'_, Arc<Mutex<ChannelPoller>>>,
     db_manager: State<'_, DatabaseManager>,
 ) -> Result<(), String> {
     // max_streamsのバリデーション（1-500の範囲に制限）
     settings.max_streams = settings.max_streams.clamp(1, 500);
 
+    // chat_join_budgetのバリデーション（1-100の範囲に制限）
+    settings.chat_join_budget = settings.chat_join_budget.clamp(1, 100);
+
     // game_idsのバリデーション（最大100件に制限）
     if settings.filters.game_ids.len() > 100 {
         return Err("ゲームIDは最大100件までです".to_string());
@@ -122,7 +126,7 @@ pub async fn save_auto_discovery_settings(
 pub async fn toggle_auto_discovery(
     app_handle: AppHandle,
     auto_discovery_poller: State<'_, Arc<Mutex<Option<AutoDiscoveryPoller>>>>,
-    channel_poller: State<'_, Arc<Mutex<crate::collectors::poller::ChannelPoller>>>,
+    channel_poller: State<'_, Arc<Mutex<ChannelPoller>>>,
     db_manager: State<'_, DatabaseManager>,
 ) -> Result<bool, String> {
     // 設定をロード
@@ -445,23 +449,48 @@ pub async fn promote_discovered_channels(
 
         if already_exists {
             // 既に登録されている場合はis_auto_discoveredフラグを更新
-            if let Err(e) = db_manager
+            let result = db_manager
                 .with_connection(|conn| {
+                    let previous =
+                        ChannelRepository::get_by_platform_channel_id(conn, "twitch", &login_name)?;
                     ChannelRepository::update_auto_discovered(
                         conn,
                         "twitch",
                         &login_name,
                         false,
                         Some(stream_info.twitch_user_id),
-                    )
-                    .db_context("update channel")
-                    .map_err(|e| e.to_string())
+                    )?;
+                    // チャット収集用に無効状態で作成された行は、昇格後にポーリングを開始する
+                    match previous {
+                        Some(Channel {
+                            id: Some(id),
+                            enabled: false,
+                            ..
+                        }) => ChannelRepository::get_by_id(conn, id),
+                        _ => Ok(None),
+                    }
                 })
                 .await
-                .db_context("get connection")
-            {
-                errors.push(format!("{}: {}", login_name, e));
-                continue;
+                .db_context("update channel");
+            match result {
+                Ok(Some(channel)) => {
+                    if let Some(poller) = app_handle.try_state::<Arc<Mutex<ChannelPoller>>>() {
+                        let mut poller = poller.lock().await;
+                        if let Err(e) =
+                            poller.start_polling(channel, &db_manager, app_handle.clone())
+                        {
+                            eprintln!(
+                                "[Discovery] Failed to start polling for {}: {}",
+                                login_name, e
+                            );
+                        }
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    errors.push(format!("{}: {}", login_name, e));
+                    continue;
+                }
             }
             eprintln!(
                 "[Discovery] Updated existing channel {} (user_id: {}) to manual registration",
@@ -540,6 +569,10 @@ pub struct DiscoveredStreamInfo {
     pub follower_count: i32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub broadcaster_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_id: Option<String>, // Twitch側の配信ID（チャットの紐付け先）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,172 @@
+use crate::api::emote_catalog::parse_emote_export;
+use crate::api::twitch_api::TwitchApiClient;
+use crate::config::settings::SettingsManager;
+use crate::constants::database as db_constants;
+use crate::database::{
+    models::CatalogEmote,
+    repositories::{base::with_transaction, ChannelRepository, EmoteCatalogRepository},
+    DatabaseManager,
+};
+use crate::error::ResultExt;
+use chrono::Local;
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+/// Twitchエモート同期結果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmoteSyncResult {
+    pub global_count: usize,
+    pub channel_count: usize,
+}
+
+/// エモートカタログを取得
+#[tauri::command]
+pub async fn get_emote_catalog(
+    db_manager: State<'_, DatabaseManager>,
+    source: Option<String>,
+    channel_id: Option<i64>,
+) -> Result<Vec<CatalogEmote>, String> {
+    db_manager
+        .with_connection(|conn| {
+            EmoteCatalogRepository::get_catalog(conn, source.as_deref(), channel_id)
+                .db_context("get emote catalog")
+                .map_err(|e| e.to_string())
+        })
+        .await
+}
+
+/// 7TV/BTTV/FFZ（またはTwitch Helix）のJSONエクスポートをカタログに取り込む
+///
+/// channel_id を省略した場合はグローバルエモートとして取り込みます。
+/// 同じソース・スコープの既存エモートは置き換えられます。
+#[tauri::command]
+pub async fn import_emote_catalog(
+    db_manager: State<'_, DatabaseManager>,
+    source: String,
+    channel_id: Option<i64>,
+    file_path: String,
+) -> Result<usize, String> {
+    let json = std::fs::read_to_string(&file_path)
+        .io_context("read emote export")
+        .map_err(|e| e.to_string())?;
+    let emotes = parse_emote_export(&source, &json, channel_id)?;
+    let updated_at = Local::now().to_rfc3339();
+
+    db_manager
+        .with_connection(|conn| {
+            with_transaction(conn, |conn| {
+                EmoteCatalogRepository::replace_source(
+                    conn,
+                    &source,
+                    channel_id,
+                    &emotes,
+                    &updated_at,
+                )
+            })
+            .db_context("import emote catalog")
+            .map_err(|e| e.to_string())
+        })
+        .await
+}
+
+/// Twitch APIからグローバルエモートとチャンネルエモートを取得してカタログを更新
+#[tauri::command]
+pub async fn sync_twitch_emotes(
+    app_handle: AppHandle,
+    db_manager: State<'_, DatabaseManager>,
+    channel_id: i64,
+) -> Result<EmoteSyncResult, String> {
+    let channel = db_manager
+        .with_connection(|conn| {
+            ChannelRepository::get_by_id(conn, channel_id)
+                .db_context("get channel")
+                .map_err(|e| e.to_string())
+        })
+        .await?
+        .ok_or_else(|| format!("Channel not found: {}", channel_id))?;
+
+    if channel.platform != db_constants::PLATFORM_TWITCH {
+        return Err("Emote sync is only supported for Twitch channels".to_string());
+    }
+
+    // 設定からClient IDを取得
+    let settings = SettingsManager::load_settings(&app_handle)
+        .config_context("load settings")
+        .map_err(|e| e.to_string())?;
+
+    let client_id = settings
+        .twitch
+        .client_id
+        .as_ref()
+        .ok_or_else(|| "Twitch Client ID not configured".to_string())?;
+
+    let api_client = TwitchApiClient::new(client_id.clone(), None).with_app_handle(app_handle);
+
+    // 不変なuser IDがあればそれを使い、なければloginから解決
+    let broadcaster_id = match channel.twitch_user_id {
+        Some(user_id) => user_id.to_string(),
+        None => api_client
+            .get_user_by_login(&channel.channel_id)
+            .await
+            .map_err(|e| format!("Failed to resolve Twitch user: {}", e))?
+            .id
+            .to_string(),
+    };
+
+    let global_emotes: Vec<CatalogEmote> = api_client
+        .get_global_emotes()
+        .await
+        .map_err(|e| format!("Failed to get global emotes: {}", e))?
+        .into_iter()
+        .map(|emote| CatalogEmote {
+            source: db_constants::EMOTE_SOURCE_TWITCH.to_string(),
+            emote_id: emote.id.to_string(),
+            name: emote.name,
+            channel_id: None,
+            image_url: Some(emote.images.url_1x),
+        })
+        .collect();
+
+    let channel_emotes: Vec<CatalogEmote> = api_client
+        .get_channel_emotes(&broadcaster_id)
+        .await
+        .map_err(|e| format!("Failed to get channel emotes: {}", e))?
+        .into_iter()
+        .map(|emote| CatalogEmote {
+            source: db_constants::EMOTE_SOURCE_TWITCH.to_string(),
+            emote_id: emote.id.to_string(),
+            name: emote.name,
+            channel_id: Some(channel_id),
+            image_url: Some(emote.images.url_1x),
+        })
+        .collect();
+
+    let updated_at = Local::now().to_rfc3339();
+    db_manager
+        .with_connection(|conn| {
+            with_transaction(conn, |conn| {
+                let global_count = EmoteCatalogRepository::replace_source(
+                    conn,
+                    db_constants::EMOTE_SOURCE_TWITCH,
+                    None,
+                    &global_emotes,
+                    &updated_at,
+                )?;
+                let channel_count = EmoteCatalogRepository::replace_source(
+                    conn,
+                    db_constants::EMOTE_SOURCE_TWITCH,
+                    Some(channel_id),
+                    &channel_emotes,
+                    &updated_at,
+                )?;
+                Ok::<_, duckdb::Error>(EmoteSyncResult {
+                    global_count,
+                    channel_count,
+                })
+            })
+            .db_context("sync twitch emotes")
+            .map_err(|e| e.to_string())
+        })
+        .await
+}
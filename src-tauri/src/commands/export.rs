@@ -1,4 +1,7 @@
-use crate::database::{repositories::StreamStatsRepository, DatabaseManager};
+use crate::database::{
+    repositories::{StreamEventRepository, StreamStatsRepository},
+    DatabaseManager,
+};
 use crate::error::ResultExt;
 use chrono::{DateTime, FixedOffset, NaiveDateTime};
 use serde::{Deserialize, Serialize};
@@ -157,6 +160,70 @@ pub async fn export_to_delimited(
     ))
 }
 
+/// 配信メタデータの変更履歴（stream_events）を区切り形式でエクスポート
+#[tauri::command]
+pub async fn export_stream_events_to_delimited(
+    db_manager: State<'_, DatabaseManager>,
+    query: ExportQuery,
+    file_path: String,
+    include_bom: Option<bool>,
+) -> Result<String, String> {
+    let events = db_manager
+        .with_connection(|conn| {
+            StreamEventRepository::get_by_channel(
+                conn,
+                query.channel_id,
+                query.start_time.as_deref(),
+                query.end_time.as_deref(),
+            )
+            .db_context("query stream events")
+            .map_err(|e| e.to_string())
+        })
+        .await?;
+
+    let delimiter = query.delimiter.as_deref().unwrap_or(",");
+
+    let mut output = String::new();
+    if include_bom.unwrap_or(false) {
+        output.push('\u{FEFF}');
+    }
+
+    output.push_str(
+        &[
+            "occurred_at",
+            "stream_id",
+            "event_type",
+            "old_value",
+            "new_value",
+        ]
+        .join(delimiter),
+    );
+    output.push('\n');
+
+    for event in &events {
+        let fields = [
+            escape_field(&normalize_timestamp(&event.occurred_at), delimiter),
+            event.stream_id.to_string(),
+            escape_field(&event.event_type, delimiter),
+            escape_field(event.old_value.as_deref().unwrap_or(""), delimiter),
+            escape_field(event.new_value.as_deref().unwrap_or(""), delimiter),
+        ];
+        output.push_str(&fields.join(delimiter));
+        output.push('\n');
+    }
+
+    std::fs::write(&file_path, output)
+        .io_context("write file")
+        .map_err(|e| e.to_string())?;
+
+    Ok(format!(
+        "Exported {} stream events to {} (delimiter: {:?})",
+        events.len(),
+        file_path,
+        delimiter
+    ))
+}
+
 #[tauri::command]
 pub async fn preview_export_data(
     _app_handle: AppHandle,
@@ -1,3 +1,4 @@
+use crate::constants::database as db_constants;
 use crate::database::{
     models::GameCategory, repositories::GameCategoryRepository, DatabaseManager,
 };
@@ -8,6 +9,8 @@ use tauri::State;
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpsertGameCategoryRequest {
+    /// 未指定の場合は twitch
+    pub platform: Option<String>,
     pub game_id: String,
     pub game_name: String,
     pub box_art_url: Option<String>,
@@ -26,15 +29,17 @@ pub async fn get_game_categories(
         .await
 }
 
-/// IDでゲームカテゴリを取得
+/// IDでゲームカテゴリを取得（platform 未指定の場合は twitch）
 #[tauri::command]
 pub async fn get_game_category(
     db_manager: State<'_, DatabaseManager>,
     game_id: String,
+    platform: Option<String>,
 ) -> Result<Option<GameCategory>, String> {
+    let platform = platform.unwrap_or_else(|| db_constants::PLATFORM_TWITCH.to_string());
     db_manager
         .with_connection(|conn| {
-            GameCategoryRepository::get_category_by_id(conn, &game_id)
+            GameCategoryRepository::get_category_by_id(conn, &platform, &game_id)
                 .map_err(|e| format!("Failed to get game category: {}", e))
         })
         .await
@@ -50,6 +55,10 @@ pub async fn upsert_game_category(
         .with_connection(|conn| {
             GameCategoryRepository::upsert_category(
                 conn,
+                request
+                    .platform
+                    .as_deref()
+                    .unwrap_or(db_constants::PLATFORM_TWITCH),
                 &request.game_id,
                 &request.game_name,
                 request.box_art_url.as_deref(),
@@ -59,15 +68,17 @@ pub async fn upsert_game_category(
         .await
 }
 
-/// ゲームカテゴリを削除
+/// ゲームカテゴリを削除（platform 未指定の場合は twitch）
 #[tauri::command]
 pub async fn delete_game_category(
     db_manager: State<'_, DatabaseManager>,
     game_id: String,
+    platform: Option<String>,
 ) -> Result<(), String> {
+    let platform = platform.unwrap_or_else(|| db_constants::PLATFORM_TWITCH.to_string());
     db_manager
         .with_connection(|conn| {
-            GameCategoryRepository::delete_category(conn, &game_id)
+            GameCategoryRepository::delete_category(conn, &platform, &game_id)
                 .map_err(|e| format!("Failed to delete game category: {}", e))
         })
         .await
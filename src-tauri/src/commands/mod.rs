@@ -2,16 +2,20 @@ pub mod analytics;
 pub mod channels;
 pub mod chat;
 pub mod config;
+pub mod currency;
 pub mod data_science;
 pub mod database;
 pub mod discovery;
+pub mod emotes;
 pub mod export;
 pub mod game_categories;
 pub mod logs;
 pub mod oauth;
+pub mod poll_policy;
 pub mod sql;
 pub mod stats;
 pub mod system;
 pub mod timeline;
 pub mod twitch;
 pub mod window;
+pub mod youtube;
@@ -62,6 +62,7 @@ pub async fn reinitialize_twitch_collector(
     >,
 ) -> Result<(), String> {
     use crate::collectors::twitch::TwitchCollector;
+    use crate::database::chat_spool::ChatSpool;
     use crate::logger::AppLogger;
     use std::sync::Arc;
 
@@ -86,6 +87,7 @@ pub async fn reinitialize_twitch_collector(
         None,
         app_handle.clone(),
         Arc::new(db_manager.inner().clone()),
+        app_handle.state::<Option<Arc<ChatSpool>>>().inner().clone(),
         Arc::new(logger.inner().clone()),
     ));
 
@@ -0,0 +1,83 @@
+use crate::database::{models::PollPolicy, repositories::PollPolicyRepository, DatabaseManager};
+use tauri::State;
+
+/// ポリシーの値を検証
+fn validate_poll_policy(policy: &PollPolicy) -> Result<(), String> {
+    for (name, hour) in [
+        ("quietHoursStart", policy.quiet_hours_start),
+        ("quietHoursEnd", policy.quiet_hours_end),
+    ] {
+        if let Some(hour) = hour {
+            if !(0..=23).contains(&hour) {
+                return Err(format!("{} must be between 0 and 23", name));
+            }
+        }
+    }
+
+    for (name, interval) in [
+        ("liveInterval", policy.live_interval),
+        ("offlineInterval", policy.offline_interval),
+        ("quietInterval", policy.quiet_interval),
+        ("startWindowInterval", policy.start_window_interval),
+    ] {
+        if let Some(interval) = interval {
+            if interval <= 0 {
+                return Err(format!("{} must be greater than 0", name));
+            }
+        }
+    }
+
+    if policy.quiet_interval.is_some()
+        && (policy.quiet_hours_start.is_none() || policy.quiet_hours_end.is_none())
+    {
+        return Err("quietInterval requires quietHoursStart and quietHoursEnd".to_string());
+    }
+
+    Ok(())
+}
+
+/// チャンネルのポーリングポリシーを取得
+#[tauri::command]
+pub async fn get_poll_policy(
+    db_manager: State<'_, DatabaseManager>,
+    channel_id: i64,
+) -> Result<Option<PollPolicy>, String> {
+    db_manager
+        .with_connection(|conn| {
+            PollPolicyRepository::get(conn, channel_id)
+                .map_err(|e| format!("Failed to get poll policy: {}", e))
+        })
+        .await
+}
+
+/// チャンネルのポーリングポリシーを保存（次回ポーリングから反映）
+#[tauri::command]
+pub async fn save_poll_policy(
+    db_manager: State<'_, DatabaseManager>,
+    policy: PollPolicy,
+) -> Result<Option<PollPolicy>, String> {
+    validate_poll_policy(&policy)?;
+
+    db_manager
+        .with_connection(|conn| {
+            PollPolicyRepository::upsert(conn, &policy)
+                .map_err(|e| format!("Failed to save poll policy: {}", e))?;
+            PollPolicyRepository::get(conn, policy.channel_id)
+                .map_err(|e| format!("Failed to get poll policy: {}", e))
+        })
+        .await
+}
+
+/// チャンネルのポーリングポリシーを削除（channels.poll_interval に戻す）
+#[tauri::command]
+pub async fn delete_poll_policy(
+    db_manager: State<'_, DatabaseManager>,
+    channel_id: i64,
+) -> Result<(), String> {
+    db_manager
+        .with_connection(|conn| {
+            PollPolicyRepository::delete(conn, channel_id)
+                .map_err(|e| format!("Failed to delete poll policy: {}", e))
+        })
+        .await
+}
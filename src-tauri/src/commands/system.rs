@@ -1,3 +1,4 @@
+use crate::collectors::health::{collect_health_snapshot, CollectorHealthSnapshot};
 use crate::collectors::poller::ChannelPoller;
 use std::sync::Arc;
 use tauri::{AppHandle, Manager};
@@ -16,3 +17,11 @@ pub async fn is_backend_ready(app_handle: AppHandle) -> Result<bool, String> {
         Ok(false) // ChannelPollerがまだ登録されていない
     }
 }
+
+/// コレクターのヘルス状態を取得（チャンネルごとのポーリング状態・IRC接続・自動発見）
+#[tauri::command]
+pub async fn get_collector_statuses(
+    app_handle: AppHandle,
+) -> Result<CollectorHealthSnapshot, String> {
+    Ok(collect_health_snapshot(&app_handle).await)
+}
@@ -1,4 +1,7 @@
-use crate::database::repositories::{StreamInfo, StreamRepository, TimelinePoint};
+use crate::constants::database as db_constants;
+use crate::database::repositories::{
+    StreamEventRepository, StreamInfo, StreamRepository, TimelinePoint,
+};
 use crate::database::DatabaseManager;
 use serde::{Deserialize, Serialize};
 use tauri::State;
@@ -93,8 +96,27 @@ fn get_stream_timeline_internal(
 ) -> Result<StreamTimelineData, Box<dyn std::error::Error + Send + Sync>> {
     let stream_info = StreamRepository::get_stream_info_by_id(conn, stream_id)?;
     let stats = StreamRepository::get_timeline_stats(conn, stream_id)?;
-    let category_changes = detect_category_changes(&stats);
-    let title_changes = detect_title_changes(&stats);
+    let events = StreamEventRepository::get_by_stream(conn, stream_id)?;
+
+    let mut category_changes = Vec::new();
+    let mut title_changes = Vec::new();
+    for event in events {
+        let from = event.old_value.unwrap_or_default();
+        let to = event.new_value.unwrap_or_default();
+        match event.event_type.as_str() {
+            db_constants::STREAM_EVENT_CATEGORY => category_changes.push(CategoryChange {
+                timestamp: event.occurred_at,
+                from_category: from,
+                to_category: to,
+            }),
+            db_constants::STREAM_EVENT_TITLE => title_changes.push(TitleChange {
+                timestamp: event.occurred_at,
+                from_title: from,
+                to_title: to,
+            }),
+            _ => {}
+        }
+    }
 
     Ok(StreamTimelineData {
         stream_info,
@@ -103,47 +125,3 @@ fn get_stream_timeline_internal(
         title_changes,
     })
 }
-
-fn detect_category_changes(stats: &[TimelinePoint]) -> Vec<CategoryChange> {
-    let mut changes = Vec::new();
-    let mut prev_category: Option<String> = None;
-
-    for stat in stats {
-        if !stat.category.is_empty() {
-            if let Some(ref prev) = prev_category {
-                if prev != &stat.category {
-                    changes.push(CategoryChange {
-                        timestamp: stat.collected_at.clone(),
-                        from_category: prev.clone(),
-                        to_category: stat.category.clone(),
-                    });
-                }
-            }
-            prev_category = Some(stat.category.clone());
-        }
-    }
-
-    changes
-}
-
-fn detect_title_changes(stats: &[TimelinePoint]) -> Vec<TitleChange> {
-    let mut changes = Vec::new();
-    let mut prev_title: Option<String> = None;
-
-    for stat in stats {
-        if !stat.title.is_empty() {
-            if let Some(ref prev) = prev_title {
-                if prev != &stat.title {
-                    changes.push(TitleChange {
-                        timestamp: stat.collected_at.clone(),
-                        from_title: prev.clone(),
-                        to_title: stat.title.clone(),
-                    });
-                }
-            }
-            prev_title = Some(stat.title.clone());
-        }
-    }
-
-    changes
-}
@@ -0,0 +1,96 @@
+use crate::api::youtube_api::{parse_channel_reference, YouTubeQuotaStatus, YouTubeQuotaTracker};
+use crate::collectors::poller::ChannelPoller;
+use crate::constants::youtube;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct YouTubeChannelInfo {
+    pub channel_id: String, // UC で始まる正規のチャンネルID
+    pub display_name: String,
+    pub profile_image_url: String,
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handle: Option<String>,
+    /// 登録者数（非公開の場合は None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub follower_count: Option<i32>,
+}
+
+/// YouTube Data APIクォータの当日の使用状況を取得
+#[tauri::command]
+pub async fn get_youtube_quota_status(
+    poller: State<'_, Arc<Mutex<ChannelPoller>>>,
+) -> Result<YouTubeQuotaStatus, String> {
+    let quota_tracker = poller
+        .lock()
+        .await
+        .get_youtube_collector()
+        .map(|collector| collector.get_quota_tracker());
+
+    match quota_tracker {
+        Some(quota_tracker) => Ok(quota_tracker.lock().await.get_status()),
+        // YouTubeCollectorが初期化されていない場合は未使用の状態を返す
+        None => Ok(YouTubeQuotaTracker::new(youtube::QUOTA_DAILY_LIMIT).get_status()),
+    }
+}
+
+/// YouTubeチャンネルを検証し、正規のチャンネルIDとプロフィールを取得
+///
+/// @ハンドル、チャンネルID、/channel/・/c/・/user/ のURL、動画（watch・youtu.be・/live/）のURLを受け付けます。
+#[tauri::command]
+pub async fn validate_youtube_channel(
+    poller: State<'_, Arc<Mutex<ChannelPoller>>>,
+    channel_id: String,
+) -> Result<YouTubeChannelInfo, String> {
+    let reference = parse_channel_reference(&channel_id).ok_or_else(|| {
+        "YouTubeチャンネルのURL、@ハンドル、またはチャンネルIDを入力してください。".to_string()
+    })?;
+
+    let collector = poller
+        .lock()
+        .await
+        .get_youtube_collector()
+        .cloned()
+        .ok_or_else(|| {
+            "YouTubeのOAuth設定が行われていません。設定画面からOAuth設定を行ってください。"
+                .to_string()
+        })?;
+
+    let channel = collector
+        .resolve_channel(&reference)
+        .await
+        .map_err(|e| format!("YouTube APIエラー: {}", e))?
+        .ok_or_else(|| {
+            format!(
+                "チャンネル '{}' が見つかりません。正しいURLまたはハンドルを入力してください。",
+                channel_id.trim()
+            )
+        })?;
+
+    let resolved_id = channel
+        .id
+        .ok_or_else(|| "YouTube APIの応答にチャンネルIDが含まれていません".to_string())?;
+    let snippet = channel.snippet.unwrap_or_default();
+    let profile_image_url = snippet
+        .thumbnails
+        .and_then(|thumbs| thumbs.high.or(thumbs.medium).or(thumbs.default))
+        .and_then(|thumb| thumb.url)
+        .unwrap_or_default();
+    let follower_count = channel
+        .statistics
+        .filter(|stats| !stats.hidden_subscriber_count.unwrap_or(false))
+        .and_then(|stats| stats.subscriber_count)
+        .map(|count| count.min(i32::MAX as u64) as i32);
+
+    Ok(YouTubeChannelInfo {
+        display_name: snippet.title.unwrap_or_else(|| resolved_id.clone()),
+        channel_id: resolved_id,
+        profile_image_url,
+        description: snippet.description.unwrap_or_default(),
+        handle: snippet.custom_url,
+        follower_count,
+    })
+}
@@ -13,6 +13,12 @@ pub struct AppSettings {
     // Twitch自動発見機能設定
     #[serde(default)]
     pub auto_discovery: Option<AutoDiscoverySettings>,
+    // 配信セッション（ライブ/オフライン判定）設定
+    #[serde(default)]
+    pub stream_session: StreamSessionSettings,
+    // 記録・再生設定（設定ファイルを直接編集しないと有効化できない）
+    #[serde(default)]
+    pub replay: Option<ReplaySettings>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +30,12 @@ pub struct TwitchSettings {
 pub struct YouTubeSettings {
     pub client_id: Option<String>,
     pub client_secret: Option<String>,
+    /// 1日に使用するData APIクォータの上限（ポーリング間隔はこの範囲に収まるよう延長される）
+    #[serde(default = "default_youtube_daily_quota_budget")]
+    pub daily_quota_budget: u32,
+    /// 動画カテゴリ名（videoCategories.list）を取得する地域コード
+    #[serde(default = "default_youtube_region_code")]
+    pub region_code: String,
 }
 
 /// 将来の機能: YouTubeスクレイピング設定
@@ -55,6 +67,12 @@ pub struct AutoDiscoverySettings {
     /// フィルター設定
     #[serde(default)]
     pub filters: AutoDiscoveryFilters,
+    /// 視聴者数上位の自動発見配信のチャットも収集するか（オプトイン）
+    #[serde(default)]
+    pub chat_enabled: bool,
+    /// チャット収集のためにJOINする自動発見チャンネル数の上限
+    #[serde(default = "default_chat_join_budget")]
+    pub chat_join_budget: u32,
 }
 
 fn deserialize_min_viewers<'de, D>(deserializer: D) -> Result<u32, D::Error>
@@ -85,10 +103,62 @@ impl Default for AutoDiscoverySettings {
             poll_interval: default_poll_interval(),
             max_streams: default_max_streams(),
             filters: AutoDiscoveryFilters::default(),
+            chat_enabled: false,
+            chat_join_budget: default_chat_join_budget(),
         }
     }
 }
 
+/// 配信セッション設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamSessionSettings {
+    /// オフライン確定までの猶予時間（秒）
+    /// この時間内に配信が再開された場合は同一セッションとして扱う
+    #[serde(default = "default_offline_confirmation_seconds")]
+    pub offline_confirmation_seconds: u64,
+}
+
+impl Default for StreamSessionSettings {
+    fn default() -> Self {
+        Self {
+            offline_confirmation_seconds: default_offline_confirmation_seconds(),
+        }
+    }
+}
+
+/// 記録・再生設定
+/// 認証情報なしでの動作確認やデモのため、JSONLキャプチャファイルの記録・再生を行う隠しオプション
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplaySettings {
+    /// 再生するキャプチャファイル（指定時はライブのコレクターの代わりに使用）
+    #[serde(default)]
+    pub replay_file: Option<String>,
+    /// ライブのStreamDataとチャットを記録するファイル
+    #[serde(default)]
+    pub record_file: Option<String>,
+    /// 再生速度（1.0 = 実時間）
+    #[serde(default = "default_replay_speed")]
+    pub speed: f64,
+}
+
+impl Default for ReplaySettings {
+    fn default() -> Self {
+        Self {
+            replay_file: None,
+            record_file: None,
+            speed: default_replay_speed(),
+        }
+    }
+}
+
+fn default_replay_speed() -> f64 {
+    1.0
+}
+
+fn default_offline_confirmation_seconds() -> u64 {
+    180 // 3分
+}
+
 fn default_poll_interval() -> u32 {
     300 // 5分
 }
@@ -97,6 +167,18 @@ fn default_max_streams() -> u32 {
     20 // デフォルト20件
 }
 
+fn default_chat_join_budget() -> u32 {
+    10 // デフォルト10チャンネル
+}
+
+fn default_youtube_daily_quota_budget() -> u32 {
+    crate::constants::youtube::QUOTA_DAILY_LIMIT
+}
+
+fn default_youtube_region_code() -> String {
+    crate::constants::youtube::DEFAULT_REGION_CODE.to_string()
+}
+
 fn default_scraping_settings() -> Option<YouTubeScrapingSettings> {
     None // デフォルトでは無効
 }
@@ -110,9 +192,13 @@ impl Default for AppSettings {
             youtube: YouTubeSettings {
                 client_id: None,
                 client_secret: None,
+                daily_quota_budget: default_youtube_daily_quota_budget(),
+                region_code: default_youtube_region_code(),
             },
             youtube_scraping: None,
             auto_discovery: None,
+            stream_session: StreamSessionSettings::default(),
+            replay: None,
         }
     }
 }
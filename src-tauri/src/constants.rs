@@ -10,6 +10,15 @@ pub mod twitch {
     /// 取得する最大ストリーム総数
     pub const MAX_TOTAL_STREAMS: usize = 500;
 
+    /// バッチポーリングスケジューラーの確認間隔（秒）
+    pub const SCHEDULER_TICK_SECS: u64 = 5;
+
+    /// ポーリングを減速するレート制限使用率の閾値（%）
+    pub const RATE_LIMIT_SLOWDOWN_THRESHOLD_PERCENT: f32 = 80.0;
+
+    /// レート制限使用率が閾値を超えた場合のポーリング間隔の倍率
+    pub const RATE_LIMIT_SLOWDOWN_FACTOR: u32 = 2;
+
     /// レート制限バケットの容量（リクエスト数/分）
     pub const RATE_LIMIT_BUCKET_CAPACITY: usize = 800;
 
@@ -21,6 +30,39 @@ pub mod twitch {
 
     /// Unauthorizedエラーテキスト
     pub const ERROR_UNAUTHORIZED_TEXT: &str = "Unauthorized";
+
+    /// 配信スケジュールの取得間隔（秒）
+    pub const SCHEDULE_SYNC_INTERVAL_SECS: u64 = 3600;
+
+    /// 1サイクルで配信スケジュールを取得するチャンネル数の上限（ポーリングを遅らせないため）
+    pub const SCHEDULE_SYNC_MAX_PER_CYCLE: usize = 3;
+
+    /// メッセージタイプ: 通常のチャット
+    pub const MESSAGE_TYPE_NORMAL: &str = "normal";
+
+    /// メッセージタイプ: ビッツ付きチャット（Cheer）
+    pub const MESSAGE_TYPE_CHEER: &str = "cheer";
+
+    /// メッセージタイプ: 新規サブスク
+    pub const MESSAGE_TYPE_SUB: &str = "sub";
+
+    /// メッセージタイプ: サブスク継続
+    pub const MESSAGE_TYPE_RESUB: &str = "resub";
+
+    /// メッセージタイプ: 個別ギフトサブ
+    pub const MESSAGE_TYPE_SUBGIFT: &str = "subgift";
+
+    /// メッセージタイプ: コミュニティ向けまとめてギフト
+    pub const MESSAGE_TYPE_SUBMYSTERYGIFT: &str = "submysterygift";
+
+    /// メッセージタイプ: レイド
+    pub const MESSAGE_TYPE_RAID: &str = "raid";
+
+    /// メッセージタイプ: アナウンス
+    pub const MESSAGE_TYPE_ANNOUNCEMENT: &str = "announcement";
+
+    /// メッセージタイプ: 上記以外のUSERNOTICE（ギフト継続、リチュアル等）
+    pub const MESSAGE_TYPE_USERNOTICE: &str = "usernotice";
 }
 
 pub mod youtube {
@@ -46,14 +88,20 @@ pub mod youtube {
     /// APIレスポンス部分: 著者詳細
     pub const PART_AUTHOR_DETAILS: &str = "authorDetails";
 
-    /// イベントタイプ: ライブ
-    pub const EVENT_TYPE_LIVE: &str = "live";
+    /// APIレスポンス部分: 統計情報
+    pub const PART_STATISTICS: &str = "statistics";
+
+    /// チャンネルの新着動画フィード（RSS/Atom、クォータを消費しない）
+    pub const CHANNEL_FEED_URL: &str = "https://www.youtube.com/feeds/videos.xml";
 
-    /// タイプ: ビデオ
-    pub const TYPE_VIDEO: &str = "video";
+    /// フィード取得のタイムアウト（秒）
+    pub const FEED_REQUEST_TIMEOUT_SECS: u64 = 15;
 
-    /// デフォルトの最大結果数
-    pub const MAX_RESULTS_DEFAULT: u32 = 1;
+    /// ライブ判定の対象にする新着動画数（アップロード再生リストから取得する場合）
+    pub const RECENT_VIDEO_LIMIT: u32 = 15;
+
+    /// videos.list 1回で指定できる動画IDの上限
+    pub const VIDEOS_LIST_MAX_IDS: usize = 50;
 
     /// メッセージタイプ: スーパーチャット
     pub const MESSAGE_TYPE_SUPERCHAT: &str = "superchat";
@@ -64,11 +112,126 @@ pub mod youtube {
     /// メッセージタイプ: スポンサー
     pub const MESSAGE_TYPE_SPONSOR: &str = "sponsor";
 
+    /// メッセージタイプ: スーパーステッカー
+    pub const MESSAGE_TYPE_SUPERSTICKER: &str = "supersticker";
+
+    /// メッセージタイプ: メンバーシップのマイルストーン
+    pub const MESSAGE_TYPE_MEMBER_MILESTONE: &str = "membermilestone";
+
+    /// メッセージタイプ: メンバーシップギフト（贈った側）
+    pub const MESSAGE_TYPE_MEMBERSHIP_GIFTING: &str = "membershipgifting";
+
+    /// メッセージタイプ: メンバーシップギフト（受け取った側）
+    pub const MESSAGE_TYPE_GIFT_MEMBERSHIP_RECEIVED: &str = "giftmembershipreceived";
+
     /// メッセージタイプ: 通常
     pub const MESSAGE_TYPE_NORMAL: &str = "normal";
 
     /// プラットフォーム名
     pub const PLATFORM_NAME: &str = "youtube";
+
+    /// APIレスポンス部分: ライブ配信詳細
+    pub const PART_LIVE_STREAMING_DETAILS: &str = "liveStreamingDetails";
+
+    /// snippet.liveBroadcastContent: 配信予定（予約済みのライブ・プレミア公開）
+    pub const LIVE_BROADCAST_CONTENT_UPCOMING: &str = "upcoming";
+
+    /// ライブチャット取得間隔（pollingIntervalMillis が返らなかった場合）
+    pub const LIVE_CHAT_DEFAULT_POLL_INTERVAL_MS: u64 = 5000;
+
+    /// ライブチャット取得間隔の下限
+    pub const LIVE_CHAT_MIN_POLL_INTERVAL_MS: u64 = 1000;
+
+    /// ライブチャット取得に失敗した場合の再試行間隔（秒）
+    pub const LIVE_CHAT_RETRY_DELAY_SECS: u64 = 30;
+
+    /// ライブチャット収集中にクォータ使用量をDBへ保存する間隔（秒）
+    pub const LIVE_CHAT_QUOTA_SAVE_INTERVAL_SECS: u64 = 60;
+
+    /// 再試行しても回復しないライブチャット取得エラーの理由（収集を終了する）
+    pub const LIVE_CHAT_TERMINAL_REASONS: &[&str] = &[
+        "liveChatEnded",
+        "liveChatDisabled",
+        "liveChatNotFound",
+        "forbidden",
+    ];
+
+    /// Data API の1日あたりのクォータ（太平洋時間の0時にリセット）
+    pub const QUOTA_DAILY_LIMIT: u32 = 10_000;
+
+    /// クォータ消費量: search.list
+    pub const QUOTA_COST_SEARCH_LIST: u32 = 100;
+
+    /// クォータ消費量: videos.list
+    pub const QUOTA_COST_VIDEOS_LIST: u32 = 1;
+
+    /// クォータ消費量: channels.list
+    pub const QUOTA_COST_CHANNELS_LIST: u32 = 1;
+
+    /// クォータ消費量: playlistItems.list
+    pub const QUOTA_COST_PLAYLIST_ITEMS_LIST: u32 = 1;
+
+    /// クォータ消費量: liveChatMessages.list
+    pub const QUOTA_COST_LIVE_CHAT_MESSAGES_LIST: u32 = 5;
+
+    /// クォータ消費量: videoCategories.list
+    pub const QUOTA_COST_VIDEO_CATEGORIES_LIST: u32 = 1;
+
+    /// 動画カテゴリ名を取得する地域（ISO 3166-1 alpha-2）
+    pub const DEFAULT_REGION_CODE: &str = "JP";
+
+    /// 動画カテゴリ一覧のキャッシュ有効期間（秒）
+    pub const VIDEO_CATEGORY_CACHE_TTL_SECS: u64 = 24 * 60 * 60;
+}
+
+pub mod kick {
+    /// Kick APIのベースURL
+    pub const API_BASE_URL: &str = "https://kick.com/api/v2";
+
+    /// Kickチャット（Pusher）のWebSocket URL
+    pub const PUSHER_WS_URL: &str =
+        "wss://ws-us2.pusher.com/app/32cbd69e4b950bf97679?protocol=7&client=js&version=8.4.0-rc2&flash=false";
+
+    /// チャットメッセージのPusherイベント名
+    pub const CHAT_MESSAGE_EVENT: &str = "App\\Events\\ChatMessageEvent";
+
+    /// WebSocket切断時の再接続待ち時間（秒）
+    pub const RECONNECT_DELAY_SECS: u64 = 5;
+
+    /// 接続ごとの受信キューの容量（満杯の間はWebSocketの読み込みを待たせる）
+    pub const CHAT_RECEIVE_QUEUE_CAPACITY: usize = 1000;
+
+    /// HTTPリクエストのタイムアウト（秒）
+    pub const REQUEST_TIMEOUT_SECS: u64 = 15;
+}
+
+pub mod polling {
+    /// 配信開始時間帯の学習に使用する期間（日）
+    pub const START_WINDOW_LOOKBACK_DAYS: i32 = 56;
+
+    /// 開始時間帯とみなす最小配信回数（同じ曜日・時刻）
+    pub const START_WINDOW_MIN_OCCURRENCES: usize = 2;
+
+    /// 学習した開始時間帯の再計算間隔（秒）
+    pub const START_WINDOW_REFRESH_SECS: u64 = 3600;
+
+    /// ポーリング間隔の下限（秒）
+    pub const MIN_POLL_INTERVAL_SECS: u64 = 10;
+
+    /// 失敗時のバックオフ初期値（秒）
+    pub const BACKOFF_BASE_SECS: u64 = 30;
+
+    /// 失敗時のバックオフ上限（秒）
+    pub const BACKOFF_MAX_SECS: u64 = 900;
+
+    /// サーキットブレーカーを開く連続失敗回数
+    pub const CIRCUIT_BREAKER_FAILURE_THRESHOLD: u32 = 5;
+
+    /// サーキットブレーカーを開いている時間（秒）
+    pub const CIRCUIT_BREAKER_OPEN_SECS: u64 = 1800;
+
+    /// collector-status-updated イベントの発行間隔（秒）
+    pub const STATUS_EVENT_INTERVAL_SECS: u64 = 5;
 }
 
 #[allow(dead_code)]
@@ -79,9 +242,93 @@ pub mod database {
     /// バッチフラッシュ間隔（秒）
     pub const BATCH_FLUSH_INTERVAL_SECS: u64 = 5;
 
+    /// Twitch・Kickのチャット取り込みキューの容量（超えた分は破棄。Twitchはスプールから再投入）
+    pub const CHAT_INGEST_QUEUE_CAPACITY: usize = 10_000;
+
+    /// DB書き込みの失敗時に保持する保存待ちチャットの上限（超えた分は破棄）
+    pub const CHAT_PENDING_BATCH_CAPACITY: usize = 10_000;
+
+    /// 発見されなくなった自動発見チャンネルのチャットを削除するまでの猶予（分）
+    pub const DISCOVERED_CHAT_PURGE_GRACE_MINUTES: i64 = 60;
+
     /// Twitchプラットフォーム名
     pub const PLATFORM_TWITCH: &str = "twitch";
 
     /// YouTubeプラットフォーム名
     pub const PLATFORM_YOUTUBE: &str = "youtube";
+
+    /// Kickプラットフォーム名
+    pub const PLATFORM_KICK: &str = "kick";
+
+    /// 監視対象として登録できるプラットフォーム
+    pub const SUPPORTED_PLATFORMS: &[&str] = &[PLATFORM_TWITCH, PLATFORM_YOUTUBE, PLATFORM_KICK];
+
+    /// stream_events.event_type: タイトル変更
+    pub const STREAM_EVENT_TITLE: &str = "title";
+
+    /// stream_events.event_type: カテゴリ（game_id）変更
+    pub const STREAM_EVENT_CATEGORY: &str = "category";
+
+    /// stream_events.event_type: タグ変更
+    pub const STREAM_EVENT_TAGS: &str = "tags";
+
+    /// stream_events.event_type: 配信言語変更
+    pub const STREAM_EVENT_LANGUAGE: &str = "language";
+
+    /// emote_catalog.source: Twitch公式エモート
+    pub const EMOTE_SOURCE_TWITCH: &str = "twitch";
+
+    /// emote_catalog.source: 7TV
+    pub const EMOTE_SOURCE_7TV: &str = "7tv";
+
+    /// emote_catalog.source: BetterTTV
+    pub const EMOTE_SOURCE_BTTV: &str = "bttv";
+
+    /// emote_catalog.source: FrankerFaceZ
+    pub const EMOTE_SOURCE_FFZ: &str = "ffz";
+
+    /// moderation_events.event_type: メッセージ削除（CLEARMSG）
+    pub const MODERATION_DELETE: &str = "delete";
+
+    /// moderation_events.event_type: タイムアウト
+    pub const MODERATION_TIMEOUT: &str = "timeout";
+
+    /// moderation_events.event_type: 永久BAN
+    pub const MODERATION_BAN: &str = "ban";
+
+    /// moderation_events.event_type: チャット全消去
+    pub const MODERATION_CLEAR: &str = "clear";
+
+    /// 配信外のタイムアウト・BANで削除済みにするチャットの遡り範囲（分）
+    pub const MODERATION_OFFLINE_WINDOW_MINUTES: i64 = 60;
+
+    /// room_state_periods.mode: スローモード（setting は間隔秒数）
+    pub const ROOM_MODE_SLOW: &str = "slow";
+
+    /// room_state_periods.mode: エモート限定モード
+    pub const ROOM_MODE_EMOTE_ONLY: &str = "emote_only";
+
+    /// room_state_periods.mode: フォロワー限定モード（setting はフォロー経過分数）
+    pub const ROOM_MODE_FOLLOWERS_ONLY: &str = "followers_only";
+
+    /// room_state_periods.mode: サブスクライバー限定モード
+    pub const ROOM_MODE_SUBS_ONLY: &str = "subs_only";
+
+    /// room_state_periods.mode: ユニークチャット（r9k）モード
+    pub const ROOM_MODE_UNIQUE_CHAT: &str = "unique_chat";
+
+    /// scheduled_streams.status: 予定どおり（未開始）
+    pub const SCHEDULE_STATUS_SCHEDULED: &str = "scheduled";
+
+    /// scheduled_streams.status: 配信が開始され streams と対応付け済み
+    pub const SCHEDULE_STATUS_STARTED: &str = "started";
+
+    /// scheduled_streams.status: 取り消し（Twitch の canceled_until、予定の削除）
+    pub const SCHEDULE_STATUS_CANCELED: &str = "canceled";
+
+    /// 配信開始を予定と対応付ける許容幅（分）。予定時刻を過ぎてこの時間内に開始されなければ不履行とみなす
+    pub const SCHEDULE_MATCH_WINDOW_MINUTES: i64 = 120;
+
+    /// 定刻開始とみなす遅れ（分）
+    pub const SCHEDULE_ON_TIME_THRESHOLD_MINUTES: i64 = 5;
 }
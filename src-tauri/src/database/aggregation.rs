@@ -337,6 +337,14 @@ mod tests {
                 message_type: "normal".to_string(),
                 badges: Some(vec!["broadcaster".to_string()]),
                 badge_info: None,
+                payload: None,
+                platform_message_id: None,
+                emotes: None,
+                bits: None,
+                reply_parent_id: None,
+                reply_parent_user_name: None,
+                reply_thread_id: None,
+                is_auto_discovered: false,
             },
             ChatMessage {
                 id: Some(2),
@@ -351,6 +359,14 @@ mod tests {
                 message_type: "normal".to_string(),
                 badges: None,
                 badge_info: None,
+                payload: None,
+                platform_message_id: None,
+                emotes: None,
+                bits: None,
+                reply_parent_id: None,
+                reply_parent_user_name: None,
+                reply_thread_id: None,
+                is_auto_discovered: false,
             },
         ];
 
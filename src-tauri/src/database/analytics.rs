@@ -1,3 +1,4 @@
+use crate::constants::database as db_constants;
 use crate::database::{
     repositories::{AggregationRepository, StreamStatsRepository},
     utils,
@@ -29,7 +30,7 @@ pub struct BroadcasterAnalytics {
 /// ゲームタイトル別統計
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameAnalytics {
-    pub game_id: String,  // Twitch game ID（プライマリキー）
+    pub game_id: String, // Twitch game ID / YouTube categoryId（platform と合わせて一意）
     pub category: String, // カテゴリ名（表示用、game_categoriesから取得）
     pub minutes_watched: i64,
     pub hours_broadcasted: f64,
@@ -40,6 +41,7 @@ pub struct GameAnalytics {
     pub total_chat_messages: i64,
     pub avg_chat_rate: f64,
     pub engagement_rate: f64,
+    pub platform: String,
 }
 
 /// データ可用性情報
@@ -452,6 +454,7 @@ fn get_game_analytics_old(
             total_chat_messages: row.get::<_, i64>(6)?,
             avg_chat_rate: row.get::<_, f64>(7)?,
             engagement_rate: row.get::<_, f64>(8)?,
+            platform: db_constants::PLATFORM_TWITCH.to_string(),
         })
     })?
     .collect::<Result<Vec<_>, _>>()?;
@@ -1,4 +1,18 @@
-use crate::database::repositories::ChatMessageRepository;
+use crate::database::data_science_analytics::calculate_pearson_correlation;
+use crate::database::models::RoomStatePeriod;
+use crate::database::repositories::base::{ChannelFilter, TimeRangeFilter};
+use crate::database::repositories::moderation_event_repository::{
+    HourlyModerationStats, StreamModerationStats,
+};
+use crate::database::repositories::monetization_repository::{
+    ChannelRevenue, ChatterMonetizationStats, CurrencyRevenue, HourlyMonetizationStats,
+    StreamMonetizationStats, StreamRevenue,
+};
+use crate::database::repositories::{
+    ChatMessageRepository, CurrencyRateRepository, ModerationEventRepository,
+    MonetizationRepository, RoomStateRepository,
+};
+use chrono::{Duration, NaiveDateTime};
 use duckdb::Connection;
 use serde::{Deserialize, Serialize};
 
@@ -11,6 +25,8 @@ pub struct ChatEngagementStats {
     pub unique_chatters: i64,
     pub viewer_count: i32,
     pub engagement_rate: f64,
+    /// このバケット中に有効だったチャット制限モード（"slow", "emote_only" など）
+    pub restricted_modes: Vec<String>,
 }
 
 /// チャットスパイク情報
@@ -21,6 +37,12 @@ pub struct ChatSpike {
     pub chat_count: i64,
     pub spike_ratio: f64,
     pub prev_count: i64,
+    /// スパイクしたバケット中に有効だったチャット制限モード
+    pub restricted_modes: Vec<String>,
+    /// 比較元（直前）のバケット中に有効だったチャット制限モード
+    ///
+    /// 直前だけ制限されていた場合、スパイクは制限解除による反動の可能性があります。
+    pub prev_restricted_modes: Vec<String>,
 }
 
 /// ユーザーセグメント統計
@@ -68,6 +90,81 @@ pub struct ChatterBehaviorStats {
     pub avg_participation_rate: f64,
 }
 
+/// モデレーション統計（配信別・時間帯別）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModerationStats {
+    pub total_timeouts: i64,
+    pub total_bans: i64,
+    pub total_deleted_messages: i64,
+    pub by_stream: Vec<StreamModerationStats>,
+    pub by_hour: Vec<HourlyModerationStats>,
+}
+
+/// 収益統計（ビッツ・サブスク・ギフト）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonetizationStats {
+    pub total_bits: i64,
+    pub total_cheers: i64,
+    pub total_subs: i64,
+    pub total_gifted_subs: i64,
+    pub by_stream: Vec<StreamMonetizationStats>,
+    pub by_hour: Vec<HourlyMonetizationStats>,
+    pub top_cheerers: Vec<ChatterMonetizationStats>,
+    /// 配信ごとのビッツ合計と最大視聴者数のピアソン相関係数
+    pub stream_peak_correlation: f64,
+    /// 1時間ごとのビッツ合計とその時間帯の最大視聴者数のピアソン相関係数
+    pub hourly_peak_correlation: f64,
+}
+
+/// Super Chat 等の売上統計（基準通貨に換算）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuperChatRevenue {
+    /// 換算先の基準通貨（レート未登録の場合は None）
+    pub base_currency: Option<String>,
+    pub total_normalized: f64,
+    /// 換算レートが未登録の通貨
+    pub unconverted_currencies: Vec<String>,
+    pub by_currency: Vec<CurrencyRevenue>,
+    pub by_stream: Vec<StreamRevenue>,
+    pub by_channel: Vec<ChannelRevenue>,
+}
+
+/// バケット [bucket, bucket + interval) と重なるチャット制限モードを取得
+fn restricted_modes_in_bucket(
+    periods: &[RoomStatePeriod],
+    bucket: &str,
+    interval_minutes: i32,
+) -> Vec<String> {
+    let parse = |value: &str| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f").ok();
+    let Some(bucket_start) = parse(bucket) else {
+        return Vec::new();
+    };
+    let bucket_end = bucket_start + Duration::minutes(interval_minutes as i64);
+
+    let mut modes: Vec<String> = periods
+        .iter()
+        .filter(|period| {
+            let starts_before_end = parse(&period.started_at)
+                .map(|started| started < bucket_end)
+                .unwrap_or(false);
+            let ends_after_start = period
+                .ended_at
+                .as_deref()
+                .and_then(parse)
+                .map(|ended| ended > bucket_start)
+                .unwrap_or(true);
+            starts_before_end && ends_after_start
+        })
+        .map(|period| period.mode.clone())
+        .collect();
+    modes.sort();
+    modes.dedup();
+    modes
+}
+
 /// エンゲージメント統計を時系列で取得（5分間隔）
 ///
 /// ChatMessageRepositoryとStreamStatsRepositoryを使用します。
@@ -89,6 +186,7 @@ pub fn get_chat_engagement_timeline(
         stream_id,
         start_time,
         end_time,
+        false,
     )?;
 
     // channel_idからchannel_nameを取得（エラーハンドリングを改善）
@@ -126,6 +224,10 @@ pub fn get_chat_engagement_timeline(
         end_time,
     )?;
 
+    // チャット制限モード（エモート限定・スローモード等）の期間を取得
+    let room_state_periods =
+        RoomStateRepository::get_periods(conn, channel_id, stream_id, start_time, end_time)?;
+
     // バケット単位でマージ
     let viewer_map: std::collections::HashMap<String, f64> = viewer_buckets
         .into_iter()
@@ -143,6 +245,11 @@ pub fn get_chat_engagement_timeline(
             };
 
             ChatEngagementStats {
+                restricted_modes: restricted_modes_in_bucket(
+                    &room_state_periods,
+                    &chat.bucket,
+                    interval_minutes,
+                ),
                 timestamp: chat.bucket,
                 chat_count: chat.chat_count,
                 unique_chatters: chat.unique_chatters,
@@ -158,6 +265,7 @@ pub fn get_chat_engagement_timeline(
 /// チャットスパイク（急増ポイント）を検出
 ///
 /// ChatMessageRepositoryを使用してスパイクを検出します。
+/// `exclude_deleted` が true の場合、削除されたメッセージ（荒らし等）を除外して判定します。
 pub fn detect_chat_spikes(
     conn: &Connection,
     channel_id: Option<i64>,
@@ -165,15 +273,26 @@ pub fn detect_chat_spikes(
     start_time: Option<&str>,
     end_time: Option<&str>,
     min_spike_ratio: f64,
+    exclude_deleted: bool,
 ) -> Result<Vec<ChatSpike>, duckdb::Error> {
     // 5分間隔でバケット取得
     let buckets = ChatMessageRepository::count_by_time_bucket(
-        conn, 5, channel_id, stream_id, start_time, end_time,
+        conn,
+        5,
+        channel_id,
+        stream_id,
+        start_time,
+        end_time,
+        exclude_deleted,
     )?;
 
+    let room_state_periods =
+        RoomStateRepository::get_periods(conn, channel_id, stream_id, start_time, end_time)?;
+
     // 前のバケットとの比較でスパイクを検出
     let mut spikes = Vec::new();
     let mut prev_count = 0i64;
+    let mut prev_bucket: Option<String> = None;
 
     for bucket in buckets {
         if prev_count > 0 {
@@ -184,10 +303,20 @@ pub fn detect_chat_spikes(
                     chat_count: bucket.chat_count,
                     spike_ratio,
                     prev_count,
+                    restricted_modes: restricted_modes_in_bucket(
+                        &room_state_periods,
+                        &bucket.bucket,
+                        5,
+                    ),
+                    prev_restricted_modes: prev_bucket
+                        .as_deref()
+                        .map(|prev| restricted_modes_in_bucket(&room_state_periods, prev, 5))
+                        .unwrap_or_default(),
                 });
             }
         }
         prev_count = bucket.chat_count;
+        prev_bucket = Some(bucket.bucket);
     }
 
     // spike_ratio降順でソート、上位20件
@@ -253,9 +382,16 @@ pub fn get_top_chatters(
     start_time: Option<&str>,
     end_time: Option<&str>,
     limit: i32,
+    exclude_deleted: bool,
 ) -> Result<Vec<TopChatter>, duckdb::Error> {
     let chatters = ChatMessageRepository::get_top_chatters(
-        conn, channel_id, stream_id, start_time, end_time, limit,
+        conn,
+        channel_id,
+        stream_id,
+        start_time,
+        end_time,
+        limit,
+        exclude_deleted,
     )?;
 
     // ChatterWithBadgesからTopChatterに変換
@@ -326,3 +462,88 @@ pub fn get_chatter_behavior_stats(
         avg_participation_rate: avg_participation,
     })
 }
+
+/// タイムアウト・BAN・メッセージ削除を配信別と1時間ごとに集計
+pub fn get_moderation_stats(
+    conn: &Connection,
+    channel_id: Option<i64>,
+    stream_id: Option<i64>,
+    start_time: Option<&str>,
+    end_time: Option<&str>,
+) -> Result<ModerationStats, duckdb::Error> {
+    let by_hour = ModerationEventRepository::count_by_hour(
+        conn, channel_id, stream_id, start_time, end_time,
+    )?;
+    let by_stream = ModerationEventRepository::count_by_stream(
+        conn, channel_id, stream_id, start_time, end_time,
+    )?;
+
+    // 配信に紐付かないイベントも含めるため、合計は時間帯別から算出
+    Ok(ModerationStats {
+        total_timeouts: by_hour.iter().map(|h| h.timeouts).sum(),
+        total_bans: by_hour.iter().map(|h| h.bans).sum(),
+        total_deleted_messages: by_hour.iter().map(|h| h.deleted_messages).sum(),
+        by_stream,
+        by_hour,
+    })
+}
+
+/// ビッツ・サブスク・ギフトを配信別・時間帯別・チャッター別に集計し、視聴者ピークとの相関を算出
+pub fn get_monetization_stats(
+    conn: &Connection,
+    channel: &ChannelFilter,
+    time_range: &TimeRangeFilter,
+    limit: i32,
+) -> Result<MonetizationStats, duckdb::Error> {
+    let by_stream = MonetizationRepository::stats_by_stream(conn, channel, time_range)?;
+    let by_hour = MonetizationRepository::stats_by_hour(conn, channel, time_range)?;
+    let top_cheerers = MonetizationRepository::top_cheerers(conn, channel, time_range, limit)?;
+
+    let (stream_viewers, stream_bits): (Vec<f64>, Vec<f64>) = by_stream
+        .iter()
+        .filter_map(|s| s.peak_viewers.map(|v| (v as f64, s.total_bits as f64)))
+        .unzip();
+    let (hourly_viewers, hourly_bits): (Vec<f64>, Vec<f64>) = by_hour
+        .iter()
+        .filter_map(|h| h.peak_viewers.map(|v| (v as f64, h.total_bits as f64)))
+        .unzip();
+
+    // stats_by_stream は streams と内部結合し配信外のビッツ・サブスクを含まないため、合計は by_hour から算出
+    Ok(MonetizationStats {
+        total_bits: by_hour.iter().map(|h| h.total_bits).sum(),
+        total_cheers: by_hour.iter().map(|h| h.cheer_count).sum(),
+        total_subs: by_hour.iter().map(|h| h.subs).sum(),
+        total_gifted_subs: by_hour.iter().map(|h| h.gifted_subs).sum(),
+        stream_peak_correlation: calculate_pearson_correlation(&stream_viewers, &stream_bits),
+        hourly_peak_correlation: calculate_pearson_correlation(&hourly_viewers, &hourly_bits),
+        by_stream,
+        by_hour,
+        top_cheerers,
+    })
+}
+
+/// Super Chat・Super Sticker の金額を基準通貨に換算し、通貨別・配信別・チャンネル別に集計
+pub fn get_super_chat_revenue(
+    conn: &Connection,
+    channel: &ChannelFilter,
+    time_range: &TimeRangeFilter,
+) -> Result<SuperChatRevenue, duckdb::Error> {
+    let by_currency = MonetizationRepository::revenue_by_currency(conn, channel, time_range)?;
+    let by_stream = MonetizationRepository::revenue_by_stream(conn, channel, time_range)?;
+    let by_channel = MonetizationRepository::revenue_by_channel(conn, channel, time_range)?;
+
+    let unconverted_currencies = by_currency
+        .iter()
+        .filter(|c| c.rate.is_none())
+        .filter_map(|c| c.currency.clone())
+        .collect();
+
+    Ok(SuperChatRevenue {
+        base_currency: CurrencyRateRepository::base_currency(conn)?,
+        total_normalized: by_currency.iter().filter_map(|c| c.normalized_amount).sum(),
+        unconverted_currencies,
+        by_currency,
+        by_stream,
+        by_channel,
+    })
+}
@@ -0,0 +1,432 @@
+/// チャットの先行書き込みスプール
+///
+/// 受信したチャットをDuckDBへ保存する前にアプリデータディレクトリへ追記し、
+/// 含まれるレコードがすべてコミットされたセグメントから削除します。DB障害中やクラッシュ時も
+/// メッセージを失わず、復旧後または次回起動時にスプールから再投入します。
+/// 追記はバッファし、バッチ保存のたびにまとめてディスクへ同期します
+/// （クラッシュ時に失われるのは最後の同期以降に受信した分だけです）。
+///
+/// 各レコードには追記順のシーケンス番号が振られ、コミットはシーケンス番号単位で記録します。
+/// 同じディレクトリを複数のインスタンスで開くと番号が衝突するため、プロセスで1つだけ開いて共有します。
+///
+/// 形式: セグメントファイル（`<先頭レコードのシーケンス番号>.spool`）に
+/// `[u32 LE 長さ][ChatMessage の JSON]` を連ねたもの
+use crate::database::models::ChatMessage;
+use std::collections::{BTreeSet, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+/// セグメントを切り替えるサイズ
+const SEGMENT_MAX_BYTES: u64 = 8 * 1024 * 1024;
+const SEGMENT_EXTENSION: &str = "spool";
+/// レコード先頭の長さプレフィックスのバイト数
+const LENGTH_PREFIX_BYTES: usize = 4;
+/// 最初のレコードのシーケンス番号
+const FIRST_SEQ: u64 = 1;
+
+struct SpoolState {
+    writer: BufWriter<File>,
+    /// 書き込み中のセグメントの先頭シーケンス番号
+    segment_base: u64,
+    segment_bytes: u64,
+    /// 次に追記するレコードのシーケンス番号
+    next_seq: u64,
+    /// 未コミットのレコードのシーケンス番号
+    pending: BTreeSet<u64>,
+    /// DBへの書き込みに失敗し、メモリ上のバッチではなくスプールから再投入が必要な状態
+    needs_replay: bool,
+}
+
+impl SpoolState {
+    /// 次のシーケンス番号から始まる新しいセグメントに切り替える
+    fn roll_segment(&mut self, dir: &Path) -> io::Result<()> {
+        let (writer, segment_bytes) = open_segment(dir, self.next_seq)?;
+        self.writer.flush()?;
+        self.writer = writer;
+        self.segment_base = self.next_seq;
+        self.segment_bytes = segment_bytes;
+        Ok(())
+    }
+}
+
+/// スプールから読み込んだ未コミットのレコード
+pub struct PendingChunk {
+    pub seqs: Vec<u64>,
+    pub messages: Vec<ChatMessage>,
+    /// 読み込んだ範囲の最後のシーケンス番号（次回はこれより後から読む）
+    pub last_seq: u64,
+}
+
+pub struct ChatSpool {
+    dir: PathBuf,
+    state: Mutex<SpoolState>,
+}
+
+impl ChatSpool {
+    /// スプールを開く
+    ///
+    /// 前回終了時に残っていたレコードはすべて未コミットとして再投入待ちの状態で開きます。
+    /// 書き込み途中で終了したセグメント末尾の不完全なレコードは切り捨てます。
+    pub fn open(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        let segments = list_segments(&dir)?;
+        let mut pending = BTreeSet::new();
+        let mut next_seq = FIRST_SEQ;
+        for (base, path) in &segments {
+            let count = repair_segment(path)?;
+            pending.extend(*base..*base + count);
+            next_seq = next_seq.max(*base + count);
+        }
+
+        let segment_base = segments.last().map(|(base, _)| *base).unwrap_or(FIRST_SEQ);
+        let (writer, segment_bytes) = open_segment(&dir, segment_base)?;
+        let needs_replay = !pending.is_empty();
+
+        Ok(Self {
+            dir,
+            state: Mutex::new(SpoolState {
+                writer,
+                segment_base,
+                segment_bytes,
+                next_seq,
+                pending,
+                needs_replay,
+            }),
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SpoolState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// スプールに残っている未コミットのメッセージ数
+    pub fn depth(&self) -> u64 {
+        self.lock().pending.len() as u64
+    }
+
+    /// 次に追記されるレコードのシーケンス番号
+    pub fn next_seq(&self) -> u64 {
+        self.lock().next_seq
+    }
+
+    /// スプールからの再投入が必要かどうか
+    pub fn needs_replay(&self) -> bool {
+        self.lock().needs_replay
+    }
+
+    /// DBへの書き込み失敗を記録（以降はスプールから再投入する）
+    pub fn mark_needs_replay(&self) {
+        self.lock().needs_replay = true;
+    }
+
+    /// 再投入が必要な状態を取り出してクリア（再投入に失敗した場合は再度 `mark_needs_replay` を呼ぶ）
+    pub fn take_needs_replay(&self) -> bool {
+        std::mem::take(&mut self.lock().needs_replay)
+    }
+
+    /// メッセージをバッファに追記し、振られたシーケンス番号を返す
+    ///
+    /// 受信処理をブロックしないよう追記のたびにはフラッシュせず、`sync` でまとめて書き出します。
+    pub fn append(&self, message: &ChatMessage) -> io::Result<u64> {
+        let payload = serde_json::to_vec(message)?;
+        let mut state = self.lock();
+
+        if state.segment_bytes >= SEGMENT_MAX_BYTES {
+            state.roll_segment(&self.dir)?;
+        }
+
+        state
+            .writer
+            .write_all(&(payload.len() as u32).to_le_bytes())?;
+        state.writer.write_all(&payload)?;
+        state.segment_bytes += (LENGTH_PREFIX_BYTES + payload.len()) as u64;
+
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.pending.insert(seq);
+        Ok(seq)
+    }
+
+    /// 追記済みの内容をディスクへ同期（DBへの書き込み前に呼ぶ）
+    pub fn sync(&self) -> io::Result<()> {
+        let mut state = self.lock();
+        state.writer.flush()?;
+        state.writer.get_ref().sync_data()
+    }
+
+    /// DBへの保存が完了したレコードをコミット済みとして記録
+    pub fn commit(&self, seqs: &[u64]) {
+        let mut state = self.lock();
+        for seq in seqs {
+            state.pending.remove(seq);
+        }
+    }
+
+    /// `after_seq` より後の未コミットのレコードを最大 `limit` 件読み込む
+    ///
+    /// 未コミットのレコードが残っていなければ `None` を返します。
+    /// 読めないレコードは再投入できないため、ログに残してコミット済みとして扱います。
+    pub fn read_pending(&self, after_seq: u64, limit: usize) -> io::Result<Option<PendingChunk>> {
+        let (wanted, segments) = {
+            let mut state = self.lock();
+            state.writer.flush()?;
+            let wanted: Vec<u64> = state
+                .pending
+                .range(after_seq + 1..)
+                .take(limit.max(1))
+                .copied()
+                .collect();
+            if wanted.is_empty() {
+                return Ok(None);
+            }
+            (wanted, list_segments(&self.dir)?)
+        };
+
+        // 対象のレコードは書き込み済みのため、ファイルの読み込みはロックの外で行う
+        let first = wanted[0];
+        let last_seq = wanted[wanted.len() - 1];
+        let wanted_set: HashSet<u64> = wanted.iter().copied().collect();
+        let mut seqs = Vec::with_capacity(wanted.len());
+        let mut messages = Vec::with_capacity(wanted.len());
+        let mut unreadable = Vec::new();
+
+        for (index, (base, path)) in segments.iter().enumerate() {
+            if *base > last_seq {
+                break;
+            }
+            if segments
+                .get(index + 1)
+                .is_some_and(|(next_base, _)| *next_base <= first)
+            {
+                continue;
+            }
+
+            let file = match File::open(path) {
+                Ok(file) => file,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+            let mut reader = BufReader::new(file);
+            let mut seq = *base;
+            while seq <= last_seq {
+                let Some(record) = read_record(&mut reader)? else {
+                    break;
+                };
+                if wanted_set.contains(&seq) {
+                    match serde_json::from_slice::<ChatMessage>(&record) {
+                        Ok(message) => {
+                            seqs.push(seq);
+                            messages.push(message);
+                        }
+                        Err(e) => {
+                            eprintln!(
+                                "[ChatSpool] Skipping unreadable record {} in {}: {}",
+                                seq,
+                                path.display(),
+                                e
+                            );
+                            unreadable.push(seq);
+                        }
+                    }
+                }
+                seq += 1;
+            }
+        }
+
+        self.commit(&unreadable);
+        Ok(Some(PendingChunk {
+            seqs,
+            messages,
+            last_seq,
+        }))
+    }
+
+    /// すべてのレコードがコミット済みになったセグメントを削除
+    pub fn truncate_committed(&self) -> io::Result<()> {
+        let mut state = self.lock();
+        let committed_below = state.pending.first().copied().unwrap_or(state.next_seq);
+
+        // すべてコミット済みなら新しいセグメントに切り替え、書き込み中のセグメントも削除対象にする
+        // （開いたままでは削除できない環境があるため）
+        if committed_below == state.next_seq && state.segment_bytes > 0 {
+            state.roll_segment(&self.dir)?;
+        }
+
+        let segments = list_segments(&self.dir)?;
+        for (index, (base, path)) in segments.iter().enumerate() {
+            if *base >= state.segment_base {
+                continue;
+            }
+            let end = segments
+                .get(index + 1)
+                .map(|(next_base, _)| *next_base)
+                .unwrap_or(state.next_seq);
+            if end <= committed_below {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn segment_path(dir: &Path, base: u64) -> PathBuf {
+    dir.join(format!("{:010}.{}", base, SEGMENT_EXTENSION))
+}
+
+fn open_segment(dir: &Path, base: u64) -> io::Result<(BufWriter<File>, u64)> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(segment_path(dir, base))?;
+    let bytes = file.metadata()?.len();
+    Ok((BufWriter::new(file), bytes))
+}
+
+/// セグメントファイルを先頭シーケンス番号順に取得
+fn list_segments(dir: &Path) -> io::Result<Vec<(u64, PathBuf)>> {
+    let mut segments: Vec<(u64, PathBuf)> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some(SEGMENT_EXTENSION))
+        .filter_map(|path| {
+            let base = path.file_stem()?.to_str()?.parse::<u64>().ok()?;
+            Some((base, path))
+        })
+        .collect();
+    segments.sort_by_key(|(base, _)| *base);
+    Ok(segments)
+}
+
+/// 長さプレフィックス付きレコードを1件読み込む（末尾または不完全なレコードでは `None`）
+fn read_record(reader: &mut impl Read) -> io::Result<Option<Vec<u8>>> {
+    let mut prefix = [0u8; LENGTH_PREFIX_BYTES];
+    if let Err(e) = reader.read_exact(&mut prefix) {
+        return if e.kind() == io::ErrorKind::UnexpectedEof {
+            Ok(None)
+        } else {
+            Err(e)
+        };
+    }
+
+    let mut record = vec![0u8; u32::from_le_bytes(prefix) as usize];
+    match reader.read_exact(&mut record) {
+        Ok(()) => Ok(Some(record)),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// 末尾の不完全なレコードを切り捨て、完全なレコード数を返す
+fn repair_segment(path: &Path) -> io::Result<u64> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut count = 0;
+    let mut valid_len = 0u64;
+    while let Some(record) = read_record(&mut reader)? {
+        count += 1;
+        valid_len += (LENGTH_PREFIX_BYTES + record.len()) as u64;
+    }
+
+    let file_len = fs::metadata(path)?.len();
+    if valid_len < file_len {
+        eprintln!(
+            "[ChatSpool] Truncating {} incomplete bytes at the end of {}",
+            file_len - valid_len,
+            path.display()
+        );
+        OpenOptions::new()
+            .write(true)
+            .open(path)?
+            .set_len(valid_len)?;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: &str) -> ChatMessage {
+        ChatMessage {
+            channel_id: Some(1),
+            timestamp: "2025-01-06T12:00:00+09:00".to_string(),
+            platform: "twitch".to_string(),
+            user_name: "viewer".to_string(),
+            message: "hello".to_string(),
+            message_type: "normal".to_string(),
+            platform_message_id: Some(id.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn pending_ids(spool: &ChatSpool, after_seq: u64, limit: usize) -> Vec<String> {
+        spool
+            .read_pending(after_seq, limit)
+            .unwrap()
+            .map(|chunk| {
+                chunk
+                    .messages
+                    .into_iter()
+                    .filter_map(|m| m.platform_message_id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_spool_survives_reopen_and_truncates_committed() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let spool = ChatSpool::open(dir.path()).unwrap();
+        let a = spool.append(&message("a")).unwrap();
+        let b = spool.append(&message("b")).unwrap();
+        spool.sync().unwrap();
+        assert_eq!(spool.depth(), 2);
+        assert!(!spool.needs_replay());
+        drop(spool);
+
+        // 書き込み途中で終了した不完全なレコードを末尾に残す
+        let (_, segment) = list_segments(dir.path()).unwrap().pop().unwrap();
+        let mut file = OpenOptions::new().append(true).open(&segment).unwrap();
+        file.write_all(&100u32.to_le_bytes()).unwrap();
+        file.write_all(b"{\"id\"").unwrap();
+        drop(file);
+
+        // 再起動後は未コミット分が再投入待ちになり、シーケンス番号は引き継がれる
+        let spool = ChatSpool::open(dir.path()).unwrap();
+        assert_eq!(spool.depth(), 2);
+        assert!(spool.take_needs_replay());
+        assert!(!spool.needs_replay());
+        let c = spool.append(&message("c")).unwrap();
+        assert_eq!(c, b + 1);
+
+        // チャンク単位で読み込める
+        assert_eq!(pending_ids(&spool, 0, 2), vec!["a", "b"]);
+        assert_eq!(pending_ids(&spool, b, 2), vec!["c"]);
+        assert!(spool.read_pending(c, 2).unwrap().is_none());
+
+        // 未コミットのレコードを含むセグメントは削除しない
+        spool.commit(&[a, c]);
+        spool.truncate_committed().unwrap();
+        assert_eq!(spool.depth(), 1);
+        assert_eq!(pending_ids(&spool, 0, 10), vec!["b"]);
+
+        spool.commit(&[b]);
+        spool.truncate_committed().unwrap();
+        assert_eq!(spool.depth(), 0);
+        assert!(spool.read_pending(0, 10).unwrap().is_none());
+        assert_eq!(list_segments(dir.path()).unwrap().len(), 1);
+
+        // 切り詰め後もシーケンス番号は単調増加する
+        let d = spool.append(&message("d")).unwrap();
+        assert_eq!(d, c + 1);
+        drop(spool);
+        let spool = ChatSpool::open(dir.path()).unwrap();
+        assert_eq!(pending_ids(&spool, 0, 10), vec!["d"]);
+        assert_eq!(spool.next_seq(), d + 1);
+    }
+}
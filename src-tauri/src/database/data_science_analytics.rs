@@ -1,7 +1,10 @@
+use crate::constants::database as db_constants;
+use crate::database::models::{CatalogEmote, MessageEmote};
+use crate::database::repositories::EmoteCatalogRepository;
 use crate::database::{query_helpers::chat_query, utils};
 use duckdb::Connection;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 // ============================================================================
 // Phase 1: Text Analysis
@@ -32,6 +35,10 @@ pub struct WordFrequency {
 pub struct EmoteAnalysisResult {
     pub emotes: Vec<EmoteUsage>,
     pub total_emote_uses: i64,
+    /// Uses of emotes owned by the message's channel
+    pub channel_emote_uses: i64,
+    /// Uses of global emotes
+    pub global_emote_uses: i64,
     pub emote_per_message_rate: f64,
     pub hourly_pattern: Vec<HourlyEmotePattern>,
 }
@@ -40,6 +47,11 @@ pub struct EmoteAnalysisResult {
 #[serde(rename_all = "camelCase")]
 pub struct EmoteUsage {
     pub name: String,
+    pub emote_id: String,
+    /// "twitch" | "7tv" | "bttv" | "ffz"
+    pub source: String,
+    /// "channel" | "global" | "unknown"
+    pub scope: String,
     pub count: i64,
     pub users: i64,
     pub percentage: f64,
@@ -181,6 +193,43 @@ pub struct SegmentAvgScore {
     pub user_count: i64,
 }
 
+/// Reply graph result (nodes = chatters, edges = who replied to whom)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplyGraphResult {
+    pub nodes: Vec<ReplyGraphNode>,
+    pub edges: Vec<ReplyGraphEdge>,
+    pub depth_distribution: Vec<ThreadDepthBucket>,
+    pub total_replies: i64,
+    pub total_threads: i64,
+    pub avg_thread_depth: f64,
+    pub max_thread_depth: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplyGraphNode {
+    pub user_name: String,
+    pub is_broadcaster: bool,
+    pub replies_sent: i64,
+    pub replies_received: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplyGraphEdge {
+    pub source: String, // replying user
+    pub target: String, // replied-to user
+    pub weight: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadDepthBucket {
+    pub depth: i64,
+    pub thread_count: i64,
+}
+
 // ============================================================================
 // Phase 4: Anomaly Detection
 // ============================================================================
@@ -361,6 +410,8 @@ const ENGLISH_STOPWORDS: &[&str] = &[
 ];
 
 /// Phase 1: Get word frequency analysis
+///
+/// `exclude_deleted` が true の場合、モデレーターに削除されたメッセージを除外します。
 pub fn get_word_frequency_analysis(
     conn: &Connection,
     channel_id: Option<i64>,
@@ -368,6 +419,7 @@ pub fn get_word_frequency_analysis(
     start_time: Option<&str>,
     end_time: Option<&str>,
     limit: i32,
+    exclude_deleted: bool,
 ) -> Result<WordFrequencyResult, duckdb::Error> {
     // First, get all messages
     let mut sql = String::from(
@@ -402,6 +454,10 @@ pub fn get_word_frequency_analysis(
         params.push(end.to_string());
     }
 
+    if exclude_deleted {
+        sql.push_str(" AND cm.is_deleted IS NOT TRUE");
+    }
+
     // パフォーマンス最適化: 最新100,000件に制限
     sql.push_str(" ORDER BY cm.timestamp DESC LIMIT 100000");
 
@@ -471,6 +527,10 @@ pub fn get_word_frequency_analysis(
 }
 
 /// Phase 1: Get emote analysis
+///
+/// Twitch公式エモートはIRCの emotes タグ（chat_messages.emotes）から、
+/// 7TV/BTTV/FFZ エモートは emote_catalog の名前と単語の完全一致で判定します。
+/// サードパーティエモートはチャンネル固有の定義をグローバル定義より優先します。
 pub fn get_emote_analysis(
     conn: &Connection,
     channel_id: Option<i64>,
@@ -484,7 +544,9 @@ pub fn get_emote_analysis(
         SELECT 
             cm.message,
             cm.user_name,
-            EXTRACT(HOUR FROM cm.timestamp) as hour
+            EXTRACT(HOUR FROM cm.timestamp) as hour,
+            cm.emotes,
+            COALESCE(cm.channel_id, s.channel_id) as channel_id
         FROM chat_messages cm
         LEFT JOIN streams s ON cm.stream_id = s.id
         WHERE 1=1
@@ -517,68 +579,122 @@ pub fn get_emote_analysis(
     // パフォーマンス最適化: 最新100,000件に制限
     sql.push_str(" ORDER BY cm.timestamp DESC LIMIT 100000");
 
+    // (message, user_name, hour, emotes, channel_id)
+    type EmoteRow = (String, String, i32, Option<String>, Option<i64>);
     let mut stmt = conn.prepare(&sql)?;
-    let data: Vec<(String, String, i32)> =
-        utils::query_map_with_params(&mut stmt, &params, |row| {
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, i32>(2)?,
-            ))
-        })?
-        .collect::<Result<Vec<_>, _>>()?;
+    let data: Vec<EmoteRow> = utils::query_map_with_params(&mut stmt, &params, |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, i32>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, Option<i64>>(4)?,
+        ))
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    // Twitch公式エモートはIDで、サードパーティエモートは名前で引けるようにする
+    let catalog = EmoteCatalogRepository::get_catalog(conn, None, channel_id)?;
+    let mut twitch_catalog: HashMap<&str, &CatalogEmote> = HashMap::new();
+    let mut third_party_catalog: HashMap<&str, Vec<&CatalogEmote>> = HashMap::new();
+    for emote in &catalog {
+        if emote.source == db_constants::EMOTE_SOURCE_TWITCH {
+            twitch_catalog.insert(emote.emote_id.as_str(), emote);
+        } else {
+            third_party_catalog
+                .entry(emote.name.as_str())
+                .or_default()
+                .push(emote);
+        }
+    }
 
     // Process emotes
-    let mut emote_counts: HashMap<String, i64> = HashMap::new();
-    let mut emote_users: HashMap<String, Vec<String>> = HashMap::new();
+    // (source, emote_id)
+    type EmoteKey = (String, String);
+    // (name, scope, count)
+    type EmoteStat = (String, &'static str, i64);
+    let mut emote_stats: HashMap<EmoteKey, EmoteStat> = HashMap::new();
+    let mut emote_users: HashMap<EmoteKey, HashSet<String>> = HashMap::new();
     let mut hourly_counts: HashMap<i32, i64> = HashMap::new();
     let mut total_emotes = 0i64;
+    let mut channel_emote_uses = 0i64;
+    let mut global_emote_uses = 0i64;
     let total_messages = data.len() as i64;
 
-    for (message, user, hour) in data {
-        // Simple emote detection (common Twitch emotes or words in PascalCase/CAPSLOCK)
-        let emotes: Vec<String> = message
-            .split_whitespace()
-            .filter(|w| {
-                (w.chars().all(|c| c.is_uppercase() || c.is_numeric())
-                    || (w.chars().next().is_some_and(|c| c.is_uppercase())
-                        && w.chars().skip(1).any(|c| c.is_uppercase())))
-                    && w.len() > 2
-                    && w.len() < 30
-                    && !w.starts_with("HTTP")
-            })
-            .map(|w| w.to_string())
-            .collect();
+    for (message, user, hour, emotes_json, message_channel_id) in data {
+        let twitch_emotes: Vec<MessageEmote> = emotes_json
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+
+        let mut found: Vec<(EmoteKey, String, &'static str)> = Vec::new();
+
+        for emote in &twitch_emotes {
+            let scope = match twitch_catalog.get(emote.id.as_str()) {
+                Some(entry) => emote_scope(entry, message_channel_id),
+                None => EMOTE_SCOPE_UNKNOWN,
+            };
+            found.push((
+                (
+                    db_constants::EMOTE_SOURCE_TWITCH.to_string(),
+                    emote.id.clone(),
+                ),
+                emote.name.clone(),
+                scope,
+            ));
+        }
+
+        // IRCでTwitchエモートと判定された単語はサードパーティ判定の対象外
+        let twitch_names: HashSet<&str> = twitch_emotes.iter().map(|e| e.name.as_str()).collect();
+        for word in message.split_whitespace() {
+            if twitch_names.contains(word) {
+                continue;
+            }
+            let Some(candidates) = third_party_catalog.get(word) else {
+                continue;
+            };
+            let entry = candidates
+                .iter()
+                .find(|e| e.channel_id.is_some() && e.channel_id == message_channel_id)
+                .or_else(|| candidates.iter().find(|e| e.channel_id.is_none()));
+            if let Some(entry) = entry {
+                found.push((
+                    (entry.source.clone(), entry.emote_id.clone()),
+                    entry.name.clone(),
+                    emote_scope(entry, message_channel_id),
+                ));
+            }
+        }
 
-        total_emotes += emotes.len() as i64;
-        *hourly_counts.entry(hour).or_insert(0) += emotes.len() as i64;
+        total_emotes += found.len() as i64;
+        *hourly_counts.entry(hour).or_insert(0) += found.len() as i64;
 
-        for emote in emotes {
-            *emote_counts.entry(emote.clone()).or_insert(0) += 1;
-            emote_users.entry(emote).or_default().push(user.clone());
+        for (key, name, scope) in found {
+            match scope {
+                EMOTE_SCOPE_CHANNEL => channel_emote_uses += 1,
+                EMOTE_SCOPE_GLOBAL => global_emote_uses += 1,
+                _ => {}
+            }
+            emote_stats.entry(key.clone()).or_insert((name, scope, 0)).2 += 1;
+            emote_users.entry(key).or_default().insert(user.clone());
         }
     }
 
     // Sort emotes by count
-    let mut emote_vec: Vec<(String, i64)> = emote_counts.into_iter().collect();
-    emote_vec.sort_by(|a, b| b.1.cmp(&a.1));
+    let mut emote_vec: Vec<(EmoteKey, EmoteStat)> = emote_stats.into_iter().collect();
+    emote_vec.sort_by(|a, b| b.1 .2.cmp(&a.1 .2));
     emote_vec.truncate(100);
 
     let emotes: Vec<EmoteUsage> = emote_vec
         .into_iter()
-        .map(|(name, count)| {
-            let users = emote_users
-                .get(&name)
-                .map(|u| {
-                    let mut unique_users: Vec<_> = u.clone();
-                    unique_users.sort();
-                    unique_users.dedup();
-                    unique_users.len() as i64
-                })
-                .unwrap_or(0);
+        .map(|(key, (name, scope, count))| {
+            let users = emote_users.get(&key).map(|u| u.len() as i64).unwrap_or(0);
+            let (source, emote_id) = key;
 
             EmoteUsage {
                 name,
+                emote_id,
+                source,
+                scope: scope.to_string(),
                 count,
                 users,
                 percentage: if total_emotes > 0 {
@@ -606,11 +722,28 @@ pub fn get_emote_analysis(
     Ok(EmoteAnalysisResult {
         emotes,
         total_emote_uses: total_emotes,
+        channel_emote_uses,
+        global_emote_uses,
         emote_per_message_rate,
         hourly_pattern,
     })
 }
 
+const EMOTE_SCOPE_CHANNEL: &str = "channel";
+const EMOTE_SCOPE_GLOBAL: &str = "global";
+const EMOTE_SCOPE_UNKNOWN: &str = "unknown";
+
+/// カタログ上のエモートが、メッセージのチャンネルから見て固有かグローバルかを判定
+///
+/// 他チャンネルのサブスクエモートなど、カタログにあってもスコープが一致しないものは unknown
+fn emote_scope(entry: &CatalogEmote, message_channel_id: Option<i64>) -> &'static str {
+    match entry.channel_id {
+        None => EMOTE_SCOPE_GLOBAL,
+        Some(id) if Some(id) == message_channel_id => EMOTE_SCOPE_CHANNEL,
+        Some(_) => EMOTE_SCOPE_UNKNOWN,
+    }
+}
+
 /// Phase 1: Get message length statistics
 pub fn get_message_length_stats(
     conn: &Connection,
@@ -924,7 +1057,7 @@ pub fn get_viewer_chat_correlation(
     })
 }
 
-fn calculate_pearson_correlation(x: &[f64], y: &[f64]) -> f64 {
+pub(crate) fn calculate_pearson_correlation(x: &[f64], y: &[f64]) -> f64 {
     if x.len() != y.len() || x.is_empty() {
         return 0.0;
     }
@@ -1108,33 +1241,58 @@ pub fn get_category_change_impact(
         });
     }
 
-    // Get category changes
+    // Get category changes（stream_events に記録された変更を使用）
     let mut sql = String::from(
         r#"
-        WITH ordered_stats AS (
+        WITH category_events AS (
             SELECT
-                ss.collected_at,
-                ss.category,
-                ss.viewer_count,
-                LAG(ss.category) OVER (ORDER BY ss.collected_at) as prev_category,
-                LAG(ss.viewer_count) OVER (ORDER BY ss.collected_at) as prev_viewers,
-                LEAD(ss.viewer_count, 5) OVER (ORDER BY ss.collected_at) as after_viewers
-            FROM stream_stats ss
-            WHERE ss.channel_name = ?
-                AND ss.category IS NOT NULL
-                AND ss.viewer_count IS NOT NULL
+                e.stream_id,
+                e.occurred_at,
+                e.old_value,
+                e.new_value,
+                (
+                    SELECT ss.viewer_count FROM stream_stats ss
+                    WHERE ss.stream_id = e.stream_id
+                        AND ss.collected_at < e.occurred_at
+                        AND ss.viewer_count IS NOT NULL
+                    ORDER BY ss.collected_at DESC
+                    LIMIT 1
+                ) as prev_viewers,
+                COALESCE(
+                    (
+                        SELECT ss.viewer_count FROM stream_stats ss
+                        WHERE ss.stream_id = e.stream_id
+                            AND ss.collected_at >= e.occurred_at
+                            AND ss.viewer_count IS NOT NULL
+                        ORDER BY ss.collected_at
+                        LIMIT 1 OFFSET 5
+                    ),
+                    (
+                        SELECT ss.viewer_count FROM stream_stats ss
+                        WHERE ss.stream_id = e.stream_id
+                            AND ss.collected_at >= e.occurred_at
+                            AND ss.viewer_count IS NOT NULL
+                        ORDER BY ss.collected_at
+                        LIMIT 1
+                    )
+                ) as after_viewers
+            FROM stream_events e
+            WHERE e.channel_id = ?
+                AND e.event_type = 'category'
+                AND e.old_value IS NOT NULL
+                AND e.new_value IS NOT NULL
         "#,
     );
 
-    let mut params = vec![filter_channel_name.clone().unwrap()];
+    let mut params = vec![channel_id.to_string()];
 
     if let Some(start) = start_time {
-        sql.push_str(" AND ss.collected_at >= ?");
+        sql.push_str(" AND e.occurred_at >= ?");
         params.push(start.to_string());
     }
 
     if let Some(end) = end_time {
-        sql.push_str(" AND ss.collected_at <= ?");
+        sql.push_str(" AND e.occurred_at <= ?");
         params.push(end.to_string());
     }
 
@@ -1142,16 +1300,14 @@ pub fn get_category_change_impact(
         r#"
         )
         SELECT
-            strftime(collected_at::TIMESTAMP, '%Y-%m-%dT%H:%M:%S.000Z') as timestamp,
-            prev_category,
-            category,
+            strftime(occurred_at::TIMESTAMP, '%Y-%m-%dT%H:%M:%S.000Z') as timestamp,
+            old_value,
+            new_value,
             prev_viewers,
-            COALESCE(after_viewers, viewer_count) as after_viewers
-        FROM ordered_stats
-        WHERE prev_category IS NOT NULL
-            AND prev_category != category
-            AND prev_viewers > 0
-        ORDER BY collected_at DESC
+            COALESCE(after_viewers, prev_viewers) as after_viewers
+        FROM category_events
+        WHERE prev_viewers > 0
+        ORDER BY occurred_at DESC
         LIMIT 50
         "#,
     );
@@ -1447,6 +1603,188 @@ pub fn get_chatter_activity_scores(
     })
 }
 
+/// Phase 3: Build reply graph (who replies to whom) and thread depth stats
+///
+/// 返信（reply_parent_id があるメッセージ）のみを対象にします。
+/// 深さは返信先をたどった段数で、起点メッセージへの直接の返信が 1 です。
+/// edges は重みの大きい順に `limit` 件まで返し、nodes はそれらに登場するユーザーを
+/// 返信を受けた数の多い順に返します。
+pub fn get_reply_graph(
+    conn: &Connection,
+    channel_id: Option<i64>,
+    stream_id: Option<i64>,
+    start_time: Option<&str>,
+    end_time: Option<&str>,
+    limit: i32,
+) -> Result<ReplyGraphResult, duckdb::Error> {
+    let mut sql = String::from(
+        r#"
+        SELECT
+            cm.platform_message_id,
+            cm.user_name,
+            cm.reply_parent_id,
+            cm.reply_parent_user_name,
+            cm.reply_thread_id,
+            LOWER(ch.channel_id) as broadcaster
+        FROM chat_messages cm
+        LEFT JOIN streams s ON cm.stream_id = s.id
+        LEFT JOIN channels ch ON ch.id = COALESCE(cm.channel_id, s.channel_id)
+        WHERE cm.reply_parent_id IS NOT NULL
+        "#,
+    );
+
+    let mut params: Vec<String> = Vec::new();
+
+    if let Some(ch_id) = channel_id {
+        sql.push_str(&format!(
+            " AND (cm.channel_id = {} OR s.channel_id = {})",
+            ch_id, ch_id
+        ));
+    }
+
+    if let Some(st_id) = stream_id {
+        sql.push_str(&format!(" AND cm.stream_id = {}", st_id));
+    }
+
+    if let Some(start) = start_time {
+        sql.push_str(" AND cm.timestamp >= ?");
+        params.push(start.to_string());
+    }
+
+    if let Some(end) = end_time {
+        sql.push_str(" AND cm.timestamp <= ?");
+        params.push(end.to_string());
+    }
+
+    // パフォーマンス最適化: 100,000件に制限
+    sql.push_str(" ORDER BY cm.timestamp LIMIT 100000");
+
+    type ReplyRow = (
+        Option<String>,
+        String,
+        String,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows: Vec<ReplyRow> = utils::query_map_with_params(&mut stmt, &params, |row| {
+        Ok((
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+        ))
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    let broadcasters: HashSet<String> = rows.iter().filter_map(|r| r.5.clone()).collect();
+
+    // 返信メッセージID → 返信先メッセージID
+    let parent_of: HashMap<&str, &str> = rows
+        .iter()
+        .filter_map(|(id, _, parent, _, _, _)| Some((id.as_deref()?, parent.as_str())))
+        .collect();
+
+    let mut thread_depths: HashMap<String, i64> = HashMap::new();
+    let mut edge_weights: HashMap<(String, String), i64> = HashMap::new();
+    let mut sent: HashMap<String, i64> = HashMap::new();
+    let mut received: HashMap<String, i64> = HashMap::new();
+
+    for (_, user_name, parent_id, parent_user, thread_id, _) in &rows {
+        // 返信先が返信である限りたどる（循環データ対策で上限を設ける）
+        let mut depth = 1i64;
+        let mut root = parent_id.as_str();
+        while let Some(next) = parent_of.get(root) {
+            depth += 1;
+            root = *next;
+            if depth >= 1000 {
+                break;
+            }
+        }
+        let thread_key = thread_id.clone().unwrap_or_else(|| root.to_string());
+        let max_depth = thread_depths.entry(thread_key).or_insert(0);
+        *max_depth = (*max_depth).max(depth);
+
+        *sent.entry(user_name.clone()).or_insert(0) += 1;
+        if let Some(target) = parent_user {
+            *received.entry(target.clone()).or_insert(0) += 1;
+            if target != user_name {
+                *edge_weights
+                    .entry((user_name.clone(), target.clone()))
+                    .or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut edges: Vec<ReplyGraphEdge> = edge_weights
+        .into_iter()
+        .map(|((source, target), weight)| ReplyGraphEdge {
+            source,
+            target,
+            weight,
+        })
+        .collect();
+    edges.sort_by(|a, b| {
+        b.weight
+            .cmp(&a.weight)
+            .then_with(|| a.source.cmp(&b.source))
+            .then_with(|| a.target.cmp(&b.target))
+    });
+    edges.truncate(limit.max(0) as usize);
+
+    let node_names: HashSet<&str> = edges
+        .iter()
+        .flat_map(|e| [e.source.as_str(), e.target.as_str()])
+        .collect();
+    let mut nodes: Vec<ReplyGraphNode> = node_names
+        .into_iter()
+        .map(|name| ReplyGraphNode {
+            user_name: name.to_string(),
+            is_broadcaster: broadcasters.contains(&name.to_lowercase()),
+            replies_sent: *sent.get(name).unwrap_or(&0),
+            replies_received: *received.get(name).unwrap_or(&0),
+        })
+        .collect();
+    nodes.sort_by(|a, b| {
+        b.replies_received
+            .cmp(&a.replies_received)
+            .then_with(|| a.user_name.cmp(&b.user_name))
+    });
+
+    let mut depth_counts: HashMap<i64, i64> = HashMap::new();
+    for depth in thread_depths.values() {
+        *depth_counts.entry(*depth).or_insert(0) += 1;
+    }
+    let mut depth_distribution: Vec<ThreadDepthBucket> = depth_counts
+        .into_iter()
+        .map(|(depth, thread_count)| ThreadDepthBucket {
+            depth,
+            thread_count,
+        })
+        .collect();
+    depth_distribution.sort_by_key(|b| b.depth);
+
+    let total_threads = thread_depths.len() as i64;
+    let avg_thread_depth = if total_threads > 0 {
+        thread_depths.values().sum::<i64>() as f64 / total_threads as f64
+    } else {
+        0.0
+    };
+
+    Ok(ReplyGraphResult {
+        nodes,
+        edges,
+        depth_distribution,
+        total_replies: rows.len() as i64,
+        total_threads,
+        avg_thread_depth,
+        max_thread_depth: thread_depths.values().copied().max().unwrap_or(0),
+    })
+}
+
 /// Phase 4: Detect anomalies using Modified Z-Score (MAD-based)
 /// This method is:
 /// - Statistically robust (not affected by outliers)
@@ -2040,3 +2378,199 @@ fn create_empty_anomaly_result() -> AnomalyResult {
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::ChatMessage;
+    use crate::database::writer::DatabaseWriter;
+
+    fn reply(id: &str, user_name: &str, parent: Option<(&str, &str)>, ts: &str) -> ChatMessage {
+        ChatMessage {
+            channel_id: Some(1),
+            timestamp: ts.to_string(),
+            platform: "twitch".to_string(),
+            user_name: user_name.to_string(),
+            message: "hi".to_string(),
+            message_type: "normal".to_string(),
+            platform_message_id: Some(id.to_string()),
+            reply_parent_id: parent.map(|(id, _)| id.to_string()),
+            reply_parent_user_name: parent.map(|(_, user)| user.to_string()),
+            reply_thread_id: parent.map(|_| "m1".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn catalog_emote(source: &str, emote_id: &str, name: &str) -> CatalogEmote {
+        CatalogEmote {
+            source: source.to_string(),
+            emote_id: emote_id.to_string(),
+            name: name.to_string(),
+            channel_id: None,
+            image_url: None,
+        }
+    }
+
+    #[test]
+    #[cfg_attr(
+        target_os = "windows",
+        ignore = "Database tests are unstable on Windows local environment"
+    )]
+    fn test_get_emote_analysis() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::init_database(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO channels (id, platform, channel_id, channel_name) VALUES (1, 'twitch', 'streamer', 'streamer')",
+            [],
+        )
+        .unwrap();
+
+        let updated_at = "2025-01-06 00:00:00";
+        EmoteCatalogRepository::replace_source(
+            &conn,
+            db_constants::EMOTE_SOURCE_TWITCH,
+            None,
+            &[catalog_emote(
+                db_constants::EMOTE_SOURCE_TWITCH,
+                "25",
+                "Kappa",
+            )],
+            updated_at,
+        )
+        .unwrap();
+        EmoteCatalogRepository::replace_source(
+            &conn,
+            db_constants::EMOTE_SOURCE_TWITCH,
+            Some(1),
+            &[catalog_emote(
+                db_constants::EMOTE_SOURCE_TWITCH,
+                "e1",
+                "streamerHype",
+            )],
+            updated_at,
+        )
+        .unwrap();
+        // 同名の7TVエモートはグローバルよりチャンネル定義が優先される
+        EmoteCatalogRepository::replace_source(
+            &conn,
+            db_constants::EMOTE_SOURCE_7TV,
+            None,
+            &[catalog_emote(db_constants::EMOTE_SOURCE_7TV, "g1", "Clap")],
+            updated_at,
+        )
+        .unwrap();
+        EmoteCatalogRepository::replace_source(
+            &conn,
+            db_constants::EMOTE_SOURCE_7TV,
+            Some(1),
+            &[catalog_emote(db_constants::EMOTE_SOURCE_7TV, "c1", "Clap")],
+            updated_at,
+        )
+        .unwrap();
+
+        let emote = |id: &str, name: &str, start: usize| MessageEmote {
+            id: id.to_string(),
+            name: name.to_string(),
+            start,
+            end: start + name.chars().count(),
+        };
+        let message =
+            |id: &str, user_name: &str, text: &str, emotes: Vec<MessageEmote>| ChatMessage {
+                channel_id: Some(1),
+                timestamp: "2025-01-06 12:00:00".to_string(),
+                platform: "twitch".to_string(),
+                user_name: user_name.to_string(),
+                message: text.to_string(),
+                message_type: "normal".to_string(),
+                platform_message_id: Some(id.to_string()),
+                emotes: Some(emotes),
+                ..Default::default()
+            };
+
+        DatabaseWriter::insert_chat_messages_batch(
+            &conn,
+            &[
+                message(
+                    "m1",
+                    "alice",
+                    "streamerHype Kappa",
+                    vec![emote("e1", "streamerHype", 0), emote("25", "Kappa", 13)],
+                ),
+                message("m2", "bob", "Clap Clap", vec![]),
+                // IRCでTwitchエモートと判定された単語は7TVとして数えない
+                message("m3", "bob", "Clap", vec![emote("e2", "Clap", 0)]),
+            ],
+        )
+        .unwrap();
+
+        let result = get_emote_analysis(&conn, Some(1), None, None, None).unwrap();
+        assert_eq!(result.total_emote_uses, 5);
+        assert_eq!(result.channel_emote_uses, 3);
+        assert_eq!(result.global_emote_uses, 1);
+
+        let find = |source: &str, emote_id: &str| {
+            result
+                .emotes
+                .iter()
+                .find(|e| e.source == source && e.emote_id == emote_id)
+                .unwrap()
+        };
+        assert_eq!(find("twitch", "e1").scope, "channel");
+        assert_eq!(find("twitch", "25").scope, "global");
+        assert_eq!(find("twitch", "e2").scope, "unknown");
+        let clap = find("7tv", "c1");
+        assert_eq!(clap.scope, "channel");
+        assert_eq!(clap.count, 2);
+        assert_eq!(clap.users, 1);
+        assert!(!result.emotes.iter().any(|e| e.emote_id == "g1"));
+    }
+
+    #[test]
+    #[cfg_attr(
+        target_os = "windows",
+        ignore = "Database tests are unstable on Windows local environment"
+    )]
+    fn test_get_reply_graph() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::init_database(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO channels (id, platform, channel_id, channel_name) VALUES (1, 'twitch', 'streamer', 'streamer')",
+            [],
+        )
+        .unwrap();
+
+        DatabaseWriter::insert_chat_messages_batch(
+            &conn,
+            &[
+                reply("m1", "streamer", None, "2025-01-06 12:00:00"),
+                reply(
+                    "m2",
+                    "alice",
+                    Some(("m1", "streamer")),
+                    "2025-01-06 12:00:10",
+                ),
+                reply("m3", "bob", Some(("m1", "streamer")), "2025-01-06 12:00:20"),
+                reply(
+                    "m4",
+                    "streamer",
+                    Some(("m2", "alice")),
+                    "2025-01-06 12:00:30",
+                ),
+            ],
+        )
+        .unwrap();
+
+        let graph = get_reply_graph(&conn, Some(1), None, None, None, 10).unwrap();
+        assert_eq!(graph.total_replies, 3);
+        assert_eq!(graph.total_threads, 1);
+        assert_eq!(graph.max_thread_depth, 2);
+        assert_eq!(graph.edges.len(), 3);
+        assert_eq!(graph.nodes[0].user_name, "streamer");
+        assert!(graph.nodes[0].is_broadcaster);
+        assert_eq!(graph.nodes[0].replies_received, 2);
+        assert!(!graph
+            .nodes
+            .iter()
+            .any(|n| n.user_name == "bob" && n.is_broadcaster));
+    }
+}
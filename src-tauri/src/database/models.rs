@@ -58,6 +58,10 @@ pub struct StreamData {
     pub started_at: String,
     pub viewer_count: Option<i32>,
     pub follower_count: Option<i32>,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,6 +73,115 @@ pub struct ChannelWithStats {
     pub current_title: String,
 }
 
+/// チャンネル別ポーリングポリシー（未設定の項目は channels.poll_interval を使用）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollPolicy {
+    pub channel_id: i64,
+    /// ライブ中のポーリング間隔（秒）
+    pub live_interval: Option<i32>,
+    /// オフライン中のポーリング間隔（秒）
+    pub offline_interval: Option<i32>,
+    /// 静穏時間帯の開始時（0-23、ローカル時刻）
+    pub quiet_hours_start: Option<i32>,
+    /// 静穏時間帯の終了時（0-23、ローカル時刻、この時刻は含まない）
+    pub quiet_hours_end: Option<i32>,
+    /// 静穏時間帯のポーリング間隔（秒）
+    pub quiet_interval: Option<i32>,
+    /// 過去の配信開始時刻から学習した開始時間帯のポーリング間隔（秒、未設定の場合は学習しない）
+    pub start_window_interval: Option<i32>,
+    pub updated_at: Option<String>,
+}
+
+/// 配信メタデータの変更イベント（stream_events）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamEvent {
+    pub id: Option<i64>,
+    pub stream_id: i64,
+    pub channel_id: i64,
+    /// "title" | "category" | "tags" | "language"
+    pub event_type: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub occurred_at: String,
+}
+
+/// チャット制限モードの有効期間（Twitch ROOMSTATE）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomStatePeriod {
+    pub id: i64,
+    pub channel_id: i64,
+    /// "slow" | "emote_only" | "followers_only" | "subs_only" | "unique_chat"
+    pub mode: String,
+    /// スローモードの間隔秒数 / フォロワー限定の必要フォロー分数
+    pub setting: Option<i64>,
+    pub started_at: String,
+    /// NULL の場合は現在も有効
+    pub ended_at: Option<String>,
+}
+
+/// 収益イベント（YouTube Super Chat・Super Sticker・メンバーシップ）
+///
+/// chat_messages とは (platform, platform_message_id) で対応します。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonetizationEvent {
+    pub id: Option<i64>,
+    pub platform: String,
+    pub platform_message_id: String,
+    pub channel_id: Option<i64>,
+    pub stream_id: Option<i64>,
+    /// chat_messages.message_type と同じ値（superchat, supersticker, sponsor など）
+    pub event_type: String,
+    /// 金額（通貨単位の100万分の1）
+    pub amount_micros: Option<i64>,
+    /// ISO 4217 通貨コード
+    pub currency: Option<String>,
+    /// 表示用の金額文字列（"¥1,000" など）
+    pub amount_display: Option<String>,
+    /// Super Chat / Super Sticker の色ティア
+    pub tier: Option<i32>,
+    pub membership_level: Option<String>,
+    /// メンバー継続月数（マイルストーン）
+    pub membership_months: Option<i32>,
+    /// ギフトしたメンバーシップ数
+    pub gift_count: Option<i32>,
+    pub sticker_id: Option<String>,
+    pub timestamp: String,
+}
+
+/// 通貨換算レート（1通貨単位あたりの基準通貨での金額）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrencyRate {
+    pub currency: String,
+    pub base_currency: String,
+    pub rate: f64,
+    pub updated_at: Option<String>,
+}
+
+/// モデレーションイベント（メッセージ削除・タイムアウト・BAN・チャット全消去）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModerationEvent {
+    pub id: Option<i64>,
+    pub channel_id: i64,
+    pub stream_id: Option<i64>,
+    pub platform: String,
+    /// "delete" | "timeout" | "ban" | "clear"
+    pub event_type: String,
+    pub target_user_id: Option<String>,
+    pub target_user_name: Option<String>,
+    /// タイムアウト秒数（timeout のみ）
+    pub duration_seconds: Option<i64>,
+    /// 削除されたメッセージのID（delete のみ）
+    pub message_id: Option<String>,
+    pub message_text: Option<String>,
+    pub occurred_at: String,
+}
+
 /// Event payload for channel stats updates
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChannelStatsEvent {
@@ -78,7 +191,38 @@ pub struct ChannelStatsEvent {
     pub title: Option<String>,
 }
 
+/// Event payload for stream session start (`stream-started`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamStartedEvent {
+    pub channel_id: i64,
+    pub stream_id: i64,
+    pub platform_stream_id: String,
+    pub title: Option<String>,
+    pub category: Option<String>,
+    pub started_at: String,
+    /// 配信切断後に新しいstream_idで再開された場合 true
+    pub is_reconnect: bool,
+}
+
+/// Event payload for stream session end (`stream-ended`)
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamEndedEvent {
+    pub channel_id: i64,
+    pub stream_id: i64,
+    pub platform_stream_id: String,
+    pub title: String,
+    pub category: String,
+    pub started_at: String,
+    pub ended_at: String,
+    pub duration_minutes: i32,
+    pub peak_viewers: i32,
+    pub avg_viewers: i32,
+    pub minutes_watched: i64,
+    pub follower_gain: i32,
+    pub total_chat_messages: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ChatMessage {
     pub id: Option<i64>,
     pub channel_id: Option<i64>,
@@ -92,18 +236,81 @@ pub struct ChatMessage {
     pub message_type: String,
     pub badges: Option<Vec<String>>,
     pub badge_info: Option<String>, // サブスク月数等の詳細情報 (例: "subscriber:24")
+    #[serde(default)]
+    pub payload: Option<String>, // USERNOTICE等の構造化情報（JSON）
+    #[serde(default)]
+    pub platform_message_id: Option<String>, // プラットフォーム側のメッセージID（重複排除用）
+    #[serde(default)]
+    pub emotes: Option<Vec<MessageEmote>>, // IRCのemotesタグから取得したエモート位置
+    #[serde(default)]
+    pub bits: Option<i64>, // Cheerで送られたビッツ数
+    #[serde(default)]
+    pub reply_parent_id: Option<String>, // 返信先メッセージの platform_message_id
+    #[serde(default)]
+    pub reply_parent_user_name: Option<String>, // 返信先ユーザーのログイン名
+    #[serde(default)]
+    pub reply_thread_id: Option<String>, // スレッド起点メッセージの platform_message_id
+    #[serde(default)]
+    pub is_auto_discovered: bool, // 自動発見チャンネルのチャット収集で取得したメッセージ
+}
+
+/// メッセージ内のエモート出現位置（Twitch IRC の emotes タグ）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MessageEmote {
+    pub id: String,
+    pub name: String,
+    /// 文字単位の開始位置（含む）
+    pub start: usize,
+    /// 文字単位の終了位置（含まない）
+    pub end: usize,
 }
 
-/// ゲームカテゴリ（Twitch game/category）
+/// エモートカタログ（Twitch公式および7TV/BTTV/FFZ）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogEmote {
+    /// "twitch" | "7tv" | "bttv" | "ffz"
+    pub source: String,
+    pub emote_id: String,
+    pub name: String,
+    /// チャンネル固有エモートの場合は channels.id（グローバルは None）
+    pub channel_id: Option<i64>,
+    pub image_url: Option<String>,
+}
+
+/// ゲームカテゴリ（Twitch game/category、YouTube 動画カテゴリ）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GameCategory {
-    pub game_id: String,              // Twitch game ID（プライマリキー）
-    pub game_name: String,            // カテゴリ名（表示用、言語ごとに異なる可能性あり）
-    pub box_art_url: Option<String>,  // ボックスアート画像URL
+    pub platform: String,  // プラットフォーム（game_id と合わせてプライマリキー）
+    pub game_id: String,   // Twitch game ID / YouTube categoryId
+    pub game_name: String, // カテゴリ名（表示用、言語ごとに異なる可能性あり）
+    pub box_art_url: Option<String>, // ボックスアート画像URL
     pub last_updated: Option<String>, // 最終更新日時
 }
 
+/// 配信予定（Twitch の配信スケジュールのセグメント、YouTube の配信予定枠）
+///
+/// (platform, schedule_id) で一意です。配信が始まると streams と対応付けられ status が started になります。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledStream {
+    pub id: Option<i64>,
+    pub channel_id: i64,
+    pub platform: String,
+    /// Twitch: セグメントID / YouTube: 動画ID
+    pub schedule_id: String,
+    pub title: Option<String>,
+    pub category: Option<String>,
+    pub scheduled_start: String,
+    pub scheduled_end: Option<String>,
+    /// scheduled / started / canceled
+    pub status: String,
+    /// 対応付けた streams.id
+    pub stream_id: Option<i64>,
+    pub actual_start: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -264,6 +264,7 @@ impl AggregationRepository {
                 SELECT
                     COALESCE(s.channel_id, c2.id) as channel_id,
                     COALESCE(c1.channel_name, c2.channel_name, ss.channel_name) as channel_name,
+                    COALESCE(c1.platform, 'twitch') as platform,
                     ss.game_id,
                     ss.viewer_count,
                     ss.collected_at,
@@ -307,6 +308,7 @@ impl AggregationRepository {
             ),
             game_stats AS (
                 SELECT
+                    platform,
                     game_id,
                     COALESCE(SUM(viewer_count * COALESCE(interval_minutes, 1)), 0)::BIGINT AS minutes_watched,
                     COALESCE(SUM(COALESCE(interval_minutes, 1)) / 60.0, 0) AS hours_broadcasted,
@@ -317,21 +319,23 @@ impl AggregationRepository {
                 FROM stats_with_interval
                 WHERE viewer_count IS NOT NULL
                     AND channel_name IS NOT NULL
-                GROUP BY game_id
+                GROUP BY platform, game_id
             ),
             channel_by_category AS (
                 SELECT
+                    platform,
                     game_id,
                     channel_name,
                     COALESCE(SUM(viewer_count * COALESCE(interval_minutes, 1)), 0)::BIGINT AS channel_mw,
-                    ROW_NUMBER() OVER (PARTITION BY game_id ORDER BY SUM(viewer_count * COALESCE(interval_minutes, 1)) DESC) as rn
+                    ROW_NUMBER() OVER (PARTITION BY platform, game_id ORDER BY SUM(viewer_count * COALESCE(interval_minutes, 1)) DESC) as rn
                 FROM stats_with_interval
                 WHERE viewer_count IS NOT NULL
                     AND channel_name IS NOT NULL
-                GROUP BY game_id, channel_name
+                GROUP BY platform, game_id, channel_name
             ),
             top_channels AS (
                 SELECT
+                    platform,
                     game_id,
                     channel_name as top_channel_login
                 FROM channel_by_category
@@ -352,11 +356,12 @@ impl AggregationRepository {
                     WHEN gs.minutes_watched > 0
                     THEN (gs.total_chat_messages::DOUBLE / gs.minutes_watched::DOUBLE) * 1000.0
                     ELSE 0.0
-                END as engagement_rate
+                END as engagement_rate,
+                gs.platform
             FROM game_stats gs
-            LEFT JOIN top_channels tc ON gs.game_id = tc.game_id
-            LEFT JOIN channels c ON (tc.top_channel_login = c.channel_id AND c.platform = 'twitch')
-            LEFT JOIN game_categories gc ON gs.game_id = gc.game_id
+            LEFT JOIN top_channels tc ON gs.platform = tc.platform AND gs.game_id = tc.game_id
+            LEFT JOIN channels c ON (tc.top_channel_login = c.channel_id AND c.platform = gs.platform)
+            LEFT JOIN game_categories gc ON gs.platform = gc.platform AND gs.game_id = gc.game_id
             ORDER BY gs.minutes_watched DESC
             "#,
         );
@@ -376,6 +381,7 @@ impl AggregationRepository {
                     total_chat_messages: row.get::<_, i64>(8)?,
                     avg_chat_rate: row.get::<_, f64>(9)?,
                     engagement_rate: row.get::<_, f64>(10)?,
+                    platform: row.get::<_, String>(11)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -384,6 +390,9 @@ impl AggregationRepository {
     }
 
     /// カテゴリ一覧を取得（MW降順）
+    ///
+    /// カテゴリIDが game_categories に登録されていれば、その名前で集計します
+    /// （YouTubeのようにIDがそのまま category に入っている過去のデータも名前で表示するため）。
     pub fn list_categories(
         conn: &Connection,
         start_time: Option<&str>,
@@ -393,10 +402,14 @@ impl AggregationRepository {
             r#"
             WITH stats_with_interval AS (
                 SELECT 
-                    ss.category,
+                    COALESCE(gc.game_name, ss.category) AS category,
                     ss.viewer_count,
                     {}
                 FROM stream_stats ss
+                LEFT JOIN streams s ON ss.stream_id = s.id
+                LEFT JOIN channels c ON s.channel_id = c.id
+                LEFT JOIN game_categories gc
+                    ON gc.platform = COALESCE(c.platform, 'twitch') AND gc.game_id = ss.game_id
                 WHERE ss.category IS NOT NULL
             "#,
             stream_stats_query::interval_with_fallback("ss")
@@ -1,6 +1,7 @@
 /// チャンネルレポジトリ
 ///
 /// チャンネルテーブルへのアクセスを抽象化
+use crate::constants::database as db_constants;
 use crate::database::models::Channel;
 use duckdb::Connection;
 
@@ -259,16 +260,43 @@ impl ChannelRepository {
         let r1 = (|| {
             let mut del_cm = conn.prepare("DELETE FROM chat_messages WHERE stream_id = ?")?;
             let mut del_ss = conn.prepare("DELETE FROM stream_stats WHERE stream_id = ?")?;
+            let mut del_sm = conn.prepare("DELETE FROM stream_metadata WHERE stream_id = ?")?;
             for stream_id in stream_ids.iter() {
                 del_cm.execute(duckdb::params![*stream_id])?;
                 del_ss.execute(duckdb::params![*stream_id])?;
+                del_sm.execute(duckdb::params![*stream_id])?;
             }
             drop(del_cm);
             drop(del_ss);
+            drop(del_sm);
             conn.execute(
                 "DELETE FROM chat_messages WHERE channel_id = ?",
                 duckdb::params![id],
             )?;
+            conn.execute(
+                "DELETE FROM channel_poll_policies WHERE channel_id = ?",
+                duckdb::params![id],
+            )?;
+            conn.execute(
+                "DELETE FROM stream_events WHERE channel_id = ?",
+                duckdb::params![id],
+            )?;
+            conn.execute(
+                "DELETE FROM moderation_events WHERE channel_id = ?",
+                duckdb::params![id],
+            )?;
+            conn.execute(
+                "DELETE FROM room_state_periods WHERE channel_id = ?",
+                duckdb::params![id],
+            )?;
+            conn.execute(
+                "DELETE FROM monetization_events WHERE channel_id = ?",
+                duckdb::params![id],
+            )?;
+            conn.execute(
+                "DELETE FROM scheduled_streams WHERE channel_id = ?",
+                duckdb::params![id],
+            )?;
             Ok(())
         })();
         match r1 {
@@ -390,6 +418,23 @@ impl ChannelRepository {
         Ok(count > 0)
     }
 
+    /// プラットフォームとチャンネルID（login / チャンネルID）でチャンネルを取得
+    pub fn get_by_platform_channel_id(
+        conn: &Connection,
+        platform: &str,
+        channel_id: &str,
+    ) -> Result<Option<Channel>, duckdb::Error> {
+        let id: Option<i64> = conn
+            .prepare("SELECT id FROM channels WHERE platform = ? AND channel_id = ?")?
+            .query_map([platform, channel_id], |row| row.get(0))?
+            .next()
+            .transpose()?;
+        match id {
+            Some(id) => Self::get_by_id(conn, id),
+            None => Ok(None),
+        }
+    }
+
     /// 自動発見フラグとtwitch_user_idを更新
     pub fn update_auto_discovered(
         conn: &Connection,
@@ -406,8 +451,9 @@ impl ChannelRepository {
             )?;
         } else {
             // 昇格時は is_auto_discovered を false、discovered_at を NULL に設定
+            // チャット収集用に無効状態で作成された行もポーリング対象にする
             conn.execute(
-                "UPDATE channels SET is_auto_discovered = false, discovered_at = NULL, twitch_user_id = ? WHERE platform = ? AND channel_id = ?",
+                "UPDATE channels SET is_auto_discovered = false, discovered_at = NULL, enabled = true, twitch_user_id = ? WHERE platform = ? AND channel_id = ?",
                 duckdb::params![twitch_user_id, platform, channel_id],
             )?;
         }
@@ -424,6 +470,8 @@ impl ChannelRepository {
     }
 
     /// 自動発見されたチャンネルのうち、最新の配信が終了しているものを取得（クリーンアップ対象）
+    ///
+    /// チャットを収集したチャンネルは猶予期間付きで削除するため対象外です。
     pub fn get_offline_auto_discovered_channels(
         conn: &Connection,
     ) -> Result<Vec<(i64, String)>, duckdb::Error> {
@@ -441,6 +489,10 @@ impl ChannelRepository {
                 SELECT 1 FROM streams s
                 WHERE s.channel_id = c.id
             )
+            AND NOT EXISTS (
+                SELECT 1 FROM chat_messages cm
+                WHERE cm.channel_id = c.id
+            )
             "#,
         )?;
         let rows = stmt.query_map([], |row| {
@@ -449,6 +501,136 @@ impl ChannelRepository {
         rows.collect::<Result<Vec<_>, _>>()
     }
 
+    /// 自動発見チャンネルのチャット収集用の行を取得または作成
+    ///
+    /// 視聴者数は自動発見ループが記録するため、ChannelPoller がポーリングしないよう
+    /// enabled = false で作成します。手動登録済みのチャンネルは対象外として None を返します。
+    pub fn ensure_auto_discovered(
+        conn: &Connection,
+        channel_id: &str,
+        channel_name: &str,
+        twitch_user_id: i64,
+    ) -> Result<Option<i64>, duckdb::Error> {
+        let existing: Option<(i64, bool)> = conn
+            .prepare(
+                "SELECT id, COALESCE(is_auto_discovered, false) FROM channels WHERE platform = ? AND channel_id = ?",
+            )?
+            .query_map(
+                duckdb::params![db_constants::PLATFORM_TWITCH, channel_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )?
+            .next()
+            .transpose()?;
+
+        match existing {
+            Some((id, true)) => Ok(Some(id)),
+            Some((_, false)) => Ok(None),
+            None => {
+                let id: i64 = conn.query_row(
+                    r#"
+                    INSERT INTO channels (
+                        platform, channel_id, channel_name, enabled,
+                        is_auto_discovered, discovered_at, twitch_user_id
+                    )
+                    VALUES (?, ?, ?, false, true, CURRENT_TIMESTAMP, ?)
+                    RETURNING id
+                    "#,
+                    duckdb::params![
+                        db_constants::PLATFORM_TWITCH,
+                        channel_id,
+                        channel_name,
+                        twitch_user_id
+                    ],
+                    |row| row.get(0),
+                )?;
+                Ok(Some(id))
+            }
+        }
+    }
+
+    /// 自動発見チャンネル（id, login）を全件取得
+    pub fn get_auto_discovered_channels(
+        conn: &Connection,
+    ) -> Result<Vec<(i64, String)>, duckdb::Error> {
+        let mut stmt =
+            conn.prepare("SELECT id, channel_id FROM channels WHERE is_auto_discovered = true")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()
+    }
+
+    /// 今回の発見結果に含まれた自動発見チャンネルの discovered_at を更新
+    ///
+    /// 削除猶予の起点になるため、発見されている間は毎サイクル更新します。
+    pub fn touch_auto_discovered(
+        conn: &Connection,
+        channel_ids: &[String],
+    ) -> Result<usize, duckdb::Error> {
+        if channel_ids.is_empty() {
+            return Ok(0);
+        }
+        let placeholders = vec!["?"; channel_ids.len()].join(", ");
+        let sql = format!(
+            "UPDATE channels SET discovered_at = CURRENT_TIMESTAMP \
+             WHERE platform = ? AND is_auto_discovered = true AND channel_id IN ({})",
+            placeholders
+        );
+        let params = std::iter::once(db_constants::PLATFORM_TWITCH.to_string())
+            .chain(channel_ids.iter().cloned());
+        conn.execute(&sql, duckdb::params_from_iter(params))
+    }
+
+    /// 自動発見チャンネルが最後に発見されてから `grace_minutes` 分以上経過したか
+    pub fn is_discovery_expired(
+        conn: &Connection,
+        channel_id: i64,
+        grace_minutes: i64,
+    ) -> Result<bool, duckdb::Error> {
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT COALESCE(
+                TRY_CAST(NULLIF(discovered_at, '') AS TIMESTAMPTZ)
+                    < CURRENT_TIMESTAMP - to_minutes(CAST(? AS BIGINT)),
+                true
+            )
+            FROM channels
+            WHERE id = ?
+            "#,
+        )?;
+        let expired: bool =
+            stmt.query_row(duckdb::params![grace_minutes, channel_id], |row| row.get(0))?;
+        Ok(expired)
+    }
+
+    /// 自動発見チャンネルの未終了の配信を終了状態にする
+    ///
+    /// `current_stream_id` に一致する配信（発見中の配信）は対象外です。
+    /// ended_at には最後に発見された時刻（discovered_at）を設定します。
+    pub fn end_auto_discovered_streams(
+        conn: &Connection,
+        channel_id: i64,
+        current_stream_id: Option<&str>,
+    ) -> Result<usize, duckdb::Error> {
+        conn.execute(
+            r#"
+            UPDATE streams
+            SET ended_at = COALESCE(
+                (
+                    SELECT CAST(TRY_CAST(NULLIF(c.discovered_at, '') AS TIMESTAMPTZ) AS TIMESTAMP)
+                    FROM channels c
+                    WHERE c.id = streams.channel_id
+                ),
+                CAST(CURRENT_TIMESTAMP AS TIMESTAMP)
+            )
+            WHERE channel_id = ?
+              AND ended_at IS NULL
+              AND stream_id IS DISTINCT FROM ?
+            "#,
+            duckdb::params![channel_id, current_stream_id],
+        )
+    }
+
     /// チャンネルが現在ライブ配信中かどうか（ended_at IS NULL の配信が存在するか）
     pub fn is_channel_live(conn: &Connection, channel_id: i64) -> Result<bool, duckdb::Error> {
         let mut stmt = conn.prepare(
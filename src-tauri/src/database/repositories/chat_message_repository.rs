@@ -53,6 +53,7 @@ impl ChatMessageRepository {
     /// * `stream_id` - フィルター用配信ID（Optional）
     /// * `start_time` - 開始時刻（Optional）
     /// * `end_time` - 終了時刻（Optional）
+    /// * `exclude_deleted` - モデレーターに削除されたメッセージを除外するか
     pub fn count_by_time_bucket(
         conn: &Connection,
         interval_minutes: i32,
@@ -60,6 +61,7 @@ impl ChatMessageRepository {
         stream_id: Option<i64>,
         start_time: Option<&str>,
         end_time: Option<&str>,
+        exclude_deleted: bool,
     ) -> Result<Vec<TimeBucketChatStats>, duckdb::Error> {
         let mut sql = format!(
             r#"
@@ -97,6 +99,10 @@ impl ChatMessageRepository {
             params.push(end.to_string());
         }
 
+        if exclude_deleted {
+            sql.push_str(" AND cm.is_deleted IS NOT TRUE");
+        }
+
         sql.push_str(" GROUP BY bucket ORDER BY bucket");
 
         let mut stmt = conn.prepare(&sql)?;
@@ -201,6 +207,7 @@ impl ChatMessageRepository {
     /// 上位チャッターを取得
     ///
     /// N+1クエリを避けるため、CTEでバッジを事前取得します。
+    /// `exclude_deleted` が true の場合、削除されたメッセージは件数に含めません。
     pub fn get_top_chatters(
         conn: &Connection,
         channel_id: Option<i64>,
@@ -208,6 +215,7 @@ impl ChatMessageRepository {
         start_time: Option<&str>,
         end_time: Option<&str>,
         limit: i32,
+        exclude_deleted: bool,
     ) -> Result<Vec<ChatterWithBadges>, duckdb::Error> {
         let mut sql = format!(
             r#"
@@ -283,6 +291,10 @@ impl ChatMessageRepository {
             params.push(end.to_string());
         }
 
+        if exclude_deleted {
+            sql.push_str(" AND cm.is_deleted IS NOT TRUE");
+        }
+
         sql.push_str(
             r#"
             GROUP BY cm.user_id, cm.user_name, cm.display_name, ub.badges
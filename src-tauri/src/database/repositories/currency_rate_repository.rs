@@ -0,0 +1,78 @@
+/// CurrencyRateRepository - currency_rates テーブル専用レポジトリ
+///
+/// Super Chat などの金額を1つの通貨で比較するため、ユーザーが登録した換算レートを保持します。
+/// 外部のレートAPIには問い合わせず、登録された値をそのまま使います。
+use crate::database::models::CurrencyRate;
+use duckdb::Connection;
+
+pub struct CurrencyRateRepository;
+
+impl CurrencyRateRepository {
+    /// 換算レートをすべて置き換え
+    ///
+    /// 基準通貨自身はレート 1.0 として常に登録します。
+    /// 呼び出し側でトランザクションを張ってください。
+    /// 戻り値: 登録したレート数
+    pub fn replace_rates(
+        conn: &Connection,
+        base_currency: &str,
+        rates: &[CurrencyRate],
+        updated_at: &str,
+    ) -> Result<usize, duckdb::Error> {
+        conn.execute("DELETE FROM currency_rates", [])?;
+
+        let mut stmt = conn.prepare(
+            r#"
+            INSERT INTO currency_rates (currency, base_currency, rate, updated_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )?;
+        stmt.execute(duckdb::params![
+            base_currency,
+            base_currency,
+            1.0,
+            updated_at
+        ])?;
+
+        let mut inserted = 1;
+        for rate in rates.iter().filter(|r| r.currency != base_currency) {
+            stmt.execute(duckdb::params![
+                rate.currency,
+                base_currency,
+                rate.rate,
+                updated_at
+            ])?;
+            inserted += 1;
+        }
+
+        Ok(inserted)
+    }
+
+    /// 登録済みの換算レートを取得
+    pub fn get_rates(conn: &Connection) -> Result<Vec<CurrencyRate>, duckdb::Error> {
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT currency, base_currency, rate, CAST(updated_at AS VARCHAR)
+            FROM currency_rates
+            ORDER BY currency
+            "#,
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(CurrencyRate {
+                currency: row.get(0)?,
+                base_currency: row.get(1)?,
+                rate: row.get(2)?,
+                updated_at: row.get(3)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()
+    }
+
+    /// 現在の基準通貨（未登録の場合は None）
+    pub fn base_currency(conn: &Connection) -> Result<Option<String>, duckdb::Error> {
+        conn.prepare("SELECT base_currency FROM currency_rates LIMIT 1")?
+            .query_map([], |row| row.get(0))?
+            .next()
+            .transpose()
+    }
+}
@@ -0,0 +1,134 @@
+/// EmoteCatalogRepository - emote_catalog テーブル専用レポジトリ
+///
+/// Twitch公式（グローバル・チャンネル）と7TV/BTTV/FFZのエモート定義を保持します。
+/// channel_id が NULL の行はグローバルエモートです。
+use crate::database::models::CatalogEmote;
+use crate::database::utils;
+use duckdb::Connection;
+
+pub struct EmoteCatalogRepository;
+
+impl EmoteCatalogRepository {
+    /// ソース・スコープ単位でカタログを置き換え
+    ///
+    /// 同じ source と channel_id（グローバルは NULL）の既存行を削除してから挿入します。
+    /// 呼び出し側でトランザクションを張ってください。
+    /// 戻り値: 挿入したエモート数
+    pub fn replace_source(
+        conn: &Connection,
+        source: &str,
+        channel_id: Option<i64>,
+        emotes: &[CatalogEmote],
+        updated_at: &str,
+    ) -> Result<usize, duckdb::Error> {
+        conn.execute(
+            "DELETE FROM emote_catalog WHERE source = ? AND channel_id IS NOT DISTINCT FROM ?",
+            duckdb::params![source, channel_id],
+        )?;
+
+        let mut stmt = conn.prepare(
+            r#"
+            INSERT INTO emote_catalog (source, emote_id, name, channel_id, image_url, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )?;
+        for emote in emotes {
+            stmt.execute(duckdb::params![
+                source,
+                emote.emote_id,
+                emote.name,
+                channel_id,
+                emote.image_url,
+                updated_at,
+            ])?;
+        }
+
+        Ok(emotes.len())
+    }
+
+    /// カタログを取得
+    ///
+    /// channel_id を指定した場合はそのチャンネルのエモートとグローバルエモートを返します。
+    pub fn get_catalog(
+        conn: &Connection,
+        source: Option<&str>,
+        channel_id: Option<i64>,
+    ) -> Result<Vec<CatalogEmote>, duckdb::Error> {
+        let mut sql = String::from(
+            "SELECT source, emote_id, name, channel_id, image_url FROM emote_catalog WHERE 1=1",
+        );
+        let mut params = Vec::new();
+
+        if let Some(source) = source {
+            sql.push_str(" AND source = ?");
+            params.push(source.to_string());
+        }
+        if let Some(ch_id) = channel_id {
+            sql.push_str(" AND (channel_id = ? OR channel_id IS NULL)");
+            params.push(ch_id.to_string());
+        }
+        sql.push_str(" ORDER BY source, channel_id NULLS LAST, name");
+
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = utils::query_map_with_params(&mut stmt, &params, |row| {
+            Ok(CatalogEmote {
+                source: row.get(0)?,
+                emote_id: row.get(1)?,
+                name: row.get(2)?,
+                channel_id: row.get(3)?,
+                image_url: row.get(4)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emote(emote_id: &str, name: &str) -> CatalogEmote {
+        CatalogEmote {
+            source: "7tv".to_string(),
+            emote_id: emote_id.to_string(),
+            name: name.to_string(),
+            channel_id: None,
+            image_url: None,
+        }
+    }
+
+    #[test]
+    #[cfg_attr(
+        target_os = "windows",
+        ignore = "Database tests are unstable on Windows local environment"
+    )]
+    fn test_replace_source_keeps_other_scopes() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::init_database(&conn).unwrap();
+        let now = "2025-01-06T12:00:00+09:00";
+
+        EmoteCatalogRepository::replace_source(&conn, "7tv", None, &[emote("g1", "EZ")], now)
+            .unwrap();
+        EmoteCatalogRepository::replace_source(
+            &conn,
+            "7tv",
+            Some(1),
+            &[emote("c1", "catJAM"), emote("c2", "Clap")],
+            now,
+        )
+        .unwrap();
+        // 再インポートは同じスコープのみ置き換える
+        EmoteCatalogRepository::replace_source(&conn, "7tv", Some(1), &[emote("c3", "Clap")], now)
+            .unwrap();
+
+        let catalog = EmoteCatalogRepository::get_catalog(&conn, Some("7tv"), Some(1)).unwrap();
+        let ids: Vec<(&str, Option<i64>)> = catalog
+            .iter()
+            .map(|e| (e.emote_id.as_str(), e.channel_id))
+            .collect();
+        assert_eq!(ids, vec![("c3", Some(1)), ("g1", None)]);
+
+        let other_channel = EmoteCatalogRepository::get_catalog(&conn, None, Some(2)).unwrap();
+        assert_eq!(other_channel.len(), 1);
+    }
+}
@@ -1,6 +1,7 @@
 /// GameCategoryRepository - game_categoriesテーブル専用レポジトリ
 ///
-/// Twitchゲームカテゴリの管理を行います。
+/// Twitchゲームカテゴリと YouTube 動画カテゴリの管理を行います。
+/// カテゴリIDはプラットフォームごとに独立しているため、(platform, game_id) で識別します。
 use crate::database::models::GameCategory;
 use chrono::Local;
 use duckdb::Connection;
@@ -10,9 +11,10 @@ pub struct GameCategoryRepository;
 impl GameCategoryRepository {
     /// カテゴリを挿入または更新（UPSERT）
     ///
-    /// game_idが既存の場合は更新、存在しない場合は挿入します。
+    /// (platform, game_id)が既存の場合は更新、存在しない場合は挿入します。
     pub fn upsert_category(
         conn: &Connection,
+        platform: &str,
         game_id: &str,
         game_name: &str,
         box_art_url: Option<&str>,
@@ -20,14 +22,14 @@ impl GameCategoryRepository {
         let now = Local::now().to_rfc3339();
         conn.execute(
             r#"
-            INSERT INTO game_categories (game_id, game_name, box_art_url, last_updated)
-            VALUES (?, ?, ?, ?)
-            ON CONFLICT(game_id) DO UPDATE SET
+            INSERT INTO game_categories (platform, game_id, game_name, box_art_url, last_updated)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(platform, game_id) DO UPDATE SET
                 game_name = excluded.game_name,
                 box_art_url = excluded.box_art_url,
                 last_updated = excluded.last_updated
             "#,
-            duckdb::params![game_id, game_name, box_art_url, now],
+            duckdb::params![platform, game_id, game_name, box_art_url, now],
         )?;
         Ok(())
     }
@@ -39,6 +41,7 @@ impl GameCategoryRepository {
         let mut stmt = conn.prepare(
             r#"
             SELECT
+                platform,
                 game_id,
                 game_name,
                 box_art_url,
@@ -48,14 +51,7 @@ impl GameCategoryRepository {
             "#,
         )?;
 
-        let results = stmt.query_map([], |row| {
-            Ok(GameCategory {
-                game_id: row.get(0)?,
-                game_name: row.get(1)?,
-                box_art_url: row.get(2)?,
-                last_updated: row.get(3)?,
-            })
-        })?;
+        let results = stmt.query_map([], row_to_game_category)?;
 
         results.collect::<Result<Vec<_>, _>>()
     }
@@ -63,35 +59,37 @@ impl GameCategoryRepository {
     /// IDでカテゴリを取得
     pub fn get_category_by_id(
         conn: &Connection,
+        platform: &str,
         game_id: &str,
     ) -> Result<Option<GameCategory>, duckdb::Error> {
         let mut stmt = conn.prepare(
             r#"
             SELECT
+                platform,
                 game_id,
                 game_name,
                 box_art_url,
                 CAST(last_updated AS VARCHAR) as last_updated
             FROM game_categories
-            WHERE game_id = ?
+            WHERE platform = ? AND game_id = ?
             "#,
         )?;
 
-        let mut results = stmt.query_map([game_id], |row| {
-            Ok(GameCategory {
-                game_id: row.get(0)?,
-                game_name: row.get(1)?,
-                box_art_url: row.get(2)?,
-                last_updated: row.get(3)?,
-            })
-        })?;
+        let mut results = stmt.query_map([platform, game_id], row_to_game_category)?;
 
         results.next().transpose()
     }
 
     /// カテゴリを削除
-    pub fn delete_category(conn: &Connection, game_id: &str) -> Result<(), duckdb::Error> {
-        conn.execute("DELETE FROM game_categories WHERE game_id = ?", [game_id])?;
+    pub fn delete_category(
+        conn: &Connection,
+        platform: &str,
+        game_id: &str,
+    ) -> Result<(), duckdb::Error> {
+        conn.execute(
+            "DELETE FROM game_categories WHERE platform = ? AND game_id = ?",
+            [platform, game_id],
+        )?;
         Ok(())
     }
 
@@ -106,6 +104,7 @@ impl GameCategoryRepository {
         let mut stmt = conn.prepare(
             r#"
             SELECT
+                platform,
                 game_id,
                 game_name,
                 box_art_url,
@@ -117,26 +116,86 @@ impl GameCategoryRepository {
             "#,
         )?;
 
-        let results = stmt.query_map([&search_pattern], |row| {
-            Ok(GameCategory {
-                game_id: row.get(0)?,
-                game_name: row.get(1)?,
-                box_art_url: row.get(2)?,
-                last_updated: row.get(3)?,
-            })
-        })?;
+        let results = stmt.query_map([&search_pattern], row_to_game_category)?;
 
         results.collect::<Result<Vec<_>, _>>()
     }
 
     /// カテゴリが存在するか確認
     #[allow(dead_code)]
-    pub fn exists(conn: &Connection, game_id: &str) -> Result<bool, duckdb::Error> {
+    pub fn exists(conn: &Connection, platform: &str, game_id: &str) -> Result<bool, duckdb::Error> {
         let count: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM game_categories WHERE game_id = ?",
-            [game_id],
+            "SELECT COUNT(*) FROM game_categories WHERE platform = ? AND game_id = ?",
+            [platform, game_id],
             |row| row.get(0),
         )?;
         Ok(count > 0)
     }
 }
+
+fn row_to_game_category(row: &duckdb::Row) -> Result<GameCategory, duckdb::Error> {
+    Ok(GameCategory {
+        platform: row.get(0)?,
+        game_id: row.get(1)?,
+        game_name: row.get(2)?,
+        box_art_url: row.get(3)?,
+        last_updated: row.get(4)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::repositories::AggregationRepository;
+
+    #[test]
+    #[cfg_attr(
+        target_os = "windows",
+        ignore = "Database tests are unstable on Windows local environment"
+    )]
+    fn test_categories_are_resolved_per_platform() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::init_database(&conn).unwrap();
+        conn.execute_batch(
+            r#"
+            INSERT INTO channels (id, platform, channel_id, channel_name)
+                VALUES (1, 'youtube', 'UCabcdefghijklmnopqrstuv', 'yt_streamer'),
+                       (2, 'twitch', 'tw_streamer', 'tw_streamer');
+            INSERT INTO streams (id, channel_id, stream_id, started_at)
+                VALUES (10, 1, 'video1', '2025-01-06 12:00:00'),
+                       (20, 2, 'stream2', '2025-01-06 12:00:00');
+            -- YouTube の過去データはカテゴリIDがそのまま category に入っている
+            INSERT INTO stream_stats (stream_id, collected_at, viewer_count, category, game_id)
+                VALUES (10, '2025-01-06 12:30:00', 300, '20', '20'),
+                       (20, '2025-01-06 12:30:00', 100, 'Just Chatting', '509658');
+            "#,
+        )
+        .unwrap();
+
+        GameCategoryRepository::upsert_category(&conn, "youtube", "20", "Gaming", None).unwrap();
+        GameCategoryRepository::upsert_category(&conn, "twitch", "20", "Other Game", None).unwrap();
+        GameCategoryRepository::upsert_category(&conn, "twitch", "509658", "Just Chatting", None)
+            .unwrap();
+
+        let youtube = GameCategoryRepository::get_category_by_id(&conn, "youtube", "20")
+            .unwrap()
+            .unwrap();
+        assert_eq!(youtube.game_name, "Gaming");
+        assert_eq!(
+            GameCategoryRepository::get_all_categories(&conn)
+                .unwrap()
+                .len(),
+            3
+        );
+
+        let categories = AggregationRepository::list_categories(&conn, None, None).unwrap();
+        assert_eq!(categories, vec!["Gaming", "Just Chatting"]);
+
+        let analytics =
+            AggregationRepository::calculate_game_analytics(&conn, None, None, None).unwrap();
+        let gaming = analytics.iter().find(|a| a.game_id == "20").unwrap();
+        assert_eq!(gaming.category, "Gaming");
+        assert_eq!(gaming.platform, "youtube");
+        assert_eq!(gaming.top_channel, "yt_streamer");
+    }
+}
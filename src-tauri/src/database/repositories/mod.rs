@@ -5,16 +5,34 @@ pub mod aggregation_repository;
 pub mod base;
 pub mod channel_repository;
 pub mod chat_message_repository;
+pub mod currency_rate_repository;
+pub mod emote_catalog_repository;
 pub mod game_category_repository;
+pub mod moderation_event_repository;
+pub mod monetization_repository;
+pub mod poll_policy_repository;
+pub mod room_state_repository;
+pub mod scheduled_stream_repository;
 pub mod sql_template_repository;
+pub mod stream_event_repository;
 pub mod stream_repository;
 pub mod stream_stats_repository;
+pub mod youtube_quota_repository;
 
 // Re-exports
 pub use aggregation_repository::AggregationRepository;
 pub use channel_repository::ChannelRepository;
 pub use chat_message_repository::ChatMessageRepository;
+pub use currency_rate_repository::CurrencyRateRepository;
+pub use emote_catalog_repository::EmoteCatalogRepository;
 pub use game_category_repository::GameCategoryRepository;
+pub use moderation_event_repository::ModerationEventRepository;
+pub use monetization_repository::MonetizationRepository;
+pub use poll_policy_repository::PollPolicyRepository;
+pub use room_state_repository::{RoomModeChange, RoomStateRepository};
+pub use scheduled_stream_repository::{ScheduleAdherence, ScheduledStreamRepository};
 pub use sql_template_repository::{SqlTemplate, SqlTemplateRepository};
+pub use stream_event_repository::{StreamEventRepository, StreamMetadata};
 pub use stream_repository::{StreamInfo, StreamRepository, TimelinePoint};
 pub use stream_stats_repository::StreamStatsRepository;
+pub use youtube_quota_repository::YouTubeQuotaRepository;
@@ -0,0 +1,351 @@
+/// ModerationEventRepository - moderation_events テーブル専用レポジトリ
+///
+/// Twitch IRC の CLEARCHAT / CLEARMSG を記録し、対象となった chat_messages に
+/// is_deleted フラグを立てます。
+use crate::constants::database as db_constants;
+use crate::database::models::ModerationEvent;
+use crate::database::utils;
+use chrono::{DateTime, Duration};
+use duckdb::Connection;
+use serde::{Deserialize, Serialize};
+
+/// 配信別モデレーション統計
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamModerationStats {
+    pub stream_id: i64,
+    pub stream_title: Option<String>,
+    pub started_at: String,
+    pub timeouts: i64,
+    pub bans: i64,
+    pub deleted_messages: i64,
+    pub unique_targets: i64,
+}
+
+/// 時間帯（1時間バケット）別モデレーション統計
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HourlyModerationStats {
+    pub hour: String,
+    pub timeouts: i64,
+    pub bans: i64,
+    pub deleted_messages: i64,
+}
+
+/// 集計クエリ共通のWHERE句（moderation_events me）
+fn build_filter(
+    channel_id: Option<i64>,
+    stream_id: Option<i64>,
+    start_time: Option<&str>,
+    end_time: Option<&str>,
+) -> (String, Vec<String>) {
+    let mut sql = String::from(" WHERE 1=1");
+    let mut params = Vec::new();
+
+    if let Some(ch_id) = channel_id {
+        sql.push_str(" AND me.channel_id = ?");
+        params.push(ch_id.to_string());
+    }
+    if let Some(st_id) = stream_id {
+        sql.push_str(" AND me.stream_id = ?");
+        params.push(st_id.to_string());
+    }
+    if let Some(start) = start_time {
+        sql.push_str(" AND me.occurred_at >= ?");
+        params.push(start.to_string());
+    }
+    if let Some(end) = end_time {
+        sql.push_str(" AND me.occurred_at <= ?");
+        params.push(end.to_string());
+    }
+
+    (sql, params)
+}
+
+pub struct ModerationEventRepository;
+
+impl ModerationEventRepository {
+    /// モデレーションイベントを保存し、対象のチャットメッセージを削除済みにする
+    ///
+    /// - delete: メッセージIDが一致するメッセージ（IDがない場合は同じユーザー・本文のうち最も新しいもの）
+    /// - timeout / ban: 同じ配信内でそのユーザーがイベント時刻までに送ったメッセージ
+    ///   （配信外のイベントは直前 `MODERATION_OFFLINE_WINDOW_MINUTES` 分以内の配信外のメッセージ）
+    /// - clear: 記録のみ（配信全体を分析対象外にはしない）
+    ///
+    /// 戻り値: 削除済みにしたメッセージ数
+    pub fn insert_and_flag(
+        conn: &Connection,
+        event: &ModerationEvent,
+    ) -> Result<usize, duckdb::Error> {
+        conn.execute(
+            r#"
+            INSERT INTO moderation_events (
+                channel_id, stream_id, platform, event_type, target_user_id, target_user_name,
+                duration_seconds, message_id, message_text, occurred_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            duckdb::params![
+                event.channel_id,
+                event.stream_id,
+                event.platform,
+                event.event_type,
+                event.target_user_id,
+                event.target_user_name,
+                event.duration_seconds,
+                event.message_id,
+                event.message_text,
+                event.occurred_at,
+            ],
+        )?;
+
+        let Some(target_user_name) = event.target_user_name.as_deref() else {
+            return Ok(0);
+        };
+
+        match event.event_type.as_str() {
+            db_constants::MODERATION_DELETE => conn.execute(
+                r#"
+                UPDATE chat_messages SET is_deleted = TRUE
+                WHERE id = (
+                    SELECT id FROM chat_messages
+                    WHERE channel_id = ? AND platform = ? AND is_deleted IS NOT TRUE
+                      AND (
+                        platform_message_id = ?
+                        OR (platform_message_id IS NULL AND user_name = ? AND message = ? AND timestamp <= ?)
+                      )
+                    ORDER BY platform_message_id IS NOT NULL DESC, timestamp DESC
+                    LIMIT 1
+                )
+                "#,
+                duckdb::params![
+                    event.channel_id,
+                    event.platform,
+                    event.message_id,
+                    target_user_name,
+                    event.message_text,
+                    event.occurred_at,
+                ],
+            ),
+            db_constants::MODERATION_TIMEOUT | db_constants::MODERATION_BAN => match event.stream_id
+            {
+                Some(stream_id) => conn.execute(
+                    r#"
+                    UPDATE chat_messages SET is_deleted = TRUE
+                    WHERE channel_id = ? AND platform = ? AND user_name = ?
+                      AND stream_id = ?
+                      AND timestamp <= ? AND is_deleted IS NOT TRUE
+                    "#,
+                    duckdb::params![
+                        event.channel_id,
+                        event.platform,
+                        target_user_name,
+                        stream_id,
+                        event.occurred_at,
+                    ],
+                ),
+                None => {
+                    // 配信外のチャットは配信で区切れないため、イベント直前の時間帯に限定する
+                    let window_start = DateTime::parse_from_rfc3339(&event.occurred_at)
+                        .map(|occurred_at| {
+                            (occurred_at
+                                - Duration::minutes(
+                                    db_constants::MODERATION_OFFLINE_WINDOW_MINUTES,
+                                ))
+                            .to_rfc3339()
+                        })
+                        .unwrap_or_else(|_| event.occurred_at.clone());
+                    conn.execute(
+                        r#"
+                        UPDATE chat_messages SET is_deleted = TRUE
+                        WHERE channel_id = ? AND platform = ? AND user_name = ?
+                          AND stream_id IS NULL
+                          AND timestamp >= ? AND timestamp <= ? AND is_deleted IS NOT TRUE
+                        "#,
+                        duckdb::params![
+                            event.channel_id,
+                            event.platform,
+                            target_user_name,
+                            window_start,
+                            event.occurred_at,
+                        ],
+                    )
+                }
+            },
+            _ => Ok(0),
+        }
+    }
+
+    /// 配信別のタイムアウト・BAN・削除件数を集計
+    pub fn count_by_stream(
+        conn: &Connection,
+        channel_id: Option<i64>,
+        stream_id: Option<i64>,
+        start_time: Option<&str>,
+        end_time: Option<&str>,
+    ) -> Result<Vec<StreamModerationStats>, duckdb::Error> {
+        let (filter, params) = build_filter(channel_id, stream_id, start_time, end_time);
+        let sql = format!(
+            r#"
+            SELECT
+                me.stream_id,
+                s.title,
+                s.started_at::VARCHAR as started_at,
+                COUNT(*) FILTER (WHERE me.event_type = '{timeout}') as timeouts,
+                COUNT(*) FILTER (WHERE me.event_type = '{ban}') as bans,
+                COUNT(*) FILTER (WHERE me.event_type = '{delete}') as deleted_messages,
+                COUNT(DISTINCT me.target_user_name) as unique_targets
+            FROM moderation_events me
+            JOIN streams s ON me.stream_id = s.id
+            {filter}
+            GROUP BY me.stream_id, s.title, s.started_at
+            ORDER BY s.started_at DESC
+            "#,
+            timeout = db_constants::MODERATION_TIMEOUT,
+            ban = db_constants::MODERATION_BAN,
+            delete = db_constants::MODERATION_DELETE,
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let results = utils::query_map_with_params(&mut stmt, &params, |row| {
+            Ok(StreamModerationStats {
+                stream_id: row.get(0)?,
+                stream_title: row.get(1)?,
+                started_at: row.get(2)?,
+                timeouts: row.get(3)?,
+                bans: row.get(4)?,
+                deleted_messages: row.get(5)?,
+                unique_targets: row.get(6)?,
+            })
+        })?;
+
+        results.collect::<Result<Vec<_>, _>>()
+    }
+
+    /// 1時間バケット別のタイムアウト・BAN・削除件数を集計
+    pub fn count_by_hour(
+        conn: &Connection,
+        channel_id: Option<i64>,
+        stream_id: Option<i64>,
+        start_time: Option<&str>,
+        end_time: Option<&str>,
+    ) -> Result<Vec<HourlyModerationStats>, duckdb::Error> {
+        let (filter, params) = build_filter(channel_id, stream_id, start_time, end_time);
+        let sql = format!(
+            r#"
+            SELECT
+                time_bucket(INTERVAL '1 hour', me.occurred_at)::VARCHAR as hour,
+                COUNT(*) FILTER (WHERE me.event_type = '{timeout}') as timeouts,
+                COUNT(*) FILTER (WHERE me.event_type = '{ban}') as bans,
+                COUNT(*) FILTER (WHERE me.event_type = '{delete}') as deleted_messages
+            FROM moderation_events me
+            {filter}
+            GROUP BY hour
+            ORDER BY hour
+            "#,
+            timeout = db_constants::MODERATION_TIMEOUT,
+            ban = db_constants::MODERATION_BAN,
+            delete = db_constants::MODERATION_DELETE,
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let results = utils::query_map_with_params(&mut stmt, &params, |row| {
+            Ok(HourlyModerationStats {
+                hour: row.get(0)?,
+                timeouts: row.get(1)?,
+                bans: row.get(2)?,
+                deleted_messages: row.get(3)?,
+            })
+        })?;
+
+        results.collect::<Result<Vec<_>, _>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::ChatMessage;
+    use crate::database::writer::DatabaseWriter;
+
+    fn chat(user_name: &str, message: &str, timestamp: &str) -> ChatMessage {
+        ChatMessage {
+            channel_id: Some(1),
+            stream_id: Some(10),
+            timestamp: timestamp.to_string(),
+            platform: "twitch".to_string(),
+            user_name: user_name.to_string(),
+            message: message.to_string(),
+            message_type: "normal".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn event(event_type: &str, user_name: &str, message_text: Option<&str>) -> ModerationEvent {
+        ModerationEvent {
+            id: None,
+            channel_id: 1,
+            stream_id: Some(10),
+            platform: "twitch".to_string(),
+            event_type: event_type.to_string(),
+            target_user_id: None,
+            target_user_name: Some(user_name.to_string()),
+            duration_seconds: None,
+            message_id: None,
+            message_text: message_text.map(|m| m.to_string()),
+            occurred_at: "2025-01-06T12:10:00+09:00".to_string(),
+        }
+    }
+
+    fn deleted_count(conn: &Connection) -> i64 {
+        conn.query_row(
+            "SELECT COUNT(*) FROM chat_messages WHERE is_deleted",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(
+        target_os = "windows",
+        ignore = "Database tests are unstable on Windows local environment"
+    )]
+    fn test_insert_and_flag() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::init_database(&conn).unwrap();
+
+        DatabaseWriter::insert_chat_messages_batch(
+            &conn,
+            &[
+                chat("spammer", "buy followers", "2025-01-06T12:00:00+09:00"),
+                chat("spammer", "buy followers", "2025-01-06T12:05:00+09:00"),
+                chat("spammer", "hello", "2025-01-06T12:06:00+09:00"),
+                chat("viewer", "hello", "2025-01-06T12:07:00+09:00"),
+            ],
+        )
+        .unwrap();
+
+        // CLEARMSG は最も新しい一致メッセージのみ削除済みにする
+        let flagged = ModerationEventRepository::insert_and_flag(
+            &conn,
+            &event("delete", "spammer", Some("buy followers")),
+        )
+        .unwrap();
+        assert_eq!(flagged, 1);
+        assert_eq!(deleted_count(&conn), 1);
+
+        // タイムアウトは同じ配信内の残りのメッセージをすべて削除済みにする
+        let flagged =
+            ModerationEventRepository::insert_and_flag(&conn, &event("timeout", "spammer", None))
+                .unwrap();
+        assert_eq!(flagged, 2);
+        assert_eq!(deleted_count(&conn), 3);
+
+        let by_hour =
+            ModerationEventRepository::count_by_hour(&conn, Some(1), None, None, None).unwrap();
+        assert_eq!(by_hour.len(), 1);
+        assert_eq!(by_hour[0].timeouts, 1);
+        assert_eq!(by_hour[0].deleted_messages, 1);
+    }
+}
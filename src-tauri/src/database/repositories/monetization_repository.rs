@@ -0,0 +1,703 @@
+/// MonetizationRepository - ビッツ・サブスク・ギフトの集計
+///
+/// chat_messages.bits（Cheer）と USERNOTICE 由来の sub / resub / subgift メッセージを集計します。
+/// まとめてギフト（submysterygift）の後には受け取り人ごとの subgift が届くため、
+/// ギフト数は subgift のみで数えます。
+///
+/// YouTube の Super Chat・Super Sticker・メンバーシップは金額や通貨を monetization_events に保存し、
+/// ユーザーが登録した currency_rates で基準通貨に換算して集計します。
+use crate::constants::{twitch, youtube};
+use crate::database::models::MonetizationEvent;
+use crate::database::repositories::base::{
+    ChannelFilter, QueryFilter, StreamStatsTimeFilter, TimeRangeFilter,
+};
+use crate::database::utils;
+use duckdb::Connection;
+use serde::{Deserialize, Serialize};
+
+/// 配信別の収益イベント統計
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamMonetizationStats {
+    pub stream_id: i64,
+    pub stream_title: Option<String>,
+    pub started_at: String,
+    pub total_bits: i64,
+    pub cheer_count: i64,
+    pub subs: i64,
+    pub gifted_subs: i64,
+    pub peak_viewers: Option<i32>,
+}
+
+/// 時間帯（1時間バケット）別の収益イベント統計
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HourlyMonetizationStats {
+    pub hour: String,
+    pub total_bits: i64,
+    pub cheer_count: i64,
+    pub subs: i64,
+    pub gifted_subs: i64,
+    pub peak_viewers: Option<i32>,
+}
+
+/// チャッター別の収益イベント統計
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatterMonetizationStats {
+    pub user_id: Option<String>,
+    pub user_name: String,
+    pub display_name: Option<String>,
+    pub total_bits: i64,
+    pub cheer_count: i64,
+    pub gifted_subs: i64,
+}
+
+/// 通貨別の Super Chat 等の売上
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrencyRevenue {
+    pub currency: Option<String>,
+    pub event_count: i64,
+    /// 元の通貨での合計金額
+    pub total_amount: f64,
+    /// 換算レート（未登録の通貨は None）
+    pub rate: Option<f64>,
+    /// 基準通貨に換算した合計金額（未登録の通貨は None）
+    pub normalized_amount: Option<f64>,
+}
+
+/// 配信別の Super Chat 等の売上とメンバーシップ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamRevenue {
+    pub stream_id: i64,
+    pub channel_id: i64,
+    pub stream_title: Option<String>,
+    pub started_at: String,
+    pub paid_count: i64,
+    /// 基準通貨に換算した合計金額（換算レート未登録の通貨は含まない）
+    pub normalized_amount: f64,
+    /// 換算レート未登録のため合計に含まれていない件数
+    pub unconverted_count: i64,
+    pub new_members: i64,
+    pub gifted_memberships: i64,
+}
+
+/// チャンネル別の Super Chat 等の売上とメンバーシップ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelRevenue {
+    pub channel_id: i64,
+    pub channel_name: String,
+    pub paid_count: i64,
+    /// 基準通貨に換算した合計金額（換算レート未登録の通貨は含まない）
+    pub normalized_amount: f64,
+    /// 換算レート未登録のため合計に含まれていない件数
+    pub unconverted_count: i64,
+    pub new_members: i64,
+    pub gifted_memberships: i64,
+}
+
+/// chat_messages（cm）用のWHERE句
+fn chat_filter(channel: &ChannelFilter, time_range: &TimeRangeFilter) -> (String, Vec<String>) {
+    let (channel_clause, mut params) = channel.to_where_clause("cm");
+    let (time_clause, time_params) = time_range.to_where_clause("cm");
+    params.extend(time_params);
+    (
+        format!(" WHERE 1=1{}{}", channel_clause, time_clause),
+        params,
+    )
+}
+
+/// stream_stats（ss）+ streams（s）用のWHERE句
+fn viewer_filter(channel: &ChannelFilter, time_range: &TimeRangeFilter) -> (String, Vec<String>) {
+    let mut sql = String::from(" WHERE 1=1");
+    let mut params = Vec::new();
+
+    if let Some(ch_id) = channel.channel_id {
+        sql.push_str(" AND s.channel_id = ?");
+        params.push(ch_id.to_string());
+    }
+    if let Some(st_id) = channel.stream_id {
+        sql.push_str(" AND ss.stream_id = ?");
+        params.push(st_id.to_string());
+    }
+
+    let (time_clause, time_params) = StreamStatsTimeFilter {
+        start_time: time_range.start_time.clone(),
+        end_time: time_range.end_time.clone(),
+    }
+    .to_where_clause("ss");
+    sql.push_str(&time_clause);
+    params.extend(time_params);
+
+    (sql, params)
+}
+
+/// 集計列（bits, cheer数, サブスク数, ギフト数）
+fn aggregate_columns() -> String {
+    format!(
+        r#"
+        COALESCE(SUM(cm.bits), 0)::BIGINT as total_bits,
+        COUNT(cm.bits) as cheer_count,
+        COUNT(*) FILTER (WHERE cm.message_type IN ('{sub}', '{resub}')) as subs,
+        COUNT(*) FILTER (WHERE cm.message_type = '{subgift}') as gifted_subs
+        "#,
+        sub = twitch::MESSAGE_TYPE_SUB,
+        resub = twitch::MESSAGE_TYPE_RESUB,
+        subgift = twitch::MESSAGE_TYPE_SUBGIFT,
+    )
+}
+
+/// monetization_events（me）用のWHERE句
+fn event_filter(channel: &ChannelFilter, time_range: &TimeRangeFilter) -> (String, Vec<String>) {
+    let (channel_clause, mut params) = channel.to_where_clause("me");
+    let (time_clause, time_params) = time_range.to_where_clause("me");
+    params.extend(time_params);
+    (
+        format!(" WHERE 1=1{}{}", channel_clause, time_clause),
+        params,
+    )
+}
+
+/// 売上の集計列（monetization_events me + currency_rates cr）
+fn revenue_columns() -> String {
+    format!(
+        r#"
+        COUNT(me.amount_micros) as paid_count,
+        COALESCE(SUM(me.amount_micros * cr.rate) / 1000000.0, 0) as normalized_amount,
+        COUNT(me.amount_micros) FILTER (WHERE cr.rate IS NULL) as unconverted_count,
+        COUNT(*) FILTER (WHERE me.event_type = '{sponsor}') as new_members,
+        COALESCE(SUM(me.gift_count), 0)::BIGINT as gifted_memberships
+        "#,
+        sponsor = youtube::MESSAGE_TYPE_SPONSOR,
+    )
+}
+
+/// 収益イベントに該当するメッセージのみを対象にする条件
+fn monetization_condition() -> String {
+    format!(
+        " AND (cm.bits IS NOT NULL OR cm.message_type IN ('{}', '{}', '{}'))",
+        twitch::MESSAGE_TYPE_SUB,
+        twitch::MESSAGE_TYPE_RESUB,
+        twitch::MESSAGE_TYPE_SUBGIFT,
+    )
+}
+
+pub struct MonetizationRepository;
+
+impl MonetizationRepository {
+    /// 配信別にビッツ・サブスク・ギフトと最大視聴者数を集計
+    pub fn stats_by_stream(
+        conn: &Connection,
+        channel: &ChannelFilter,
+        time_range: &TimeRangeFilter,
+    ) -> Result<Vec<StreamMonetizationStats>, duckdb::Error> {
+        let (filter, params) = chat_filter(channel, time_range);
+        // 最大視聴者数は期間で絞らず配信全体から取る
+        let sql = format!(
+            r#"
+            WITH monetization AS (
+                SELECT
+                    cm.stream_id,
+                    {columns}
+                FROM chat_messages cm
+                {filter}{condition}
+                GROUP BY cm.stream_id
+            )
+            SELECT
+                m.stream_id,
+                s.title,
+                s.started_at::VARCHAR as started_at,
+                m.total_bits,
+                m.cheer_count,
+                m.subs,
+                m.gifted_subs,
+                (SELECT MAX(ss.viewer_count) FROM stream_stats ss WHERE ss.stream_id = m.stream_id) as peak_viewers
+            FROM monetization m
+            JOIN streams s ON m.stream_id = s.id
+            ORDER BY s.started_at DESC
+            "#,
+            columns = aggregate_columns(),
+            condition = monetization_condition(),
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let results = utils::query_map_with_params(&mut stmt, &params, |row| {
+            Ok(StreamMonetizationStats {
+                stream_id: row.get(0)?,
+                stream_title: row.get(1)?,
+                started_at: row.get(2)?,
+                total_bits: row.get(3)?,
+                cheer_count: row.get(4)?,
+                subs: row.get(5)?,
+                gifted_subs: row.get(6)?,
+                peak_viewers: row.get(7)?,
+            })
+        })?;
+
+        results.collect::<Result<Vec<_>, _>>()
+    }
+
+    /// 1時間バケット別にビッツ・サブスク・ギフトとその時間帯の最大視聴者数を集計
+    ///
+    /// 視聴者数の相関を取れるよう、収益イベントのない配信中の時間帯も 0 件として含めます。
+    pub fn stats_by_hour(
+        conn: &Connection,
+        channel: &ChannelFilter,
+        time_range: &TimeRangeFilter,
+    ) -> Result<Vec<HourlyMonetizationStats>, duckdb::Error> {
+        let (viewer_where, mut params) = viewer_filter(channel, time_range);
+        let (filter, chat_params) = chat_filter(channel, time_range);
+        params.extend(chat_params);
+
+        let sql = format!(
+            r#"
+            WITH peaks AS (
+                SELECT
+                    time_bucket(INTERVAL '1 hour', ss.collected_at) as hour,
+                    MAX(ss.viewer_count) as peak_viewers
+                FROM stream_stats ss
+                JOIN streams s ON ss.stream_id = s.id
+                {viewer_where}
+                GROUP BY hour
+            ),
+            monetization AS (
+                SELECT
+                    time_bucket(INTERVAL '1 hour', cm.timestamp) as hour,
+                    {columns}
+                FROM chat_messages cm
+                {filter}{condition}
+                GROUP BY hour
+            )
+            SELECT
+                COALESCE(m.hour, p.hour)::VARCHAR as hour,
+                COALESCE(m.total_bits, 0) as total_bits,
+                COALESCE(m.cheer_count, 0) as cheer_count,
+                COALESCE(m.subs, 0) as subs,
+                COALESCE(m.gifted_subs, 0) as gifted_subs,
+                p.peak_viewers
+            FROM monetization m
+            FULL OUTER JOIN peaks p ON p.hour = m.hour
+            ORDER BY 1
+            "#,
+            columns = aggregate_columns(),
+            condition = monetization_condition(),
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let results = utils::query_map_with_params(&mut stmt, &params, |row| {
+            Ok(HourlyMonetizationStats {
+                hour: row.get(0)?,
+                total_bits: row.get(1)?,
+                cheer_count: row.get(2)?,
+                subs: row.get(3)?,
+                gifted_subs: row.get(4)?,
+                peak_viewers: row.get(5)?,
+            })
+        })?;
+
+        results.collect::<Result<Vec<_>, _>>()
+    }
+
+    /// チャッター別にビッツとギフト数を集計（ビッツの多い順）
+    pub fn top_cheerers(
+        conn: &Connection,
+        channel: &ChannelFilter,
+        time_range: &TimeRangeFilter,
+        limit: i32,
+    ) -> Result<Vec<ChatterMonetizationStats>, duckdb::Error> {
+        let (filter, params) = chat_filter(channel, time_range);
+        let sql = format!(
+            r#"
+            SELECT
+                cm.user_id,
+                cm.user_name,
+                MAX(cm.display_name) as display_name,
+                COALESCE(SUM(cm.bits), 0)::BIGINT as total_bits,
+                COUNT(cm.bits) as cheer_count,
+                COUNT(*) FILTER (WHERE cm.message_type = '{subgift}') as gifted_subs
+            FROM chat_messages cm
+            {filter}
+              AND (cm.bits IS NOT NULL OR cm.message_type = '{subgift}')
+            GROUP BY cm.user_id, cm.user_name
+            ORDER BY total_bits DESC, gifted_subs DESC
+            LIMIT {limit}
+            "#,
+            subgift = twitch::MESSAGE_TYPE_SUBGIFT,
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let results = utils::query_map_with_params(&mut stmt, &params, |row| {
+            Ok(ChatterMonetizationStats {
+                user_id: row.get(0)?,
+                user_name: row.get(1)?,
+                display_name: row.get(2)?,
+                total_bits: row.get(3)?,
+                cheer_count: row.get(4)?,
+                gifted_subs: row.get(5)?,
+            })
+        })?;
+
+        results.collect::<Result<Vec<_>, _>>()
+    }
+
+    /// 収益イベントを保存（同じメッセージIDのイベントは無視）
+    pub fn insert_events(
+        conn: &Connection,
+        events: &[MonetizationEvent],
+    ) -> Result<usize, duckdb::Error> {
+        let mut stmt = conn.prepare(
+            r#"
+            INSERT INTO monetization_events (
+                platform, platform_message_id, channel_id, stream_id, event_type,
+                amount_micros, currency, amount_display, tier, membership_level,
+                membership_months, gift_count, sticker_id, timestamp
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT DO NOTHING
+            "#,
+        )?;
+
+        let mut inserted = 0;
+        for event in events {
+            inserted += stmt.execute(duckdb::params![
+                event.platform,
+                event.platform_message_id,
+                event.channel_id,
+                event.stream_id,
+                event.event_type,
+                event.amount_micros,
+                event.currency,
+                event.amount_display,
+                event.tier,
+                event.membership_level,
+                event.membership_months,
+                event.gift_count,
+                event.sticker_id,
+                event.timestamp,
+            ])?;
+        }
+
+        Ok(inserted)
+    }
+
+    /// 通貨別に Super Chat・Super Sticker 等の金額を集計（換算後の金額の多い順）
+    pub fn revenue_by_currency(
+        conn: &Connection,
+        channel: &ChannelFilter,
+        time_range: &TimeRangeFilter,
+    ) -> Result<Vec<CurrencyRevenue>, duckdb::Error> {
+        let (filter, params) = event_filter(channel, time_range);
+        let sql = format!(
+            r#"
+            SELECT
+                me.currency,
+                COUNT(*) as event_count,
+                SUM(me.amount_micros) / 1000000.0 as total_amount,
+                cr.rate,
+                SUM(me.amount_micros * cr.rate) / 1000000.0 as normalized_amount
+            FROM monetization_events me
+            LEFT JOIN currency_rates cr ON cr.currency = me.currency
+            {filter}
+              AND me.amount_micros IS NOT NULL
+            GROUP BY me.currency, cr.rate
+            ORDER BY normalized_amount DESC NULLS LAST, total_amount DESC
+            "#
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let results = utils::query_map_with_params(&mut stmt, &params, |row| {
+            Ok(CurrencyRevenue {
+                currency: row.get(0)?,
+                event_count: row.get(1)?,
+                total_amount: row.get(2)?,
+                rate: row.get(3)?,
+                normalized_amount: row.get(4)?,
+            })
+        })?;
+
+        results.collect::<Result<Vec<_>, _>>()
+    }
+
+    /// 配信別に換算後の売上とメンバーシップを集計
+    pub fn revenue_by_stream(
+        conn: &Connection,
+        channel: &ChannelFilter,
+        time_range: &TimeRangeFilter,
+    ) -> Result<Vec<StreamRevenue>, duckdb::Error> {
+        let (filter, params) = event_filter(channel, time_range);
+        let sql = format!(
+            r#"
+            SELECT
+                s.id,
+                s.channel_id,
+                s.title,
+                s.started_at::VARCHAR as started_at,
+                {columns}
+            FROM monetization_events me
+            JOIN streams s ON me.stream_id = s.id
+            LEFT JOIN currency_rates cr ON cr.currency = me.currency
+            {filter}
+            GROUP BY s.id, s.channel_id, s.title, s.started_at
+            ORDER BY s.started_at DESC
+            "#,
+            columns = revenue_columns(),
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let results = utils::query_map_with_params(&mut stmt, &params, |row| {
+            Ok(StreamRevenue {
+                stream_id: row.get(0)?,
+                channel_id: row.get(1)?,
+                stream_title: row.get(2)?,
+                started_at: row.get(3)?,
+                paid_count: row.get(4)?,
+                normalized_amount: row.get(5)?,
+                unconverted_count: row.get(6)?,
+                new_members: row.get(7)?,
+                gifted_memberships: row.get(8)?,
+            })
+        })?;
+
+        results.collect::<Result<Vec<_>, _>>()
+    }
+
+    /// チャンネル別に換算後の売上とメンバーシップを集計（売上の多い順）
+    pub fn revenue_by_channel(
+        conn: &Connection,
+        channel: &ChannelFilter,
+        time_range: &TimeRangeFilter,
+    ) -> Result<Vec<ChannelRevenue>, duckdb::Error> {
+        let (filter, params) = event_filter(channel, time_range);
+        let sql = format!(
+            r#"
+            SELECT
+                c.id,
+                COALESCE(NULLIF(c.display_name, ''), c.channel_name) as channel_name,
+                {columns}
+            FROM monetization_events me
+            JOIN channels c ON me.channel_id = c.id
+            LEFT JOIN currency_rates cr ON cr.currency = me.currency
+            {filter}
+            GROUP BY c.id, c.display_name, c.channel_name
+            ORDER BY normalized_amount DESC
+            "#,
+            columns = revenue_columns(),
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let results = utils::query_map_with_params(&mut stmt, &params, |row| {
+            Ok(ChannelRevenue {
+                channel_id: row.get(0)?,
+                channel_name: row.get(1)?,
+                paid_count: row.get(2)?,
+                normalized_amount: row.get(3)?,
+                unconverted_count: row.get(4)?,
+                new_members: row.get(5)?,
+                gifted_memberships: row.get(6)?,
+            })
+        })?;
+
+        results.collect::<Result<Vec<_>, _>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::ChatMessage;
+    use crate::database::writer::DatabaseWriter;
+
+    fn chat(
+        user_name: &str,
+        message_type: &str,
+        bits: Option<i64>,
+        timestamp: &str,
+    ) -> ChatMessage {
+        ChatMessage {
+            channel_id: Some(1),
+            stream_id: Some(10),
+            timestamp: timestamp.to_string(),
+            platform: "twitch".to_string(),
+            user_id: Some(format!("id-{}", user_name)),
+            user_name: user_name.to_string(),
+            message: "cheer100".to_string(),
+            message_type: message_type.to_string(),
+            bits,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    #[cfg_attr(
+        target_os = "windows",
+        ignore = "Database tests are unstable on Windows local environment"
+    )]
+    fn test_monetization_stats() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::init_database(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO channels (id, platform, channel_id, channel_name) VALUES (1, 'twitch', 'streamer', 'streamer')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO streams (id, channel_id, stream_id, started_at) VALUES (10, 1, 's1', '2025-01-06 12:00:00')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO stream_stats (stream_id, collected_at, viewer_count) VALUES (10, '2025-01-06 12:30:00', 300)",
+            [],
+        )
+        .unwrap();
+
+        DatabaseWriter::insert_chat_messages_batch(
+            &conn,
+            &[
+                chat("alice", "cheer", Some(100), "2025-01-06 12:10:00"),
+                chat("alice", "cheer", Some(500), "2025-01-06 12:20:00"),
+                chat("bob", "cheer", Some(50), "2025-01-06 13:05:00"),
+                chat("carol", "subgift", None, "2025-01-06 12:40:00"),
+                chat("dave", "resub", None, "2025-01-06 12:45:00"),
+                chat("erin", "normal", None, "2025-01-06 12:50:00"),
+            ],
+        )
+        .unwrap();
+
+        let channel = ChannelFilter {
+            channel_id: Some(1),
+            stream_id: None,
+        };
+        let time_range = TimeRangeFilter::default();
+
+        let by_stream =
+            MonetizationRepository::stats_by_stream(&conn, &channel, &time_range).unwrap();
+        assert_eq!(by_stream.len(), 1);
+        assert_eq!(by_stream[0].total_bits, 650);
+        assert_eq!(by_stream[0].cheer_count, 3);
+        assert_eq!(by_stream[0].subs, 1);
+        assert_eq!(by_stream[0].gifted_subs, 1);
+        assert_eq!(by_stream[0].peak_viewers, Some(300));
+
+        let by_hour = MonetizationRepository::stats_by_hour(&conn, &channel, &time_range).unwrap();
+        assert_eq!(by_hour.len(), 2);
+        assert_eq!(by_hour[0].total_bits, 600);
+        assert_eq!(by_hour[0].peak_viewers, Some(300));
+        assert_eq!(by_hour[1].peak_viewers, None);
+
+        let top = MonetizationRepository::top_cheerers(&conn, &channel, &time_range, 10).unwrap();
+        let names: Vec<&str> = top.iter().map(|c| c.user_name.as_str()).collect();
+        assert_eq!(names, vec!["alice", "bob", "carol"]);
+    }
+    fn event(id: &str, event_type: &str, amount: Option<(i64, &str)>) -> MonetizationEvent {
+        MonetizationEvent {
+            id: None,
+            platform: "youtube".to_string(),
+            platform_message_id: id.to_string(),
+            channel_id: Some(1),
+            stream_id: Some(10),
+            event_type: event_type.to_string(),
+            amount_micros: amount.map(|(micros, _)| micros),
+            currency: amount.map(|(_, currency)| currency.to_string()),
+            amount_display: None,
+            tier: None,
+            membership_level: None,
+            membership_months: None,
+            gift_count: None,
+            sticker_id: None,
+            timestamp: "2025-01-06 12:10:00".to_string(),
+        }
+    }
+
+    #[test]
+    #[cfg_attr(
+        target_os = "windows",
+        ignore = "Database tests are unstable on Windows local environment"
+    )]
+    fn test_super_chat_revenue_is_normalized() {
+        use crate::database::models::CurrencyRate;
+        use crate::database::repositories::CurrencyRateRepository;
+
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::init_database(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO channels (id, platform, channel_id, channel_name) VALUES (1, 'youtube', 'UCstreamer', 'streamer')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO streams (id, channel_id, stream_id, started_at) VALUES (10, 1, 'v1', '2025-01-06 12:00:00')",
+            [],
+        )
+        .unwrap();
+
+        let mut gift = event("gift", "membershipgifting", None);
+        gift.gift_count = Some(5);
+        let events = vec![
+            event("sc-1", "superchat", Some((1_000_000_000, "JPY"))),
+            event("sc-2", "superchat", Some((5_000_000, "USD"))),
+            event("ss-1", "supersticker", Some((2_000_000, "EUR"))),
+            event("member", "sponsor", None),
+            gift,
+        ];
+        assert_eq!(
+            MonetizationRepository::insert_events(&conn, &events).unwrap(),
+            5
+        );
+        // 再試行で同じイベントを受け取っても二重に保存しない
+        assert_eq!(
+            MonetizationRepository::insert_events(&conn, &events[..1]).unwrap(),
+            0
+        );
+
+        let rates = vec![CurrencyRate {
+            currency: "USD".to_string(),
+            base_currency: "JPY".to_string(),
+            rate: 150.0,
+            updated_at: None,
+        }];
+        CurrencyRateRepository::replace_rates(&conn, "JPY", &rates, "2025-01-06 00:00:00").unwrap();
+        assert_eq!(
+            CurrencyRateRepository::base_currency(&conn)
+                .unwrap()
+                .as_deref(),
+            Some("JPY")
+        );
+
+        let channel = ChannelFilter::default();
+        let time_range = TimeRangeFilter::default();
+
+        let by_currency =
+            MonetizationRepository::revenue_by_currency(&conn, &channel, &time_range).unwrap();
+        let currencies: Vec<(Option<&str>, Option<f64>)> = by_currency
+            .iter()
+            .map(|c| (c.currency.as_deref(), c.normalized_amount))
+            .collect();
+        assert_eq!(
+            currencies,
+            vec![
+                (Some("JPY"), Some(1000.0)),
+                (Some("USD"), Some(750.0)),
+                (Some("EUR"), None),
+            ]
+        );
+
+        let by_stream =
+            MonetizationRepository::revenue_by_stream(&conn, &channel, &time_range).unwrap();
+        assert_eq!(by_stream.len(), 1);
+        assert_eq!(by_stream[0].paid_count, 3);
+        assert_eq!(by_stream[0].normalized_amount, 1750.0);
+        assert_eq!(by_stream[0].unconverted_count, 1);
+        assert_eq!(by_stream[0].new_members, 1);
+        assert_eq!(by_stream[0].gifted_memberships, 5);
+
+        let by_channel =
+            MonetizationRepository::revenue_by_channel(&conn, &channel, &time_range).unwrap();
+        assert_eq!(by_channel.len(), 1);
+        assert_eq!(by_channel[0].channel_name, "streamer");
+        assert_eq!(by_channel[0].normalized_amount, 1750.0);
+    }
+}
@@ -0,0 +1,106 @@
+/// PollPolicyRepository - channel_poll_policiesテーブル専用レポジトリ
+///
+/// チャンネル別ポーリングポリシーと、開始時間帯の学習に使う配信開始時刻を扱います。
+use crate::database::models::PollPolicy;
+use chrono::Local;
+use duckdb::{Connection, OptionalExt};
+
+pub struct PollPolicyRepository;
+
+impl PollPolicyRepository {
+    /// チャンネルのポリシーを取得（未設定の場合は None）
+    pub fn get(conn: &Connection, channel_id: i64) -> Result<Option<PollPolicy>, duckdb::Error> {
+        conn.query_row(
+            r#"
+            SELECT
+                channel_id,
+                live_interval,
+                offline_interval,
+                quiet_hours_start,
+                quiet_hours_end,
+                quiet_interval,
+                start_window_interval,
+                CAST(updated_at AS VARCHAR) as updated_at
+            FROM channel_poll_policies
+            WHERE channel_id = ?
+            "#,
+            [channel_id],
+            |row| {
+                Ok(PollPolicy {
+                    channel_id: row.get(0)?,
+                    live_interval: row.get(1)?,
+                    offline_interval: row.get(2)?,
+                    quiet_hours_start: row.get(3)?,
+                    quiet_hours_end: row.get(4)?,
+                    quiet_interval: row.get(5)?,
+                    start_window_interval: row.get(6)?,
+                    updated_at: row.get(7)?,
+                })
+            },
+        )
+        .optional()
+    }
+
+    /// ポリシーを挿入または更新（UPSERT）
+    pub fn upsert(conn: &Connection, policy: &PollPolicy) -> Result<(), duckdb::Error> {
+        let now = Local::now().to_rfc3339();
+        conn.execute(
+            r#"
+            INSERT INTO channel_poll_policies (
+                channel_id, live_interval, offline_interval, quiet_hours_start,
+                quiet_hours_end, quiet_interval, start_window_interval, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(channel_id) DO UPDATE SET
+                live_interval = excluded.live_interval,
+                offline_interval = excluded.offline_interval,
+                quiet_hours_start = excluded.quiet_hours_start,
+                quiet_hours_end = excluded.quiet_hours_end,
+                quiet_interval = excluded.quiet_interval,
+                start_window_interval = excluded.start_window_interval,
+                updated_at = excluded.updated_at
+            "#,
+            duckdb::params![
+                policy.channel_id,
+                policy.live_interval,
+                policy.offline_interval,
+                policy.quiet_hours_start,
+                policy.quiet_hours_end,
+                policy.quiet_interval,
+                policy.start_window_interval,
+                now,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// ポリシーを削除（channels.poll_interval に戻す）
+    pub fn delete(conn: &Connection, channel_id: i64) -> Result<(), duckdb::Error> {
+        conn.execute(
+            "DELETE FROM channel_poll_policies WHERE channel_id = ?",
+            [channel_id],
+        )?;
+        Ok(())
+    }
+
+    /// 直近の配信開始時刻を取得（UTC、開始時間帯の学習用）
+    pub fn get_recent_start_times(
+        conn: &Connection,
+        channel_id: i64,
+        lookback_days: i32,
+    ) -> Result<Vec<String>, duckdb::Error> {
+        let sql = format!(
+            r#"
+            SELECT CAST(started_at AS VARCHAR)
+            FROM streams
+            WHERE channel_id = ?
+              AND started_at >= CAST(CURRENT_TIMESTAMP AS TIMESTAMP) - INTERVAL '{} days'
+            ORDER BY started_at
+            "#,
+            lookback_days
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map([channel_id], |row| row.get::<_, String>(0))?;
+        rows.collect::<Result<Vec<_>, _>>()
+    }
+}
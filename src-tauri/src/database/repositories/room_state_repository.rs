@@ -0,0 +1,198 @@
+/// RoomStateRepository - room_state_periods テーブル専用レポジトリ
+///
+/// Twitch ROOMSTATE で通知されるチャット制限モード（スロー・エモート限定など）を
+/// チャンネルごとの有効期間として記録します。ended_at が NULL の行は現在も有効です。
+use crate::database::models::RoomStatePeriod;
+use crate::database::utils;
+use duckdb::Connection;
+
+/// ROOMSTATE から読み取ったモード1件分の変更
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoomModeChange {
+    pub mode: &'static str,
+    pub enabled: bool,
+    /// スローモードの間隔秒数 / フォロワー限定の必要フォロー分数
+    pub setting: Option<i64>,
+}
+
+pub struct RoomStateRepository;
+
+impl RoomStateRepository {
+    /// ROOMSTATE の変更を期間として反映
+    ///
+    /// - 有効化: 開いている期間がなければ開始（設定値が変わった場合は閉じてから開き直す）
+    /// - 無効化: 開いている期間を閉じる
+    ///
+    /// 接続時の ROOMSTATE には全モードが含まれるため、同じ状態の再通知は無視します。
+    /// 戻り値: 開始・終了した期間の数
+    pub fn apply_changes(
+        conn: &Connection,
+        channel_id: i64,
+        changes: &[RoomModeChange],
+        changed_at: &str,
+    ) -> Result<usize, duckdb::Error> {
+        let mut updated = 0;
+
+        for change in changes {
+            let open: Option<(i64, Option<i64>)> = conn
+                .prepare(
+                    r#"
+                    SELECT id, setting FROM room_state_periods
+                    WHERE channel_id = ? AND mode = ? AND ended_at IS NULL
+                    ORDER BY started_at DESC
+                    LIMIT 1
+                    "#,
+                )?
+                .query_map(duckdb::params![channel_id, change.mode], |row| {
+                    Ok((row.get(0)?, row.get(1)?))
+                })?
+                .next()
+                .transpose()?;
+
+            if let Some((id, setting)) = open {
+                if change.enabled && setting == change.setting {
+                    continue;
+                }
+                conn.execute(
+                    "UPDATE room_state_periods SET ended_at = ? WHERE id = ?",
+                    duckdb::params![changed_at, id],
+                )?;
+                updated += 1;
+            }
+
+            if change.enabled {
+                conn.execute(
+                    r#"
+                    INSERT INTO room_state_periods (channel_id, mode, setting, started_at)
+                    VALUES (?, ?, ?, ?)
+                    "#,
+                    duckdb::params![channel_id, change.mode, change.setting, changed_at],
+                )?;
+                updated += 1;
+            }
+        }
+
+        Ok(updated)
+    }
+
+    /// 指定期間と重なる制限モード期間を取得
+    ///
+    /// stream_id を指定した場合はその配信のチャンネルを対象にします。
+    pub fn get_periods(
+        conn: &Connection,
+        channel_id: Option<i64>,
+        stream_id: Option<i64>,
+        start_time: Option<&str>,
+        end_time: Option<&str>,
+    ) -> Result<Vec<RoomStatePeriod>, duckdb::Error> {
+        let mut sql = String::from(
+            r#"
+            SELECT id, channel_id, mode, setting,
+                   CAST(started_at AS VARCHAR), CAST(ended_at AS VARCHAR)
+            FROM room_state_periods
+            WHERE 1=1
+            "#,
+        );
+        let mut params = Vec::new();
+
+        if let Some(ch_id) = channel_id {
+            sql.push_str(" AND channel_id = ?");
+            params.push(ch_id.to_string());
+        }
+        if let Some(st_id) = stream_id {
+            sql.push_str(" AND channel_id = (SELECT channel_id FROM streams WHERE id = ?)");
+            params.push(st_id.to_string());
+        }
+        if let Some(start) = start_time {
+            sql.push_str(" AND (ended_at IS NULL OR ended_at >= ?)");
+            params.push(start.to_string());
+        }
+        if let Some(end) = end_time {
+            sql.push_str(" AND started_at <= ?");
+            params.push(end.to_string());
+        }
+        sql.push_str(" ORDER BY started_at");
+
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = utils::query_map_with_params(&mut stmt, &params, |row| {
+            Ok(RoomStatePeriod {
+                id: row.get(0)?,
+                channel_id: row.get(1)?,
+                mode: row.get(2)?,
+                setting: row.get(3)?,
+                started_at: row.get(4)?,
+                ended_at: row.get(5)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::database as db_constants;
+
+    fn change(mode: &'static str, enabled: bool, setting: Option<i64>) -> RoomModeChange {
+        RoomModeChange {
+            mode,
+            enabled,
+            setting,
+        }
+    }
+
+    #[test]
+    #[cfg_attr(
+        target_os = "windows",
+        ignore = "Database tests are unstable on Windows local environment"
+    )]
+    fn test_apply_changes_opens_and_closes_periods() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::init_database(&conn).unwrap();
+
+        // 接続時の全モード通知（スローモード30秒のみ有効）
+        let joined = [
+            change(db_constants::ROOM_MODE_SLOW, true, Some(30)),
+            change(db_constants::ROOM_MODE_EMOTE_ONLY, false, None),
+        ];
+        RoomStateRepository::apply_changes(&conn, 1, &joined, "2025-01-06 12:00:00").unwrap();
+        // 再接続による同じ状態の再通知は期間を分割しない
+        assert_eq!(
+            RoomStateRepository::apply_changes(&conn, 1, &joined, "2025-01-06 12:05:00").unwrap(),
+            0
+        );
+
+        let emote_only = [change(db_constants::ROOM_MODE_EMOTE_ONLY, true, None)];
+        RoomStateRepository::apply_changes(&conn, 1, &emote_only, "2025-01-06 12:10:00").unwrap();
+        let slow_changed = [change(db_constants::ROOM_MODE_SLOW, true, Some(60))];
+        RoomStateRepository::apply_changes(&conn, 1, &slow_changed, "2025-01-06 12:20:00").unwrap();
+        let emote_off = [change(db_constants::ROOM_MODE_EMOTE_ONLY, false, None)];
+        RoomStateRepository::apply_changes(&conn, 1, &emote_off, "2025-01-06 12:30:00").unwrap();
+
+        let periods = RoomStateRepository::get_periods(&conn, Some(1), None, None, None).unwrap();
+        let summary: Vec<(&str, Option<i64>, bool)> = periods
+            .iter()
+            .map(|p| (p.mode.as_str(), p.setting, p.ended_at.is_some()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("slow", Some(30), true),
+                ("emote_only", None, true),
+                ("slow", Some(60), false),
+            ]
+        );
+
+        // 12:35 以降と重なるのは現在も有効なスローモードのみ
+        let active = RoomStateRepository::get_periods(
+            &conn,
+            Some(1),
+            None,
+            Some("2025-01-06 12:35:00"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].setting, Some(60));
+    }
+}
@@ -0,0 +1,567 @@
+/// ScheduledStreamRepository - 配信予定（scheduled_streams）の管理と予定遵守率の集計
+///
+/// Twitch の配信スケジュールと YouTube の配信予定枠を保存し、実際に始まった配信と対応付けます。
+/// 予定時刻を過ぎて一定時間内に始まらなかった予定は「不履行」として集計します。
+use crate::constants::database as db_constants;
+use crate::database::models::ScheduledStream;
+use crate::database::utils;
+use duckdb::Connection;
+use serde::{Deserialize, Serialize};
+
+/// チャンネル別の予定遵守率
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleAdherence {
+    pub channel_id: i64,
+    pub channel_name: String,
+    pub platform: String,
+    /// 予定時刻を過ぎた予定の数（開始・取り消し・不履行の合計）
+    pub scheduled_count: i64,
+    pub started_count: i64,
+    pub canceled_count: i64,
+    /// 予定時刻から許容幅を過ぎても始まらなかった予定の数
+    pub missed_count: i64,
+    /// started_count / scheduled_count
+    pub adherence_rate: f64,
+    /// 予定時刻からの開始の遅れ（分、早く始まった場合は負）
+    pub avg_delay_minutes: Option<f64>,
+    pub median_delay_minutes: Option<f64>,
+    /// 開始した予定のうち定刻（遅れが閾値以内）に始まった割合
+    pub on_time_rate: Option<f64>,
+}
+
+fn row_to_scheduled_stream(row: &duckdb::Row) -> Result<ScheduledStream, duckdb::Error> {
+    Ok(ScheduledStream {
+        id: row.get(0)?,
+        channel_id: row.get(1)?,
+        platform: row.get(2)?,
+        schedule_id: row.get(3)?,
+        title: row.get(4)?,
+        category: row.get(5)?,
+        scheduled_start: row.get(6)?,
+        scheduled_end: row.get(7)?,
+        status: row.get(8)?,
+        stream_id: row.get(9)?,
+        actual_start: row.get(10)?,
+    })
+}
+
+pub struct ScheduledStreamRepository;
+
+impl ScheduledStreamRepository {
+    /// チャンネルの配信予定を取得結果に合わせて更新
+    ///
+    /// 取得した予定を保存し、取得結果から消えた未来の予定を取り消し扱いにします。
+    /// `horizon` を指定した場合はその時刻までの予定のみ取り消します（ページングで取得しきれない先の予定を守るため）。
+    /// 取り消した予定の数を返します。
+    pub fn sync_channel_schedule(
+        conn: &Connection,
+        channel_id: i64,
+        platform: &str,
+        entries: &[ScheduledStream],
+        horizon: Option<&str>,
+        now: &str,
+    ) -> Result<usize, duckdb::Error> {
+        Self::upsert_schedules(conn, entries, now)?;
+
+        let mut sql = format!(
+            r#"
+            UPDATE scheduled_streams
+            SET status = '{canceled}', updated_at = CAST(? AS TIMESTAMP)
+            WHERE channel_id = ?
+              AND platform = ?
+              AND status = '{scheduled}'
+              AND scheduled_start > CAST(? AS TIMESTAMP)
+            "#,
+            canceled = db_constants::SCHEDULE_STATUS_CANCELED,
+            scheduled = db_constants::SCHEDULE_STATUS_SCHEDULED,
+        );
+        let mut params: Vec<String> = vec![
+            now.to_string(),
+            channel_id.to_string(),
+            platform.to_string(),
+            now.to_string(),
+        ];
+        if let Some(horizon) = horizon {
+            sql.push_str(" AND scheduled_start <= CAST(? AS TIMESTAMP)");
+            params.push(horizon.to_string());
+        }
+        if !entries.is_empty() {
+            // 予定の数は取得結果に依存するため params_from_iter で渡す
+            let placeholders = vec!["?"; entries.len()].join(", ");
+            sql.push_str(&format!(" AND schedule_id NOT IN ({})", placeholders));
+            params.extend(entries.iter().map(|entry| entry.schedule_id.clone()));
+        }
+
+        conn.execute(&sql, duckdb::params_from_iter(params.iter()))
+    }
+
+    /// 未開始の未来の配信予定IDを取得
+    pub fn get_pending_schedule_ids(
+        conn: &Connection,
+        channel_id: i64,
+        platform: &str,
+        now: &str,
+    ) -> Result<Vec<String>, duckdb::Error> {
+        let sql = format!(
+            r#"
+            SELECT schedule_id
+            FROM scheduled_streams
+            WHERE channel_id = ?
+              AND platform = ?
+              AND status = '{scheduled}'
+              AND scheduled_start > CAST(? AS TIMESTAMP)
+            "#,
+            scheduled = db_constants::SCHEDULE_STATUS_SCHEDULED,
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(duckdb::params![channel_id, platform, now], |row| {
+            row.get::<_, String>(0)
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()
+    }
+
+    /// 指定した未開始の配信予定を取り消し扱いにする
+    ///
+    /// 取り消した予定の数を返します。
+    pub fn cancel_schedules(
+        conn: &Connection,
+        platform: &str,
+        schedule_ids: &[String],
+        now: &str,
+    ) -> Result<usize, duckdb::Error> {
+        if schedule_ids.is_empty() {
+            return Ok(0);
+        }
+        let placeholders = vec!["?"; schedule_ids.len()].join(", ");
+        let sql = format!(
+            r#"
+            UPDATE scheduled_streams
+            SET status = '{canceled}', updated_at = CAST(? AS TIMESTAMP)
+            WHERE platform = ?
+              AND status = '{scheduled}'
+              AND schedule_id IN ({placeholders})
+            "#,
+            canceled = db_constants::SCHEDULE_STATUS_CANCELED,
+            scheduled = db_constants::SCHEDULE_STATUS_SCHEDULED,
+        );
+        let mut params = vec![now.to_string(), platform.to_string()];
+        params.extend(schedule_ids.iter().cloned());
+        conn.execute(&sql, duckdb::params_from_iter(params.iter()))
+    }
+
+    /// 配信予定を保存（(platform, schedule_id) が既存の場合は更新）
+    ///
+    /// 配信と対応付け済み（started）の予定は状態を変えません。
+    pub fn upsert_schedules(
+        conn: &Connection,
+        entries: &[ScheduledStream],
+        now: &str,
+    ) -> Result<usize, duckdb::Error> {
+        let sql = format!(
+            r#"
+            INSERT INTO scheduled_streams (
+                channel_id, platform, schedule_id, title, category,
+                scheduled_start, scheduled_end, status, first_seen_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, CAST(? AS TIMESTAMP), CAST(? AS TIMESTAMP), ?, CAST(? AS TIMESTAMP), CAST(? AS TIMESTAMP))
+            ON CONFLICT (platform, schedule_id) DO UPDATE SET
+                title = excluded.title,
+                category = excluded.category,
+                scheduled_start = excluded.scheduled_start,
+                scheduled_end = excluded.scheduled_end,
+                status = CASE
+                    WHEN scheduled_streams.status = '{started}' THEN scheduled_streams.status
+                    ELSE excluded.status
+                END,
+                updated_at = excluded.updated_at
+            "#,
+            started = db_constants::SCHEDULE_STATUS_STARTED,
+        );
+        let mut stmt = conn.prepare(&sql)?;
+
+        let mut upserted = 0;
+        for entry in entries {
+            upserted += stmt.execute(duckdb::params![
+                entry.channel_id,
+                entry.platform,
+                entry.schedule_id,
+                entry.title,
+                entry.category,
+                entry.scheduled_start,
+                entry.scheduled_end,
+                entry.status,
+                now,
+                now,
+            ])?;
+        }
+
+        Ok(upserted)
+    }
+
+    /// 始まった配信を配信予定と対応付け
+    ///
+    /// 予定IDが配信IDと一致するもの（YouTube の予定枠）を優先し、なければ開始時刻が許容幅内で
+    /// 最も近い未開始の予定を選びます。対応付けた予定のIDを返します。
+    pub fn match_started_stream(
+        conn: &Connection,
+        channel_id: i64,
+        stream_db_id: i64,
+        platform_stream_id: &str,
+        started_at: &str,
+    ) -> Result<Option<i64>, duckdb::Error> {
+        let sql = format!(
+            r#"
+            SELECT id
+            FROM scheduled_streams
+            WHERE channel_id = ?
+              AND stream_id IS NULL
+              AND (
+                  schedule_id = ?
+                  OR (
+                      status = '{scheduled}'
+                      AND ABS(date_diff('second', scheduled_start, CAST(? AS TIMESTAMP))) <= {window} * 60
+                  )
+              )
+            ORDER BY
+                (schedule_id = ?) DESC,
+                ABS(date_diff('second', scheduled_start, CAST(? AS TIMESTAMP)))
+            LIMIT 1
+            "#,
+            scheduled = db_constants::SCHEDULE_STATUS_SCHEDULED,
+            window = db_constants::SCHEDULE_MATCH_WINDOW_MINUTES,
+        );
+
+        let schedule_id: Option<i64> = {
+            let mut stmt = conn.prepare(&sql)?;
+            let mut rows = stmt.query_map(
+                duckdb::params![
+                    channel_id,
+                    platform_stream_id,
+                    started_at,
+                    platform_stream_id,
+                    started_at
+                ],
+                |row| row.get(0),
+            )?;
+            rows.next().transpose()?
+        };
+
+        if let Some(id) = schedule_id {
+            conn.execute(
+                r#"
+                UPDATE scheduled_streams
+                SET status = ?, stream_id = ?, actual_start = CAST(? AS TIMESTAMP), updated_at = CURRENT_TIMESTAMP
+                WHERE id = ?
+                "#,
+                duckdb::params![
+                    db_constants::SCHEDULE_STATUS_STARTED,
+                    stream_db_id,
+                    started_at,
+                    id
+                ],
+            )?;
+        }
+
+        Ok(schedule_id)
+    }
+
+    /// 配信予定を予定時刻順に取得
+    pub fn get_scheduled_streams(
+        conn: &Connection,
+        channel_id: Option<i64>,
+        start_time: Option<&str>,
+        end_time: Option<&str>,
+    ) -> Result<Vec<ScheduledStream>, duckdb::Error> {
+        let mut sql = String::from(
+            r#"
+            SELECT
+                id,
+                channel_id,
+                platform,
+                schedule_id,
+                title,
+                category,
+                CAST(scheduled_start AS VARCHAR) as scheduled_start,
+                CAST(scheduled_end AS VARCHAR) as scheduled_end,
+                status,
+                stream_id,
+                CAST(actual_start AS VARCHAR) as actual_start
+            FROM scheduled_streams
+            WHERE 1=1
+            "#,
+        );
+        let mut params = Vec::new();
+
+        if let Some(ch_id) = channel_id {
+            sql.push_str(" AND channel_id = ?");
+            params.push(ch_id.to_string());
+        }
+        if let Some(start) = start_time {
+            sql.push_str(" AND scheduled_start >= CAST(? AS TIMESTAMP)");
+            params.push(start.to_string());
+        }
+        if let Some(end) = end_time {
+            sql.push_str(" AND scheduled_start <= CAST(? AS TIMESTAMP)");
+            params.push(end.to_string());
+        }
+        sql.push_str(" ORDER BY scheduled_start");
+
+        let mut stmt = conn.prepare(&sql)?;
+        let results = utils::query_map_with_params(&mut stmt, &params, row_to_scheduled_stream)?;
+
+        results.collect::<Result<Vec<_>, _>>()
+    }
+
+    /// チャンネル別に予定の遵守率・開始の遅れ・取り消し数を集計
+    ///
+    /// 予定時刻が `now` より前の予定のみが対象です。予定時刻から許容幅を過ぎていない未開始の予定は
+    /// まだ始まる可能性があるため集計に含めません。
+    pub fn adherence_by_channel(
+        conn: &Connection,
+        channel_id: Option<i64>,
+        start_time: Option<&str>,
+        end_time: Option<&str>,
+        now: &str,
+    ) -> Result<Vec<ScheduleAdherence>, duckdb::Error> {
+        let mut filter = String::from(" WHERE sc.scheduled_start <= CAST(? AS TIMESTAMP)");
+        let mut params = vec![now.to_string(), now.to_string()];
+
+        if let Some(ch_id) = channel_id {
+            filter.push_str(" AND sc.channel_id = ?");
+            params.push(ch_id.to_string());
+        }
+        if let Some(start) = start_time {
+            filter.push_str(" AND sc.scheduled_start >= CAST(? AS TIMESTAMP)");
+            params.push(start.to_string());
+        }
+        if let Some(end) = end_time {
+            filter.push_str(" AND sc.scheduled_start <= CAST(? AS TIMESTAMP)");
+            params.push(end.to_string());
+        }
+
+        let sql = format!(
+            r#"
+            WITH outcomes AS (
+                SELECT
+                    sc.channel_id,
+                    CASE
+                        WHEN sc.status = '{scheduled}'
+                            AND sc.scheduled_start < CAST(? AS TIMESTAMP) - INTERVAL '{window} minutes'
+                            THEN 'missed'
+                        ELSE sc.status
+                    END as outcome,
+                    CASE
+                        WHEN sc.status = '{started}' AND sc.actual_start IS NOT NULL
+                            THEN date_diff('second', sc.scheduled_start, sc.actual_start) / 60.0
+                    END as delay_minutes
+                FROM scheduled_streams sc
+                {filter}
+            )
+            SELECT
+                c.id,
+                COALESCE(NULLIF(c.display_name, ''), c.channel_name) as channel_name,
+                c.platform,
+                COUNT(*) FILTER (WHERE o.outcome IN ('{started}', '{canceled}', 'missed')) as scheduled_count,
+                COUNT(*) FILTER (WHERE o.outcome = '{started}') as started_count,
+                COUNT(*) FILTER (WHERE o.outcome = '{canceled}') as canceled_count,
+                COUNT(*) FILTER (WHERE o.outcome = 'missed') as missed_count,
+                AVG(o.delay_minutes) as avg_delay_minutes,
+                MEDIAN(o.delay_minutes) as median_delay_minutes,
+                COUNT(*) FILTER (WHERE o.delay_minutes <= {on_time}) as on_time_count
+            FROM outcomes o
+            JOIN channels c ON o.channel_id = c.id
+            GROUP BY c.id, c.display_name, c.channel_name, c.platform
+            HAVING scheduled_count > 0
+            ORDER BY scheduled_count DESC
+            "#,
+            scheduled = db_constants::SCHEDULE_STATUS_SCHEDULED,
+            started = db_constants::SCHEDULE_STATUS_STARTED,
+            canceled = db_constants::SCHEDULE_STATUS_CANCELED,
+            window = db_constants::SCHEDULE_MATCH_WINDOW_MINUTES,
+            on_time = db_constants::SCHEDULE_ON_TIME_THRESHOLD_MINUTES,
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let results = utils::query_map_with_params(&mut stmt, &params, |row| {
+            let scheduled_count: i64 = row.get(3)?;
+            let started_count: i64 = row.get(4)?;
+            let on_time_count: i64 = row.get(9)?;
+            Ok(ScheduleAdherence {
+                channel_id: row.get(0)?,
+                channel_name: row.get(1)?,
+                platform: row.get(2)?,
+                scheduled_count,
+                started_count,
+                canceled_count: row.get(5)?,
+                missed_count: row.get(6)?,
+                adherence_rate: started_count as f64 / scheduled_count as f64,
+                avg_delay_minutes: row.get(7)?,
+                median_delay_minutes: row.get(8)?,
+                on_time_rate: if started_count > 0 {
+                    Some(on_time_count as f64 / started_count as f64)
+                } else {
+                    None
+                },
+            })
+        })?;
+
+        results.collect::<Result<Vec<_>, _>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(schedule_id: &str, scheduled_start: &str, status: &str) -> ScheduledStream {
+        ScheduledStream {
+            id: None,
+            channel_id: 1,
+            platform: "twitch".to_string(),
+            schedule_id: schedule_id.to_string(),
+            title: Some("Weekly stream".to_string()),
+            category: None,
+            scheduled_start: scheduled_start.to_string(),
+            scheduled_end: None,
+            status: status.to_string(),
+            stream_id: None,
+            actual_start: None,
+        }
+    }
+
+    #[test]
+    #[cfg_attr(
+        target_os = "windows",
+        ignore = "Database tests are unstable on Windows local environment"
+    )]
+    fn test_schedule_adherence() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::init_database(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO channels (id, platform, channel_id, channel_name) VALUES (1, 'twitch', 'streamer', 'streamer')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO streams (id, channel_id, stream_id, started_at) VALUES (10, 1, 's1', '2025-01-06 12:10:00')",
+            [],
+        )
+        .unwrap();
+
+        let now = "2025-01-10 00:00:00";
+        let entries = vec![
+            entry("late", "2025-01-06 12:00:00", "scheduled"),
+            entry("missed", "2025-01-07 12:00:00", "scheduled"),
+            entry("canceled", "2025-01-08 12:00:00", "canceled"),
+            entry("removed", "2025-01-12 12:00:00", "scheduled"),
+            entry("future", "2025-01-13 12:00:00", "scheduled"),
+        ];
+        ScheduledStreamRepository::sync_channel_schedule(&conn, 1, "twitch", &entries, None, now)
+            .unwrap();
+
+        // 取得結果から消えた未来の予定は取り消し扱い
+        let canceled = ScheduledStreamRepository::sync_channel_schedule(
+            &conn,
+            1,
+            "twitch",
+            &[entries[4].clone()],
+            None,
+            now,
+        )
+        .unwrap();
+        assert_eq!(canceled, 1);
+
+        // 配信IDが一致しなくても許容幅内で最も近い予定と対応付ける
+        let matched = ScheduledStreamRepository::match_started_stream(
+            &conn,
+            1,
+            10,
+            "s1",
+            "2025-01-06 12:10:00",
+        )
+        .unwrap();
+        assert!(matched.is_some());
+        assert_eq!(
+            ScheduledStreamRepository::match_started_stream(
+                &conn,
+                1,
+                11,
+                "s2",
+                "2025-01-09 20:00:00",
+            )
+            .unwrap(),
+            None
+        );
+
+        let schedules =
+            ScheduledStreamRepository::get_scheduled_streams(&conn, Some(1), None, None).unwrap();
+        let statuses: Vec<(&str, &str)> = schedules
+            .iter()
+            .map(|s| (s.schedule_id.as_str(), s.status.as_str()))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("late", "started"),
+                ("missed", "scheduled"),
+                ("canceled", "canceled"),
+                ("removed", "canceled"),
+                ("future", "scheduled"),
+            ]
+        );
+        assert_eq!(schedules[0].stream_id, Some(10));
+
+        let adherence =
+            ScheduledStreamRepository::adherence_by_channel(&conn, None, None, None, now).unwrap();
+        assert_eq!(adherence.len(), 1);
+        assert_eq!(adherence[0].scheduled_count, 3);
+        assert_eq!(adherence[0].started_count, 1);
+        assert_eq!(adherence[0].canceled_count, 1);
+        assert_eq!(adherence[0].missed_count, 1);
+        assert_eq!(adherence[0].avg_delay_minutes, Some(10.0));
+        assert_eq!(adherence[0].on_time_rate, Some(0.0));
+    }
+
+    #[test]
+    #[cfg_attr(
+        target_os = "windows",
+        ignore = "Database tests are unstable on Windows local environment"
+    )]
+    fn test_cancel_confirmed_schedules() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::init_database(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO channels (id, platform, channel_id, channel_name) VALUES (1, 'twitch', 'streamer', 'streamer')",
+            [],
+        )
+        .unwrap();
+
+        let now = "2025-01-10 00:00:00";
+        let entries = vec![
+            entry("past", "2025-01-09 12:00:00", "scheduled"),
+            entry("gone", "2025-01-12 12:00:00", "scheduled"),
+            entry("kept", "2025-01-13 12:00:00", "scheduled"),
+        ];
+        ScheduledStreamRepository::upsert_schedules(&conn, &entries, now).unwrap();
+
+        // 未来の未開始の予定のみが確認対象
+        let mut pending =
+            ScheduledStreamRepository::get_pending_schedule_ids(&conn, 1, "twitch", now).unwrap();
+        pending.sort();
+        assert_eq!(pending, vec!["gone".to_string(), "kept".to_string()]);
+
+        let canceled = ScheduledStreamRepository::cancel_schedules(
+            &conn,
+            "twitch",
+            &["gone".to_string()],
+            now,
+        )
+        .unwrap();
+        assert_eq!(canceled, 1);
+        assert_eq!(
+            ScheduledStreamRepository::get_pending_schedule_ids(&conn, 1, "twitch", now).unwrap(),
+            vec!["kept".to_string()]
+        );
+    }
+}
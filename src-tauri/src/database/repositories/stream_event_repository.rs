@@ -0,0 +1,359 @@
+/// StreamEventRepository - stream_events / stream_metadata テーブル専用レポジトリ
+///
+/// ポーリング時に配信メタデータ（タイトル・カテゴリ・タグ・言語）を前回値と比較し、
+/// 変更があれば stream_events に旧値・新値を記録します。
+use crate::constants::database as db_constants;
+use crate::database::models::{StreamData, StreamEvent};
+use crate::database::utils;
+use duckdb::{Connection, OptionalExt};
+
+/// 配信ごとの最新メタデータ（stream_metadata）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamMetadata {
+    pub title: Option<String>,
+    pub category: Option<String>,
+    pub game_id: Option<String>,
+    pub language: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+/// 検出した変更（event_type, 旧値, 新値）
+pub type MetadataChange = (&'static str, Option<String>, Option<String>);
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value.as_ref().filter(|v| !v.is_empty()).cloned()
+}
+
+impl StreamMetadata {
+    pub fn from_stream_data(stream_data: &StreamData) -> Self {
+        Self {
+            title: non_empty(&stream_data.title),
+            category: non_empty(&stream_data.category),
+            game_id: non_empty(&stream_data.game_id),
+            language: non_empty(&stream_data.language),
+            tags: stream_data.tags.clone(),
+        }
+    }
+
+    /// 前回値からの変更を検出
+    ///
+    /// どちらかが不明（None）の項目は比較しません。
+    pub fn changes_from(&self, previous: &StreamMetadata) -> Vec<MetadataChange> {
+        let mut changes = Vec::new();
+
+        if let (Some(old), Some(new)) = (&previous.title, &self.title) {
+            if old != new {
+                changes.push((
+                    db_constants::STREAM_EVENT_TITLE,
+                    Some(old.clone()),
+                    Some(new.clone()),
+                ));
+            }
+        }
+
+        // カテゴリは game_id で比較し、どちらかに game_id がない場合のみ名前で比較
+        let category_changed = match (&previous.game_id, &self.game_id) {
+            (Some(old), Some(new)) => old != new,
+            _ => {
+                matches!((&previous.category, &self.category), (Some(old), Some(new)) if old != new)
+            }
+        };
+        if category_changed {
+            changes.push((
+                db_constants::STREAM_EVENT_CATEGORY,
+                previous
+                    .category
+                    .clone()
+                    .or_else(|| previous.game_id.clone()),
+                self.category.clone().or_else(|| self.game_id.clone()),
+            ));
+        }
+
+        if let (Some(old), Some(new)) = (&previous.language, &self.language) {
+            if old != new {
+                changes.push((
+                    db_constants::STREAM_EVENT_LANGUAGE,
+                    Some(old.clone()),
+                    Some(new.clone()),
+                ));
+            }
+        }
+
+        if let (Some(old), Some(new)) = (&previous.tags, &self.tags) {
+            // 並び順の違いは変更として扱わない
+            let (mut old_sorted, mut new_sorted) = (old.clone(), new.clone());
+            old_sorted.sort();
+            new_sorted.sort();
+            if old_sorted != new_sorted {
+                changes.push((
+                    db_constants::STREAM_EVENT_TAGS,
+                    Some(old.join(", ")),
+                    Some(new.join(", ")),
+                ));
+            }
+        }
+
+        changes
+    }
+
+    /// 今回取得できなかった項目は前回値を引き継ぐ
+    fn merged_with(&self, previous: &StreamMetadata) -> StreamMetadata {
+        StreamMetadata {
+            title: self.title.clone().or_else(|| previous.title.clone()),
+            category: self.category.clone().or_else(|| previous.category.clone()),
+            game_id: self.game_id.clone().or_else(|| previous.game_id.clone()),
+            language: self.language.clone().or_else(|| previous.language.clone()),
+            tags: self.tags.clone().or_else(|| previous.tags.clone()),
+        }
+    }
+}
+
+fn row_to_stream_event(row: &duckdb::Row) -> Result<StreamEvent, duckdb::Error> {
+    Ok(StreamEvent {
+        id: row.get(0)?,
+        stream_id: row.get(1)?,
+        channel_id: row.get(2)?,
+        event_type: row.get(3)?,
+        old_value: row.get(4)?,
+        new_value: row.get(5)?,
+        occurred_at: row.get(6)?,
+    })
+}
+
+const STREAM_EVENT_COLUMNS: &str = r#"
+    id, stream_id, channel_id, event_type, old_value, new_value,
+    CAST(occurred_at AS VARCHAR) as occurred_at
+"#;
+
+pub struct StreamEventRepository;
+
+impl StreamEventRepository {
+    /// 配信の最新メタデータを取得
+    pub fn get_metadata(
+        conn: &Connection,
+        stream_id: i64,
+    ) -> Result<Option<StreamMetadata>, duckdb::Error> {
+        conn.query_row(
+            "SELECT title, category, game_id, language, tags FROM stream_metadata WHERE stream_id = ?",
+            [stream_id],
+            |row| {
+                let tags: Option<String> = row.get(4)?;
+                Ok(StreamMetadata {
+                    title: row.get(0)?,
+                    category: row.get(1)?,
+                    game_id: row.get(2)?,
+                    language: row.get(3)?,
+                    tags: tags.and_then(|t| serde_json::from_str(&t).ok()),
+                })
+            },
+        )
+        .optional()
+    }
+
+    /// 配信の最新メタデータを保存（UPSERT）
+    pub fn upsert_metadata(
+        conn: &Connection,
+        stream_id: i64,
+        metadata: &StreamMetadata,
+        updated_at: &str,
+    ) -> Result<(), duckdb::Error> {
+        let tags = metadata
+            .tags
+            .as_ref()
+            .and_then(|t| serde_json::to_string(t).ok());
+        conn.execute(
+            r#"
+            INSERT INTO stream_metadata (stream_id, title, category, game_id, language, tags, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(stream_id) DO UPDATE SET
+                title = excluded.title,
+                category = excluded.category,
+                game_id = excluded.game_id,
+                language = excluded.language,
+                tags = excluded.tags,
+                updated_at = excluded.updated_at
+            "#,
+            duckdb::params![
+                stream_id,
+                metadata.title,
+                metadata.category,
+                metadata.game_id,
+                metadata.language,
+                tags,
+                updated_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// 前回値と比較して変更をstream_eventsに記録し、最新メタデータを更新
+    ///
+    /// 配信の初回ポーリングでは比較対象がないためイベントは記録しません。
+    /// 戻り値: 記録したイベント数
+    pub fn record_changes(
+        conn: &Connection,
+        channel_id: i64,
+        stream_id: i64,
+        current: &StreamMetadata,
+        occurred_at: &str,
+    ) -> Result<usize, duckdb::Error> {
+        let previous = Self::get_metadata(conn, stream_id)?;
+
+        let (changes, merged) = match &previous {
+            Some(previous) => (
+                current.changes_from(previous),
+                current.merged_with(previous),
+            ),
+            None => (Vec::new(), current.clone()),
+        };
+
+        for (event_type, old_value, new_value) in &changes {
+            conn.execute(
+                r#"
+                INSERT INTO stream_events (stream_id, channel_id, event_type, old_value, new_value, occurred_at)
+                VALUES (?, ?, ?, ?, ?, ?)
+                "#,
+                duckdb::params![
+                    stream_id,
+                    channel_id,
+                    event_type,
+                    old_value,
+                    new_value,
+                    occurred_at,
+                ],
+            )?;
+        }
+
+        if previous.as_ref() != Some(&merged) {
+            Self::upsert_metadata(conn, stream_id, &merged, occurred_at)?;
+        }
+
+        Ok(changes.len())
+    }
+
+    /// 配信の変更イベントを取得（発生順）
+    pub fn get_by_stream(
+        conn: &Connection,
+        stream_id: i64,
+    ) -> Result<Vec<StreamEvent>, duckdb::Error> {
+        let sql = format!(
+            "SELECT {} FROM stream_events WHERE stream_id = ? ORDER BY occurred_at ASC, id ASC",
+            STREAM_EVENT_COLUMNS
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map([stream_id], row_to_stream_event)?;
+        rows.collect::<Result<Vec<_>, _>>()
+    }
+
+    /// チャンネルの変更イベントを期間指定で取得（発生順）
+    pub fn get_by_channel(
+        conn: &Connection,
+        channel_id: i64,
+        start_time: Option<&str>,
+        end_time: Option<&str>,
+    ) -> Result<Vec<StreamEvent>, duckdb::Error> {
+        let mut sql = format!(
+            "SELECT {} FROM stream_events WHERE channel_id = ?",
+            STREAM_EVENT_COLUMNS
+        );
+        let mut params = vec![channel_id.to_string()];
+
+        if let Some(start) = start_time {
+            sql.push_str(" AND occurred_at >= ?");
+            params.push(start.to_string());
+        }
+        if let Some(end) = end_time {
+            sql.push_str(" AND occurred_at <= ?");
+            params.push(end.to_string());
+        }
+        sql.push_str(" ORDER BY occurred_at ASC, id ASC");
+
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = utils::query_map_with_params(&mut stmt, &params, row_to_stream_event)?;
+        rows.collect::<Result<Vec<_>, _>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(title: &str, game_id: &str, category: &str) -> StreamMetadata {
+        StreamMetadata {
+            title: Some(title.to_string()),
+            category: Some(category.to_string()),
+            game_id: Some(game_id.to_string()),
+            language: Some("ja".to_string()),
+            tags: Some(vec!["日本語".to_string(), "FPS".to_string()]),
+        }
+    }
+
+    #[test]
+    fn test_changes_from_detects_each_field() {
+        let previous = metadata("before", "1", "Just Chatting");
+        let mut current = metadata("after", "2", "VALORANT");
+        current.language = Some("en".to_string());
+        current.tags = Some(vec!["English".to_string()]);
+
+        let changes = current.changes_from(&previous);
+        let types: Vec<&str> = changes.iter().map(|(t, _, _)| *t).collect();
+        assert_eq!(types, vec!["title", "category", "language", "tags"]);
+        assert_eq!(changes[1].1.as_deref(), Some("Just Chatting"));
+        assert_eq!(changes[1].2.as_deref(), Some("VALORANT"));
+    }
+
+    #[test]
+    fn test_changes_from_ignores_unknown_and_reordered_values() {
+        let previous = metadata("same", "1", "Just Chatting");
+
+        // カテゴリ名の表記揺れは game_id が同じなら変更として扱わない
+        let mut current = metadata("same", "1", "雑談");
+        current.tags = Some(vec!["FPS".to_string(), "日本語".to_string()]);
+        current.language = None;
+        assert!(current.changes_from(&previous).is_empty());
+
+        let merged = current.merged_with(&previous);
+        assert_eq!(merged.language.as_deref(), Some("ja"));
+    }
+
+    #[test]
+    #[cfg_attr(
+        target_os = "windows",
+        ignore = "Database tests are unstable on Windows local environment"
+    )]
+    fn test_record_changes_roundtrip() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::init_database(&conn).unwrap();
+
+        let first = metadata("before", "1", "Just Chatting");
+        let recorded = StreamEventRepository::record_changes(
+            &conn,
+            1,
+            10,
+            &first,
+            "2025-01-06T12:00:00+09:00",
+        )
+        .unwrap();
+        assert_eq!(recorded, 0);
+
+        let second = metadata("after", "1", "Just Chatting");
+        let recorded = StreamEventRepository::record_changes(
+            &conn,
+            1,
+            10,
+            &second,
+            "2025-01-06T12:01:00+09:00",
+        )
+        .unwrap();
+        assert_eq!(recorded, 1);
+
+        let events = StreamEventRepository::get_by_stream(&conn, 10).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "title");
+        assert_eq!(events[0].old_value.as_deref(), Some("before"));
+        assert_eq!(events[0].new_value.as_deref(), Some("after"));
+
+        let by_channel = StreamEventRepository::get_by_channel(&conn, 1, None, None).unwrap();
+        assert_eq!(by_channel.len(), 1);
+    }
+}
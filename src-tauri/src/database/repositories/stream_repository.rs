@@ -254,6 +254,18 @@ impl StreamRepository {
         )
     }
 
+    /// チャンネルの終了していない配信ID一覧を取得（新しい順）
+    pub fn get_open_stream_ids(
+        conn: &Connection,
+        channel_id: i64,
+    ) -> Result<Vec<i64>, duckdb::Error> {
+        let mut stmt = conn.prepare(
+            "SELECT id FROM streams WHERE channel_id = ? AND ended_at IS NULL ORDER BY started_at DESC",
+        )?;
+        let rows = stmt.query_map([channel_id], |row| row.get::<_, i64>(0))?;
+        rows.collect::<Result<Vec<_>, _>>()
+    }
+
     /// 比較用：基準配信と時間帯が重なる配信をサジェスト
     pub fn get_suggested_streams_for_comparison(
         conn: &Connection,
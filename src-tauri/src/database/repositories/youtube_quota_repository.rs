@@ -0,0 +1,104 @@
+/// YouTubeQuotaRepository - youtube_quota_usage テーブル専用レポジトリ
+///
+/// YouTube Data API のクォータ使用量をエンドポイント別・日付（太平洋時間）別に保持し、
+/// 再起動後も当日の使用量を引き継げるようにします。
+use duckdb::Connection;
+
+/// エンドポイント1件分の使用量
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuotaUsageRow {
+    pub endpoint: String,
+    pub calls: u32,
+    pub units: u32,
+}
+
+pub struct YouTubeQuotaRepository;
+
+impl YouTubeQuotaRepository {
+    /// 使用量を加算（UPSERT）
+    pub fn add_usage(
+        conn: &Connection,
+        quota_date: &str,
+        usage: &QuotaUsageRow,
+        updated_at: &str,
+    ) -> Result<(), duckdb::Error> {
+        conn.execute(
+            r#"
+            INSERT INTO youtube_quota_usage (quota_date, endpoint, calls, units, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(quota_date, endpoint) DO UPDATE SET
+                calls = calls + excluded.calls,
+                units = units + excluded.units,
+                updated_at = excluded.updated_at
+            "#,
+            duckdb::params![
+                quota_date,
+                usage.endpoint,
+                usage.calls,
+                usage.units,
+                updated_at
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// 指定日の使用量を取得
+    pub fn get_usage(
+        conn: &Connection,
+        quota_date: &str,
+    ) -> Result<Vec<QuotaUsageRow>, duckdb::Error> {
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT endpoint, calls, units
+            FROM youtube_quota_usage
+            WHERE quota_date = ?
+            ORDER BY endpoint
+            "#,
+        )?;
+        let rows = stmt.query_map([quota_date], |row| {
+            Ok(QuotaUsageRow {
+                endpoint: row.get(0)?,
+                calls: row.get(1)?,
+                units: row.get(2)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(
+        target_os = "windows",
+        ignore = "Database tests are unstable on Windows local environment"
+    )]
+    fn test_add_usage_accumulates_per_day() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::init_database(&conn).unwrap();
+
+        let search = QuotaUsageRow {
+            endpoint: "search.list".to_string(),
+            calls: 1,
+            units: 100,
+        };
+        YouTubeQuotaRepository::add_usage(&conn, "2025-01-06", &search, "2025-01-06 10:00:00")
+            .unwrap();
+        YouTubeQuotaRepository::add_usage(&conn, "2025-01-06", &search, "2025-01-06 10:01:00")
+            .unwrap();
+        YouTubeQuotaRepository::add_usage(&conn, "2025-01-07", &search, "2025-01-07 10:00:00")
+            .unwrap();
+
+        let usage = YouTubeQuotaRepository::get_usage(&conn, "2025-01-06").unwrap();
+        assert_eq!(
+            usage,
+            vec![QuotaUsageRow {
+                endpoint: "search.list".to_string(),
+                calls: 2,
+                units: 200,
+            }]
+        );
+    }
+}
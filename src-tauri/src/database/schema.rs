@@ -1,3 +1,4 @@
+use crate::database::repositories::base::with_transaction;
 use duckdb::Connection;
 
 pub fn init_database(conn: &Connection) -> Result<(), duckdb::Error> {
@@ -13,7 +14,7 @@ pub fn init_database(conn: &Connection) -> Result<(), duckdb::Error> {
         r#"
         CREATE TABLE IF NOT EXISTS channels (
             id BIGINT PRIMARY KEY DEFAULT nextval('channels_id_seq'),
-            platform TEXT NOT NULL CHECK(platform IN ('twitch', 'youtube')),
+            platform TEXT NOT NULL CHECK(platform IN ('twitch', 'youtube', 'kick')),
             channel_id TEXT NOT NULL,
             channel_name TEXT NOT NULL,
             enabled BOOLEAN NOT NULL DEFAULT 1,
@@ -648,6 +649,145 @@ fn migrate_database_schema(conn: &Connection) -> Result<(), duckdb::Error> {
         eprintln!("[Migration] display_name column added successfully");
     }
 
+    // chat_messagesテーブルにpayloadフィールドを追加（USERNOTICE等の構造化情報、JSON）
+    let mut chat_messages_has_payload = conn.prepare(
+        "SELECT COUNT(*) FROM pragma_table_info('chat_messages') WHERE name = 'payload'",
+    )?;
+    let chat_messages_has_payload_count: i64 =
+        chat_messages_has_payload.query_row([], |row| row.get(0))?;
+
+    if chat_messages_has_payload_count == 0 {
+        eprintln!("[Migration] Adding payload column to chat_messages table");
+        conn.execute("ALTER TABLE chat_messages ADD COLUMN payload TEXT", [])?;
+        eprintln!("[Migration] payload column added successfully");
+    }
+
+    // chat_messagesテーブルにis_deletedフィールドを追加（モデレーターに削除されたメッセージ）
+    let mut chat_messages_has_is_deleted = conn.prepare(
+        "SELECT COUNT(*) FROM pragma_table_info('chat_messages') WHERE name = 'is_deleted'",
+    )?;
+    let chat_messages_has_is_deleted_count: i64 =
+        chat_messages_has_is_deleted.query_row([], |row| row.get(0))?;
+
+    if chat_messages_has_is_deleted_count == 0 {
+        eprintln!("[Migration] Adding is_deleted column to chat_messages table");
+        conn.execute(
+            "ALTER TABLE chat_messages ADD COLUMN is_deleted BOOLEAN DEFAULT FALSE",
+            [],
+        )?;
+        eprintln!("[Migration] is_deleted column added successfully");
+    }
+
+    // chat_messagesテーブルにplatform_message_idフィールドを追加（Twitchのメッセージid等）
+    let mut chat_messages_has_platform_message_id = conn.prepare(
+        "SELECT COUNT(*) FROM pragma_table_info('chat_messages') WHERE name = 'platform_message_id'",
+    )?;
+    let chat_messages_has_platform_message_id_count: i64 =
+        chat_messages_has_platform_message_id.query_row([], |row| row.get(0))?;
+
+    if chat_messages_has_platform_message_id_count == 0 {
+        eprintln!("[Migration] Adding platform_message_id column to chat_messages table");
+        conn.execute(
+            "ALTER TABLE chat_messages ADD COLUMN platform_message_id TEXT",
+            [],
+        )?;
+        eprintln!("[Migration] platform_message_id column added successfully");
+    }
+
+    // chat_messagesテーブルにemotesフィールドを追加（IRCのemotesタグ、JSON）
+    let mut chat_messages_has_emotes = conn
+        .prepare("SELECT COUNT(*) FROM pragma_table_info('chat_messages') WHERE name = 'emotes'")?;
+    let chat_messages_has_emotes_count: i64 =
+        chat_messages_has_emotes.query_row([], |row| row.get(0))?;
+
+    if chat_messages_has_emotes_count == 0 {
+        eprintln!("[Migration] Adding emotes column to chat_messages table");
+        conn.execute("ALTER TABLE chat_messages ADD COLUMN emotes TEXT", [])?;
+        eprintln!("[Migration] emotes column added successfully");
+    }
+
+    // chat_messagesテーブルにbitsフィールドを追加（Cheerのビッツ数）
+    let mut chat_messages_has_bits = conn
+        .prepare("SELECT COUNT(*) FROM pragma_table_info('chat_messages') WHERE name = 'bits'")?;
+    let chat_messages_has_bits_count: i64 =
+        chat_messages_has_bits.query_row([], |row| row.get(0))?;
+
+    if chat_messages_has_bits_count == 0 {
+        eprintln!("[Migration] Adding bits column to chat_messages table");
+        conn.execute("ALTER TABLE chat_messages ADD COLUMN bits INTEGER", [])?;
+        eprintln!("[Migration] bits column added successfully");
+    }
+
+    // chat_messagesテーブルにreply_parent_idフィールドを追加（返信先メッセージID）
+    let mut chat_messages_has_reply_parent_id = conn.prepare(
+        "SELECT COUNT(*) FROM pragma_table_info('chat_messages') WHERE name = 'reply_parent_id'",
+    )?;
+    let chat_messages_has_reply_parent_id_count: i64 =
+        chat_messages_has_reply_parent_id.query_row([], |row| row.get(0))?;
+
+    if chat_messages_has_reply_parent_id_count == 0 {
+        eprintln!("[Migration] Adding reply_parent_id column to chat_messages table");
+        conn.execute(
+            "ALTER TABLE chat_messages ADD COLUMN reply_parent_id TEXT",
+            [],
+        )?;
+        eprintln!("[Migration] reply_parent_id column added successfully");
+    }
+
+    // chat_messagesテーブルにreply_parent_user_nameフィールドを追加（返信先ユーザー）
+    let mut chat_messages_has_reply_parent_user_name = conn.prepare(
+        "SELECT COUNT(*) FROM pragma_table_info('chat_messages') WHERE name = 'reply_parent_user_name'",
+    )?;
+    let chat_messages_has_reply_parent_user_name_count: i64 =
+        chat_messages_has_reply_parent_user_name.query_row([], |row| row.get(0))?;
+
+    if chat_messages_has_reply_parent_user_name_count == 0 {
+        eprintln!("[Migration] Adding reply_parent_user_name column to chat_messages table");
+        conn.execute(
+            "ALTER TABLE chat_messages ADD COLUMN reply_parent_user_name TEXT",
+            [],
+        )?;
+        eprintln!("[Migration] reply_parent_user_name column added successfully");
+    }
+
+    // chat_messagesテーブルにreply_thread_idフィールドを追加（スレッド起点メッセージID）
+    let mut chat_messages_has_reply_thread_id = conn.prepare(
+        "SELECT COUNT(*) FROM pragma_table_info('chat_messages') WHERE name = 'reply_thread_id'",
+    )?;
+    let chat_messages_has_reply_thread_id_count: i64 =
+        chat_messages_has_reply_thread_id.query_row([], |row| row.get(0))?;
+
+    if chat_messages_has_reply_thread_id_count == 0 {
+        eprintln!("[Migration] Adding reply_thread_id column to chat_messages table");
+        conn.execute(
+            "ALTER TABLE chat_messages ADD COLUMN reply_thread_id TEXT",
+            [],
+        )?;
+        eprintln!("[Migration] reply_thread_id column added successfully");
+    }
+
+    // chat_messagesテーブルにis_auto_discoveredフィールドを追加（自動発見チャンネル由来のチャット）
+    let mut chat_messages_has_is_auto_discovered = conn.prepare(
+        "SELECT COUNT(*) FROM pragma_table_info('chat_messages') WHERE name = 'is_auto_discovered'",
+    )?;
+    let chat_messages_has_is_auto_discovered_count: i64 =
+        chat_messages_has_is_auto_discovered.query_row([], |row| row.get(0))?;
+
+    if chat_messages_has_is_auto_discovered_count == 0 {
+        eprintln!("[Migration] Adding is_auto_discovered column to chat_messages table");
+        conn.execute(
+            "ALTER TABLE chat_messages ADD COLUMN is_auto_discovered BOOLEAN DEFAULT false",
+            [],
+        )?;
+        eprintln!("[Migration] is_auto_discovered column added successfully");
+    }
+
+    // 同じメッセージの二重保存を防ぐ（IDのない既存メッセージはNULLのため対象外）
+    conn.execute(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_chat_messages_platform_message_id ON chat_messages(platform, platform_message_id)",
+        [],
+    )?;
+
     // 既存のchat_messagesのchannel_idをstreams経由で更新
     eprintln!("[Migration] Updating chat_messages.channel_id from streams table");
     let update_result = conn.execute(
@@ -708,21 +848,26 @@ fn migrate_database_schema(conn: &Connection) -> Result<(), duckdb::Error> {
     )?;
     eprintln!("[Migration] chat_messages.user_id index created successfully");
 
-    // game_categoriesテーブルを作成（カテゴリIDキャッシュ用）
+    // game_categoriesテーブルを作成（カテゴリIDキャッシュ用、IDはプラットフォームごとに独立）
     eprintln!("[Migration] Creating game_categories table if not exists");
     conn.execute(
         r#"
         CREATE TABLE IF NOT EXISTS game_categories (
-            game_id TEXT PRIMARY KEY,
+            platform TEXT NOT NULL DEFAULT 'twitch',
+            game_id TEXT NOT NULL,
             game_name TEXT NOT NULL,
             box_art_url TEXT,
-            last_updated TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            last_updated TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            PRIMARY KEY (platform, game_id)
         )
         "#,
         [],
     )?;
     eprintln!("[Migration] game_categories table created");
 
+    // game_categoriesテーブルにplatformを追加（YouTubeの動画カテゴリIDとTwitchのゲームIDを区別する）
+    migrate_game_categories_platform(conn)?;
+
     // stream_statsテーブルにgame_idフィールドを追加
     let mut stream_stats_has_game_id = conn
         .prepare("SELECT COUNT(*) FROM pragma_table_info('stream_stats') WHERE name = 'game_id'")?;
@@ -740,6 +885,500 @@ fn migrate_database_schema(conn: &Connection) -> Result<(), duckdb::Error> {
         eprintln!("[Migration] Created index on stream_stats.game_id");
     }
 
+    // channel_poll_policiesテーブルを作成（チャンネル別ポーリングポリシー）
+    eprintln!("[Migration] Creating channel_poll_policies table if not exists");
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS channel_poll_policies (
+            channel_id BIGINT PRIMARY KEY,
+            live_interval INTEGER,
+            offline_interval INTEGER,
+            quiet_hours_start INTEGER,
+            quiet_hours_end INTEGER,
+            quiet_interval INTEGER,
+            start_window_interval INTEGER,
+            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+        [],
+    )?;
+    eprintln!("[Migration] channel_poll_policies table created");
+
+    // stream_eventsテーブルを作成（配信メタデータの変更履歴）
+    let stream_events_exists: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM duckdb_tables() WHERE table_name = 'stream_events'",
+        [],
+        |row| row.get(0),
+    )?;
+    if stream_events_exists == 0 {
+        eprintln!("[Migration] Creating stream_events table");
+        conn.execute(
+            "CREATE SEQUENCE IF NOT EXISTS stream_events_id_seq START 1",
+            [],
+        )?;
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS stream_events (
+                id BIGINT PRIMARY KEY DEFAULT nextval('stream_events_id_seq'),
+                stream_id BIGINT NOT NULL,
+                channel_id BIGINT NOT NULL,
+                event_type TEXT NOT NULL,
+                old_value TEXT,
+                new_value TEXT,
+                occurred_at TIMESTAMP NOT NULL
+            )
+            "#,
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_stream_events_stream ON stream_events(stream_id, occurred_at)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_stream_events_channel ON stream_events(channel_id, occurred_at)",
+            [],
+        )?;
+
+        // 既存のstream_statsからタイトル・カテゴリの変更を復元
+        eprintln!("[Migration] Backfilling stream_events from stream_stats");
+        for (event_type, column) in [("title", "title"), ("category", "category")] {
+            conn.execute(
+                &format!(
+                    r#"
+                    INSERT INTO stream_events (stream_id, channel_id, event_type, old_value, new_value, occurred_at)
+                    SELECT stream_id, channel_id, '{event_type}', prev_value, value, collected_at
+                    FROM (
+                        SELECT
+                            ss.stream_id,
+                            s.channel_id,
+                            ss.collected_at,
+                            ss.{column} AS value,
+                            LAG(ss.{column}) OVER (PARTITION BY ss.stream_id ORDER BY ss.collected_at) AS prev_value
+                        FROM stream_stats ss
+                        JOIN streams s ON s.id = ss.stream_id
+                        WHERE ss.{column} IS NOT NULL AND ss.{column} != ''
+                    )
+                    WHERE prev_value IS NOT NULL AND prev_value != value
+                    "#
+                ),
+                [],
+            )?;
+        }
+        eprintln!("[Migration] stream_events table created");
+    }
+
+    // stream_metadataテーブルを作成（変更検出用の配信ごとの最新メタデータ）
+    let stream_metadata_exists: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM duckdb_tables() WHERE table_name = 'stream_metadata'",
+        [],
+        |row| row.get(0),
+    )?;
+    if stream_metadata_exists == 0 {
+        eprintln!("[Migration] Creating stream_metadata table");
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS stream_metadata (
+                stream_id BIGINT PRIMARY KEY,
+                title TEXT,
+                category TEXT,
+                game_id TEXT,
+                language TEXT,
+                tags TEXT,
+                updated_at TIMESTAMP
+            )
+            "#,
+            [],
+        )?;
+
+        // 配信中のストリームは最新のstream_statsを初期値にする
+        conn.execute(
+            r#"
+            INSERT INTO stream_metadata (stream_id, title, category, game_id, updated_at)
+            SELECT stream_id, NULLIF(title, ''), NULLIF(category, ''), NULLIF(game_id, ''), collected_at
+            FROM (
+                SELECT
+                    ss.stream_id,
+                    ss.title,
+                    ss.category,
+                    ss.game_id,
+                    ss.collected_at,
+                    ROW_NUMBER() OVER (PARTITION BY ss.stream_id ORDER BY ss.collected_at DESC) AS rn
+                FROM stream_stats ss
+                JOIN streams s ON s.id = ss.stream_id
+                WHERE s.ended_at IS NULL
+            )
+            WHERE rn = 1
+            "#,
+            [],
+        )?;
+        eprintln!("[Migration] stream_metadata table created");
+    }
+
+    // moderation_eventsテーブルを作成（メッセージ削除・タイムアウト・BAN）
+    let moderation_events_exists: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM duckdb_tables() WHERE table_name = 'moderation_events'",
+        [],
+        |row| row.get(0),
+    )?;
+    if moderation_events_exists == 0 {
+        eprintln!("[Migration] Creating moderation_events table");
+        conn.execute(
+            "CREATE SEQUENCE IF NOT EXISTS moderation_events_id_seq START 1",
+            [],
+        )?;
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS moderation_events (
+                id BIGINT PRIMARY KEY DEFAULT nextval('moderation_events_id_seq'),
+                channel_id BIGINT NOT NULL,
+                stream_id BIGINT,
+                platform TEXT NOT NULL,
+                event_type TEXT NOT NULL,
+                target_user_id TEXT,
+                target_user_name TEXT,
+                duration_seconds BIGINT,
+                message_id TEXT,
+                message_text TEXT,
+                occurred_at TIMESTAMP NOT NULL
+            )
+            "#,
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_moderation_events_channel ON moderation_events(channel_id, occurred_at)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_moderation_events_stream ON moderation_events(stream_id)",
+            [],
+        )?;
+        eprintln!("[Migration] moderation_events table created");
+    }
+
+    // emote_catalogテーブルを作成（Twitch公式・7TV・BTTV・FFZのエモート定義）
+    let emote_catalog_exists: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM duckdb_tables() WHERE table_name = 'emote_catalog'",
+        [],
+        |row| row.get(0),
+    )?;
+    if emote_catalog_exists == 0 {
+        eprintln!("[Migration] Creating emote_catalog table");
+        conn.execute(
+            "CREATE SEQUENCE IF NOT EXISTS emote_catalog_id_seq START 1",
+            [],
+        )?;
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS emote_catalog (
+                id BIGINT PRIMARY KEY DEFAULT nextval('emote_catalog_id_seq'),
+                source TEXT NOT NULL,
+                emote_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                channel_id BIGINT,
+                image_url TEXT,
+                updated_at TIMESTAMP NOT NULL
+            )
+            "#,
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_emote_catalog_source ON emote_catalog(source, channel_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_emote_catalog_name ON emote_catalog(name)",
+            [],
+        )?;
+        eprintln!("[Migration] emote_catalog table created");
+    }
+
+    // room_state_periodsテーブルを作成（Twitch ROOMSTATE のチャット制限モード期間）
+    let room_state_periods_exists: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM duckdb_tables() WHERE table_name = 'room_state_periods'",
+        [],
+        |row| row.get(0),
+    )?;
+    if room_state_periods_exists == 0 {
+        eprintln!("[Migration] Creating room_state_periods table");
+        conn.execute(
+            "CREATE SEQUENCE IF NOT EXISTS room_state_periods_id_seq START 1",
+            [],
+        )?;
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS room_state_periods (
+                id BIGINT PRIMARY KEY DEFAULT nextval('room_state_periods_id_seq'),
+                channel_id BIGINT NOT NULL,
+                mode TEXT NOT NULL,
+                setting BIGINT,
+                started_at TIMESTAMP NOT NULL,
+                ended_at TIMESTAMP
+            )
+            "#,
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_room_state_periods_channel ON room_state_periods(channel_id, started_at)",
+            [],
+        )?;
+        eprintln!("[Migration] room_state_periods table created");
+    }
+
+    // monetization_eventsテーブルを作成（YouTube Super Chat・Super Sticker・メンバーシップ）
+    let monetization_events_exists: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM duckdb_tables() WHERE table_name = 'monetization_events'",
+        [],
+        |row| row.get(0),
+    )?;
+    if monetization_events_exists == 0 {
+        eprintln!("[Migration] Creating monetization_events table");
+        conn.execute(
+            "CREATE SEQUENCE IF NOT EXISTS monetization_events_id_seq START 1",
+            [],
+        )?;
+        // chat_messages とは (platform, platform_message_id) で対応する
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS monetization_events (
+                id BIGINT PRIMARY KEY DEFAULT nextval('monetization_events_id_seq'),
+                platform TEXT NOT NULL,
+                platform_message_id TEXT NOT NULL,
+                channel_id BIGINT,
+                stream_id BIGINT,
+                event_type TEXT NOT NULL,
+                amount_micros BIGINT,
+                currency TEXT,
+                amount_display TEXT,
+                tier INTEGER,
+                membership_level TEXT,
+                membership_months INTEGER,
+                gift_count INTEGER,
+                sticker_id TEXT,
+                timestamp TIMESTAMP NOT NULL,
+                UNIQUE (platform, platform_message_id)
+            )
+            "#,
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_monetization_events_stream ON monetization_events(stream_id)",
+            [],
+        )?;
+        eprintln!("[Migration] monetization_events table created");
+    }
+
+    // currency_ratesテーブルを作成（ユーザーが登録する通貨換算レート）
+    let currency_rates_exists: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM duckdb_tables() WHERE table_name = 'currency_rates'",
+        [],
+        |row| row.get(0),
+    )?;
+    if currency_rates_exists == 0 {
+        eprintln!("[Migration] Creating currency_rates table");
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS currency_rates (
+                currency TEXT PRIMARY KEY,
+                base_currency TEXT NOT NULL,
+                rate DOUBLE NOT NULL,
+                updated_at TIMESTAMP NOT NULL
+            )
+            "#,
+            [],
+        )?;
+        eprintln!("[Migration] currency_rates table created");
+    }
+
+    // youtube_quota_usageテーブルを作成（YouTube Data APIのクォータ使用量、太平洋時間の日付単位）
+    let youtube_quota_usage_exists: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM duckdb_tables() WHERE table_name = 'youtube_quota_usage'",
+        [],
+        |row| row.get(0),
+    )?;
+    if youtube_quota_usage_exists == 0 {
+        eprintln!("[Migration] Creating youtube_quota_usage table");
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS youtube_quota_usage (
+                quota_date DATE NOT NULL,
+                endpoint TEXT NOT NULL,
+                calls BIGINT NOT NULL DEFAULT 0,
+                units BIGINT NOT NULL DEFAULT 0,
+                updated_at TIMESTAMP,
+                PRIMARY KEY (quota_date, endpoint)
+            )
+            "#,
+            [],
+        )?;
+        eprintln!("[Migration] youtube_quota_usage table created");
+    }
+
+    // scheduled_streamsテーブルを作成（Twitch の配信スケジュールと YouTube の配信予定枠）
+    let scheduled_streams_exists: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM duckdb_tables() WHERE table_name = 'scheduled_streams'",
+        [],
+        |row| row.get(0),
+    )?;
+    if scheduled_streams_exists == 0 {
+        eprintln!("[Migration] Creating scheduled_streams table");
+        conn.execute(
+            "CREATE SEQUENCE IF NOT EXISTS scheduled_streams_id_seq START 1",
+            [],
+        )?;
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS scheduled_streams (
+                id BIGINT PRIMARY KEY DEFAULT nextval('scheduled_streams_id_seq'),
+                channel_id BIGINT NOT NULL,
+                platform TEXT NOT NULL,
+                schedule_id TEXT NOT NULL,
+                title TEXT,
+                category TEXT,
+                scheduled_start TIMESTAMP NOT NULL,
+                scheduled_end TIMESTAMP,
+                status TEXT NOT NULL DEFAULT 'scheduled',
+                stream_id BIGINT,
+                actual_start TIMESTAMP,
+                first_seen_at TIMESTAMP NOT NULL,
+                updated_at TIMESTAMP NOT NULL,
+                UNIQUE (platform, schedule_id)
+            )
+            "#,
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_scheduled_streams_channel ON scheduled_streams(channel_id, scheduled_start)",
+            [],
+        )?;
+        eprintln!("[Migration] scheduled_streams table created");
+    }
+
+    // channels.platform のCHECK制約に 'kick' を追加
+    migrate_channels_platform_check(conn)?;
+
     eprintln!("[Migration] All migrations completed successfully");
     Ok(())
 }
+
+/// game_categories の主キーを (platform, game_id) に変更する
+///
+/// DuckDBは主キーを変更できないため、既存の行をTwitchのカテゴリとして新しいテーブルへ移します。
+/// 名前がIDのままの行（YouTubeの動画カテゴリIDがそのまま登録されたもの）は移しません。
+fn migrate_game_categories_platform(conn: &Connection) -> Result<(), duckdb::Error> {
+    let has_platform: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('game_categories') WHERE name = 'platform'",
+        [],
+        |row| row.get(0),
+    )?;
+    if has_platform > 0 {
+        return Ok(());
+    }
+
+    eprintln!("[Migration] Adding platform column to game_categories table");
+    with_transaction(conn, |conn| {
+        conn.execute(
+            r#"
+            CREATE TABLE game_categories_new (
+                platform TEXT NOT NULL DEFAULT 'twitch',
+                game_id TEXT NOT NULL,
+                game_name TEXT NOT NULL,
+                box_art_url TEXT,
+                last_updated TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY (platform, game_id)
+            )
+            "#,
+            [],
+        )?;
+        conn.execute(
+            r#"
+            INSERT INTO game_categories_new (platform, game_id, game_name, box_art_url, last_updated)
+            SELECT 'twitch', game_id, game_name, box_art_url, last_updated
+            FROM game_categories
+            WHERE game_name <> game_id
+            "#,
+            [],
+        )?;
+        conn.execute("DROP TABLE game_categories", [])?;
+        conn.execute(
+            "ALTER TABLE game_categories_new RENAME TO game_categories",
+            [],
+        )?;
+        Ok::<(), duckdb::Error>(())
+    })?;
+    eprintln!("[Migration] game_categories platform column added");
+    Ok(())
+}
+
+/// channels.platform のCHECK制約を新しいプラットフォームに対応させる
+///
+/// DuckDBはCHECK制約を変更できず、外部キーで参照されているテーブルは削除できないため、
+/// channels / streams / stream_stats を退避してから作り直します。
+fn migrate_channels_platform_check(conn: &Connection) -> Result<(), duckdb::Error> {
+    let table_sql = |table: &str| -> Result<String, duckdb::Error> {
+        conn.query_row(
+            "SELECT sql FROM duckdb_tables() WHERE table_name = ?",
+            [table],
+            |row| row.get(0),
+        )
+    };
+
+    let channels_sql = table_sql("channels")?;
+    if channels_sql.contains("'kick'") {
+        return Ok(());
+    }
+
+    eprintln!("[Migration] Updating channels.platform CHECK constraint to allow 'kick'");
+    let streams_sql = table_sql("streams")?;
+    let stream_stats_sql = table_sql("stream_stats")?;
+
+    // 再作成するインデックス（主キー・UNIQUE制約はテーブル定義に含まれる）
+    let index_sqls: Vec<String> = {
+        let mut stmt = conn.prepare(
+            "SELECT sql FROM duckdb_indexes() \
+             WHERE table_name IN ('channels', 'streams', 'stream_stats') AND sql IS NOT NULL",
+        )?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+
+    // 'youtube' はCHECK制約内にのみ出現する
+    let new_channels_sql = channels_sql.replacen("'youtube'", "'youtube', 'kick'", 1);
+
+    with_transaction(conn, |conn| {
+        for table in ["channels", "streams", "stream_stats"] {
+            conn.execute(
+                &format!(
+                    "CREATE TEMP TABLE {}_backup AS SELECT * FROM {}",
+                    table, table
+                ),
+                [],
+            )?;
+        }
+
+        // 参照する側から削除
+        for table in ["stream_stats", "streams", "channels"] {
+            conn.execute(&format!("DROP TABLE {}", table), [])?;
+        }
+
+        for sql in [&new_channels_sql, &streams_sql, &stream_stats_sql] {
+            conn.execute(sql, [])?;
+        }
+
+        for table in ["channels", "streams", "stream_stats"] {
+            conn.execute(
+                &format!("INSERT INTO {} SELECT * FROM {}_backup", table, table),
+                [],
+            )?;
+            conn.execute(&format!("DROP TABLE {}_backup", table), [])?;
+        }
+
+        for sql in &index_sqls {
+            conn.execute(sql, [])?;
+        }
+
+        Ok::<(), duckdb::Error>(())
+    })?;
+
+    eprintln!("[Migration] channels.platform CHECK constraint updated");
+    Ok(())
+}
@@ -96,6 +96,14 @@ pub fn row_to_chat_message(row: &Row) -> DuckResult<ChatMessage> {
     // 9: message_type
     // 10: badges (CAST(... AS VARCHAR))
     // 11: badge_info
+    // 12: payload
+    // 13: platform_message_id
+    // 14: emotes (JSON)
+    // 15: bits
+    // 16: reply_parent_id
+    // 17: reply_parent_user_name
+    // 18: reply_thread_id
+    // 19: is_auto_discovered
     let badges: Option<Vec<String>> = match row.get::<_, Option<String>>(10)? {
         None => None,
         Some(badges_str) if badges_str.is_empty() => None,
@@ -137,6 +145,22 @@ pub fn row_to_chat_message(row: &Row) -> DuckResult<ChatMessage> {
         message_type: row.get(9)?,
         badges,
         badge_info: row.get::<_, Option<String>>(11).ok().flatten(),
+        payload: row.get::<_, Option<String>>(12).ok().flatten(),
+        platform_message_id: row.get::<_, Option<String>>(13).ok().flatten(),
+        emotes: row
+            .get::<_, Option<String>>(14)
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str(&json).ok()),
+        bits: row.get::<_, Option<i64>>(15).ok().flatten(),
+        reply_parent_id: row.get::<_, Option<String>>(16).ok().flatten(),
+        reply_parent_user_name: row.get::<_, Option<String>>(17).ok().flatten(),
+        reply_thread_id: row.get::<_, Option<String>>(18).ok().flatten(),
+        is_auto_discovered: row
+            .get::<_, Option<bool>>(19)
+            .ok()
+            .flatten()
+            .unwrap_or(false),
     })
 }
 
@@ -175,6 +199,7 @@ pub fn query_chat_messages(
     params: &[String],
 ) -> DuckResult<Vec<ChatMessage>> {
     let mut stmt = conn.prepare(sql)?;
-    let rows = query_map_with_params(&mut stmt, params, row_to_chat_message)?;
+    // 種別フィルタなどでパラメータ数が可変のため params_from_iter で渡す
+    let rows = stmt.query_map(duckdb::params_from_iter(params.iter()), row_to_chat_message)?;
     rows.collect()
 }
@@ -1,5 +1,36 @@
 use crate::database::models::{ChatMessage, Stream, StreamStats};
+use duckdb::arrow::array::{
+    ArrayRef, BooleanArray, Int64Array, ListBuilder, StringArray, StringBuilder,
+};
+use duckdb::arrow::datatypes::{Field, Schema};
+use duckdb::arrow::error::ArrowError;
+use duckdb::arrow::record_batch::RecordBatch;
 use duckdb::{Connection, OptionalExt};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Appender で値を渡す chat_messages の列（id と is_deleted は既定値を使う）
+const CHAT_MESSAGE_APPEND_COLUMNS: [&str; 19] = [
+    "channel_id",
+    "stream_id",
+    "timestamp",
+    "platform",
+    "user_id",
+    "user_name",
+    "display_name",
+    "message",
+    "message_type",
+    "badges",
+    "badge_info",
+    "payload",
+    "platform_message_id",
+    "emotes",
+    "bits",
+    "reply_parent_id",
+    "reply_parent_user_name",
+    "reply_thread_id",
+    "is_auto_discovered",
+];
 
 pub struct DatabaseWriter;
 
@@ -64,6 +95,25 @@ impl DatabaseWriter {
         }
     }
 
+    /// 配信を終了状態にする
+    ///
+    /// ended_at には最後に観測した stream_stats.collected_at を設定します
+    /// （統計が1件もない場合は started_at）。既に終了済みの配信は更新しません。
+    /// 戻り値: 更新した行数
+    pub fn close_stream(conn: &Connection, stream_db_id: i64) -> Result<usize, duckdb::Error> {
+        conn.execute(
+            r#"
+            UPDATE streams
+            SET ended_at = COALESCE(
+                (SELECT MAX(ss.collected_at) FROM stream_stats ss WHERE ss.stream_id = ?),
+                started_at
+            )
+            WHERE id = ? AND ended_at IS NULL
+            "#,
+            duckdb::params![stream_db_id, stream_db_id],
+        )
+    }
+
     pub fn insert_stream_stats(
         conn: &Connection,
         stats: &StreamStats,
@@ -92,76 +142,47 @@ impl DatabaseWriter {
         Ok(())
     }
 
-    pub fn insert_chat_messages_batch(
+    /// チャットメッセージをまとめて保存
+    ///
+    /// platform_message_id が保存済み（またはバッチ内で重複）のメッセージはスキップします。
+    /// 再接続やバッチの再試行で同じメッセージを受け取っても二重に保存されません。
+    /// 戻り値: 実際に保存したメッセージ（スキップしたものを除く）
+    pub fn insert_chat_messages_batch<'a>(
         conn: &Connection,
-        messages: &[ChatMessage],
-    ) -> Result<(), duckdb::Error> {
+        messages: &'a [ChatMessage],
+    ) -> Result<Vec<&'a ChatMessage>, duckdb::Error> {
         if messages.is_empty() {
-            return Ok(());
+            return Ok(Vec::new());
         }
 
         // バッチインサート用のトランザクション開始
         conn.execute("BEGIN TRANSACTION", [])?;
 
-        // エラー時のROLLBACK処理を含むスコープ
+        // エラー時のROLLBACK処理を含むスコープ（戻り値は実際に保存したメッセージ）
         let result = (|| {
-            // パフォーマンス最適化: マルチロウINSERTを使用
-            // DuckDBでは大量の行を一度にINSERTする方が効率的
-
-            // VALUES句を構築
-            let values_placeholders: Vec<String> = messages
-                .iter()
-                .map(|msg| {
-                    // badges を DuckDB 配列リテラル形式に変換
-                    let badges_literal = match &msg.badges {
-                        None => "NULL".to_string(),
-                        Some(badges) if badges.is_empty() => "NULL".to_string(),
-                        Some(badges) => {
-                            let escaped_badges: Vec<String> = badges
-                                .iter()
-                                .map(|b| format!("'{}'", b.replace("'", "''")))
-                                .collect();
-                            format!("ARRAY[{}]", escaped_badges.join(", "))
-                        }
-                    };
-                    format!("(?, ?, ?, ?, ?, ?, ?, ?, ?, {}, ?)", badges_literal)
-                })
-                .collect();
-
-            let sql = format!(
-                "INSERT INTO chat_messages (channel_id, stream_id, timestamp, platform, user_id, user_name, display_name, message, message_type, badges, badge_info) VALUES {}",
-                values_placeholders.join(", ")
-            );
-
-            // すべてのパラメータを順番に配列に格納
-            let mut params: Vec<Box<dyn duckdb::ToSql>> = Vec::new();
-            for message in messages {
-                params.push(Box::new(message.channel_id));
-                params.push(Box::new(message.stream_id));
-                params.push(Box::new(message.timestamp.clone()));
-                params.push(Box::new(message.platform.clone()));
-                params.push(Box::new(message.user_id.clone()));
-                params.push(Box::new(message.user_name.clone()));
-                params.push(Box::new(message.display_name.clone()));
-                params.push(Box::new(message.message.clone()));
-                params.push(Box::new(message.message_type.clone()));
-                // badges はリテラルで埋め込み済みのためスキップ
-                params.push(Box::new(message.badge_info.clone()));
+            let inserted = Self::filter_new_chat_messages(conn, messages)?;
+            if inserted.is_empty() {
+                return Ok(inserted);
             }
 
-            // パラメータ参照を作成
-            let param_refs: Vec<&dyn duckdb::ToSql> = params.iter().map(|p| p.as_ref()).collect();
-
-            conn.execute(&sql, param_refs.as_slice())?;
+            // Appender で一括追加（badges は TEXT[] にネイティブなLISTとして渡す）
+            let batch = Self::chat_messages_record_batch(&inserted)
+                .map_err(|e| duckdb::Error::ToSqlConversionFailure(Box::new(e)))?;
+            let mut appender = conn.appender("chat_messages")?;
+            for column in CHAT_MESSAGE_APPEND_COLUMNS {
+                appender.add_column(column)?;
+            }
+            appender.append_record_batch(batch)?;
+            appender.flush()?;
 
-            Ok::<(), duckdb::Error>(())
+            Ok::<Vec<&ChatMessage>, duckdb::Error>(inserted)
         })();
 
         // エラーハンドリング: エラーの場合はROLLBACK、成功の場合はCOMMIT
         match result {
-            Ok(_) => {
+            Ok(inserted) => {
                 conn.execute("COMMIT", [])?;
-                Ok(())
+                Ok(inserted)
             }
             Err(e) => {
                 // ROLLBACKを試行（ROLLBACKが失敗しても元のエラーを返す）
@@ -172,4 +193,204 @@ impl DatabaseWriter {
             }
         }
     }
+
+    /// チャットメッセージを CHAT_MESSAGE_APPEND_COLUMNS の順の RecordBatch に変換
+    ///
+    /// timestamp と bits は Appender がテーブルの型（TIMESTAMP / INTEGER）へキャストします。
+    fn chat_messages_record_batch(messages: &[&ChatMessage]) -> Result<RecordBatch, ArrowError> {
+        fn text<'a>(
+            messages: &[&'a ChatMessage],
+            f: impl Fn(&'a ChatMessage) -> Option<&'a str>,
+        ) -> ArrayRef {
+            Arc::new(messages.iter().map(|m| f(m)).collect::<StringArray>())
+        }
+        fn int64(messages: &[&ChatMessage], f: impl Fn(&ChatMessage) -> Option<i64>) -> ArrayRef {
+            Arc::new(messages.iter().map(|m| f(m)).collect::<Int64Array>())
+        }
+
+        // 空のバッジは従来どおり NULL として保存
+        let mut badges = ListBuilder::new(StringBuilder::new());
+        for message in messages {
+            match &message.badges {
+                Some(list) if !list.is_empty() => {
+                    for badge in list {
+                        badges.values().append_value(badge);
+                    }
+                    badges.append(true);
+                }
+                _ => badges.append(false),
+            }
+        }
+
+        let emotes: Vec<Option<String>> = messages
+            .iter()
+            .map(|m| {
+                m.emotes
+                    .as_ref()
+                    .and_then(|emotes| serde_json::to_string(emotes).ok())
+            })
+            .collect();
+
+        let columns: Vec<ArrayRef> = vec![
+            int64(messages, |m| m.channel_id),
+            int64(messages, |m| m.stream_id),
+            text(messages, |m| Some(m.timestamp.as_str())),
+            text(messages, |m| Some(m.platform.as_str())),
+            text(messages, |m| m.user_id.as_deref()),
+            text(messages, |m| Some(m.user_name.as_str())),
+            text(messages, |m| m.display_name.as_deref()),
+            text(messages, |m| Some(m.message.as_str())),
+            text(messages, |m| Some(m.message_type.as_str())),
+            Arc::new(badges.finish()),
+            text(messages, |m| m.badge_info.as_deref()),
+            text(messages, |m| m.payload.as_deref()),
+            text(messages, |m| m.platform_message_id.as_deref()),
+            Arc::new(emotes.into_iter().collect::<StringArray>()),
+            int64(messages, |m| m.bits),
+            text(messages, |m| m.reply_parent_id.as_deref()),
+            text(messages, |m| m.reply_parent_user_name.as_deref()),
+            text(messages, |m| m.reply_thread_id.as_deref()),
+            Arc::new(
+                messages
+                    .iter()
+                    .map(|m| Some(m.is_auto_discovered))
+                    .collect::<BooleanArray>(),
+            ),
+        ];
+
+        let fields: Vec<Field> = CHAT_MESSAGE_APPEND_COLUMNS
+            .iter()
+            .zip(&columns)
+            .map(|(name, column)| Field::new(*name, column.data_type().clone(), true))
+            .collect();
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+    }
+
+    /// 保存済み・バッチ内重複のメッセージを除外
+    fn filter_new_chat_messages<'a>(
+        conn: &Connection,
+        messages: &'a [ChatMessage],
+    ) -> Result<Vec<&'a ChatMessage>, duckdb::Error> {
+        let ids: Vec<String> = messages
+            .iter()
+            .filter_map(|m| m.platform_message_id.clone())
+            .collect();
+
+        let mut seen: HashSet<(String, String)> = HashSet::new();
+        if !ids.is_empty() {
+            let sql = format!(
+                "SELECT platform, platform_message_id FROM chat_messages WHERE platform_message_id IN ({})",
+                vec!["?"; ids.len()].join(", ")
+            );
+            let mut stmt = conn.prepare(&sql)?;
+            // IDの数はバッチサイズに依存するため params_from_iter で渡す
+            let existing = stmt.query_map(duckdb::params_from_iter(ids.iter()), |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?;
+            for key in existing {
+                seen.insert(key?);
+            }
+        }
+
+        Ok(messages
+            .iter()
+            .filter(|m| match &m.platform_message_id {
+                Some(id) => seen.insert((m.platform.clone(), id.clone())),
+                None => true,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chat(platform_message_id: Option<&str>, message: &str) -> ChatMessage {
+        ChatMessage {
+            channel_id: Some(1),
+            stream_id: Some(10),
+            timestamp: "2025-01-06T12:00:00+09:00".to_string(),
+            platform: "twitch".to_string(),
+            user_id: Some("100".to_string()),
+            user_name: "viewer".to_string(),
+            message: message.to_string(),
+            message_type: "normal".to_string(),
+            badges: Some(vec!["subscriber".to_string()]),
+            platform_message_id: platform_message_id.map(|id| id.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    #[cfg_attr(
+        target_os = "windows",
+        ignore = "Database tests are unstable on Windows local environment"
+    )]
+    fn test_insert_chat_messages_batch_skips_duplicates() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::init_database(&conn).unwrap();
+
+        let first = vec![chat(Some("a"), "hello"), chat(Some("a"), "hello")];
+        DatabaseWriter::insert_chat_messages_batch(&conn, &first).unwrap();
+
+        // 再接続などで同じメッセージを再受信しても二重に保存しない
+        let second = vec![
+            chat(Some("a"), "hello"),
+            chat(Some("b"), "world"),
+            chat(None, "no id"),
+        ];
+        DatabaseWriter::insert_chat_messages_batch(&conn, &second).unwrap();
+
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM chat_messages", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    #[cfg_attr(
+        target_os = "windows",
+        ignore = "Database tests are unstable on Windows local environment"
+    )]
+    fn test_insert_chat_messages_batch_appends_badges_as_list() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::init_database(&conn).unwrap();
+
+        let mut badged = chat(Some("a"), "hello");
+        badged.badges = Some(vec!["moderator".to_string(), "it's".to_string()]);
+        badged.bits = Some(100);
+        let mut unbadged = chat(Some("b"), "world");
+        unbadged.badges = Some(Vec::new());
+        DatabaseWriter::insert_chat_messages_batch(&conn, &[badged, unbadged]).unwrap();
+
+        let rows: Vec<(i64, bool, bool, Option<i64>, bool)> = conn
+            .prepare(
+                r#"
+                SELECT id, list_contains(badges, 'moderator'), list_contains(badges, 'it''s'),
+                       bits, badges IS NULL
+                FROM chat_messages ORDER BY platform_message_id
+                "#,
+            )
+            .unwrap()
+            .query_map([], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get::<_, Option<bool>>(1)?.unwrap_or(false),
+                    row.get::<_, Option<bool>>(2)?.unwrap_or(false),
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
+            })
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        // id はシーケンスの既定値、空のバッジは NULL
+        assert_ne!(rows[0].0, rows[1].0);
+        assert!(rows[0].1 && rows[0].2);
+        assert_eq!(rows[0].3, Some(100));
+        assert!(!rows[0].4);
+        assert!(rows[1].4);
+    }
 }
@@ -19,15 +19,20 @@ use tauri::{
 use tokio::sync::Mutex;
 
 use collectors::{
-    auto_discovery::AutoDiscoveryPoller, poller::ChannelPoller, twitch::TwitchCollector,
+    auto_discovery::AutoDiscoveryPoller,
+    kick::KickCollector,
+    poller::ChannelPoller,
+    replay::{self, ReplayCapture, ReplayChatSource, ReplayClock, ReplayCollector, ReplayRecorder},
+    twitch::TwitchCollector,
     youtube::YouTubeCollector,
 };
 use commands::{
     analytics::{
         detect_chat_spikes, get_broadcaster_analytics, get_channel_daily_stats,
         get_chat_engagement_timeline, get_chatter_behavior_stats, get_data_availability,
-        get_game_analytics, get_game_daily_stats, get_time_pattern_stats, get_top_chatters,
-        get_user_segment_stats, list_game_categories,
+        get_game_analytics, get_game_daily_stats, get_moderation_stats, get_monetization_stats,
+        get_schedule_adherence, get_scheduled_streams, get_super_chat_revenue,
+        get_time_pattern_stats, get_top_chatters, get_user_segment_stats, list_game_categories,
     },
     channels::{
         add_channel, list_channels, list_channels_basic, remove_channel, toggle_channel,
@@ -39,9 +44,10 @@ use commands::{
         get_oauth_config, has_oauth_config, recreate_database, save_oauth_config, save_token,
         verify_token,
     },
+    currency::{get_currency_rates, save_currency_rates},
     data_science::{
         detect_anomalies, get_category_change_impact, get_chatter_activity_scores,
-        get_emote_analysis, get_message_length_stats, get_viewer_chat_correlation,
+        get_emote_analysis, get_message_length_stats, get_reply_graph, get_viewer_chat_correlation,
         get_word_frequency_analysis,
     },
     database::get_database_info,
@@ -50,28 +56,31 @@ use commands::{
         promote_discovered_channel, promote_discovered_channels, save_auto_discovery_settings,
         search_twitch_games, toggle_auto_discovery, DiscoveredStreamInfo,
     },
-    export::{export_to_delimited, preview_export_data},
+    emotes::{get_emote_catalog, import_emote_catalog, sync_twitch_emotes},
+    export::{export_stream_events_to_delimited, export_to_delimited, preview_export_data},
     game_categories::{
         delete_game_category, get_game_categories, get_game_category, search_game_categories,
         upsert_game_category,
     },
     logs::get_logs,
     oauth::{poll_twitch_device_token, reinitialize_twitch_collector, start_twitch_device_auth},
+    poll_policy::{delete_poll_policy, get_poll_policy, save_poll_policy},
     sql::{
         delete_sql_template, execute_sql, list_database_tables, list_sql_templates,
         save_sql_template,
     },
     stats::{get_realtime_chat_rate, get_stream_stats},
-    system::is_backend_ready,
+    system::{get_collector_statuses, is_backend_ready},
     timeline::{
         get_channel_streams, get_stream_timeline, get_streams_by_date_range,
         get_suggested_streams_for_comparison,
     },
     twitch::{get_twitch_rate_limit_status, validate_twitch_channel},
     window::show_main_window,
+    youtube::{get_youtube_quota_status, validate_youtube_channel},
 };
 use config::settings::SettingsManager;
-use database::DatabaseManager;
+use database::{chat_spool::ChatSpool, DatabaseManager};
 use logger::AppLogger;
 use std::sync::Arc;
 
@@ -212,6 +221,22 @@ pub fn run() {
             };
             app.manage(db_manager.clone());
 
+            // DB障害やクラッシュでチャットを失わないよう、DBと同じディレクトリにスプールを置く
+            // （シーケンス番号が衝突しないよう、コレクターを再初期化しても同じスプールを共有する）
+            let spool_dir = db_manager.get_db_path().with_file_name("chat_spool");
+            let chat_spool: Option<Arc<ChatSpool>> = match ChatSpool::open(&spool_dir) {
+                Ok(spool) => Some(Arc::new(spool)),
+                Err(e) => {
+                    logger.error(&format!(
+                        "Failed to open chat spool at {}: {}",
+                        spool_dir.display(),
+                        e
+                    ));
+                    None
+                }
+            };
+            app.manage(chat_spool);
+
             // Ctrl+C / SIGTERMシグナルハンドラを設定（ホットリロード対策）
             let db_manager_for_signal = db_manager.clone();
             let logger_for_signal = logger.clone();
@@ -295,59 +320,120 @@ pub fn run() {
                             }
                         };
 
-                        // Initialize Twitch collector if credentials are available
-                        // Device Code Flow uses only client_id (no client_secret required)
-                        if let Some(client_id) = &settings.twitch.client_id {
-                            let collector = Arc::new(TwitchCollector::new_with_app(
-                                client_id.clone(),
-                                None,
-                                app_handle_for_init.clone(),
-                                Arc::new(db_manager.inner().clone()),
-                                Arc::new(logger_for_init.clone()),
-                            ));
-                            // IRC DB ハンドラーを初期化
-                            collector.initialize_irc().await;
-
-                            // Register collector - lock only for registration
-                            {
-                                let mut poller = poller_for_init.lock().await;
-                                poller.register_twitch_collector(collector);
+                        // 記録モード: ライブのStreamDataとチャットをキャプチャファイルにも書き出す
+                        let replay_settings = settings.replay.clone().unwrap_or_default();
+                        if let Some(record_file) = &replay_settings.record_file {
+                            match ReplayRecorder::create(std::path::Path::new(record_file)) {
+                                Ok(recorder) => {
+                                    replay::set_active_recorder(Some(Arc::new(recorder)));
+                                    logger_for_init.info(&format!("Recording live data to {}", record_file));
+                                }
+                                Err(e) => {
+                                    logger_for_init.error(&format!("Failed to create capture file {}: {}", record_file, e));
+                                }
                             }
-                            logger_for_init.info("Twitch collector initialized successfully with IRC support");
-                        } else {
-                            logger_for_init.info("Twitch credentials not configured, skipping collector initialization");
                         }
 
-                        // Initialize YouTube collector if credentials are available
-                        if let (Some(client_id), Some(client_secret)) = (&settings.youtube.client_id, &settings.youtube.client_secret) {
-                            match YouTubeCollector::new(
-                                client_id.clone(),
-                                client_secret.clone(),
-                                "http://localhost:8081/callback".to_string(),
-                                Arc::new(db_manager.inner().clone()),
-                            )
-                            .await
-                            {
-                                Ok(collector) => {
-                                    // Register collector - lock only for registration
+                        // 再生モード: キャプチャファイルをライブのコレクターの代わりに使用
+                        let replay_started = match &replay_settings.replay_file {
+                            Some(replay_file) => match ReplayCapture::load(std::path::Path::new(replay_file)) {
+                                Ok(capture) => {
+                                    let capture = Arc::new(capture);
+                                    let clock = ReplayClock::start(&capture, replay_settings.speed);
                                     {
                                         let mut poller = poller_for_init.lock().await;
-                                        poller.register_collector(
-                                            crate::constants::database::PLATFORM_YOUTUBE.to_string(),
-                                            Arc::new(collector),
-                                        );
+                                        poller.register_replay_collector(Arc::new(ReplayCollector::new(
+                                            Arc::clone(&capture),
+                                            clock.clone(),
+                                        )));
                                     }
-                                    logger_for_init
-                                        .info("YouTube collector initialized successfully");
+                                    ReplayChatSource::spawn(
+                                        capture,
+                                        clock,
+                                        Arc::new(db_manager.inner().clone()),
+                                        Arc::new(logger_for_init.clone()),
+                                    );
+                                    logger_for_init.info(&format!(
+                                        "Replaying capture file {} at {}x speed",
+                                        replay_file, replay_settings.speed
+                                    ));
+                                    true
                                 }
                                 Err(e) => {
-                                    logger_for_init
-                                        .error(&format!("Failed to initialize YouTube collector: {}", e));
+                                    logger_for_init.error(&format!("Failed to load capture file {}: {}", replay_file, e));
+                                    false
+                                }
+                            },
+                            None => false,
+                        };
+
+                        if !replay_started {
+                            // Initialize Twitch collector if credentials are available
+                            // Device Code Flow uses only client_id (no client_secret required)
+                            if let Some(client_id) = &settings.twitch.client_id {
+                                let collector = Arc::new(TwitchCollector::new_with_app(
+                                    client_id.clone(),
+                                    None,
+                                    app_handle_for_init.clone(),
+                                    Arc::new(db_manager.inner().clone()),
+                                    app_handle_for_init.state::<Option<Arc<ChatSpool>>>().inner().clone(),
+                                    Arc::new(logger_for_init.clone()),
+                                ));
+                                // IRC DB ハンドラーを初期化
+                                collector.initialize_irc().await;
+
+                                // Register collector - lock only for registration
+                                {
+                                    let mut poller = poller_for_init.lock().await;
+                                    poller.register_twitch_collector(collector);
                                 }
+                                logger_for_init.info("Twitch collector initialized successfully with IRC support");
+                            } else {
+                                logger_for_init.info("Twitch credentials not configured, skipping collector initialization");
                             }
-                        } else {
-                            logger_for_init
-                                .info("YouTube credentials not configured, skipping collector initialization");
+
+                            // Initialize YouTube collector if credentials are available
+                            if let (Some(client_id), Some(client_secret)) = (&settings.youtube.client_id, &settings.youtube.client_secret) {
+                                match YouTubeCollector::new(
+                                    client_id.clone(),
+                                    client_secret.clone(),
+                                    "http://localhost:8081/callback".to_string(),
+                                    settings.youtube.daily_quota_budget,
+                                    settings.youtube.region_code.clone(),
+                                    Arc::new(db_manager.inner().clone()),
+                                    Arc::new(logger_for_init.clone()),
+                                )
+                                .await
+                                {
+                                    Ok(collector) => {
+                                        // Register collector - lock only for registration
+                                        {
+                                            let mut poller = poller_for_init.lock().await;
+                                            poller.register_youtube_collector(Arc::new(collector));
+                                        }
+                                        logger_for_init
+                                            .info("YouTube collector initialized successfully");
+                                    }
+                                    Err(e) => {
+                                        logger_for_init
+                                            .error(&format!("Failed to initialize YouTube collector: {}", e));
+                                    }
+                                }
+                            } else {
+                                logger_for_init
+                                    .info("YouTube credentials not configured, skipping collector initialization");
+                            }
+
+                            // Kick collector (public API, no credentials required)
+                            {
+                                let collector = Arc::new(KickCollector::new(
+                                    Arc::new(db_manager.inner().clone()),
+                                    Arc::new(logger_for_init.clone()),
+                                ));
+                                let mut poller = poller_for_init.lock().await;
+                                poller.register_kick_collector(collector);
+                            }
+                            logger_for_init.info("Kick collector initialized successfully");
                         }
 
                         // Start polling for existing enabled channels
@@ -421,6 +507,9 @@ pub fn run() {
 
                         // Emit backend-ready event to notify frontend that all collectors and pollers are initialized
                         let _ = app_handle_for_init.emit("backend-ready", ());
+
+                        // コレクターのヘルス状態を定期的にフロントエンドへ通知
+                        crate::collectors::health::start_health_reporter(app_handle_for_init.clone());
                         logger_for_init.info("Backend fully initialized, frontend queries are now safe");
                     });
 
@@ -504,6 +593,8 @@ pub fn run() {
             get_data_availability,
             get_game_daily_stats,
             get_channel_daily_stats,
+            get_scheduled_streams,
+            get_schedule_adherence,
             // Chat Analytics commands
             get_chat_engagement_timeline,
             detect_chat_spikes,
@@ -511,6 +602,9 @@ pub fn run() {
             get_top_chatters,
             get_time_pattern_stats,
             get_chatter_behavior_stats,
+            get_moderation_stats,
+            get_monetization_stats,
+            get_super_chat_revenue,
             // Data Science commands
             get_word_frequency_analysis,
             get_emote_analysis,
@@ -518,7 +612,15 @@ pub fn run() {
             get_viewer_chat_correlation,
             get_category_change_impact,
             get_chatter_activity_scores,
+            get_reply_graph,
             detect_anomalies,
+            // Emote commands
+            get_emote_catalog,
+            import_emote_catalog,
+            sync_twitch_emotes,
+            // Currency commands
+            get_currency_rates,
+            save_currency_rates,
             // Channel commands
             add_channel,
             remove_channel,
@@ -528,6 +630,7 @@ pub fn run() {
             toggle_channel,
             // System commands
             is_backend_ready,
+            get_collector_statuses,
             // Chat commands
             get_chat_messages,
             get_chat_messages_around_timestamp,
@@ -559,6 +662,10 @@ pub fn run() {
             upsert_game_category,
             delete_game_category,
             search_game_categories,
+            // Poll Policy commands
+            get_poll_policy,
+            save_poll_policy,
+            delete_poll_policy,
             // SQL commands
             execute_sql,
             list_sql_templates,
@@ -579,12 +686,16 @@ pub fn run() {
             get_suggested_streams_for_comparison,
             // Export commands
             export_to_delimited,
+            export_stream_events_to_delimited,
             preview_export_data,
             // Logs commands
             get_logs,
             // Twitch commands
             validate_twitch_channel,
             get_twitch_rate_limit_status,
+            // YouTube commands
+            get_youtube_quota_status,
+            validate_youtube_channel,
             // Window commands
             show_main_window,
         ])
@@ -0,0 +1,579 @@
+use crate::collectors::replay;
+use crate::constants::{database as db_constants, kick};
+use crate::database::models::ChatMessage;
+use crate::database::DatabaseManager;
+use crate::logger::AppLogger;
+use crate::websocket::twitch_irc::{
+    ChatIngestMetrics, IngestCounters, IrcConnectionStatus, INGEST_DROP_LOG_INTERVAL,
+};
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time::{interval, sleep, Duration, MissedTickBehavior};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+/// Kickチャットのメッセージ（Pusherイベントから抽出）
+#[derive(Debug, Clone, PartialEq)]
+pub struct KickChatMessage {
+    pub id: Option<String>,
+    pub content: String,
+    pub created_at: Option<String>,
+    pub sender_id: Option<i64>,
+    pub username: String,
+    pub slug: Option<String>,
+    pub badges: Vec<String>,
+}
+
+/// Pusherから受信したイベント
+#[derive(Debug, Clone, PartialEq)]
+pub enum PusherEvent {
+    Ping,
+    /// チャットルームの購読が完了した
+    Subscribed,
+    ChatMessage(KickChatMessage),
+    Other,
+}
+
+#[derive(Deserialize)]
+struct PusherFrame {
+    event: String,
+    #[serde(default)]
+    data: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct ChatMessagePayload {
+    id: Option<String>,
+    content: String,
+    created_at: Option<String>,
+    sender: ChatSender,
+}
+
+#[derive(Deserialize)]
+struct ChatSender {
+    id: Option<i64>,
+    username: String,
+    slug: Option<String>,
+    identity: Option<ChatIdentity>,
+}
+
+#[derive(Deserialize)]
+struct ChatIdentity {
+    #[serde(default)]
+    badges: Vec<ChatBadge>,
+}
+
+#[derive(Deserialize)]
+struct ChatBadge {
+    #[serde(rename = "type")]
+    badge_type: String,
+}
+
+/// Pusherのフレームを解析
+///
+/// アプリケーションイベントの data はJSON文字列としてエンコードされているため二重にデコードします。
+pub fn parse_pusher_event(text: &str) -> Option<PusherEvent> {
+    let frame: PusherFrame = serde_json::from_str(text).ok()?;
+
+    match frame.event.as_str() {
+        "pusher:ping" => Some(PusherEvent::Ping),
+        "pusher_internal:subscription_succeeded" => Some(PusherEvent::Subscribed),
+        event if event == kick::CHAT_MESSAGE_EVENT => {
+            let payload: ChatMessagePayload = match frame.data {
+                serde_json::Value::String(data) => serde_json::from_str(&data).ok()?,
+                data => serde_json::from_value(data).ok()?,
+            };
+            Some(PusherEvent::ChatMessage(KickChatMessage {
+                id: payload.id,
+                content: payload.content,
+                created_at: payload.created_at,
+                sender_id: payload.sender.id,
+                username: payload.sender.username,
+                slug: payload.sender.slug,
+                badges: payload
+                    .sender
+                    .identity
+                    .map(|identity| identity.badges.into_iter().map(|b| b.badge_type).collect())
+                    .unwrap_or_default(),
+            }))
+        }
+        _ => Some(PusherEvent::Other),
+    }
+}
+
+/// チャットルームを購読し、切断されるまでメッセージを転送する
+///
+/// 購読の完了通知を受け取った時点で `is_connected` を true にします。
+/// 戻り値: 接続が正常に閉じられた場合は Ok(())
+pub async fn listen_chatroom(
+    ws_url: &str,
+    chatroom_id: i64,
+    tx: &mpsc::Sender<KickChatMessage>,
+    is_connected: &AtomicBool,
+) -> Result<(), String> {
+    let (mut ws, _) = connect_async(ws_url).await.map_err(|e| e.to_string())?;
+
+    let subscribe = serde_json::json!({
+        "event": "pusher:subscribe",
+        "data": {"auth": "", "channel": format!("chatrooms.{}.v2", chatroom_id)},
+    });
+    ws.send(Message::Text(subscribe.to_string().into()))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    while let Some(frame) = ws.next().await {
+        let text = match frame.map_err(|e| e.to_string())? {
+            Message::Text(text) => text,
+            Message::Ping(payload) => {
+                ws.send(Message::Pong(payload))
+                    .await
+                    .map_err(|e| e.to_string())?;
+                continue;
+            }
+            Message::Close(_) => break,
+            _ => continue,
+        };
+
+        match parse_pusher_event(text.as_str()) {
+            Some(PusherEvent::Ping) => {
+                let pong = serde_json::json!({"event": "pusher:pong", "data": {}});
+                ws.send(Message::Text(pong.to_string().into()))
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+            Some(PusherEvent::Subscribed) => is_connected.store(true, Ordering::SeqCst),
+            Some(PusherEvent::ChatMessage(message)) => {
+                if tx.send(message).await.is_err() {
+                    // 受信側が終了している
+                    break;
+                }
+            }
+            Some(PusherEvent::Other) | None => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// チャンネルごとのチャット接続管理
+struct KickChatConnection {
+    channel_name: String,
+    chatroom_id: i64,
+    stream_id: Arc<Mutex<Option<i64>>>,
+    is_connected: Arc<AtomicBool>,
+    message_count: Arc<AtomicU64>,
+    last_message_at: Arc<Mutex<Option<String>>>,
+    task: JoinHandle<()>,
+}
+
+/// 複数のKickチャット接続を管理するマネージャー
+pub struct KickChatManager {
+    channels: Arc<Mutex<HashMap<i64, KickChatConnection>>>,
+    ws_url: String,
+    message_tx: mpsc::Sender<ChatMessage>,
+    ingest_counters: Arc<IngestCounters>,
+    logger: Arc<AppLogger>,
+}
+
+impl KickChatManager {
+    pub fn new(db_manager: Arc<DatabaseManager>, logger: Arc<AppLogger>) -> Self {
+        Self::with_ws_url(kick::PUSHER_WS_URL, db_manager, logger)
+    }
+
+    /// WebSocket URLを指定して作成（テスト用モックサーバーなど）
+    pub fn with_ws_url(
+        ws_url: impl Into<String>,
+        db_manager: Arc<DatabaseManager>,
+        logger: Arc<AppLogger>,
+    ) -> Self {
+        let (message_tx, mut message_rx) =
+            mpsc::channel::<ChatMessage>(db_constants::CHAT_INGEST_QUEUE_CAPACITY);
+        let ingest_counters = Arc::new(IngestCounters::default());
+        let counters = Arc::clone(&ingest_counters);
+        let logger_clone = Arc::clone(&logger);
+
+        // DB書き込みタスク（100件または5秒ごとにバッチ保存）
+        tokio::spawn(async move {
+            let mut batch: Vec<ChatMessage> = Vec::new();
+            let mut ticker = interval(Duration::from_secs(db_constants::BATCH_FLUSH_INTERVAL_SECS));
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+            loop {
+                tokio::select! {
+                    message = message_rx.recv() => match message {
+                        Some(message) => {
+                            batch.push(message);
+                            if batch.len() >= db_constants::CHAT_BATCH_SIZE {
+                                Self::flush_batch(&db_manager, &mut batch, &counters, &logger_clone)
+                                    .await;
+                            }
+                        }
+                        None => break,
+                    },
+                    _ = ticker.tick() => {
+                        Self::flush_batch(&db_manager, &mut batch, &counters, &logger_clone).await;
+                    }
+                }
+            }
+
+            // 残りのメッセージをフラッシュ
+            Self::flush_batch(&db_manager, &mut batch, &counters, &logger_clone).await;
+            if !batch.is_empty() {
+                let dropped = counters.record_dropped(batch.len() as u64);
+                logger_clone.error(&format!(
+                    "[KickChat] Discarded {} unsaved chat messages ({} dropped so far)",
+                    batch.len(),
+                    dropped
+                ));
+            }
+        });
+
+        Self {
+            channels: Arc::new(Mutex::new(HashMap::new())),
+            ws_url: ws_url.into(),
+            message_tx,
+            ingest_counters,
+            logger,
+        }
+    }
+
+    /// チャット取り込みの状態を取得
+    pub fn ingest_metrics(&self) -> ChatIngestMetrics {
+        self.ingest_counters.metrics(
+            (self.message_tx.max_capacity() - self.message_tx.capacity()) as u64,
+            self.message_tx.max_capacity() as u64,
+        )
+    }
+
+    /// バッチメッセージをデータベースに書き込み
+    ///
+    /// 失敗したバッチは CHAT_PENDING_BATCH_CAPACITY 件まで保持して次回再試行し、超えた分は破棄します。
+    async fn flush_batch(
+        db_manager: &Arc<DatabaseManager>,
+        batch: &mut Vec<ChatMessage>,
+        counters: &IngestCounters,
+        logger: &Arc<AppLogger>,
+    ) {
+        if batch.is_empty() {
+            return;
+        }
+
+        let started = Instant::now();
+        let result = db_manager
+            .with_connection(|conn| {
+                crate::database::writer::DatabaseWriter::insert_chat_messages_batch(conn, batch)
+            })
+            .await;
+
+        match result {
+            Ok(inserted) => {
+                // 記録モードでは保存したチャットをキャプチャファイルにも書き出す
+                if let Some(recorder) = replay::active_recorder() {
+                    recorder.record_chat_batch(&inserted);
+                }
+                counters.record_insert(batch.len(), started);
+                batch.clear();
+            }
+            Err(e) => {
+                logger.error(&format!("[KickChat] Failed to save chat messages: {}", e));
+                // DB障害が続いてもメモリを使い切らないよう、保持する件数に上限を設ける
+                let capacity = db_constants::CHAT_PENDING_BATCH_CAPACITY;
+                if batch.len() > capacity {
+                    let discarded = batch.len() - capacity;
+                    batch.truncate(capacity);
+                    let dropped = counters.record_dropped(discarded as u64);
+                    logger.error(&format!(
+                        "[KickChat] Pending chat exceeded {} messages; discarded {} ({} dropped so far)",
+                        capacity, discarded, dropped
+                    ));
+                }
+            }
+        }
+    }
+
+    /// 指定したチャンネルのチャット収集を開始
+    pub async fn start_channel_collection(
+        &self,
+        channel_id: i64,
+        channel_name: &str,
+        chatroom_id: i64,
+    ) -> Result<(), String> {
+        let mut channels = self.channels.lock().await;
+
+        if let Some(connection) = channels.get(&channel_id) {
+            if connection.chatroom_id == chatroom_id && !connection.task.is_finished() {
+                return Ok(());
+            }
+            connection.task.abort();
+        }
+
+        let stream_id = Arc::new(Mutex::new(None));
+        let is_connected = Arc::new(AtomicBool::new(false));
+        let message_count = Arc::new(AtomicU64::new(0));
+        let last_message_at = Arc::new(Mutex::new(None));
+
+        let task = tokio::spawn(Self::run_connection(
+            self.ws_url.clone(),
+            channel_id,
+            channel_name.to_string(),
+            chatroom_id,
+            Arc::clone(&stream_id),
+            Arc::clone(&is_connected),
+            Arc::clone(&message_count),
+            Arc::clone(&last_message_at),
+            self.message_tx.clone(),
+            Arc::clone(&self.ingest_counters),
+            Arc::clone(&self.logger),
+        ));
+
+        channels.insert(
+            channel_id,
+            KickChatConnection {
+                channel_name: channel_name.to_string(),
+                chatroom_id,
+                stream_id,
+                is_connected,
+                message_count,
+                last_message_at,
+                task,
+            },
+        );
+
+        self.logger.info(&format!(
+            "[KickChat] Started collection for channel {} (id: {}, chatroom: {})",
+            channel_name, channel_id, chatroom_id
+        ));
+        Ok(())
+    }
+
+    /// 接続が切れた場合は再接続を繰り返す
+    #[allow(clippy::too_many_arguments)]
+    async fn run_connection(
+        ws_url: String,
+        channel_id: i64,
+        channel_name: String,
+        chatroom_id: i64,
+        stream_id: Arc<Mutex<Option<i64>>>,
+        is_connected: Arc<AtomicBool>,
+        message_count: Arc<AtomicU64>,
+        last_message_at: Arc<Mutex<Option<String>>>,
+        message_tx: mpsc::Sender<ChatMessage>,
+        counters: Arc<IngestCounters>,
+        logger: Arc<AppLogger>,
+    ) {
+        loop {
+            let (tx, mut rx) = mpsc::channel::<KickChatMessage>(kick::CHAT_RECEIVE_QUEUE_CAPACITY);
+
+            // 受信したメッセージをChatMessageに変換して書き込みタスクへ転送
+            let forward_stream_id = Arc::clone(&stream_id);
+            let forward_count = Arc::clone(&message_count);
+            let forward_last_message_at = Arc::clone(&last_message_at);
+            let forward_tx = message_tx.clone();
+            let forward_counters = Arc::clone(&counters);
+            let forward_logger = Arc::clone(&logger);
+            let forwarder = tokio::spawn(async move {
+                while let Some(message) = rx.recv().await {
+                    forward_count.fetch_add(1, Ordering::SeqCst);
+                    *forward_last_message_at.lock().await = Some(Local::now().to_rfc3339());
+                    let stream_id = *forward_stream_id.lock().await;
+                    let message = Self::to_chat_message(channel_id, stream_id, message);
+                    // 書き込みが滞っても受信を止めないよう、キューが満杯の場合は破棄して件数を記録
+                    if let Err(TrySendError::Full(_)) = forward_tx.try_send(message) {
+                        let dropped = forward_counters.record_dropped(1);
+                        // 高負荷時にログが溢れないよう間引く
+                        if dropped % INGEST_DROP_LOG_INTERVAL == 1 {
+                            forward_logger.error(&format!(
+                                "[KickChat] Chat ingest queue is full ({} messages dropped so far)",
+                                dropped
+                            ));
+                        }
+                    }
+                }
+            });
+
+            let result = listen_chatroom(&ws_url, chatroom_id, &tx, &is_connected).await;
+            is_connected.store(false, Ordering::SeqCst);
+            drop(tx);
+            let _ = forwarder.await;
+
+            match result {
+                Ok(()) => logger.info(&format!(
+                    "[KickChat] Connection closed for {}, reconnecting",
+                    channel_name
+                )),
+                Err(e) => logger.error(&format!(
+                    "[KickChat] Connection error for {}: {}, reconnecting",
+                    channel_name, e
+                )),
+            }
+
+            sleep(Duration::from_secs(kick::RECONNECT_DELAY_SECS)).await;
+        }
+    }
+
+    fn to_chat_message(
+        channel_id: i64,
+        stream_id: Option<i64>,
+        message: KickChatMessage,
+    ) -> ChatMessage {
+        // Kickの created_at はRFC3339またはUTCの "YYYY-MM-DD HH:MM:SS"
+        let timestamp = message
+            .created_at
+            .as_deref()
+            .and_then(|created_at| {
+                chrono::DateTime::parse_from_rfc3339(created_at)
+                    .map(|dt| dt.with_timezone(&Local))
+                    .ok()
+                    .or_else(|| {
+                        NaiveDateTime::parse_from_str(created_at, "%Y-%m-%d %H:%M:%S")
+                            .ok()
+                            .map(|naive| Utc.from_utc_datetime(&naive).with_timezone(&Local))
+                    })
+            })
+            .unwrap_or_else(Local::now)
+            .to_rfc3339();
+
+        ChatMessage {
+            id: None,
+            channel_id: Some(channel_id),
+            stream_id,
+            timestamp,
+            platform: db_constants::PLATFORM_KICK.to_string(),
+            user_id: message.sender_id.map(|id| id.to_string()),
+            user_name: message
+                .slug
+                .unwrap_or_else(|| message.username.to_lowercase()),
+            display_name: Some(message.username),
+            message: message.content,
+            message_type: "normal".to_string(),
+            badges: if message.badges.is_empty() {
+                None
+            } else {
+                Some(message.badges)
+            },
+            badge_info: None,
+            payload: None,
+            platform_message_id: message.id,
+            emotes: None,
+            bits: None,
+            reply_parent_id: None,
+            reply_parent_user_name: None,
+            reply_thread_id: None,
+            is_auto_discovered: false,
+        }
+    }
+
+    /// 指定したチャンネルのチャット収集を停止
+    pub async fn stop_channel_collection(&self, channel_id: i64) -> Result<(), String> {
+        let mut channels = self.channels.lock().await;
+
+        if let Some(connection) = channels.remove(&channel_id) {
+            connection.task.abort();
+            connection.is_connected.store(false, Ordering::SeqCst);
+            self.logger.info(&format!(
+                "[KickChat] Stopped collection for channel_id: {}",
+                channel_id
+            ));
+            Ok(())
+        } else {
+            Err(format!(
+                "No Kick chat connection found for channel_id: {}",
+                channel_id
+            ))
+        }
+    }
+
+    /// 配信状態変更時にstream_idを更新
+    pub async fn update_channel_stream(&self, channel_id: i64, stream_id: Option<i64>) {
+        let channels = self.channels.lock().await;
+
+        if let Some(connection) = channels.get(&channel_id) {
+            *connection.stream_id.lock().await = stream_id;
+        }
+    }
+
+    /// 全チャンネルのチャット接続状態を取得
+    pub async fn get_connection_statuses(&self) -> Vec<IrcConnectionStatus> {
+        let channels = self.channels.lock().await;
+        let mut statuses = Vec::with_capacity(channels.len());
+
+        for (channel_id, connection) in channels.iter() {
+            statuses.push(IrcConnectionStatus {
+                channel_id: *channel_id,
+                channel_name: connection.channel_name.clone(),
+                stream_id: *connection.stream_id.lock().await,
+                is_connected: connection.is_connected.load(Ordering::SeqCst),
+                message_count: connection.message_count.load(Ordering::SeqCst),
+                last_message_at: connection.last_message_at.lock().await.clone(),
+            });
+        }
+
+        statuses.sort_by_key(|status| status.channel_id);
+        statuses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::accept_async;
+
+    const CHAT_FRAME: &str = r##"{"event":"App\\Events\\ChatMessageEvent","channel":"chatrooms.99.v2","data":"{\"id\":\"abc-123\",\"chatroom_id\":99,\"content\":\"hello kick\",\"type\":\"message\",\"created_at\":\"2025-01-06T12:00:00+00:00\",\"sender\":{\"id\":5,\"username\":\"Viewer\",\"slug\":\"viewer\",\"identity\":{\"color\":\"#FFFFFF\",\"badges\":[{\"type\":\"subscriber\",\"text\":\"Subscriber\",\"count\":3}]}}}"}"##;
+
+    #[test]
+    fn test_parse_chat_message_event() {
+        let Some(PusherEvent::ChatMessage(message)) = parse_pusher_event(CHAT_FRAME) else {
+            panic!("expected chat message");
+        };
+        assert_eq!(message.id.as_deref(), Some("abc-123"));
+        assert_eq!(message.content, "hello kick");
+        assert_eq!(message.username, "Viewer");
+        assert_eq!(message.badges, vec!["subscriber".to_string()]);
+
+        assert_eq!(
+            parse_pusher_event(r#"{"event":"pusher:ping","data":{}}"#),
+            Some(PusherEvent::Ping)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_listen_chatroom_against_mock_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // モックPusherサーバー: 購読要求を受け取ったら購読完了とチャットイベントを1件送信して切断
+        let server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(socket).await.unwrap();
+            let subscribe = ws.next().await.unwrap().unwrap();
+            assert!(subscribe.to_text().unwrap().contains("chatrooms.99.v2"));
+            ws.send(Message::Text(
+                r#"{"event":"pusher_internal:subscription_succeeded","channel":"chatrooms.99.v2","data":"{}"}"#.into(),
+            ))
+            .await
+            .unwrap();
+            ws.send(Message::Text(CHAT_FRAME.into())).await.unwrap();
+            ws.close(None).await.unwrap();
+        });
+
+        let (tx, mut rx) = mpsc::channel(8);
+        let is_connected = AtomicBool::new(false);
+        listen_chatroom(&format!("ws://{}", addr), 99, &tx, &is_connected)
+            .await
+            .unwrap();
+        server.await.unwrap();
+        assert!(is_connected.load(Ordering::SeqCst));
+
+        let message = rx.recv().await.unwrap();
+        assert_eq!(message.content, "hello kick");
+        assert_eq!(message.slug.as_deref(), Some("viewer"));
+    }
+}
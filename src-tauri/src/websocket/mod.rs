@@ -1 +1,2 @@
+pub mod kick_chat;
 pub mod twitch_irc;
@@ -1,13 +1,28 @@
-use crate::database::models::ChatMessage;
+use crate::collectors::replay;
+use crate::constants::database as db_constants;
+use crate::constants::twitch;
+use crate::database::chat_spool::ChatSpool;
+use crate::database::models::{ChatMessage, MessageEmote, ModerationEvent};
+use crate::database::repositories::base::with_transaction;
+use crate::database::repositories::{
+    ModerationEventRepository, RoomModeChange, RoomStateRepository,
+};
 use crate::database::DatabaseManager;
 use crate::logger::AppLogger;
-use chrono::Local;
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::Instant;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::sync::{Mutex, RwLock};
+use tokio::time::{interval, Duration, MissedTickBehavior};
 use twitch_irc::login::StaticLoginCredentials;
-use twitch_irc::message::ServerMessage;
+use twitch_irc::message::{
+    Badge, ClearChatAction, ClearChatMessage, Emote, FollowersOnlyMode, IRCMessage,
+    RoomStateMessage, ServerMessage, UserNoticeEvent, UserNoticeMessage,
+};
 use twitch_irc::ClientConfig;
 use twitch_irc::SecureTCPTransport;
 use twitch_irc::TwitchIRCClient;
@@ -17,128 +32,637 @@ struct ChannelConnection {
     channel_id: i64,
     channel_name: String,
     stream_id: Arc<Mutex<Option<i64>>>,
+    auto_discovered: bool, // 自動発見によるチャット収集か
     is_connected: Arc<AtomicBool>,
     message_count: Arc<AtomicU64>,
     last_message_at: Arc<Mutex<Option<String>>>,
 }
 
+/// IRC接続の状態（ヘルス表示用）
+#[derive(Debug, Clone, Serialize)]
+pub struct IrcConnectionStatus {
+    pub channel_id: i64,
+    pub channel_name: String,
+    pub stream_id: Option<i64>,
+    pub is_connected: bool,
+    pub message_count: u64,
+    pub last_message_at: Option<String>,
+}
+
+/// USERNOTICE（サブスク・ギフト・レイド・アナウンス等）の構造化情報
+///
+/// chat_messages.payload にJSONとして保存します。該当しない項目は出力しません。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserNoticePayload {
+    /// Twitchの msg-id（sub, resub, subgift, raid, announcement など）
+    pub msg_id: String,
+    /// サブスクのティア（"1000", "2000", "3000", "Prime"）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tier: Option<String>,
+    /// 累計サブスク月数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cumulative_months: Option<u64>,
+    /// 連続サブスク月数（本人が共有した場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub streak_months: Option<u64>,
+    /// ギフトの受け取り人数（個別ギフトは1）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gift_recipient_count: Option<u64>,
+    /// 個別ギフトの受け取りユーザー（login）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gift_recipient: Option<String>,
+    /// 匿名ギフトかどうか
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_anonymous: Option<bool>,
+    /// レイドで来た視聴者数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raider_viewer_count: Option<u64>,
+    /// アナウンスの色（PRIMARY, BLUE など）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub announcement_color: Option<String>,
+    /// Twitchが生成したシステムメッセージ
+    pub system_message: String,
+}
+
+/// USERNOTICEを message_type と構造化情報に変換
+pub fn parse_user_notice(msg: &UserNoticeMessage) -> (&'static str, UserNoticePayload) {
+    let mut payload = UserNoticePayload {
+        msg_id: msg.event_id.clone(),
+        system_message: msg.system_message.clone(),
+        ..Default::default()
+    };
+
+    let message_type = match &msg.event {
+        UserNoticeEvent::SubOrResub {
+            is_resub,
+            cumulative_months,
+            streak_months,
+            sub_plan,
+            ..
+        } => {
+            payload.tier = Some(sub_plan.clone());
+            payload.cumulative_months = Some(*cumulative_months);
+            payload.streak_months = *streak_months;
+            if *is_resub {
+                twitch::MESSAGE_TYPE_RESUB
+            } else {
+                twitch::MESSAGE_TYPE_SUB
+            }
+        }
+        UserNoticeEvent::SubGift {
+            is_sender_anonymous,
+            cumulative_months,
+            recipient,
+            sub_plan,
+            ..
+        } => {
+            payload.tier = Some(sub_plan.clone());
+            payload.cumulative_months = Some(*cumulative_months);
+            payload.gift_recipient_count = Some(1);
+            payload.gift_recipient = Some(recipient.login.clone());
+            payload.is_anonymous = Some(*is_sender_anonymous);
+            twitch::MESSAGE_TYPE_SUBGIFT
+        }
+        UserNoticeEvent::SubMysteryGift {
+            mass_gift_count,
+            sub_plan,
+            ..
+        } => {
+            payload.tier = Some(sub_plan.clone());
+            payload.gift_recipient_count = Some(*mass_gift_count);
+            payload.is_anonymous = Some(false);
+            twitch::MESSAGE_TYPE_SUBMYSTERYGIFT
+        }
+        UserNoticeEvent::AnonSubMysteryGift {
+            mass_gift_count,
+            sub_plan,
+        } => {
+            payload.tier = Some(sub_plan.clone());
+            payload.gift_recipient_count = Some(*mass_gift_count);
+            payload.is_anonymous = Some(true);
+            twitch::MESSAGE_TYPE_SUBMYSTERYGIFT
+        }
+        UserNoticeEvent::Raid { viewer_count, .. } => {
+            payload.raider_viewer_count = Some(*viewer_count);
+            twitch::MESSAGE_TYPE_RAID
+        }
+        // アナウンスは twitch-irc で型付けされていないため msg-id とタグから判定
+        _ if msg.event_id == "announcement" => {
+            payload.announcement_color =
+                msg.source.tags.0.get("msg-param-color").cloned().flatten();
+            twitch::MESSAGE_TYPE_ANNOUNCEMENT
+        }
+        _ => twitch::MESSAGE_TYPE_USERNOTICE,
+    };
+
+    (message_type, payload)
+}
+
+/// IRCの tmi-sent-ts（サーバー送信時刻）をローカル時刻のRFC3339文字列に変換
+///
+/// 受信時刻ではなく送信時刻を使うことで、再接続や保存遅延があっても時系列がずれません。
+fn server_time(server_timestamp: &DateTime<Utc>) -> String {
+    server_timestamp.with_timezone(&Local).to_rfc3339()
+}
+
+/// CLEARCHAT をモデレーションイベントに変換
+fn clear_chat_to_event(
+    msg: &ClearChatMessage,
+    channel_id: i64,
+    stream_id: Option<i64>,
+) -> ModerationEvent {
+    let (event_type, target_user_id, target_user_name, duration_seconds) = match &msg.action {
+        ClearChatAction::UserTimedOut {
+            user_login,
+            user_id,
+            timeout_length,
+        } => (
+            db_constants::MODERATION_TIMEOUT,
+            Some(user_id.clone()),
+            Some(user_login.clone()),
+            Some(timeout_length.as_secs() as i64),
+        ),
+        ClearChatAction::UserBanned {
+            user_login,
+            user_id,
+        } => (
+            db_constants::MODERATION_BAN,
+            Some(user_id.clone()),
+            Some(user_login.clone()),
+            None,
+        ),
+        ClearChatAction::ChatCleared => (db_constants::MODERATION_CLEAR, None, None, None),
+    };
+
+    ModerationEvent {
+        id: None,
+        channel_id,
+        stream_id,
+        platform: db_constants::PLATFORM_TWITCH.to_string(),
+        event_type: event_type.to_string(),
+        target_user_id,
+        target_user_name,
+        duration_seconds,
+        message_id: None,
+        message_text: None,
+        occurred_at: server_time(&msg.server_timestamp),
+    }
+}
+
+/// ROOMSTATE をモードごとの変更に変換
+///
+/// 接続直後は全モードが、以降は変更されたモードのみが通知されます。
+fn room_state_changes(msg: &RoomStateMessage) -> Vec<RoomModeChange> {
+    let mut changes = Vec::new();
+
+    if let Some(slow_mode) = msg.slow_mode {
+        let seconds = slow_mode.as_secs() as i64;
+        changes.push(RoomModeChange {
+            mode: db_constants::ROOM_MODE_SLOW,
+            enabled: seconds > 0,
+            setting: (seconds > 0).then_some(seconds),
+        });
+    }
+    if let Some(emote_only) = msg.emote_only {
+        changes.push(RoomModeChange {
+            mode: db_constants::ROOM_MODE_EMOTE_ONLY,
+            enabled: emote_only,
+            setting: None,
+        });
+    }
+    if let Some(followers_only) = &msg.follwers_only {
+        let setting = match followers_only {
+            FollowersOnlyMode::Enabled(duration) => Some(duration.as_secs() as i64 / 60),
+            FollowersOnlyMode::Disabled => None,
+        };
+        changes.push(RoomModeChange {
+            mode: db_constants::ROOM_MODE_FOLLOWERS_ONLY,
+            enabled: setting.is_some(),
+            setting,
+        });
+    }
+    if let Some(subscribers_only) = msg.subscribers_only {
+        changes.push(RoomModeChange {
+            mode: db_constants::ROOM_MODE_SUBS_ONLY,
+            enabled: subscribers_only,
+            setting: None,
+        });
+    }
+    if let Some(r9k) = msg.r9k {
+        changes.push(RoomModeChange {
+            mode: db_constants::ROOM_MODE_UNIQUE_CHAT,
+            enabled: r9k,
+            setting: None,
+        });
+    }
+
+    changes
+}
+
+/// バッジ情報を配列として取得（バッジ名のみ）
+fn badge_names(badges: &[Badge]) -> Option<Vec<String>> {
+    if badges.is_empty() {
+        None
+    } else {
+        Some(badges.iter().map(|badge| badge.name.clone()).collect())
+    }
+}
+
+/// twitch-irc が型付きで公開していないタグ（返信情報など）を取得
+fn irc_tag(source: &IRCMessage, key: &str) -> Option<String> {
+    source
+        .tags
+        .0
+        .get(key)
+        .cloned()
+        .flatten()
+        .filter(|value| !value.is_empty())
+}
+
+/// emotes タグ（エモートID・名前・文字位置）を取得
+fn message_emotes(emotes: &[Emote]) -> Option<Vec<MessageEmote>> {
+    if emotes.is_empty() {
+        None
+    } else {
+        Some(
+            emotes
+                .iter()
+                .map(|emote| MessageEmote {
+                    id: emote.id.clone(),
+                    name: emote.code.clone(),
+                    start: emote.char_range.start,
+                    end: emote.char_range.end,
+                })
+                .collect(),
+        )
+    }
+}
+
+/// badge_info（サブスク月数等の詳細情報）を "name:version" のカンマ区切りで取得
+fn badge_info_string(badge_info: &[Badge]) -> Option<String> {
+    if badge_info.is_empty() {
+        None
+    } else {
+        Some(
+            badge_info
+                .iter()
+                .map(|bi| format!("{}:{}", bi.name, bi.version))
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+    }
+}
+
+/// スプールから再投入する際の1回のINSERTあたりのメッセージ数
+const SPOOL_REPLAY_CHUNK_SIZE: usize = 500;
+
+/// 取り込みキューの破棄をログに出す間隔（件数）
+pub(crate) const INGEST_DROP_LOG_INTERVAL: u64 = 1000;
+
+/// 保存待ちのモデレーションイベントの上限（DB書き込みが滞った場合にメモリを使い切らないため）
+const MODERATION_BATCH_CAPACITY: usize = 10_000;
+
+/// 受信ループから取り込みタスクへ渡すイベント
+enum IngestEvent {
+    /// チャット（`spool_seq` はスプールに追記したレコードのシーケンス番号）
+    Chat {
+        message: Box<ChatMessage>,
+        spool_seq: Option<u64>,
+    },
+    Moderation(ModerationEvent),
+    RoomState {
+        channel_id: i64,
+        changes: Vec<RoomModeChange>,
+        changed_at: String,
+    },
+}
+
+/// 保存待ちのチャット（`spool_seqs` は `messages` と同じ順のスプールのシーケンス番号）
+#[derive(Default)]
+struct ChatBatch {
+    messages: Vec<ChatMessage>,
+    spool_seqs: Vec<Option<u64>>,
+}
+
+impl ChatBatch {
+    /// スプールに追記済みのチャットを破棄（スプールに書けなかったものは残す）
+    fn clear_spooled(&mut self) {
+        let mut seqs = self.spool_seqs.iter();
+        self.messages
+            .retain(|_| seqs.next().is_some_and(|seq| seq.is_none()));
+        self.spool_seqs.retain(Option::is_none);
+    }
+}
+
+/// 取り込みタスクの累計値（YouTube・Kickのチャット取り込みでも使用）
+#[derive(Default)]
+pub(crate) struct IngestCounters {
+    dropped: AtomicU64,
+    moderation_dropped: AtomicU64,
+    inserted: AtomicU64,
+    insert_batches: AtomicU64,
+    total_insert_micros: AtomicU64,
+    last_insert_micros: AtomicU64,
+}
+
+impl IngestCounters {
+    /// 破棄したイベント数を加算し、累計を返す
+    pub(crate) fn record_dropped(&self, count: u64) -> u64 {
+        self.dropped.fetch_add(count, Ordering::Relaxed) + count
+    }
+
+    /// 保存にかかった時間と件数を記録
+    pub(crate) fn record_insert(&self, count: usize, started: Instant) {
+        let elapsed_micros = started.elapsed().as_micros() as u64;
+        self.inserted.fetch_add(count as u64, Ordering::Relaxed);
+        self.insert_batches.fetch_add(1, Ordering::Relaxed);
+        self.total_insert_micros
+            .fetch_add(elapsed_micros, Ordering::Relaxed);
+        self.last_insert_micros
+            .store(elapsed_micros, Ordering::Relaxed);
+    }
+
+    /// ヘルス表示用の状態に変換（キューを持たない取り込みは 0 を渡す）
+    pub(crate) fn metrics(&self, queue_depth: u64, queue_capacity: u64) -> ChatIngestMetrics {
+        let batches = self.insert_batches.load(Ordering::Relaxed);
+        let micros_to_ms = |micros: u64| micros as f64 / 1000.0;
+
+        ChatIngestMetrics {
+            queue_depth,
+            queue_capacity,
+            dropped_count: self.dropped.load(Ordering::Relaxed),
+            moderation_dropped_count: self.moderation_dropped.load(Ordering::Relaxed),
+            inserted_count: self.inserted.load(Ordering::Relaxed),
+            last_insert_latency_ms: (batches > 0)
+                .then(|| micros_to_ms(self.last_insert_micros.load(Ordering::Relaxed))),
+            avg_insert_latency_ms: (batches > 0).then(|| {
+                micros_to_ms(self.total_insert_micros.load(Ordering::Relaxed)) / batches as f64
+            }),
+        }
+    }
+}
+
+/// チャット取り込みの状態（ヘルス表示用）
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ChatIngestMetrics {
+    /// 取り込みキューで保存待ちのイベント数
+    pub queue_depth: u64,
+    pub queue_capacity: u64,
+    /// キューが満杯、または保存待ちが上限に達して破棄したイベント数
+    /// （Twitchのチャットはスプールから再投入される）
+    pub dropped_count: u64,
+    /// 保存待ちが上限に達して破棄したモデレーションイベント数
+    pub moderation_dropped_count: u64,
+    /// 保存したチャット数（スプールからの再投入を含む）
+    pub inserted_count: u64,
+    /// 直近のバッチ保存にかかった時間
+    pub last_insert_latency_ms: Option<f64>,
+    /// バッチ保存にかかった時間の平均
+    pub avg_insert_latency_ms: Option<f64>,
+}
+
 /// 複数のTwitch IRC接続を管理するマネージャー
 pub struct TwitchIrcManager {
-    channels: Arc<Mutex<HashMap<i64, ChannelConnection>>>,
+    channels: Arc<Mutex<HashMap<i64, Arc<ChannelConnection>>>>,
+    /// 小文字のチャンネル名 → 接続（受信メッセージの振り分け用）
+    logins: Arc<RwLock<HashMap<String, Arc<ChannelConnection>>>>,
     client: Arc<TwitchIRCClient<SecureTCPTransport, StaticLoginCredentials>>,
     logger: Arc<AppLogger>,
+    /// 未コミットのチャットを保持する先行書き込みスプール（開けなかった場合は None）
+    spool: Option<Arc<ChatSpool>>,
+    ingest_tx: mpsc::Sender<IngestEvent>,
+    ingest_counters: Arc<IngestCounters>,
 }
 
 impl TwitchIrcManager {
-    pub fn new(db_manager: Arc<DatabaseManager>, logger: Arc<AppLogger>) -> Self {
+    /// `spool` はプロセスで共有する先行書き込みスプール（開けなかった場合は None）
+    pub fn new(
+        db_manager: Arc<DatabaseManager>,
+        spool: Option<Arc<ChatSpool>>,
+        logger: Arc<AppLogger>,
+    ) -> Self {
         // 匿名ログインの設定
         let config = ClientConfig::default();
         let (mut incoming_messages, client) =
             TwitchIRCClient::<SecureTCPTransport, StaticLoginCredentials>::new(config);
 
         let client = Arc::new(client);
-        let channels: Arc<Mutex<HashMap<i64, ChannelConnection>>> =
-            Arc::new(Mutex::new(HashMap::new()));
-        let db_manager_clone = Arc::clone(&db_manager);
+        let logins: Arc<RwLock<HashMap<String, Arc<ChannelConnection>>>> =
+            Arc::new(RwLock::new(HashMap::new()));
+
+        // DBへの書き込みは取り込みタスクに任せ、受信ループは解析とキュー投入のみ行う
+        let (ingest_tx, ingest_rx) = mpsc::channel(db_constants::CHAT_INGEST_QUEUE_CAPACITY);
+        let ingest_counters = Arc::new(IngestCounters::default());
+        tokio::spawn(Self::run_ingest(
+            ingest_rx,
+            Arc::clone(&db_manager),
+            spool.clone(),
+            Arc::clone(&ingest_counters),
+            Arc::clone(&logger),
+        ));
+
         let logger_clone = Arc::clone(&logger);
-        let channels_clone = Arc::clone(&channels);
+        let logins_clone = Arc::clone(&logins);
+        let spool_clone = spool.clone();
+        let ingest_tx_clone = ingest_tx.clone();
+        let counters_clone = Arc::clone(&ingest_counters);
 
         // メッセージ受信タスクを開始（バックグラウンドで継続実行）
         let _incoming_task = tokio::spawn(async move {
-            let mut batch = Vec::new();
-            let mut last_flush = std::time::Instant::now();
+            let spool = spool_clone.as_deref();
 
             while let Some(message) = incoming_messages.recv().await {
-                match message {
+                let event = match message {
                     ServerMessage::Privmsg(msg) => {
-                        // チャンネル名から channel_id と stream_id を取得
-                        let channel_name = msg.channel_login.as_str();
-                        let channels_lock = channels_clone.lock().await;
-
-                        if let Some(conn) = channels_lock
-                            .values()
-                            .find(|c| c.channel_name.to_lowercase() == channel_name)
-                        {
-                            let channel_id = conn.channel_id;
-                            let stream_id = *conn.stream_id.lock().await;
-
-                            // 統計を更新
-                            conn.message_count.fetch_add(1, Ordering::SeqCst);
-                            *conn.last_message_at.lock().await = Some(Local::now().to_rfc3339());
-
-                            // バッジ情報を配列として取得（バッジ名のみ）
-                            let badges = if msg.badges.is_empty() {
-                                None
-                            } else {
-                                Some(
-                                    msg.badges
-                                        .iter()
-                                        .map(|badge| badge.name.clone())
-                                        .collect::<Vec<String>>(),
-                                )
-                            };
-
-                            // badge_info（サブスク月数等の詳細情報）を取得
-                            let badge_info = if msg.badge_info.is_empty() {
-                                None
-                            } else {
-                                Some(
-                                    msg.badge_info
-                                        .iter()
-                                        .map(|bi| format!("{}:{}", bi.name, bi.version))
-                                        .collect::<Vec<_>>()
-                                        .join(","),
-                                )
-                            };
-
-                            let chat_message = ChatMessage {
-                                id: None,
-                                channel_id: Some(channel_id),
-                                stream_id,
-                                timestamp: Local::now().to_rfc3339(),
-                                platform: crate::constants::database::PLATFORM_TWITCH.to_string(),
-                                user_id: Some(msg.sender.id.clone()),
-                                user_name: msg.sender.login.clone(),
-                                display_name: Some(msg.sender.name.clone()), // Twitch表示名を保存
-                                message: msg.message_text.clone(),
-                                message_type: "normal".to_string(),
-                                badges,
-                                badge_info,
-                            };
-
-                            batch.push(chat_message);
+                        match Self::resolve_channel(&logins_clone, &msg.channel_login, true).await {
+                            Some((channel_id, stream_id, auto_discovered)) => {
+                                let message = ChatMessage {
+                                    id: None,
+                                    channel_id: Some(channel_id),
+                                    stream_id,
+                                    timestamp: server_time(&msg.server_timestamp),
+                                    platform: db_constants::PLATFORM_TWITCH.to_string(),
+                                    user_id: Some(msg.sender.id.clone()),
+                                    user_name: msg.sender.login.clone(),
+                                    display_name: Some(msg.sender.name.clone()), // Twitch表示名を保存
+                                    message: msg.message_text.clone(),
+                                    message_type: if msg.bits.is_some() {
+                                        twitch::MESSAGE_TYPE_CHEER
+                                    } else {
+                                        twitch::MESSAGE_TYPE_NORMAL
+                                    }
+                                    .to_string(),
+                                    badges: badge_names(&msg.badges),
+                                    badge_info: badge_info_string(&msg.badge_info),
+                                    payload: None,
+                                    platform_message_id: Some(msg.message_id.clone()),
+                                    emotes: message_emotes(&msg.emotes),
+                                    bits: msg.bits.map(|bits| bits as i64),
+                                    reply_parent_id: irc_tag(&msg.source, "reply-parent-msg-id"),
+                                    reply_parent_user_name: irc_tag(
+                                        &msg.source,
+                                        "reply-parent-user-login",
+                                    ),
+                                    reply_thread_id: irc_tag(
+                                        &msg.source,
+                                        "reply-thread-parent-msg-id",
+                                    ),
+                                    is_auto_discovered: auto_discovered,
+                                };
+                                let spool_seq = Self::spool_message(spool, &message, &logger_clone);
+                                Some(IngestEvent::Chat {
+                                    message: Box::new(message),
+                                    spool_seq,
+                                })
+                            }
+                            None => None,
                         }
                     }
-                    ServerMessage::Join(_) => {}
-                    ServerMessage::Part(_) => {}
+                    ServerMessage::UserNotice(msg) => {
+                        match Self::resolve_channel(&logins_clone, &msg.channel_login, true).await {
+                            Some((channel_id, stream_id, auto_discovered)) => {
+                                let (message_type, payload) = parse_user_notice(&msg);
+
+                                // レイドやギフトには本文がないため、システムメッセージを本文として保存
+                                let message = msg
+                                    .message_text
+                                    .clone()
+                                    .unwrap_or_else(|| msg.system_message.clone());
+
+                                let message = ChatMessage {
+                                    id: None,
+                                    channel_id: Some(channel_id),
+                                    stream_id,
+                                    timestamp: server_time(&msg.server_timestamp),
+                                    platform: db_constants::PLATFORM_TWITCH.to_string(),
+                                    user_id: Some(msg.sender.id.clone()),
+                                    user_name: msg.sender.login.clone(),
+                                    display_name: Some(msg.sender.name.clone()),
+                                    message,
+                                    message_type: message_type.to_string(),
+                                    badges: badge_names(&msg.badges),
+                                    badge_info: badge_info_string(&msg.badge_info),
+                                    payload: serde_json::to_string(&payload).ok(),
+                                    platform_message_id: Some(msg.message_id.clone()),
+                                    emotes: message_emotes(&msg.emotes),
+                                    bits: None,
+                                    reply_parent_id: None,
+                                    reply_parent_user_name: None,
+                                    reply_thread_id: None,
+                                    is_auto_discovered: auto_discovered,
+                                };
+                                let spool_seq = Self::spool_message(spool, &message, &logger_clone);
+                                Some(IngestEvent::Chat {
+                                    message: Box::new(message),
+                                    spool_seq,
+                                })
+                            }
+                            None => None,
+                        }
+                    }
+                    ServerMessage::ClearChat(msg) => {
+                        Self::resolve_channel(&logins_clone, &msg.channel_login, false)
+                            .await
+                            .map(|(channel_id, stream_id, _)| {
+                                IngestEvent::Moderation(clear_chat_to_event(
+                                    &msg, channel_id, stream_id,
+                                ))
+                            })
+                    }
+                    ServerMessage::ClearMsg(msg) => {
+                        Self::resolve_channel(&logins_clone, &msg.channel_login, false)
+                            .await
+                            .map(|(channel_id, stream_id, _)| {
+                                IngestEvent::Moderation(ModerationEvent {
+                                    id: None,
+                                    channel_id,
+                                    stream_id,
+                                    platform: db_constants::PLATFORM_TWITCH.to_string(),
+                                    event_type: db_constants::MODERATION_DELETE.to_string(),
+                                    target_user_id: None,
+                                    target_user_name: Some(msg.sender_login.clone()),
+                                    duration_seconds: None,
+                                    message_id: Some(msg.message_id.clone()),
+                                    message_text: Some(msg.message_text.clone()),
+                                    occurred_at: server_time(&msg.server_timestamp),
+                                })
+                            })
+                    }
+                    ServerMessage::RoomState(msg) => {
+                        Self::resolve_channel(&logins_clone, &msg.channel_login, false)
+                            .await
+                            .map(|(channel_id, _, _)| IngestEvent::RoomState {
+                                channel_id,
+                                changes: room_state_changes(&msg),
+                                // ROOMSTATE にはサーバー時刻がないため受信時刻を使う
+                                changed_at: Local::now().to_rfc3339(),
+                            })
+                    }
                     ServerMessage::Reconnect(_) => {
                         logger_clone.info("[IRC] Server requested reconnect");
+                        None
                     }
-                    _ => {}
-                }
+                    _ => None,
+                };
 
-                // バッチフラッシュ（100件または5秒ごと）
-                if (batch.len() >= 100 || last_flush.elapsed().as_secs() >= 5) && !batch.is_empty()
-                {
-                    Self::flush_batch(&db_manager_clone, &mut batch, &logger_clone).await;
-                    last_flush = std::time::Instant::now();
+                if let Some(event) = event {
+                    Self::enqueue(
+                        &ingest_tx_clone,
+                        event,
+                        spool,
+                        &counters_clone,
+                        &logger_clone,
+                    );
                 }
             }
-
-            // 残りのメッセージをフラッシュ
-            if !batch.is_empty() {
-                Self::flush_batch(&db_manager_clone, &mut batch, &logger_clone).await;
-            }
         });
 
         // incoming_taskを保持しないため、バックグラウンドで継続実行される
-        // db_managerは直接保持せず、flush_batch内で使用する
+        // db_managerは直接保持せず、取り込みタスク内で使用する
 
         Self {
-            channels,
+            channels: Arc::new(Mutex::new(HashMap::new())),
+            logins,
             client,
             logger,
+            spool,
+            ingest_tx,
+            ingest_counters,
+        }
+    }
+
+    /// スプールに残っているチャット数
+    pub fn spool_depth(&self) -> u64 {
+        self.spool.as_ref().map(|spool| spool.depth()).unwrap_or(0)
+    }
+
+    /// チャット取り込みの状態を取得
+    pub fn ingest_metrics(&self) -> ChatIngestMetrics {
+        self.ingest_counters.metrics(
+            (self.ingest_tx.max_capacity() - self.ingest_tx.capacity()) as u64,
+            self.ingest_tx.max_capacity() as u64,
+        )
+    }
+
+    /// チャンネル名から channel_id と stream_id を取得
+    ///
+    /// `count_message` が true の場合は受信統計を更新します。
+    async fn resolve_channel(
+        logins: &RwLock<HashMap<String, Arc<ChannelConnection>>>,
+        channel_login: &str,
+        count_message: bool,
+    ) -> Option<(i64, Option<i64>, bool)> {
+        let conn = logins.read().await.get(channel_login).cloned()?;
+
+        let stream_id = *conn.stream_id.lock().await;
+
+        if count_message {
+            // 統計を更新
+            conn.message_count.fetch_add(1, Ordering::SeqCst);
+            *conn.last_message_at.lock().await = Some(Local::now().to_rfc3339());
         }
+
+        Some((conn.channel_id, stream_id, conn.auto_discovered))
     }
 
     /// データベース書き込みハンドラーを起動（twitch-ircでは不要だが互換性のために残す）
@@ -147,33 +671,335 @@ impl TwitchIrcManager {
             .info("[IRC] Database handler is integrated into message receiver");
     }
 
+    /// 取り込みキューへイベントを投入
+    ///
+    /// 受信ループを止めないよう、キューが満杯の場合は待たずに破棄して件数を記録します。
+    /// 破棄したチャットはスプールに残っているため、次回の書き込みでスプールから再投入します。
+    fn enqueue(
+        tx: &mpsc::Sender<IngestEvent>,
+        event: IngestEvent,
+        spool: Option<&ChatSpool>,
+        counters: &IngestCounters,
+        logger: &AppLogger,
+    ) {
+        match tx.try_send(event) {
+            Ok(()) => {}
+            Err(TrySendError::Full(event)) => {
+                let dropped = counters.record_dropped(1);
+                if let (IngestEvent::Chat { .. }, Some(spool)) = (&event, spool) {
+                    spool.mark_needs_replay();
+                }
+                // 高負荷時にログが溢れないよう間引く
+                if dropped % INGEST_DROP_LOG_INTERVAL == 1 {
+                    logger.error(&format!(
+                        "[IRC] Chat ingest queue is full ({} events dropped so far)",
+                        dropped
+                    ));
+                }
+            }
+            Err(TrySendError::Closed(_)) => {
+                logger.error("[IRC] Chat ingest task has stopped");
+            }
+        }
+    }
+
+    /// 取り込みタスク本体
+    ///
+    /// チャットを CHAT_BATCH_SIZE 件または BATCH_FLUSH_INTERVAL_SECS 秒ごとにまとめて保存します。
+    async fn run_ingest(
+        mut rx: mpsc::Receiver<IngestEvent>,
+        db_manager: Arc<DatabaseManager>,
+        spool: Option<Arc<ChatSpool>>,
+        counters: Arc<IngestCounters>,
+        logger: Arc<AppLogger>,
+    ) {
+        let spool = spool.as_deref();
+        let mut batch = ChatBatch::default();
+        let mut moderation_batch: Vec<ModerationEvent> = Vec::new();
+        let mut ticker = interval(Duration::from_secs(db_constants::BATCH_FLUSH_INTERVAL_SECS));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        // 前回終了時に未コミットだったチャットを再投入
+        Self::flush_batch(&db_manager, spool, &mut batch, &counters, &logger).await;
+
+        loop {
+            tokio::select! {
+                event = rx.recv() => match event {
+                    Some(IngestEvent::Chat { message, spool_seq }) => {
+                        batch.messages.push(*message);
+                        batch.spool_seqs.push(spool_seq);
+                        if batch.messages.len() < db_constants::CHAT_BATCH_SIZE {
+                            continue;
+                        }
+                    }
+                    Some(IngestEvent::Moderation(event)) => {
+                        if moderation_batch.len() < MODERATION_BATCH_CAPACITY {
+                            moderation_batch.push(event);
+                        } else {
+                            let dropped =
+                                counters.moderation_dropped.fetch_add(1, Ordering::Relaxed) + 1;
+                            if dropped % INGEST_DROP_LOG_INTERVAL == 1 {
+                                logger.error(&format!(
+                                    "[IRC] Moderation event backlog is full ({} events dropped so far)",
+                                    dropped
+                                ));
+                            }
+                        }
+                        continue;
+                    }
+                    Some(IngestEvent::RoomState {
+                        channel_id,
+                        changes,
+                        changed_at,
+                    }) => {
+                        Self::save_room_state(&db_manager, channel_id, &changes, &changed_at, &logger)
+                            .await;
+                        continue;
+                    }
+                    None => break,
+                },
+                _ = ticker.tick() => {}
+            }
+
+            Self::flush_batch(&db_manager, spool, &mut batch, &counters, &logger).await;
+            // 削除対象のメッセージが保存されてからモデレーションを反映する
+            if batch.messages.is_empty() && !spool.is_some_and(ChatSpool::needs_replay) {
+                Self::flush_moderation_events(&db_manager, &mut moderation_batch, &logger).await;
+            }
+        }
+
+        // 残りのメッセージをフラッシュ
+        Self::flush_batch(&db_manager, spool, &mut batch, &counters, &logger).await;
+        if batch.messages.is_empty() && !spool.is_some_and(ChatSpool::needs_replay) {
+            Self::flush_moderation_events(&db_manager, &mut moderation_batch, &logger).await;
+        }
+    }
+
+    /// 受信したメッセージをスプールへ先行書き込みし、レコードのシーケンス番号を返す
+    fn spool_message(
+        spool: Option<&ChatSpool>,
+        message: &ChatMessage,
+        logger: &AppLogger,
+    ) -> Option<u64> {
+        match spool?.append(message) {
+            Ok(seq) => Some(seq),
+            Err(e) => {
+                logger.error(&format!("[IRC] Failed to append chat to spool: {}", e));
+                None
+            }
+        }
+    }
+
     /// バッチメッセージをデータベースに書き込み
+    ///
+    /// 以前の書き込みに失敗している場合は、メモリ上のバッチではなくスプールの未コミット分を再投入します。
+    /// 保存できたレコードだけをコミットし、すべてコミット済みになったセグメントを削除します。
     async fn flush_batch(
         db_manager: &Arc<DatabaseManager>,
-        batch: &mut Vec<ChatMessage>,
+        spool: Option<&ChatSpool>,
+        batch: &mut ChatBatch,
+        counters: &IngestCounters,
+        logger: &Arc<AppLogger>,
+    ) {
+        if let Some(spool) = spool {
+            if spool.take_needs_replay() {
+                if Self::replay_spool(db_manager, spool, counters, logger).await {
+                    // バッチのチャットはスプールに追記済みのため、再投入で保存されている
+                    batch.clear_spooled();
+                } else {
+                    spool.mark_needs_replay();
+                    batch.clear_spooled();
+                    return;
+                }
+            } else if !batch.messages.is_empty() {
+                // DBへ書き込む前にスプールをディスクへ同期
+                if let Err(e) = spool.sync() {
+                    logger.error(&format!("[IRC] Failed to sync chat spool: {}", e));
+                }
+            }
+        }
+
+        if !batch.messages.is_empty() {
+            let started = Instant::now();
+            let result = db_manager
+                .with_connection(|conn| {
+                    crate::database::writer::DatabaseWriter::insert_chat_messages_batch(
+                        conn,
+                        &batch.messages,
+                    )
+                })
+                .await;
+
+            match result {
+                Ok(inserted) => {
+                    // 記録モードでは保存したチャットをキャプチャファイルにも書き出す
+                    if let Some(recorder) = replay::active_recorder() {
+                        recorder.record_chat_batch(&inserted);
+                    }
+                    counters.record_insert(batch.messages.len(), started);
+                    logger.info(&format!(
+                        "[IRC] Saved {} chat messages to database",
+                        batch.messages.len()
+                    ));
+                    if let Some(spool) = spool {
+                        let seqs: Vec<u64> = batch.spool_seqs.iter().flatten().copied().collect();
+                        spool.commit(&seqs);
+                    }
+                    batch.messages.clear();
+                    batch.spool_seqs.clear();
+                }
+                Err(e) => {
+                    logger.error(&format!("[IRC] Failed to save chat messages: {}", e));
+                    // スプールに残っているものはメモリ上のバッチから破棄し、復旧後にスプールから再投入する
+                    // （スプールに書けなかったものはバッチに残して次回再試行）
+                    if let Some(spool) = spool {
+                        spool.mark_needs_replay();
+                        batch.clear_spooled();
+                    }
+                    return;
+                }
+            }
+        }
+
+        if let Some(spool) = spool {
+            if let Err(e) = spool.truncate_committed() {
+                logger.error(&format!("[IRC] Failed to truncate chat spool: {}", e));
+            }
+        }
+    }
+
+    /// スプールの未コミット分を SPOOL_REPLAY_CHUNK_SIZE 件ずつ再投入（すべて保存できたら true）
+    ///
+    /// 再投入中に追記されたチャットはバッチから保存されるため、開始時点までのレコードを対象にします。
+    async fn replay_spool(
+        db_manager: &Arc<DatabaseManager>,
+        spool: &ChatSpool,
+        counters: &IngestCounters,
+        logger: &Arc<AppLogger>,
+    ) -> bool {
+        let end_seq = spool.next_seq();
+        let mut after_seq = 0;
+        let mut replayed = 0;
+
+        while after_seq + 1 < end_seq {
+            let chunk = match spool.read_pending(after_seq, SPOOL_REPLAY_CHUNK_SIZE) {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => break,
+                Err(e) => {
+                    // 読めないレコードはコミットせずスプールに残し、次回に持ち越す
+                    logger.error(&format!("[IRC] Failed to read chat spool: {}", e));
+                    return false;
+                }
+            };
+            after_seq = chunk.last_seq;
+            if chunk.messages.is_empty() {
+                continue;
+            }
+
+            let started = Instant::now();
+            let result = db_manager
+                .with_connection(|conn| {
+                    crate::database::writer::DatabaseWriter::insert_chat_messages_batch(
+                        conn,
+                        &chunk.messages,
+                    )
+                })
+                .await;
+            match result {
+                Ok(inserted) => {
+                    if let Some(recorder) = replay::active_recorder() {
+                        recorder.record_chat_batch(&inserted);
+                    }
+                }
+                Err(e) => {
+                    logger.error(&format!("[IRC] Failed to replay chat spool: {}", e));
+                    return false;
+                }
+            }
+
+            counters.record_insert(chunk.messages.len(), started);
+            spool.commit(&chunk.seqs);
+            replayed += chunk.messages.len();
+        }
+
+        if replayed > 0 {
+            logger.info(&format!(
+                "[IRC] Replayed {} chat messages from spool to database",
+                replayed
+            ));
+        }
+        true
+    }
+
+    /// モデレーションイベントを保存し、対象メッセージを削除済みにする
+    async fn flush_moderation_events(
+        db_manager: &Arc<DatabaseManager>,
+        events: &mut Vec<ModerationEvent>,
+        logger: &Arc<AppLogger>,
+    ) {
+        if events.is_empty() {
+            return;
+        }
+
+        let result = db_manager
+            .with_connection(|conn| {
+                with_transaction(conn, |conn| {
+                    let mut flagged = 0;
+                    for event in events.iter() {
+                        flagged += ModerationEventRepository::insert_and_flag(conn, event)?;
+                    }
+                    Ok::<usize, duckdb::Error>(flagged)
+                })
+            })
+            .await;
+
+        match result {
+            Ok(flagged) => {
+                logger.info(&format!(
+                    "[IRC] Saved {} moderation events ({} messages flagged as deleted)",
+                    events.len(),
+                    flagged
+                ));
+                events.clear();
+            }
+            Err(e) => {
+                logger.error(&format!("[IRC] Failed to save moderation events: {}", e));
+            }
+        }
+    }
+
+    /// チャット制限モードの変更を保存
+    ///
+    /// 頻度が低く、チャット量の解釈に使うためバッチを待たずに書き込みます。
+    async fn save_room_state(
+        db_manager: &Arc<DatabaseManager>,
+        channel_id: i64,
+        changes: &[RoomModeChange],
+        changed_at: &str,
         logger: &Arc<AppLogger>,
     ) {
-        if batch.is_empty() {
+        if changes.is_empty() {
             return;
         }
 
         let result = db_manager
             .with_connection(|conn| {
-                crate::database::writer::DatabaseWriter::insert_chat_messages_batch(conn, batch)
+                with_transaction(conn, |conn| {
+                    RoomStateRepository::apply_changes(conn, channel_id, changes, changed_at)
+                })
             })
             .await;
 
         match result {
-            Ok(_) => {
+            Ok(0) => {}
+            Ok(updated) => {
                 logger.info(&format!(
-                    "[IRC] Saved {} chat messages to database",
-                    batch.len()
+                    "[IRC] Updated {} room state periods for channel {}",
+                    updated, channel_id
                 ));
-                batch.clear();
             }
             Err(e) => {
-                logger.error(&format!("[IRC] Failed to save chat messages: {}", e));
-                // エラー時はバッチを保持して次回再試行
+                logger.error(&format!("[IRC] Failed to save room state: {}", e));
             }
         }
     }
@@ -184,6 +1010,7 @@ impl TwitchIrcManager {
         channel_id: i64,
         channel_name: &str,
         _access_token: &str,
+        auto_discovered: bool,
     ) -> Result<(), String> {
         let mut channels = self.channels.lock().await;
 
@@ -201,15 +1028,20 @@ impl TwitchIrcManager {
             .join(channel_login.clone())
             .map_err(|e| e.to_string())?;
 
-        let connection = ChannelConnection {
+        let connection = Arc::new(ChannelConnection {
             channel_id,
             channel_name: channel_name.to_string(),
             stream_id: Arc::new(Mutex::new(None)),
+            auto_discovered,
             is_connected: Arc::new(AtomicBool::new(true)),
             message_count: Arc::new(AtomicU64::new(0)),
             last_message_at: Arc::new(Mutex::new(None)),
-        };
+        });
 
+        self.logins
+            .write()
+            .await
+            .insert(channel_login, Arc::clone(&connection));
         channels.insert(channel_id, connection);
 
         self.logger.info(&format!(
@@ -226,6 +1058,7 @@ impl TwitchIrcManager {
         if let Some(connection) = channels.remove(&channel_id) {
             // チャンネルから退出
             let channel_login = connection.channel_name.to_lowercase();
+            self.logins.write().await.remove(&channel_login);
             self.client.part(channel_login);
 
             connection.is_connected.store(false, Ordering::SeqCst);
@@ -256,9 +1089,180 @@ impl TwitchIrcManager {
         }
     }
 
+    /// 全チャンネルのIRC接続状態を取得
+    pub async fn get_connection_statuses(&self) -> Vec<IrcConnectionStatus> {
+        let channels = self.channels.lock().await;
+        let mut statuses = Vec::with_capacity(channels.len());
+
+        for connection in channels.values() {
+            statuses.push(IrcConnectionStatus {
+                channel_id: connection.channel_id,
+                channel_name: connection.channel_name.clone(),
+                stream_id: *connection.stream_id.lock().await,
+                is_connected: connection.is_connected.load(Ordering::SeqCst),
+                message_count: connection.message_count.load(Ordering::SeqCst),
+                last_message_at: connection.last_message_at.lock().await.clone(),
+            });
+        }
+
+        statuses.sort_by_key(|status| status.channel_id);
+        statuses
+    }
+
     /// アクセストークンを更新（twitch-ircでは認証なし接続のため不要だが互換性のために残す）
     pub async fn update_access_token(&self, _token: String) {
         self.logger
             .info("[IRC] Token update not needed for anonymous connection");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use twitch_irc::message::PrivmsgMessage;
+
+    fn parse(raw: &str) -> UserNoticeMessage {
+        UserNoticeMessage::try_from(IRCMessage::parse(raw).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_clear_chat_to_event() {
+        let timeout = ClearChatMessage::try_from(
+            IRCMessage::parse("@ban-duration=600;room-id=11148817;target-user-id=148973258;tmi-sent-ts=1594553828245 :tmi.twitch.tv CLEARCHAT #pajlada :fabzeef").unwrap(),
+        )
+        .unwrap();
+        let event = clear_chat_to_event(&timeout, 1, Some(10));
+        assert_eq!(event.event_type, "timeout");
+        assert_eq!(event.target_user_name.as_deref(), Some("fabzeef"));
+        assert_eq!(event.target_user_id.as_deref(), Some("148973258"));
+        assert_eq!(event.duration_seconds, Some(600));
+
+        let cleared = ClearChatMessage::try_from(
+            IRCMessage::parse(
+                "@room-id=40286300;tmi-sent-ts=1594561392337 :tmi.twitch.tv CLEARCHAT #randers",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let event = clear_chat_to_event(&cleared, 1, None);
+        assert_eq!(event.event_type, "clear");
+        assert!(event.target_user_name.is_none());
+    }
+
+    #[test]
+    fn test_room_state_changes() {
+        let joined = RoomStateMessage::try_from(
+            IRCMessage::parse("@emote-only=0;followers-only=10;r9k=0;room-id=40286300;slow=30;subs-only=0 :tmi.twitch.tv ROOMSTATE #randers").unwrap(),
+        )
+        .unwrap();
+        let changes = room_state_changes(&joined);
+        assert_eq!(changes.len(), 5);
+        assert!(changes.contains(&RoomModeChange {
+            mode: "slow",
+            enabled: true,
+            setting: Some(30),
+        }));
+        assert!(changes.contains(&RoomModeChange {
+            mode: "followers_only",
+            enabled: true,
+            setting: Some(10),
+        }));
+        assert!(changes.contains(&RoomModeChange {
+            mode: "emote_only",
+            enabled: false,
+            setting: None,
+        }));
+
+        // 変更通知は変わったモードのみ
+        let emote_only = RoomStateMessage::try_from(
+            IRCMessage::parse("@emote-only=1;room-id=40286300 :tmi.twitch.tv ROOMSTATE #randers")
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            room_state_changes(&emote_only),
+            vec![RoomModeChange {
+                mode: "emote_only",
+                enabled: true,
+                setting: None,
+            }]
+        );
+
+        let slow_off = RoomStateMessage::try_from(
+            IRCMessage::parse("@room-id=40286300;slow=0 :tmi.twitch.tv ROOMSTATE #randers")
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(!room_state_changes(&slow_off)[0].enabled);
+    }
+
+    #[test]
+    fn test_message_emotes() {
+        let msg = PrivmsgMessage::try_from(
+            IRCMessage::parse("@badge-info=;badges=;color=#0000FF;display-name=JuN1oRRRR;emotes=25:0-4,12-16/1902:6-10;flags=;id=f9c5774b-faa7-4378-b1af-c4e08b532dc2;mod=0;room-id=11148817;subscriber=0;tmi-sent-ts=1594556065407;turbo=0;user-id=29803735;user-type= :jun1orrrr!jun1orrrr@jun1orrrr.tmi.twitch.tv PRIVMSG #pajlada :Kappa Keepo Kappa").unwrap(),
+        )
+        .unwrap();
+
+        let emotes = message_emotes(&msg.emotes).unwrap();
+        assert_eq!(emotes.len(), 3);
+        let kappa: Vec<_> = emotes.iter().filter(|e| e.id == "25").collect();
+        assert_eq!(kappa.len(), 2);
+        assert!(kappa.iter().all(|e| e.name == "Kappa"));
+        let keepo = emotes.iter().find(|e| e.id == "1902").unwrap();
+        assert_eq!(keepo.name, "Keepo");
+        assert_eq!((keepo.start, keepo.end), (6, 11));
+
+        assert!(message_emotes(&[]).is_none());
+    }
+
+    #[test]
+    fn test_irc_tag_reply_parent() {
+        let msg = PrivmsgMessage::try_from(
+            IRCMessage::parse("@badge-info=;badges=;client-nonce=cd56193132f934ac71b4d5ac488d4bd6;color=;display-name=LeftSwing;emotes=;first-msg=0;flags=;id=5b4f63a9-776f-4fce-bf3c-d9707f52e32d;mod=0;reply-parent-display-name=Retoon;reply-parent-msg-body=hello;reply-parent-msg-id=6b13e51b-7ecb-43b5-ba5b-2bb5288df696;reply-parent-user-id=37940952;reply-parent-user-login=retoon;reply-thread-parent-msg-id=6b13e51b-7ecb-43b5-ba5b-2bb5288df696;reply-thread-parent-user-login=retoon;returning-chatter=0;room-id=37940952;subscriber=0;tmi-sent-ts=1673925983585;turbo=0;user-id=133651738;user-type= :leftswing!leftswing@leftswing.tmi.twitch.tv PRIVMSG #retoon :@Retoon yes").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            irc_tag(&msg.source, "reply-parent-msg-id").as_deref(),
+            Some("6b13e51b-7ecb-43b5-ba5b-2bb5288df696")
+        );
+        assert_eq!(
+            irc_tag(&msg.source, "reply-parent-user-login").as_deref(),
+            Some("retoon")
+        );
+        assert!(irc_tag(&msg.source, "flags").is_none());
+        assert!(irc_tag(&msg.source, "missing").is_none());
+    }
+
+    #[test]
+    fn test_parse_user_notice_resub() {
+        let msg = parse("@badge-info=subscriber/2;badges=subscriber/0;color=#0000FF;display-name=Gutrin;emotes=;flags=;id=e0975c76-054c-4954-8cb0-91b8867ec1ca;login=gutrin;mod=0;msg-id=resub;msg-param-cumulative-months=2;msg-param-months=0;msg-param-should-share-streak=1;msg-param-streak-months=2;msg-param-sub-plan-name=Channel\\sSubscription;msg-param-sub-plan=1000;room-id=71092938;subscriber=1;system-msg=Gutrin\\ssubscribed\\sat\\sTier\\s1.;tmi-sent-ts=1581713640019;user-id=21156217;user-type= :tmi.twitch.tv USERNOTICE #xqcow :xqcL");
+
+        let (message_type, payload) = parse_user_notice(&msg);
+        assert_eq!(message_type, "resub");
+        assert_eq!(payload.msg_id, "resub");
+        assert_eq!(payload.tier.as_deref(), Some("1000"));
+        assert_eq!(payload.cumulative_months, Some(2));
+        assert_eq!(payload.streak_months, Some(2));
+        assert_eq!(payload.gift_recipient_count, None);
+    }
+
+    #[test]
+    fn test_parse_user_notice_gift_and_raid() {
+        let gift = parse("@badge-info=;badges=;color=;display-name=AnAnonymousGifter;emotes=;flags=;id=8db97752-3dee-460b-9001-e925d0e2ba5b;login=ananonymousgifter;mod=0;msg-id=submysterygift;msg-param-mass-gift-count=10;msg-param-sub-plan=1000;room-id=71092938;subscriber=0;system-msg=An\\sanonymous\\suser\\sis\\sgifting\\s10\\sTier\\s1\\sSubs;tmi-sent-ts=1585447099603;user-id=274598607;user-type= :tmi.twitch.tv USERNOTICE #xqcow");
+        let (message_type, payload) = parse_user_notice(&gift);
+        assert_eq!(message_type, "submysterygift");
+        assert_eq!(payload.gift_recipient_count, Some(10));
+        assert_eq!(payload.is_anonymous, Some(true));
+
+        let raid = parse("@badge-info=;badges=;color=#FF69B4;display-name=iamelisabete;emotes=;flags=;id=bb99dda7-3736-4583-9114-52aa11b23d17;login=iamelisabete;mod=0;msg-id=raid;msg-param-displayName=iamelisabete;msg-param-login=iamelisabete;msg-param-profileImageURL=https://example.com/a.png;msg-param-viewerCount=430;room-id=71092938;subscriber=0;system-msg=430\\sraiders\\sfrom\\siamelisabete\\shave\\sjoined!;tmi-sent-ts=1594517796120;user-id=155874595;user-type= :tmi.twitch.tv USERNOTICE #xqcow");
+        let (message_type, payload) = parse_user_notice(&raid);
+        assert_eq!(message_type, "raid");
+        assert_eq!(payload.raider_viewer_count, Some(430));
+
+        // 該当しない項目はJSONに出力しない
+        let json = serde_json::to_string(&payload).unwrap();
+        assert!(!json.contains("tier"));
+        assert!(json.contains("\"raider_viewer_count\":430"));
+    }
+}
@@ -1,10 +1,14 @@
 import { invoke } from '@tauri-apps/api/core';
 import { z } from 'zod';
 import {
+  CollectorHealthSnapshotSchema,
   OAuthConfigSchema,
   TwitchRateLimitStatusSchema,
+  YouTubeQuotaStatusSchema,
+  type CollectorHealthSnapshot,
   type OAuthConfig,
   type TwitchRateLimitStatus,
+  type YouTubeQuotaStatus,
 } from '../schemas';
 
 /**
@@ -97,6 +101,22 @@ export const getTwitchRateLimitStatus = async (): Promise<TwitchRateLimitStatus>
   return TwitchRateLimitStatusSchema.parse(result);
 };
 
+/**
+ * YouTube Data APIクォータの当日の使用状況を取得
+ */
+export const getYouTubeQuotaStatus = async (): Promise<YouTubeQuotaStatus> => {
+  const result = await invoke<unknown>('get_youtube_quota_status');
+  return YouTubeQuotaStatusSchema.parse(result);
+};
+
+/**
+ * コレクターのヘルス状態を取得
+ */
+export const getCollectorStatuses = async (): Promise<CollectorHealthSnapshot> => {
+  const result = await invoke<unknown>('get_collector_statuses');
+  return CollectorHealthSnapshotSchema.parse(result);
+};
+
 export interface TwitchChannelInfo {
   channel_id: string;
   twitch_user_id: number;
@@ -120,3 +140,24 @@ export const validateTwitchChannel = async (
   });
   return result as TwitchChannelInfo;
 };
+
+export interface YouTubeChannelInfo {
+  channel_id: string;
+  display_name: string;
+  profile_image_url: string;
+  description: string;
+  handle?: string;
+  follower_count?: number;
+}
+
+/**
+ * YouTubeチャンネルを検証（@ハンドルやURLを正規のチャンネルIDに解決）
+ */
+export const validateYouTubeChannel = async (
+  channelId: string
+): Promise<YouTubeChannelInfo> => {
+  const result = await invoke<unknown>('validate_youtube_channel', {
+    channel_id: channelId,
+  });
+  return result as YouTubeChannelInfo;
+};
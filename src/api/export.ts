@@ -28,3 +28,18 @@ export async function exportToDelimited(
     includeBom,
   });
 }
+
+/**
+ * 配信メタデータの変更履歴を区切り形式でエクスポート
+ */
+export async function exportStreamEventsToDelimited(
+  query: ExportQuery,
+  filePath: string,
+  includeBom?: boolean
+): Promise<string> {
+  return await invoke<string>('export_stream_events_to_delimited', {
+    query,
+    filePath,
+    includeBom,
+  });
+}
@@ -18,8 +18,11 @@ export async function getGameCategories(): Promise<GameCategory[]> {
 /**
  * IDでゲームカテゴリを取得
  */
-export async function getGameCategory(gameId: string): Promise<GameCategory | null> {
-  const result = await invoke<unknown>('get_game_category', { gameId });
+export async function getGameCategory(
+  gameId: string,
+  platform: string = 'twitch'
+): Promise<GameCategory | null> {
+  const result = await invoke<unknown>('get_game_category', { gameId, platform });
   return result === null ? null : GameCategorySchema.parse(result);
 }
 
@@ -34,8 +37,8 @@ export async function upsertGameCategory(request: UpsertGameCategoryRequest): Pr
 /**
  * ゲームカテゴリを削除
  */
-export async function deleteGameCategory(gameId: string): Promise<void> {
-  await invoke('delete_game_category', { gameId });
+export async function deleteGameCategory(gameId: string, platform: string = 'twitch'): Promise<void> {
+  await invoke('delete_game_category', { gameId, platform });
 }
 
 /**
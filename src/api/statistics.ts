@@ -5,6 +5,8 @@ import {
   GameAnalyticsSchema,
   DailyStatsSchema,
   DataAvailabilitySchema,
+  ScheduledStreamSchema,
+  ScheduleAdherenceSchema,
   ChatEngagementStatsSchema,
   ChatSpikeSchema,
   UserSegmentStatsSchema,
@@ -12,18 +14,27 @@ import {
   TimePatternStatsSchema,
   ChatterBehaviorStatsSchema,
   ChatAnalyticsQuerySchema,
+  ModerationStatsSchema,
+  MonetizationStatsSchema,
+  SuperChatRevenueSchema,
+  CurrencyRateSchema,
   WordFrequencyResultSchema,
   EmoteAnalysisResultSchema,
+  CatalogEmoteSchema,
+  EmoteSyncResultSchema,
   MessageLengthStatsSchema,
   CorrelationResultSchema,
   CategoryImpactResultSchema,
   ChatterScoreResultSchema,
+  ReplyGraphResultSchema,
   AnomalyResultSchema,
   ChatMessageSchema,
   type BroadcasterAnalytics,
   type GameAnalytics,
   type DailyStats,
   type DataAvailability,
+  type ScheduledStream,
+  type ScheduleAdherence,
   type ChatEngagementStats,
   type ChatSpike,
   type UserSegmentStats,
@@ -31,12 +42,19 @@ import {
   type TimePatternStats,
   type ChatterBehaviorStats,
   type ChatAnalyticsQuery,
+  type ModerationStats,
+  type MonetizationStats,
+  type SuperChatRevenue,
+  type CurrencyRate,
   type WordFrequencyResult,
   type EmoteAnalysisResult,
+  type CatalogEmote,
+  type EmoteSyncResult,
   type MessageLengthStats,
   type CorrelationResult,
   type CategoryImpactResult,
   type ChatterScoreResult,
+  type ReplyGraphResult,
   type AnomalyResult,
   type ChatMessage,
 } from '../schemas';
@@ -111,6 +129,35 @@ export const getChannelDailyStats = async (params: {
   return z.array(DailyStatsSchema).parse(result);
 };
 
+// ========== Schedule ==========
+
+export const getScheduledStreams = async (params: {
+  channelId?: number;
+  startTime?: string;
+  endTime?: string;
+}): Promise<ScheduledStream[]> => {
+  const result = await invoke<unknown>('get_scheduled_streams', {
+    channelId: params.channelId,
+    startTime: params.startTime,
+    endTime: params.endTime,
+  });
+  return z.array(ScheduledStreamSchema).parse(result);
+};
+
+/** 配信予定の遵守率（開始・取り消し・不履行の件数と予定時刻からの遅れ）をチャンネル別に取得 */
+export const getScheduleAdherence = async (params: {
+  channelId?: number;
+  startTime?: string;
+  endTime?: string;
+}): Promise<ScheduleAdherence[]> => {
+  const result = await invoke<unknown>('get_schedule_adherence', {
+    channelId: params.channelId,
+    startTime: params.startTime,
+    endTime: params.endTime,
+  });
+  return z.array(ScheduleAdherenceSchema).parse(result);
+};
+
 // ========== Chat Analytics ==========
 
 export const getChatEngagementTimeline = async (
@@ -137,6 +184,7 @@ export const detectChatSpikes = async (
     startTime: validatedQuery.startTime,
     endTime: validatedQuery.endTime,
     minSpikeRatio: validatedQuery.minSpikeRatio ?? 2.0,
+    excludeDeleted: validatedQuery.excludeDeleted,
   });
   return z.array(ChatSpikeSchema).parse(result);
 };
@@ -164,6 +212,7 @@ export const getTopChatters = async (
     startTime: validatedQuery.startTime,
     endTime: validatedQuery.endTime,
     limit: validatedQuery.limit ?? 50,
+    excludeDeleted: validatedQuery.excludeDeleted,
   });
   return z.array(TopChatterSchema).parse(result);
 };
@@ -193,6 +242,59 @@ export const getChatterBehaviorStats = async (
   return ChatterBehaviorStatsSchema.parse(result);
 };
 
+export const getModerationStats = async (
+  query: ChatAnalyticsQuery
+): Promise<ModerationStats> => {
+  const validatedQuery = ChatAnalyticsQuerySchema.parse(query);
+  const result = await invoke<unknown>('get_moderation_stats', {
+    channelId: validatedQuery.channelId,
+    streamId: validatedQuery.streamId,
+    startTime: validatedQuery.startTime,
+    endTime: validatedQuery.endTime,
+  });
+  return ModerationStatsSchema.parse(result);
+};
+
+export const getMonetizationStats = async (
+  query: ChatAnalyticsQuery
+): Promise<MonetizationStats> => {
+  const validatedQuery = ChatAnalyticsQuerySchema.parse(query);
+  const result = await invoke<unknown>('get_monetization_stats', {
+    channelId: validatedQuery.channelId,
+    streamId: validatedQuery.streamId,
+    startTime: validatedQuery.startTime,
+    endTime: validatedQuery.endTime,
+    limit: validatedQuery.limit,
+  });
+  return MonetizationStatsSchema.parse(result);
+};
+
+export const getSuperChatRevenue = async (
+  query: ChatAnalyticsQuery
+): Promise<SuperChatRevenue> => {
+  const validatedQuery = ChatAnalyticsQuerySchema.parse(query);
+  const result = await invoke<unknown>('get_super_chat_revenue', {
+    channelId: validatedQuery.channelId,
+    streamId: validatedQuery.streamId,
+    startTime: validatedQuery.startTime,
+    endTime: validatedQuery.endTime,
+  });
+  return SuperChatRevenueSchema.parse(result);
+};
+
+export const getCurrencyRates = async (): Promise<CurrencyRate[]> => {
+  const result = await invoke<unknown>('get_currency_rates');
+  return z.array(CurrencyRateSchema).parse(result);
+};
+
+/** 通貨換算レートを保存（既存のレートは置き換え。rate は1通貨単位あたりの基準通貨での金額） */
+export const saveCurrencyRates = async (
+  baseCurrency: string,
+  rates: CurrencyRate[]
+): Promise<number> => {
+  return await invoke<number>('save_currency_rates', { baseCurrency, rates });
+};
+
 export const getChatMessages = async (params: {
   streamId?: number;
   channelId?: number;
@@ -200,6 +302,8 @@ export const getChatMessages = async (params: {
   endTime?: string;
   limit?: number;
   offset?: number;
+  /** 取得するメッセージ種別（例: ['sub', 'resub', 'raid']）。未指定時は全種別 */
+  messageTypes?: string[];
 }): Promise<ChatMessage[]> => {
   const result = await invoke<unknown>('get_chat_messages', { query: params });
   return z.array(ChatMessageSchema).parse(result);
@@ -229,6 +333,35 @@ export const getEmoteAnalysis = async (params: {
   return EmoteAnalysisResultSchema.parse(result);
 };
 
+export const getEmoteCatalog = async (params: {
+  source?: CatalogEmote['source'];
+  channelId?: number;
+}): Promise<CatalogEmote[]> => {
+  const result = await invoke<unknown>('get_emote_catalog', {
+    source: params.source,
+    channelId: params.channelId,
+  });
+  return z.array(CatalogEmoteSchema).parse(result);
+};
+
+/** 7TV/BTTV/FFZ のJSONエクスポートを取り込む（channelId 省略時はグローバル） */
+export const importEmoteCatalog = async (params: {
+  source: CatalogEmote['source'];
+  channelId?: number;
+  filePath: string;
+}): Promise<number> => {
+  return await invoke<number>('import_emote_catalog', {
+    source: params.source,
+    channelId: params.channelId,
+    filePath: params.filePath,
+  });
+};
+
+export const syncTwitchEmotes = async (channelId: number): Promise<EmoteSyncResult> => {
+  const result = await invoke<unknown>('sync_twitch_emotes', { channelId });
+  return EmoteSyncResultSchema.parse(result);
+};
+
 export const getMessageLengthStats = async (params: {
   channelId?: number;
   streamId?: number;
@@ -270,6 +403,23 @@ export const getChatterActivityScores = async (params: {
   return ChatterScoreResultSchema.parse(result);
 };
 
+export const getReplyGraph = async (params: {
+  channelId?: number;
+  streamId?: number;
+  startTime?: string;
+  endTime?: string;
+  limit?: number;
+}): Promise<ReplyGraphResult> => {
+  const result = await invoke<unknown>('get_reply_graph', {
+    channelId: params.channelId,
+    streamId: params.streamId,
+    startTime: params.startTime,
+    endTime: params.endTime,
+    limit: params.limit,
+  });
+  return ReplyGraphResultSchema.parse(result);
+};
+
 export const detectAnomalies = async (params: {
   channelId?: number;
   streamId?: number;
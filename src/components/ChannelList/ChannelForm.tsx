@@ -7,7 +7,7 @@ import * as channelsApi from "../../api/channels";
 import * as configApi from "../../api/config";
 
 interface ChannelFormData {
-  platform: 'twitch' | 'youtube';
+  platform: 'twitch' | 'youtube' | 'kick';
   channel_id: string;
   channel_name: string;
   poll_interval: number;
@@ -20,7 +20,7 @@ interface ChannelFormProps {
 
 export function ChannelForm({ onSuccess, onCancel }: ChannelFormProps) {
   const [isValidating, setIsValidating] = useState(false);
-  const [validatedInfo, setValidatedInfo] = useState<configApi.TwitchChannelInfo | null>(null);
+  const [validatedInfo, setValidatedInfo] = useState<configApi.TwitchChannelInfo | configApi.YouTubeChannelInfo | null>(null);
   const [validationError, setValidationError] = useState<string | null>(null);
 
   const { register, handleSubmit, formState: { errors }, reset, watch, setValue } = useForm<ChannelFormData>({
@@ -89,43 +89,57 @@ export function ChannelForm({ onSuccess, onCancel }: ChannelFormProps) {
     }
   };
 
+  // YouTubeチャンネルのバリデーション（@ハンドルやURLを正規のチャンネルIDに解決）
+  const handleValidateYouTubeChannel = async (channelUrl: string) => {
+    if (!channelUrl.trim()) {
+      setValidationError('チャンネルURLを入力してください');
+      return;
+    }
+
+    setIsValidating(true);
+    setValidationError(null);
+    setValidatedInfo(null);
+
+    try {
+      const info = await configApi.validateYouTubeChannel(channelUrl.trim());
+
+      setValidatedInfo(info);
+      // 検証成功時、display_nameを自動設定
+      setValue('channel_name', info.display_name);
+      setValidationError(null);
+    } catch (error: any) {
+      const errorMessage = String(error);
+      setValidationError(errorMessage);
+      setValidatedInfo(null);
+    } finally {
+      setIsValidating(false);
+    }
+  };
+
   const onSubmit = async (data: ChannelFormData) => {
     try {
-      // Twitchの場合、バリデーションが必要
-      if (data.platform === 'twitch' && !validatedInfo) {
+      // Twitch・YouTubeの場合、バリデーションが必要
+      if ((data.platform === 'twitch' || data.platform === 'youtube') && !validatedInfo) {
         setValidationError('チャンネルを追加する前に、「チャンネルを確認」ボタンで検証してください。');
         return;
       }
 
-      // YouTube URLからチャンネルIDを抽出
+      // Twitch・YouTubeの場合、検証済みのchannel_idを使用
       let channelId = data.channel_id;
-      if (data.platform === 'youtube' && data.channel_id.includes('youtube.com')) {
-        // URLからチャンネルIDを抽出
-        const urlMatch = data.channel_id.match(/(?:channel\/|user\/|c\/)([a-zA-Z0-9_-]+)/);
-        if (urlMatch) {
-          channelId = urlMatch[1];
-        } else {
-          // 短縮URLの場合
-          const shortUrlMatch = data.channel_id.match(/youtu\.be\/([a-zA-Z0-9_-]+)/);
-          if (shortUrlMatch) {
-            channelId = shortUrlMatch[1];
-          }
-        }
-      }
-
-      // Twitchの場合、検証済みのchannel_idを使用
-      if (data.platform === 'twitch' && validatedInfo) {
+      if (data.platform !== 'kick' && validatedInfo) {
         channelId = validatedInfo.channel_id;
       }
 
       const submitData = {
         ...data,
         channel_id: channelId,
-        // Twitchの場合、検証済みの情報を追加
-        ...(data.platform === 'twitch' && validatedInfo ? {
+        // 検証済みの情報を追加
+        ...(data.platform !== 'kick' && validatedInfo ? {
           display_name: validatedInfo.display_name,
           profile_image_url: validatedInfo.profile_image_url,
           follower_count: validatedInfo.follower_count,
+        } : {}),
+        ...(data.platform === 'twitch' && validatedInfo && 'twitch_user_id' in validatedInfo ? {
           broadcaster_type: validatedInfo.broadcaster_type,
           twitch_user_id: validatedInfo.twitch_user_id,
         } : {}),
@@ -172,6 +186,7 @@ export function ChannelForm({ onSuccess, onCancel }: ChannelFormProps) {
           >
             <option value="twitch">Twitch</option>
             <option value="youtube">YouTube</option>
+            <option value="kick">Kick</option>
           </select>
           {errors.platform && (
             <p className="mt-1 text-sm text-red-600 dark:text-red-400">{errors.platform.message}</p>
@@ -189,10 +204,10 @@ export function ChannelForm({ onSuccess, onCancel }: ChannelFormProps) {
                 required: watch("platform") === 'youtube' ? "チャンネルURLを入力してください" : "チャンネルIDを入力してください",
                 validate: (value) => {
                   if (watch("platform") === 'youtube') {
-                    // YouTube URLの検証
-                    const urlPattern = /^(https?:\/\/)?(www\.)?(youtube\.com\/(channel\/|user\/|c\/)|youtu\.be\/)/;
-                    if (!urlPattern.test(value)) {
-                      return "有効なYouTubeチャンネルURLを入力してください";
+                    // YouTube URL・@ハンドルの検証（チャンネルIDへの解決はバックエンドで行う）
+                    const urlPattern = /^((https?:\/\/)?([a-z]+\.)?(youtube\.com|youtu\.be)\/|@|UC)/;
+                    if (!urlPattern.test(value.trim())) {
+                      return "有効なYouTubeチャンネルURLまたは@ハンドルを入力してください";
                     }
                   } else {
                     // Twitch IDの検証
@@ -210,13 +225,15 @@ export function ChannelForm({ onSuccess, onCancel }: ChannelFormProps) {
                 }
               })}
               type="text"
-              placeholder={watch("platform") === 'youtube' ? "例: https://www.youtube.com/channel/UC..." : "例: shroud"}
+              placeholder={watch("platform") === 'youtube' ? "例: https://www.youtube.com/@handle" : "例: shroud"}
               className="input-field flex-1"
             />
-            {watch("platform") === 'twitch' && (
+            {watch("platform") !== 'kick' && (
               <button
                 type="button"
-                onClick={() => handleValidateTwitchChannel(watch("channel_id"))}
+                onClick={() => watch("platform") === 'youtube'
+                  ? handleValidateYouTubeChannel(watch("channel_id"))
+                  : handleValidateTwitchChannel(watch("channel_id"))}
                 disabled={isValidating || !watch("channel_id")}
                 className="btn-secondary whitespace-nowrap disabled:opacity-50 disabled:cursor-not-allowed"
               >
@@ -305,7 +322,7 @@ export function ChannelForm({ onSuccess, onCancel }: ChannelFormProps) {
           <strong>注意:</strong> プラットフォームとチャンネルIDは後から変更できません。
           正しい情報を入力してください。
           {watch("platform") === 'youtube' && (
-            <span className="block mt-1">YouTubeの場合は、チャンネルURLを入力してください。</span>
+            <span className="block mt-1">YouTubeの場合は、チャンネルURL・@ハンドル・配信URLのいずれかを入力してください。</span>
           )}
         </div>
       </div>
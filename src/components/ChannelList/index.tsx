@@ -13,7 +13,7 @@ import { useAppStateStore } from "../../stores/appStateStore";
 export function ChannelList() {
   const [showAddForm, setShowAddForm] = useState(false);
   const [editingChannel, setEditingChannel] = useState<ChannelWithStats | null>(null);
-  const [filter, setFilter] = useState<'all' | 'twitch' | 'youtube'>('all');
+  const [filter, setFilter] = useState<'all' | 'twitch' | 'youtube' | 'kick'>('all');
 
   const queryClient = useQueryClient();
   const backendReady = useAppStateStore((state) => state.backendReady);
@@ -186,6 +186,16 @@ export function ChannelList() {
         >
           ▶️ YouTube ({channels.filter(c => c.platform === 'youtube').length})
         </button>
+        <button
+          onClick={() => setFilter('kick')}
+          className={`px-4 py-2 rounded-lg text-sm font-medium transition-all duration-200 ${
+            filter === 'kick'
+              ? 'bg-gradient-to-r from-purple-500 to-indigo-600 text-white shadow-md'
+              : 'bg-white dark:bg-slate-700 text-gray-700 dark:text-gray-300 hover:bg-gray-50 dark:hover:bg-slate-600 border border-gray-200 dark:border-slate-600'
+          }`}
+        >
+          🟢 Kick ({channels.filter(c => c.platform === 'kick').length})
+        </button>
       </div>
 
       {/* 新規追加フォーム */}
@@ -15,6 +15,8 @@ export function AutoDiscoveryForm() {
       languages: [],
       min_viewers: 0,
     },
+    chat_enabled: false,
+    chat_join_budget: 10,
   });
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
@@ -345,6 +347,49 @@ export function AutoDiscoveryForm() {
             />
           </div>
 
+          {/* チャット収集 */}
+          <div>
+            <label className="flex items-center space-x-3">
+              <input
+                type="checkbox"
+                checked={settings.chat_enabled}
+                onChange={(e) =>
+                  setSettings((prev) => ({
+                    ...prev,
+                    chat_enabled: e.target.checked,
+                  }))
+                }
+                className="rounded border-gray-300 text-blue-600 shadow-sm focus:border-blue-300 focus:ring focus:ring-blue-200 focus:ring-opacity-50 dark:border-gray-600 dark:bg-gray-700 dark:focus:ring-gray-600"
+              />
+              <span className="text-sm font-medium text-gray-700 dark:text-gray-300">
+                視聴者数上位の配信のチャットも収集する
+              </span>
+            </label>
+            {settings.chat_enabled && (
+              <div className="mt-3">
+                <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
+                  チャット接続数の上限
+                </label>
+                <input
+                  type="number"
+                  min="1"
+                  max="100"
+                  value={settings.chat_join_budget}
+                  onChange={(e) =>
+                    setSettings((prev) => ({
+                      ...prev,
+                      chat_join_budget: parseInt(e.target.value) || 10,
+                    }))
+                  }
+                  className="input-field"
+                />
+                <p className="text-xs text-gray-500 dark:text-gray-400 mt-1">
+                  上位から外れた配信は退出し、発見されなくなった配信はチャットごと削除されます
+                </p>
+              </div>
+            )}
+          </div>
+
           {/* 言語フィルター */}
           <div>
             <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
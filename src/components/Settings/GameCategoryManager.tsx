@@ -29,7 +29,8 @@ export function GameCategoryManager() {
 
   // 削除ミューテーション
   const deleteMutation = useMutation({
-    mutationFn: gameCategoriesApi.deleteGameCategory,
+    mutationFn: ({ gameId, platform }: { gameId: string; platform: string }) =>
+      gameCategoriesApi.deleteGameCategory(gameId, platform),
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ['game-categories'] });
     },
@@ -44,8 +45,8 @@ export function GameCategoryManager() {
     );
   }) || [];
 
-  const handleDelete = (gameId: string) => {
-    deleteMutation.mutate(gameId);
+  const handleDelete = (gameId: string, platform: string) => {
+    deleteMutation.mutate({ gameId, platform });
   };
 
   return (
@@ -55,7 +56,7 @@ export function GameCategoryManager() {
           ゲームカテゴリ管理
         </h2>
         <p className="text-xs text-gray-500 dark:text-gray-400 mt-1">
-          Twitch カテゴリID→名前の管理（自動発見された配信から自動登録されます）
+          Twitch / YouTube カテゴリID→名前の管理（監視中・自動発見された配信から自動登録されます）
         </p>
       </div>
 
@@ -107,11 +108,14 @@ export function GameCategoryManager() {
             <tbody className="divide-y divide-gray-200 dark:divide-gray-700">
               {filteredCategories.map((category) => (
                 <tr
-                  key={category.gameId}
+                  key={`${category.platform}:${category.gameId}`}
                   className="hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors"
                 >
                   <td className="px-3 py-2 font-mono text-gray-600 dark:text-gray-400">
                     {category.gameId}
+                    {category.platform !== 'twitch' && (
+                      <span className="ml-1 text-xs text-gray-400 dark:text-gray-500">({category.platform})</span>
+                    )}
                   </td>
                   <td className="px-3 py-2 text-gray-900 dark:text-gray-100">
                     <div className="flex items-center gap-2">
@@ -134,7 +138,7 @@ export function GameCategoryManager() {
                   </td>
                   <td className="px-3 py-2 text-right">
                     <button
-                      onClick={() => handleDelete(category.gameId)}
+                      onClick={() => handleDelete(category.gameId, category.platform)}
                       disabled={deleteMutation.isPending}
                       className="text-red-600 dark:text-red-400 hover:text-red-700 dark:hover:text-red-300 disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
                     >
@@ -9,6 +9,16 @@ interface EngagementTabProps {
   endTime: string;
 }
 
+const ROOM_MODE_LABELS: Record<string, string> = {
+  slow: 'スローモード',
+  emote_only: 'エモート限定',
+  followers_only: 'フォロワー限定',
+  subs_only: 'サブスク限定',
+  unique_chat: 'ユニークチャット',
+};
+
+const formatRoomMode = (mode: string) => ROOM_MODE_LABELS[mode] ?? mode;
+
 const EngagementTab = ({ channelId, startTime, endTime }: EngagementTabProps) => {
   // エンゲージメントタイムライン取得
   const { data: timelineData, isLoading: timelineLoading } = useQuery({
@@ -135,7 +145,7 @@ const EngagementTab = ({ channelId, startTime, endTime }: EngagementTabProps) =>
           チャットスパイク検出（2倍以上の急増）
         </h3>
         {spikesLoading ? (
-          <TableSkeleton rows={5} columns={4} />
+          <TableSkeleton rows={5} columns={5} />
         ) : spikesData && spikesData.length > 0 ? (
           <div className="overflow-x-auto">
             <table className="min-w-full divide-y divide-gray-200 dark:divide-gray-700">
@@ -153,6 +163,9 @@ const EngagementTab = ({ channelId, startTime, endTime }: EngagementTabProps) =>
                   <th className="px-6 py-3 text-left text-xs font-medium text-gray-500 dark:text-gray-400 uppercase tracking-wider">
                     スパイク倍率
                   </th>
+                  <th className="px-6 py-3 text-left text-xs font-medium text-gray-500 dark:text-gray-400 uppercase tracking-wider">
+                    チャット制限
+                  </th>
                 </tr>
               </thead>
               <tbody className="bg-white dark:bg-gray-800 divide-y divide-gray-200 dark:divide-gray-700">
@@ -170,6 +183,13 @@ const EngagementTab = ({ channelId, startTime, endTime }: EngagementTabProps) =>
                     <td className="px-6 py-4 whitespace-nowrap text-sm font-bold text-red-600 dark:text-red-400">
                       {(spike.spikeRatio || 0).toFixed(2)}x
                     </td>
+                    <td className="px-6 py-4 whitespace-nowrap text-sm text-gray-500 dark:text-gray-400">
+                      {spike.restrictedModes.length > 0
+                        ? spike.restrictedModes.map(formatRoomMode).join(', ')
+                        : spike.prevRestrictedModes.length > 0
+                          ? `解除直後（${spike.prevRestrictedModes.map(formatRoomMode).join(', ')}）`
+                          : '-'}
+                    </td>
                   </tr>
                 ))}
               </tbody>
@@ -176,7 +176,7 @@ const WordAnalysisTab = ({ channelId, startTime, endTime }: WordAnalysisTabProps
         
         {/* Emote Summary */}
         {emoteData && (
-          <div className="grid grid-cols-1 md:grid-cols-2 gap-4 mb-6">
+          <div className="grid grid-cols-1 md:grid-cols-4 gap-4 mb-6">
             <div className="bg-indigo-50 dark:bg-indigo-900/20 rounded-lg p-4">
               <p className="text-sm text-indigo-600 dark:text-indigo-400">総エモート使用</p>
               <p className="text-2xl font-bold text-indigo-900 dark:text-indigo-100">
@@ -189,6 +189,18 @@ const WordAnalysisTab = ({ channelId, startTime, endTime }: WordAnalysisTabProps
                 {(emoteData.emotePerMessageRate || 0).toFixed(2)}
               </p>
             </div>
+            <div className="bg-purple-50 dark:bg-purple-900/20 rounded-lg p-4">
+              <p className="text-sm text-purple-600 dark:text-purple-400">チャンネルエモート</p>
+              <p className="text-2xl font-bold text-purple-900 dark:text-purple-100">
+                {emoteData.channelEmoteUses.toLocaleString()}
+              </p>
+            </div>
+            <div className="bg-teal-50 dark:bg-teal-900/20 rounded-lg p-4">
+              <p className="text-sm text-teal-600 dark:text-teal-400">グローバルエモート</p>
+              <p className="text-2xl font-bold text-teal-900 dark:text-teal-100">
+                {emoteData.globalEmoteUses.toLocaleString()}
+              </p>
+            </div>
           </div>
         )}
 
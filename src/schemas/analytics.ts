@@ -36,6 +36,7 @@ export const GameAnalyticsSchema = z.object({
   total_chat_messages: z.number(),
   avg_chat_rate: z.number(),
   engagement_rate: z.number(),
+  platform: z.string(),
 });
 
 /**
@@ -59,8 +60,44 @@ export const DailyStatsSchema = z.object({
   collection_hours: z.number(),
 });
 
+/**
+ * Scheduled stream schema (Twitch schedule segment / YouTube upcoming broadcast)
+ */
+export const ScheduledStreamSchema = z.object({
+  id: z.number().nullable(),
+  channelId: z.number(),
+  platform: z.string(),
+  scheduleId: z.string(),
+  title: z.string().nullable(),
+  category: z.string().nullable(),
+  scheduledStart: z.string(),
+  scheduledEnd: z.string().nullable(),
+  status: z.enum(['scheduled', 'started', 'canceled']),
+  streamId: z.number().nullable(),
+  actualStart: z.string().nullable(),
+});
+
+/**
+ * Schedule adherence schema (per channel)
+ */
+export const ScheduleAdherenceSchema = z.object({
+  channelId: z.number(),
+  channelName: z.string(),
+  platform: z.string(),
+  scheduledCount: z.number(),
+  startedCount: z.number(),
+  canceledCount: z.number(),
+  missedCount: z.number(),
+  adherenceRate: z.number(),
+  avgDelayMinutes: z.number().nullable(),
+  medianDelayMinutes: z.number().nullable(),
+  onTimeRate: z.number().nullable(),
+});
+
 // Export types
 export type BroadcasterAnalytics = z.infer<typeof BroadcasterAnalyticsSchema>;
 export type GameAnalytics = z.infer<typeof GameAnalyticsSchema>;
 export type DataAvailability = z.infer<typeof DataAvailabilitySchema>;
 export type DailyStats = z.infer<typeof DailyStatsSchema>;
+export type ScheduledStream = z.infer<typeof ScheduledStreamSchema>;
+export type ScheduleAdherence = z.infer<typeof ScheduleAdherenceSchema>;
@@ -3,7 +3,7 @@ import { z } from 'zod';
 /**
  * Platform enum
  */
-export const PlatformSchema = z.enum(['twitch', 'youtube']);
+export const PlatformSchema = z.enum(['twitch', 'youtube', 'kick']);
 
 /**
  * Channel schema (database model)
@@ -19,6 +19,29 @@ export const ChatMessageSchema = z.object({
   message_type: z.string(),
   badges: z.array(z.string()).nullish(),
   badge_info: z.string().nullish(),
+  // USERNOTICE（サブスク・レイド等）の構造化情報（JSON文字列）
+  payload: z.string().nullish(),
+  // プラットフォーム側のメッセージID（Twitchのmsg id等）
+  platform_message_id: z.string().nullish(),
+  // Cheerで送られたビッツ数
+  bits: z.number().nullish(),
+  // 返信情報（返信先メッセージID・ユーザー、スレッド起点メッセージID）
+  reply_parent_id: z.string().nullish(),
+  reply_parent_user_name: z.string().nullish(),
+  reply_thread_id: z.string().nullish(),
+  // 自動発見チャンネルのチャット収集で取得したメッセージか
+  is_auto_discovered: z.boolean().optional(),
+  // IRCのemotesタグ（エモートIDと文字位置）
+  emotes: z
+    .array(
+      z.object({
+        id: z.string(),
+        name: z.string(),
+        start: z.number(),
+        end: z.number(),
+      })
+    )
+    .nullish(),
 });
 
 /**
@@ -53,6 +76,7 @@ export const ChatEngagementStatsSchema = z.object({
   uniqueChatters: z.number(),
   viewerCount: z.number(),
   engagementRate: z.number(),
+  restrictedModes: z.array(z.string()),
 });
 
 /**
@@ -63,6 +87,8 @@ export const ChatSpikeSchema = z.object({
   chatCount: z.number(),
   spikeRatio: z.number(),
   prevCount: z.number(),
+  restrictedModes: z.array(z.string()),
+  prevRestrictedModes: z.array(z.string()),
 });
 
 /**
@@ -117,6 +143,130 @@ export const ChatterBehaviorStatsSchema = z.object({
   avgParticipationRate: z.number(),
 });
 
+/**
+ * Moderation stats schemas
+ */
+export const StreamModerationStatsSchema = z.object({
+  streamId: z.number(),
+  streamTitle: z.string().nullish(),
+  startedAt: z.string(),
+  timeouts: z.number(),
+  bans: z.number(),
+  deletedMessages: z.number(),
+  uniqueTargets: z.number(),
+});
+
+export const HourlyModerationStatsSchema = z.object({
+  hour: z.string(),
+  timeouts: z.number(),
+  bans: z.number(),
+  deletedMessages: z.number(),
+});
+
+export const ModerationStatsSchema = z.object({
+  totalTimeouts: z.number(),
+  totalBans: z.number(),
+  totalDeletedMessages: z.number(),
+  byStream: z.array(StreamModerationStatsSchema),
+  byHour: z.array(HourlyModerationStatsSchema),
+});
+
+/**
+ * Monetization stats schemas (bits / subs / gifted subs)
+ */
+export const StreamMonetizationStatsSchema = z.object({
+  streamId: z.number(),
+  streamTitle: z.string().nullish(),
+  startedAt: z.string(),
+  totalBits: z.number(),
+  cheerCount: z.number(),
+  subs: z.number(),
+  giftedSubs: z.number(),
+  peakViewers: z.number().nullable(),
+});
+
+export const HourlyMonetizationStatsSchema = z.object({
+  hour: z.string(),
+  totalBits: z.number(),
+  cheerCount: z.number(),
+  subs: z.number(),
+  giftedSubs: z.number(),
+  peakViewers: z.number().nullable(),
+});
+
+export const ChatterMonetizationStatsSchema = z.object({
+  userId: z.string().nullish(),
+  userName: z.string(),
+  displayName: z.string().nullish(),
+  totalBits: z.number(),
+  cheerCount: z.number(),
+  giftedSubs: z.number(),
+});
+
+export const MonetizationStatsSchema = z.object({
+  totalBits: z.number(),
+  totalCheers: z.number(),
+  totalSubs: z.number(),
+  totalGiftedSubs: z.number(),
+  byStream: z.array(StreamMonetizationStatsSchema),
+  byHour: z.array(HourlyMonetizationStatsSchema),
+  topCheerers: z.array(ChatterMonetizationStatsSchema),
+  streamPeakCorrelation: z.number(),
+  hourlyPeakCorrelation: z.number(),
+});
+
+/**
+ * Super Chat revenue schemas (YouTube, normalised to the base currency)
+ */
+export const CurrencyRevenueSchema = z.object({
+  currency: z.string().nullish(),
+  eventCount: z.number(),
+  totalAmount: z.number(),
+  rate: z.number().nullish(),
+  normalizedAmount: z.number().nullish(),
+});
+
+export const StreamRevenueSchema = z.object({
+  streamId: z.number(),
+  channelId: z.number(),
+  streamTitle: z.string().nullish(),
+  startedAt: z.string(),
+  paidCount: z.number(),
+  normalizedAmount: z.number(),
+  unconvertedCount: z.number(),
+  newMembers: z.number(),
+  giftedMemberships: z.number(),
+});
+
+export const ChannelRevenueSchema = z.object({
+  channelId: z.number(),
+  channelName: z.string(),
+  paidCount: z.number(),
+  normalizedAmount: z.number(),
+  unconvertedCount: z.number(),
+  newMembers: z.number(),
+  giftedMemberships: z.number(),
+});
+
+export const SuperChatRevenueSchema = z.object({
+  baseCurrency: z.string().nullish(),
+  totalNormalized: z.number(),
+  unconvertedCurrencies: z.array(z.string()),
+  byCurrency: z.array(CurrencyRevenueSchema),
+  byStream: z.array(StreamRevenueSchema),
+  byChannel: z.array(ChannelRevenueSchema),
+});
+
+/**
+ * Currency conversion rate (amount in the base currency per unit)
+ */
+export const CurrencyRateSchema = z.object({
+  currency: z.string(),
+  baseCurrency: z.string(),
+  rate: z.number(),
+  updatedAt: z.string().nullish(),
+});
+
 /**
  * Chat analytics query schema
  */
@@ -129,6 +279,8 @@ export const ChatAnalyticsQuerySchema = z.object({
   minSpikeRatio: z.number().optional(),
   limit: z.number().optional(),
   groupByDay: z.boolean().optional(),
+  // モデレーターに削除されたメッセージを集計から除外する
+  excludeDeleted: z.boolean().optional(),
 });
 
 // Export types
@@ -143,3 +295,7 @@ export type TopChatter = z.infer<typeof TopChatterSchema>;
 export type TimePatternStats = z.infer<typeof TimePatternStatsSchema>;
 export type ChatterBehaviorStats = z.infer<typeof ChatterBehaviorStatsSchema>;
 export type ChatAnalyticsQuery = z.infer<typeof ChatAnalyticsQuerySchema>;
+export type ModerationStats = z.infer<typeof ModerationStatsSchema>;
+export type MonetizationStats = z.infer<typeof MonetizationStatsSchema>;
+export type SuperChatRevenue = z.infer<typeof SuperChatRevenueSchema>;
+export type CurrencyRate = z.infer<typeof CurrencyRateSchema>;
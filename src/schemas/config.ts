@@ -35,11 +35,67 @@ export const CollectorStatusSchema = z.object({
   channel_name: z.string(),
   platform: z.string(),
   is_running: z.boolean(),
-  last_poll_at: z.string().optional(),
-  last_success_at: z.string().optional(),
-  last_error: z.string().optional(),
+  last_poll_at: z.string().nullable().optional(),
+  last_success_at: z.string().nullable().optional(),
+  last_error: z.string().nullable().optional(),
   poll_count: z.number(),
   error_count: z.number(),
+  consecutive_failures: z.number(),
+  breaker_state: z.enum(['closed', 'open', 'half_open']),
+  next_retry_at: z.string().nullable().optional(),
+});
+
+/**
+ * IRC connection status schema
+ */
+export const IrcConnectionStatusSchema = z.object({
+  channel_id: z.number(),
+  channel_name: z.string(),
+  stream_id: z.number().nullable(),
+  is_connected: z.boolean(),
+  message_count: z.number(),
+  last_message_at: z.string().nullable(),
+});
+
+/**
+ * Auto discovery loop status schema
+ */
+export const AutoDiscoveryStatusSchema = z.object({
+  is_running: z.boolean(),
+  last_run_at: z.string().nullable(),
+  last_success_at: z.string().nullable(),
+  last_error: z.string().nullable(),
+  last_discovered_count: z.number(),
+  run_count: z.number(),
+  error_count: z.number(),
+});
+
+/**
+ * Chat ingest metrics schema (queue fields are 0 for platforms without an ingest queue)
+ */
+export const ChatIngestMetricsSchema = z.object({
+  queue_depth: z.number(),
+  queue_capacity: z.number(),
+  dropped_count: z.number(),
+  moderation_dropped_count: z.number(),
+  inserted_count: z.number(),
+  last_insert_latency_ms: z.number().nullable(),
+  avg_insert_latency_ms: z.number().nullable(),
+});
+
+/**
+ * Collector health snapshot schema (get_collector_statuses / collector-status-updated)
+ */
+export const CollectorHealthSnapshotSchema = z.object({
+  collectors: z.array(CollectorStatusSchema),
+  irc_connections: z.array(IrcConnectionStatusSchema),
+  kick_chat_connections: z.array(IrcConnectionStatusSchema),
+  auto_discovery: AutoDiscoveryStatusSchema.nullable(),
+  chat_spool_depth: z.number(),
+  chat_ingest: ChatIngestMetricsSchema,
+  kick_chat_ingest: ChatIngestMetricsSchema,
+  youtube_chat_ingest: ChatIngestMetricsSchema,
+  generated_at: z.string(),
 });
 
 /**
@@ -54,9 +110,36 @@ export const TwitchRateLimitStatusSchema = z.object({
   request_count: z.number(),
 });
 
+/**
+ * YouTube Data API quota status schema
+ */
+export const YouTubeEndpointUsageSchema = z.object({
+  endpoint: z.string(),
+  unit_cost: z.number(),
+  calls: z.number(),
+  units: z.number(),
+});
+
+export const YouTubeQuotaStatusSchema = z.object({
+  quota_date: z.string(),
+  units_used: z.number(),
+  daily_budget: z.number(),
+  daily_limit: z.number(),
+  units_remaining: z.number(),
+  usage_percent: z.number(),
+  resets_at: z.string(),
+  resets_in_seconds: z.number(),
+  endpoints: z.array(YouTubeEndpointUsageSchema),
+});
+
 // Export types
 export type OAuthConfig = z.infer<typeof OAuthConfigSchema>;
 export type DbInitStatus = z.infer<typeof DbInitStatusSchema>;
 export type DeviceAuthStatus = z.infer<typeof DeviceAuthStatusSchema>;
 export type CollectorStatus = z.infer<typeof CollectorStatusSchema>;
+export type IrcConnectionStatus = z.infer<typeof IrcConnectionStatusSchema>;
+export type ChatIngestMetrics = z.infer<typeof ChatIngestMetricsSchema>;
+export type AutoDiscoveryStatus = z.infer<typeof AutoDiscoveryStatusSchema>;
+export type CollectorHealthSnapshot = z.infer<typeof CollectorHealthSnapshotSchema>;
 export type TwitchRateLimitStatus = z.infer<typeof TwitchRateLimitStatusSchema>;
+export type YouTubeQuotaStatus = z.infer<typeof YouTubeQuotaStatusSchema>;
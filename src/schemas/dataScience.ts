@@ -27,6 +27,9 @@ export const WordFrequencyResultSchema = z.object({
  */
 export const EmoteUsageSchema = z.object({
   name: z.string(),
+  emoteId: z.string(),
+  source: z.enum(['twitch', '7tv', 'bttv', 'ffz']),
+  scope: z.enum(['channel', 'global', 'unknown']),
   count: z.number(),
   users: z.number(),
   percentage: z.number(),
@@ -46,10 +49,31 @@ export const HourlyEmotePatternSchema = z.object({
 export const EmoteAnalysisResultSchema = z.object({
   emotes: z.array(EmoteUsageSchema),
   totalEmoteUses: z.number(),
+  channelEmoteUses: z.number(),
+  globalEmoteUses: z.number(),
   emotePerMessageRate: z.number(),
   hourlyPattern: z.array(HourlyEmotePatternSchema),
 });
 
+/**
+ * Emote catalog entry schema (Twitch / 7TV / BTTV / FFZ)
+ */
+export const CatalogEmoteSchema = z.object({
+  source: z.enum(['twitch', '7tv', 'bttv', 'ffz']),
+  emoteId: z.string(),
+  name: z.string(),
+  channelId: z.number().nullable(),
+  imageUrl: z.string().nullable(),
+});
+
+/**
+ * Twitch emote sync result schema
+ */
+export const EmoteSyncResultSchema = z.object({
+  globalCount: z.number(),
+  channelCount: z.number(),
+});
+
 /**
  * Length distribution schema
  */
@@ -184,6 +208,37 @@ export const ChatterScoreResultSchema = z.object({
   segmentAvgScores: z.array(SegmentAvgScoreSchema),
 });
 
+/**
+ * Reply graph schemas (nodes = chatters, edges = who replied to whom)
+ */
+export const ReplyGraphNodeSchema = z.object({
+  userName: z.string(),
+  isBroadcaster: z.boolean(),
+  repliesSent: z.number(),
+  repliesReceived: z.number(),
+});
+
+export const ReplyGraphEdgeSchema = z.object({
+  source: z.string(),
+  target: z.string(),
+  weight: z.number(),
+});
+
+export const ThreadDepthBucketSchema = z.object({
+  depth: z.number(),
+  threadCount: z.number(),
+});
+
+export const ReplyGraphResultSchema = z.object({
+  nodes: z.array(ReplyGraphNodeSchema),
+  edges: z.array(ReplyGraphEdgeSchema),
+  depthDistribution: z.array(ThreadDepthBucketSchema),
+  totalReplies: z.number(),
+  totalThreads: z.number(),
+  avgThreadDepth: z.number(),
+  maxThreadDepth: z.number(),
+});
+
 // ========== Phase 4: Anomaly Detection ==========
 
 /**
@@ -245,6 +300,8 @@ export type WordFrequencyResult = z.infer<typeof WordFrequencyResultSchema>;
 export type EmoteUsage = z.infer<typeof EmoteUsageSchema>;
 export type HourlyEmotePattern = z.infer<typeof HourlyEmotePatternSchema>;
 export type EmoteAnalysisResult = z.infer<typeof EmoteAnalysisResultSchema>;
+export type CatalogEmote = z.infer<typeof CatalogEmoteSchema>;
+export type EmoteSyncResult = z.infer<typeof EmoteSyncResultSchema>;
 export type LengthDistribution = z.infer<typeof LengthDistributionSchema>;
 export type SegmentLengthStats = z.infer<typeof SegmentLengthStatsSchema>;
 export type MessageLengthStats = z.infer<typeof MessageLengthStatsSchema>;
@@ -258,6 +315,9 @@ export type ChatterActivityScore = z.infer<typeof ChatterActivityScoreSchema>;
 export type ScoreDistribution = z.infer<typeof ScoreDistributionSchema>;
 export type SegmentAvgScore = z.infer<typeof SegmentAvgScoreSchema>;
 export type ChatterScoreResult = z.infer<typeof ChatterScoreResultSchema>;
+export type ReplyGraphNode = z.infer<typeof ReplyGraphNodeSchema>;
+export type ReplyGraphEdge = z.infer<typeof ReplyGraphEdgeSchema>;
+export type ReplyGraphResult = z.infer<typeof ReplyGraphResultSchema>;
 export type Anomaly = z.infer<typeof AnomalySchema>;
 export type TrendStats = z.infer<typeof TrendStatsSchema>;
 export type AnomalyResult = z.infer<typeof AnomalyResultSchema>;
@@ -33,6 +33,8 @@ export const DiscoveredStreamInfoSchema = z.object({
   viewer_count: z.number().nullable().optional(),
   follower_count: z.number(),
   broadcaster_type: z.string().nullable().optional(),
+  stream_id: z.string().nullable().optional(),
+  started_at: z.string().nullable().optional(),
 });
 
 /**
@@ -52,6 +54,8 @@ export const AutoDiscoverySettingsSchema = z.object({
   poll_interval: z.number(),
   max_streams: z.number(),
   filters: AutoDiscoveryFiltersSchema,
+  chat_enabled: z.boolean().default(false),
+  chat_join_budget: z.number().default(10),
 });
 
 // Export types
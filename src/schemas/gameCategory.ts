@@ -4,6 +4,7 @@ import { z } from 'zod';
  * Game category schema
  */
 export const GameCategorySchema = z.object({
+  platform: z.string(),
   gameId: z.string(),
   gameName: z.string(),
   // Rust側は Option<String> なので null が来る場合も許容する
@@ -15,6 +16,8 @@ export const GameCategorySchema = z.object({
  * Upsert game category request schema
  */
 export const UpsertGameCategoryRequestSchema = z.object({
+  // 未指定の場合は twitch
+  platform: z.string().optional(),
   gameId: z.string(),
   gameName: z.string(),
   boxArtUrl: z.string().optional(),
@@ -43,7 +43,7 @@ export const StreamInfoSchema = z.object({
   stream_id: z.string(),
   channel_id: z.number(),
   channel_name: z.string(),
-  platform: z.enum(['twitch', 'youtube']).optional(),
+  platform: z.enum(['twitch', 'youtube', 'kick']).optional(),
   title: z.string(),
   category: z.string(),
   started_at: z.string(),